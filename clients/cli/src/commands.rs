@@ -0,0 +1,339 @@
+use std::path::{Path, PathBuf};
+
+use anchor_client::{
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::{
+        commitment_config::CommitmentConfig,
+        instruction::Instruction,
+        pubkey::Pubkey,
+        signature::{read_keypair_file, Keypair, Signer},
+        transaction::Transaction,
+    },
+};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anyhow::{anyhow, bail, Context, Result};
+use auction_house::{
+    pda::{
+        find_auction_house_address, find_auction_house_fee_account_address,
+        find_auction_house_treasury_address, find_auctioneer_address,
+        find_auctioneer_trade_state_address,
+    },
+    AuctionHouse, AuthorityScope,
+};
+use auctioneer::pda::{find_auctioneer_authority_address, find_listing_config_address};
+use nft_auction_client::{
+    accounts::{decode_auction_house, decode_listing_config},
+    instructions, is_native_treasury,
+};
+use serde_json::{json, Value};
+
+/// Sends, or simulates with `--dry-run`, transactions signed by the configured keypair.
+pub struct Runner {
+    rpc: RpcClient,
+    /// Only read-only commands run without one.
+    payer: Option<Keypair>,
+    dry_run: bool,
+}
+
+impl Runner {
+    pub fn new(url: &str, keypair: Option<PathBuf>, dry_run: bool) -> Result<Self> {
+        let payer = match keypair {
+            Some(path) => Some(read_keypair(&path)?),
+            None => default_keypair_path()
+                .filter(|path| path.exists())
+                .map(|path| read_keypair(&path))
+                .transpose()?,
+        };
+
+        Ok(Self {
+            rpc: RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed()),
+            payer,
+            dry_run,
+        })
+    }
+
+    pub fn create_auction_house(
+        &self,
+        treasury_mint: Pubkey,
+        seller_fee_basis_points: u16,
+        can_change_sale_price: bool,
+        fee_withdrawal_destination: Option<Pubkey>,
+        treasury_withdrawal_destination_owner: Option<Pubkey>,
+    ) -> Result<Value> {
+        let authority = self.payer()?.pubkey();
+        let treasury_token_program = self.token_program(&treasury_mint)?;
+        let (auction_house, _) = find_auction_house_address(&authority, &treasury_mint);
+
+        let ix = instructions::auction_house::create_auction_house(
+            &authority,
+            &authority,
+            &treasury_mint,
+            &treasury_token_program,
+            &fee_withdrawal_destination.unwrap_or(authority),
+            &treasury_withdrawal_destination_owner.unwrap_or(authority),
+            seller_fee_basis_points,
+            can_change_sale_price,
+        );
+
+        Ok(json!({
+            "auction_house": auction_house.to_string(),
+            "auction_house_fee_account":
+                find_auction_house_fee_account_address(&auction_house).0.to_string(),
+            "auction_house_treasury":
+                find_auction_house_treasury_address(&auction_house).0.to_string(),
+            "transaction": self.process(&[ix])?,
+        }))
+    }
+
+    pub fn delegate_auctioneer(
+        &self,
+        auction_house: &Pubkey,
+        auctioneer_authority: Option<Pubkey>,
+        scopes: Vec<AuthorityScope>,
+    ) -> Result<Value> {
+        let auction_house_data = self.auction_house_as_authority(auction_house)?;
+        let auctioneer_authority = auctioneer_authority
+            .unwrap_or_else(|| find_auctioneer_authority_address(auction_house).0);
+
+        let ix = instructions::auction_house::delegate_auctioneer(
+            auction_house,
+            &auction_house_data,
+            &auctioneer_authority,
+            scopes,
+        );
+
+        Ok(json!({
+            "auctioneer_authority": auctioneer_authority.to_string(),
+            "auctioneer":
+                find_auctioneer_address(auction_house, &auctioneer_authority).0.to_string(),
+            "transaction": self.process(&[ix])?,
+        }))
+    }
+
+    pub fn authorize_auctioneer(&self, auction_house: &Pubkey) -> Result<Value> {
+        let ix = instructions::auctioneer::authorize(&self.payer()?.pubkey(), auction_house);
+
+        Ok(json!({
+            "auctioneer_authority": find_auctioneer_authority_address(auction_house).0.to_string(),
+            "transaction": self.process(&[ix])?,
+        }))
+    }
+
+    pub fn withdraw_from_fee(&self, auction_house: &Pubkey, amount: u64) -> Result<Value> {
+        let auction_house_data = self.auction_house_as_authority(auction_house)?;
+
+        let ix = instructions::auction_house::withdraw_from_fee(
+            auction_house,
+            &auction_house_data,
+            amount,
+        );
+
+        Ok(json!({
+            "destination": auction_house_data.fee_withdrawal_destination.to_string(),
+            "amount": amount,
+            "transaction": self.process(&[ix])?,
+        }))
+    }
+
+    pub fn withdraw_from_treasury(&self, auction_house: &Pubkey, amount: u64) -> Result<Value> {
+        let auction_house_data = self.auction_house_as_authority(auction_house)?;
+        let treasury_token_program = self.token_program(&auction_house_data.treasury_mint)?;
+
+        let ix = instructions::auction_house::withdraw_from_treasury(
+            auction_house,
+            &auction_house_data,
+            &treasury_token_program,
+            amount,
+        );
+
+        Ok(json!({
+            "destination": auction_house_data.treasury_withdrawal_destination.to_string(),
+            "amount": amount,
+            "transaction": self.process(&[ix])?,
+        }))
+    }
+
+    pub fn show_auction_house(&self, auction_house: &Pubkey) -> Result<Value> {
+        let data = self.auction_house(auction_house)?;
+
+        let fee_account_balance = self.rpc.get_balance(&data.auction_house_fee_account)?;
+        let treasury_balance = if is_native_treasury(&data.treasury_mint) {
+            self.rpc.get_balance(&data.auction_house_treasury)?
+        } else {
+            self.rpc
+                .get_token_account_balance(&data.auction_house_treasury)?
+                .amount
+                .parse()?
+        };
+
+        Ok(json!({
+            "address": auction_house.to_string(),
+            "version": data.version,
+            "authority": data.authority.to_string(),
+            "creator": data.creator.to_string(),
+            "treasury_mint": data.treasury_mint.to_string(),
+            "auction_house_fee_account": data.auction_house_fee_account.to_string(),
+            "auction_house_treasury": data.auction_house_treasury.to_string(),
+            "fee_withdrawal_destination": data.fee_withdrawal_destination.to_string(),
+            "treasury_withdrawal_destination": data.treasury_withdrawal_destination.to_string(),
+            "seller_fee_basis_points": data.seller_fee_basis_points,
+            "can_change_sale_price": data.can_change_sale_price,
+            "has_auctioneer": data.has_auctioneer,
+            "auctioneer_count": data.auctioneer_count,
+            "has_withdrawal_policy": data.has_withdrawal_policy,
+            "is_paused": data.is_paused,
+            "has_collection_allowlist": data.has_collection_allowlist,
+            "has_blocklist": data.has_blocklist,
+            "pending_authority": (data.pending_authority != Pubkey::default())
+                .then(|| data.pending_authority.to_string()),
+            "fee_account_balance": fee_account_balance,
+            "treasury_balance": treasury_balance,
+        }))
+    }
+
+    pub fn show_listing(
+        &self,
+        auction_house: &Pubkey,
+        seller: &Pubkey,
+        mint: &Pubkey,
+        token_account: Option<Pubkey>,
+        token_size: u64,
+    ) -> Result<Value> {
+        let auction_house_data = self.auction_house(auction_house)?;
+        let token_account = match token_account {
+            Some(token_account) => token_account,
+            None => get_associated_token_address_with_program_id(
+                seller,
+                mint,
+                &self.token_program(mint)?,
+            ),
+        };
+
+        let (listing_config, _) = find_listing_config_address(
+            seller,
+            auction_house,
+            &token_account,
+            &auction_house_data.treasury_mint,
+            mint,
+            token_size,
+        );
+        let (seller_trade_state, _) = find_auctioneer_trade_state_address(
+            seller,
+            auction_house,
+            &token_account,
+            &auction_house_data.treasury_mint,
+            mint,
+            token_size,
+        );
+
+        let account = self
+            .rpc
+            .get_account(&listing_config)
+            .with_context(|| format!("no listing config at {listing_config}"))?;
+        let data = decode_listing_config(&account.data)
+            .map_err(|e| anyhow!("failed to decode listing config {listing_config}: {e}"))?;
+        let is_listed = self
+            .rpc
+            .get_account_with_commitment(&seller_trade_state, self.rpc.commitment())?
+            .value
+            .is_some();
+
+        Ok(json!({
+            "listing_config": listing_config.to_string(),
+            "seller_trade_state": seller_trade_state.to_string(),
+            "token_account": token_account.to_string(),
+            "is_listed": is_listed,
+            "start_time": data.start_time,
+            "end_time": data.end_time,
+            "reserve_price": data.reserve_price,
+            "min_bid_increment": data.min_bid_increment,
+            "time_ext_period": data.time_ext_period,
+            "time_ext_delta": data.time_ext_delta,
+            "highest_bid": {
+                "amount": data.highest_bid.amount,
+                "buyer_trade_state": data.highest_bid.buyer_trade_state.to_string(),
+            },
+        }))
+    }
+
+    fn payer(&self) -> Result<&Keypair> {
+        self.payer
+            .as_ref()
+            .ok_or_else(|| anyhow!("no keypair found, pass one with --keypair"))
+    }
+
+    fn auction_house(&self, auction_house: &Pubkey) -> Result<AuctionHouse> {
+        let account = self
+            .rpc
+            .get_account(auction_house)
+            .with_context(|| format!("no Auction House at {auction_house}"))?;
+
+        decode_auction_house(&account.data)
+            .map_err(|e| anyhow!("failed to decode Auction House {auction_house}: {e}"))
+    }
+
+    /// Fetch an Auction House whose authority is the keypair, which must sign its admin
+    /// instructions.
+    fn auction_house_as_authority(&self, auction_house: &Pubkey) -> Result<AuctionHouse> {
+        let auction_house_data = self.auction_house(auction_house)?;
+        let payer = self.payer()?.pubkey();
+        if auction_house_data.authority != payer {
+            bail!(
+                "keypair {payer} is not the authority of Auction House {auction_house}, {} is",
+                auction_house_data.authority
+            );
+        }
+
+        Ok(auction_house_data)
+    }
+
+    /// Token program owning `mint`.
+    fn token_program(&self, mint: &Pubkey) -> Result<Pubkey> {
+        if is_native_treasury(mint) {
+            return Ok(spl_token::id());
+        }
+
+        Ok(self
+            .rpc
+            .get_account(mint)
+            .with_context(|| format!("no mint at {mint}"))?
+            .owner)
+    }
+
+    /// Send the instructions in one transaction, or simulate it with `--dry-run`.
+    fn process(&self, instructions: &[Instruction]) -> Result<Value> {
+        let payer = self.payer()?;
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &[payer],
+            self.rpc.get_latest_blockhash()?,
+        );
+
+        if !self.dry_run {
+            let signature = self.rpc.send_and_confirm_transaction(&transaction)?;
+            return Ok(json!({ "signature": signature.to_string() }));
+        }
+
+        let simulation = self.rpc.simulate_transaction(&transaction)?.value;
+        let logs = simulation.logs.unwrap_or_default();
+        if let Some(err) = simulation.err {
+            bail!("simulation failed: {err}\n{}", logs.join("\n"));
+        }
+
+        Ok(json!({
+            "simulated": true,
+            "units_consumed": simulation.units_consumed,
+            "logs": logs,
+        }))
+    }
+}
+
+fn read_keypair(path: &Path) -> Result<Keypair> {
+    read_keypair_file(path).map_err(|e| anyhow!("failed to read keypair {}: {e}", path.display()))
+}
+
+/// The Solana CLI default keypair, `~/.config/solana/id.json`.
+fn default_keypair_path() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config/solana/id.json"))
+}
@@ -0,0 +1,187 @@
+//! Operator CLI for Auction House administration.
+
+mod commands;
+mod output;
+
+use std::path::PathBuf;
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::Result;
+use clap::{Parser, Subcommand, ValueEnum};
+
+use crate::{commands::Runner, output::OutputFormat};
+
+#[derive(Parser)]
+#[command(name = "nft-auction", version, about)]
+struct Cli {
+    /// RPC URL of the cluster, a local test validator by default.
+    #[arg(
+        long,
+        short = 'u',
+        global = true,
+        env = "NFT_AUCTION_RPC_URL",
+        default_value = "http://127.0.0.1:8899"
+    )]
+    url: String,
+
+    /// Keypair signing and paying for transactions, the Solana CLI default keypair if omitted.
+    #[arg(long, short = 'k', global = true, env = "NFT_AUCTION_KEYPAIR")]
+    keypair: Option<PathBuf>,
+
+    /// Simulate transactions instead of sending them.
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    output: OutputFormat,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create an Auction House with the keypair as its authority.
+    CreateAuctionHouse {
+        /// Treasury mint, native SOL if omitted.
+        #[arg(long)]
+        treasury_mint: Option<Pubkey>,
+        #[arg(long)]
+        seller_fee_basis_points: u16,
+        #[arg(long)]
+        can_change_sale_price: bool,
+        /// Account receiving fee withdrawals, the keypair if omitted.
+        #[arg(long)]
+        fee_withdrawal_destination: Option<Pubkey>,
+        /// Owner of the account receiving treasury withdrawals, the keypair if omitted.
+        #[arg(long)]
+        treasury_withdrawal_destination_owner: Option<Pubkey>,
+    },
+    /// Delegate scopes of an Auction House to an auctioneer.
+    DelegateAuctioneer {
+        #[arg(long)]
+        auction_house: Pubkey,
+        /// Auctioneer authority, the Auctioneer program PDA of the Auction House if omitted.
+        #[arg(long)]
+        auctioneer_authority: Option<Pubkey>,
+        #[arg(long, value_enum, value_delimiter = ',', required = true)]
+        scopes: Vec<Scope>,
+    },
+    /// Create the Auctioneer program authority of an Auction House.
+    AuthorizeAuctioneer {
+        #[arg(long)]
+        auction_house: Pubkey,
+    },
+    /// Withdraw from the fee account to the fee withdrawal destination.
+    WithdrawFromFee {
+        #[arg(long)]
+        auction_house: Pubkey,
+        /// Amount in lamports.
+        #[arg(long)]
+        amount: u64,
+    },
+    /// Withdraw from the treasury to the treasury withdrawal destination.
+    WithdrawFromTreasury {
+        #[arg(long)]
+        auction_house: Pubkey,
+        /// Amount in base units of the treasury mint.
+        #[arg(long)]
+        amount: u64,
+    },
+    /// Show an Auction House and its balances.
+    ShowAuctionHouse {
+        #[arg(long)]
+        auction_house: Pubkey,
+    },
+    /// Show the Auctioneer listing of a token.
+    ShowListing {
+        #[arg(long)]
+        auction_house: Pubkey,
+        #[arg(long)]
+        seller: Pubkey,
+        #[arg(long)]
+        mint: Pubkey,
+        /// Seller token account, the associated token account of the mint if omitted.
+        #[arg(long)]
+        token_account: Option<Pubkey>,
+        #[arg(long, default_value_t = 1)]
+        token_size: u64,
+    },
+}
+
+/// Command line names of `AuthorityScope`.
+#[derive(Clone, Copy, ValueEnum)]
+enum Scope {
+    Deposit,
+    Buy,
+    PublicBuy,
+    ExecuteSale,
+    Sell,
+    Cancel,
+    Withdraw,
+}
+
+impl From<Scope> for auction_house::AuthorityScope {
+    fn from(scope: Scope) -> Self {
+        match scope {
+            Scope::Deposit => Self::Deposit,
+            Scope::Buy => Self::Buy,
+            Scope::PublicBuy => Self::PublicBuy,
+            Scope::ExecuteSale => Self::ExecuteSale,
+            Scope::Sell => Self::Sell,
+            Scope::Cancel => Self::Cancel,
+            Scope::Withdraw => Self::Withdraw,
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let runner = Runner::new(&cli.url, cli.keypair, cli.dry_run)?;
+
+    let value = match cli.command {
+        Command::CreateAuctionHouse {
+            treasury_mint,
+            seller_fee_basis_points,
+            can_change_sale_price,
+            fee_withdrawal_destination,
+            treasury_withdrawal_destination_owner,
+        } => runner.create_auction_house(
+            treasury_mint.unwrap_or_else(spl_token::native_mint::id),
+            seller_fee_basis_points,
+            can_change_sale_price,
+            fee_withdrawal_destination,
+            treasury_withdrawal_destination_owner,
+        )?,
+        Command::DelegateAuctioneer {
+            auction_house,
+            auctioneer_authority,
+            scopes,
+        } => runner.delegate_auctioneer(
+            &auction_house,
+            auctioneer_authority,
+            scopes.into_iter().map(Into::into).collect(),
+        )?,
+        Command::AuthorizeAuctioneer { auction_house } => {
+            runner.authorize_auctioneer(&auction_house)?
+        }
+        Command::WithdrawFromFee {
+            auction_house,
+            amount,
+        } => runner.withdraw_from_fee(&auction_house, amount)?,
+        Command::WithdrawFromTreasury {
+            auction_house,
+            amount,
+        } => runner.withdraw_from_treasury(&auction_house, amount)?,
+        Command::ShowAuctionHouse { auction_house } => runner.show_auction_house(&auction_house)?,
+        Command::ShowListing {
+            auction_house,
+            seller,
+            mint,
+            token_account,
+            token_size,
+        } => runner.show_listing(&auction_house, &seller, &mint, token_account, token_size)?,
+    };
+
+    output::print(&value, cli.output)
+}
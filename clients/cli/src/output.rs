@@ -0,0 +1,44 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use serde_json::Value;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum OutputFormat {
+    /// One `key: value` line per field.
+    Text,
+    /// Pretty printed JSON.
+    Json,
+}
+
+pub fn print(value: &Value, format: OutputFormat) -> Result<()> {
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(value)?),
+        OutputFormat::Text => print_text(value, ""),
+    }
+
+    Ok(())
+}
+
+/// Print objects as `key: value` lines, nested objects with dotted keys.
+fn print_text(value: &Value, prefix: &str) {
+    match value {
+        Value::Object(fields) => {
+            for (key, field) in fields {
+                let key = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                print_text(field, &key);
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                print_text(item, &format!("{prefix}[{index}]"));
+            }
+        }
+        Value::String(text) => println!("{prefix}: {text}"),
+        Value::Null => println!("{prefix}: -"),
+        other => println!("{prefix}: {other}"),
+    }
+}
@@ -0,0 +1,107 @@
+//! Indexes Auction House and Auctioneer activity into SQLite, following a validator or replaying
+//! dumps, so listings, bids, sales and fees can be queried without polling accounts.
+
+mod schema;
+mod source;
+mod store;
+
+use std::{path::PathBuf, time::Duration};
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use rusqlite::params;
+use serde_json::{json, Value};
+
+use crate::store::Store;
+
+#[derive(Parser)]
+#[command(name = "nft-auction-indexer", version, about)]
+struct Cli {
+    /// SQLite database to index into, created if missing.
+    #[arg(long, short = 'd', global = true, default_value = "nft-auction.sqlite")]
+    database: PathBuf,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Index new transactions and accounts of a validator until interrupted.
+    Follow {
+        #[arg(
+            long,
+            short = 'u',
+            env = "NFT_AUCTION_RPC_URL",
+            default_value = "http://127.0.0.1:8899"
+        )]
+        url: String,
+        /// Seconds between polls.
+        #[arg(long, default_value_t = 5)]
+        poll_interval: u64,
+    },
+    /// Index JSON lines dumps of transactions and accounts.
+    Replay {
+        /// `{signature, slot, block_time, err, instructions: [{program_id, accounts, data}]}` per
+        /// line, oldest first, with base64 instruction data.
+        #[arg(long)]
+        transactions: Option<PathBuf>,
+        /// `{pubkey, owner, lamports, data, slot}` per line, with base64 account data.
+        #[arg(long)]
+        accounts: Option<PathBuf>,
+    },
+    /// Print the active auctions of an Auction House as JSON.
+    ActiveAuctions {
+        #[arg(long)]
+        auction_house: Pubkey,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let mut store = Store::open(&cli.database)?;
+
+    match cli.command {
+        Command::Follow { url, poll_interval } => {
+            source::follow(&mut store, &url, Duration::from_secs(poll_interval))
+        }
+        Command::Replay {
+            transactions,
+            accounts,
+        } => source::replay(&mut store, transactions.as_deref(), accounts.as_deref()),
+        Command::ActiveAuctions { auction_house } => {
+            let auctions = active_auctions(&store, &auction_house)?;
+            println!("{}", serde_json::to_string_pretty(&auctions)?);
+            Ok(())
+        }
+    }
+}
+
+fn active_auctions(store: &Store, auction_house: &Pubkey) -> Result<Value> {
+    let mut statement = store.connection().prepare(
+        "SELECT trade_state, seller, mint, metadata, token_size, listing_config, start_time,
+             end_time, reserve_price, min_bid_increment, highest_bid, highest_bid_trade_state
+         FROM listings
+         WHERE auction_house = ?1 AND status = 'active' AND listing_config IS NOT NULL
+         ORDER BY end_time",
+    )?;
+    let rows = statement.query_map(params![auction_house.to_string()], |row| {
+        Ok(json!({
+            "seller_trade_state": row.get::<_, String>(0)?,
+            "seller": row.get::<_, String>(1)?,
+            "mint": row.get::<_, Option<String>>(2)?,
+            "metadata": row.get::<_, String>(3)?,
+            "token_size": row.get::<_, i64>(4)?,
+            "listing_config": row.get::<_, String>(5)?,
+            "start_time": row.get::<_, Option<i64>>(6)?,
+            "end_time": row.get::<_, Option<i64>>(7)?,
+            "reserve_price": row.get::<_, Option<i64>>(8)?,
+            "min_bid_increment": row.get::<_, Option<i64>>(9)?,
+            "highest_bid": row.get::<_, Option<i64>>(10)?,
+            "highest_bid_trade_state": row.get::<_, Option<String>>(11)?,
+        }))
+    })?;
+
+    Ok(Value::Array(rows.collect::<Result<_, _>>()?))
+}
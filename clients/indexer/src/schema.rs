@@ -0,0 +1,100 @@
+/// Tables the indexer projects program state into.
+///
+/// Public keys and signatures are stored base58 encoded. Amounts are in base units of the
+/// treasury mint. `status` is `active`, `cancelled`, `expired`, `sold` or `filled`, or `closed` when
+/// the trade state disappeared without a matching instruction being indexed.
+pub const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS auction_houses (
+    address TEXT PRIMARY KEY,
+    authority TEXT NOT NULL,
+    treasury_mint TEXT NOT NULL,
+    seller_fee_basis_points INTEGER NOT NULL,
+    is_paused INTEGER NOT NULL,
+    updated_slot INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS listings (
+    trade_state TEXT PRIMARY KEY,
+    auction_house TEXT NOT NULL,
+    seller TEXT NOT NULL,
+    token_account TEXT NOT NULL,
+    metadata TEXT NOT NULL,
+    mint TEXT,
+    -- NULL for auctions, priced by their highest bid.
+    price INTEGER,
+    token_size INTEGER NOT NULL,
+    listing_config TEXT,
+    start_time INTEGER,
+    end_time INTEGER,
+    reserve_price INTEGER,
+    min_bid_increment INTEGER,
+    highest_bid INTEGER,
+    highest_bid_trade_state TEXT,
+    status TEXT NOT NULL,
+    signature TEXT NOT NULL,
+    updated_slot INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS listings_by_auction_house ON listings (auction_house, status);
+CREATE INDEX IF NOT EXISTS listings_by_listing_config ON listings (listing_config);
+
+CREATE TABLE IF NOT EXISTS bids (
+    trade_state TEXT PRIMARY KEY,
+    auction_house TEXT NOT NULL,
+    buyer TEXT NOT NULL,
+    token_account TEXT NOT NULL,
+    metadata TEXT NOT NULL,
+    mint TEXT,
+    price INTEGER NOT NULL,
+    token_size INTEGER NOT NULL,
+    is_public INTEGER NOT NULL,
+    listing_config TEXT,
+    escrow_payment_account TEXT NOT NULL,
+    status TEXT NOT NULL,
+    signature TEXT NOT NULL,
+    updated_slot INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS bids_by_auction_house ON bids (auction_house, status);
+
+CREATE TABLE IF NOT EXISTS sales (
+    signature TEXT NOT NULL,
+    instruction_index INTEGER NOT NULL,
+    auction_house TEXT NOT NULL,
+    buyer TEXT NOT NULL,
+    seller TEXT NOT NULL,
+    mint TEXT NOT NULL,
+    price INTEGER NOT NULL,
+    token_size INTEGER NOT NULL,
+    buyer_trade_state TEXT NOT NULL,
+    seller_trade_state TEXT NOT NULL,
+    slot INTEGER NOT NULL,
+    block_time INTEGER,
+    PRIMARY KEY (signature, instruction_index)
+);
+CREATE INDEX IF NOT EXISTS sales_by_auction_house ON sales (auction_house, slot);
+
+-- Marketplace fees charged on sales and withdrawals from the fee account and treasury.
+CREATE TABLE IF NOT EXISTS fees (
+    signature TEXT NOT NULL,
+    instruction_index INTEGER NOT NULL,
+    auction_house TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    slot INTEGER NOT NULL,
+    PRIMARY KEY (signature, instruction_index)
+);
+
+-- Last observed balance of each buyer escrow.
+CREATE TABLE IF NOT EXISTS escrows (
+    address TEXT PRIMARY KEY,
+    auction_house TEXT NOT NULL,
+    wallet TEXT NOT NULL,
+    balance INTEGER,
+    updated_slot INTEGER
+);
+
+-- Newest indexed signature of each program, where following resumes.
+CREATE TABLE IF NOT EXISTS cursors (
+    program_id TEXT PRIMARY KEY,
+    signature TEXT NOT NULL
+);
+";
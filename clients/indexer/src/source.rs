@@ -0,0 +1,292 @@
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+    str::FromStr,
+    thread,
+    time::Duration,
+};
+
+use anchor_client::{
+    solana_client::{
+        rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient},
+        rpc_config::RpcTransactionConfig,
+        rpc_response::RpcConfirmedTransactionStatusWithSignature,
+    },
+    solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature},
+};
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Deserialize;
+use solana_transaction_status::UiTransactionEncoding;
+
+use crate::store::{AccountRecord, InstructionRecord, Store, TransactionRecord};
+
+/// Programs whose transactions are indexed.
+const PROGRAM_IDS: [Pubkey; 2] = [auction_house::ID, auctioneer::ID];
+
+/// Most accounts `getMultipleAccounts` returns per request.
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// A transaction of a `--transactions` dump, one JSON object per line.
+#[derive(Deserialize)]
+struct TransactionDump {
+    signature: String,
+    slot: u64,
+    block_time: Option<i64>,
+    /// Failed transactions are skipped, as they changed nothing.
+    #[serde(default)]
+    err: Option<serde_json::Value>,
+    instructions: Vec<InstructionDump>,
+}
+
+#[derive(Deserialize)]
+struct InstructionDump {
+    program_id: String,
+    accounts: Vec<String>,
+    /// Base64 encoded.
+    data: String,
+}
+
+/// An account of an `--accounts` dump, one JSON object per line.
+#[derive(Deserialize)]
+struct AccountDump {
+    pubkey: String,
+    owner: String,
+    lamports: u64,
+    /// Base64 encoded.
+    data: String,
+    slot: u64,
+}
+
+/// Index new transactions and refresh accounts every `poll_interval`, until interrupted.
+pub fn follow(store: &mut Store, url: &str, poll_interval: Duration) -> Result<()> {
+    let rpc = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+
+    loop {
+        index_new_transactions(&rpc, store)?;
+        refresh_accounts(&rpc, store)?;
+        thread::sleep(poll_interval);
+    }
+}
+
+/// Index transaction and account dumps, transactions first so accounts settle their state.
+pub fn replay(
+    store: &mut Store,
+    transactions: Option<&Path>,
+    accounts: Option<&Path>,
+) -> Result<()> {
+    if let Some(path) = transactions {
+        for line in read_lines(path)? {
+            let dump: TransactionDump = serde_json::from_str(&line?)?;
+            if dump.err.is_none() {
+                store.apply_transaction(&dump.into_record()?)?;
+            }
+        }
+    }
+
+    if let Some(path) = accounts {
+        for line in read_lines(path)? {
+            let dump: AccountDump = serde_json::from_str(&line?)?;
+            store.apply_account(&dump.into_record()?)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Index the transactions of both programs since their cursors, oldest first.
+fn index_new_transactions(rpc: &RpcClient, store: &mut Store) -> Result<()> {
+    let mut statuses = Vec::new();
+    let mut cursors = Vec::new();
+    for program_id in PROGRAM_IDS {
+        let signatures = new_signatures(rpc, store, &program_id)?;
+        if let Some(newest) = signatures.first() {
+            cursors.push((program_id, newest.signature.clone()));
+        }
+        statuses.extend(signatures);
+    }
+
+    // Auctioneer transactions also list the Auction House through CPI.
+    let mut seen = HashSet::new();
+    statuses.retain(|status| seen.insert(status.signature.clone()));
+    statuses.sort_by_key(|status| status.slot);
+
+    for status in statuses.iter().filter(|status| status.err.is_none()) {
+        let tx = fetch_transaction(rpc, &status.signature)?;
+        store.apply_transaction(&tx)?;
+    }
+
+    // Only moved once everything before them is indexed, so a failed poll is retried.
+    for (program_id, signature) in cursors {
+        store.set_cursor(&program_id, &signature)?;
+    }
+
+    Ok(())
+}
+
+/// Signatures of `program_id` newer than its cursor, newest first.
+fn new_signatures(
+    rpc: &RpcClient,
+    store: &Store,
+    program_id: &Pubkey,
+) -> Result<Vec<RpcConfirmedTransactionStatusWithSignature>> {
+    let until = store
+        .cursor(program_id)?
+        .map(|signature| Signature::from_str(&signature))
+        .transpose()?;
+
+    let mut signatures = Vec::new();
+    let mut before = None;
+    loop {
+        let page = rpc.get_signatures_for_address_with_config(
+            program_id,
+            GetConfirmedSignaturesForAddress2Config {
+                before,
+                until,
+                limit: None,
+                commitment: Some(rpc.commitment()),
+            },
+        )?;
+        let Some(last) = page.last() else {
+            break;
+        };
+
+        before = Some(Signature::from_str(&last.signature)?);
+        signatures.extend(page);
+    }
+
+    Ok(signatures)
+}
+
+fn fetch_transaction(rpc: &RpcClient, signature: &str) -> Result<TransactionRecord> {
+    let tx = rpc.get_transaction_with_config(
+        &Signature::from_str(signature)?,
+        RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(rpc.commitment()),
+            max_supported_transaction_version: Some(0),
+        },
+    )?;
+    let transaction = tx
+        .transaction
+        .transaction
+        .decode()
+        .ok_or_else(|| anyhow!("failed to decode transaction {signature}"))?;
+
+    // Instructions using address lookup table accounts are skipped, marketplace instructions fit
+    // in legacy transactions.
+    let keys = transaction.message.static_account_keys();
+    let instructions = transaction
+        .message
+        .instructions()
+        .iter()
+        .filter_map(|ix| {
+            Some(InstructionRecord {
+                program_id: *keys.get(ix.program_id_index as usize)?,
+                accounts: ix
+                    .accounts
+                    .iter()
+                    .map(|index| keys.get(*index as usize).copied())
+                    .collect::<Option<_>>()?,
+                data: ix.data.clone(),
+            })
+        })
+        .collect();
+
+    Ok(TransactionRecord {
+        signature: signature.to_string(),
+        slot: tx.slot,
+        block_time: tx.block_time,
+        instructions,
+    })
+}
+
+/// Apply the accounts of both programs, then the tracked accounts they don't own.
+fn refresh_accounts(rpc: &RpcClient, store: &mut Store) -> Result<()> {
+    let slot = rpc.get_slot()?;
+
+    let mut seen = HashSet::new();
+    for program_id in PROGRAM_IDS {
+        for (pubkey, account) in rpc.get_program_accounts(&program_id)? {
+            seen.insert(pubkey);
+            store.apply_account(&AccountRecord {
+                pubkey,
+                owner: account.owner,
+                lamports: account.lamports,
+                data: account.data,
+                slot,
+            })?;
+        }
+    }
+
+    let tracked: Vec<Pubkey> = store
+        .tracked_accounts()?
+        .into_iter()
+        .filter(|pubkey| !seen.contains(pubkey))
+        .collect();
+    for chunk in tracked.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        for (pubkey, account) in chunk.iter().zip(rpc.get_multiple_accounts(chunk)?) {
+            match account {
+                Some(account) => store.apply_account(&AccountRecord {
+                    pubkey: *pubkey,
+                    owner: account.owner,
+                    lamports: account.lamports,
+                    data: account.data,
+                    slot,
+                })?,
+                None => store.apply_missing_account(pubkey, slot)?,
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn read_lines(path: &Path) -> Result<impl Iterator<Item = std::io::Result<String>>> {
+    let file = File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+
+    Ok(BufReader::new(file)
+        .lines()
+        .filter(|line| !matches!(line, Ok(line) if line.trim().is_empty())))
+}
+
+impl TransactionDump {
+    fn into_record(self) -> Result<TransactionRecord> {
+        let instructions = self
+            .instructions
+            .into_iter()
+            .map(|ix| {
+                Ok(InstructionRecord {
+                    program_id: ix.program_id.parse()?,
+                    accounts: ix
+                        .accounts
+                        .iter()
+                        .map(|account| account.parse())
+                        .collect::<Result<_, _>>()?,
+                    data: STANDARD.decode(ix.data)?,
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(TransactionRecord {
+            signature: self.signature,
+            slot: self.slot,
+            block_time: self.block_time,
+            instructions,
+        })
+    }
+}
+
+impl AccountDump {
+    fn into_record(self) -> Result<AccountRecord> {
+        Ok(AccountRecord {
+            pubkey: self.pubkey.parse()?,
+            owner: self.owner.parse()?,
+            lamports: self.lamports,
+            data: STANDARD.decode(self.data)?,
+            slot: self.slot,
+        })
+    }
+}
@@ -0,0 +1,478 @@
+use std::path::Path;
+
+use anchor_client::solana_sdk::{pubkey::Pubkey, system_program};
+use anyhow::Result;
+use auction_house::constants::{
+    TRADE_STATE_SIZE, TRADE_STATE_V2_SIZE, TRADE_STATE_WITH_EXPIRY_SIZE,
+};
+use nft_auction_client::{
+    accounts::{decode_auction_house, decode_listing_config},
+    decode::{decode_instruction, MarketInstruction},
+};
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+
+use crate::schema::SCHEMA;
+
+/// Size of an SPL token account, whose mint is followed by its owner and amount.
+const TOKEN_ACCOUNT_SIZE: usize = 165;
+
+/// An instruction of an indexed transaction.
+pub struct InstructionRecord {
+    pub program_id: Pubkey,
+    pub accounts: Vec<Pubkey>,
+    pub data: Vec<u8>,
+}
+
+/// A successful transaction calling the Auction House or Auctioneer.
+pub struct TransactionRecord {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    /// Top-level instructions only, CPIs are implied by the instruction that made them.
+    pub instructions: Vec<InstructionRecord>,
+}
+
+/// An account as observed at `slot`.
+pub struct AccountRecord {
+    pub pubkey: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub slot: u64,
+}
+
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(SCHEMA)?;
+
+        Ok(Self { conn })
+    }
+
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Project the marketplace instructions of a transaction. Applying a transaction again is a
+    /// no-op, so replays and overlapping polls are safe.
+    pub fn apply_transaction(&mut self, tx: &TransactionRecord) -> Result<()> {
+        let db = self.conn.transaction()?;
+        for (index, ix) in tx.instructions.iter().enumerate() {
+            if let Some(instruction) = decode_instruction(&ix.program_id, &ix.accounts, &ix.data) {
+                apply_instruction(&db, tx, index, instruction)?;
+            }
+        }
+
+        db.commit()?;
+        Ok(())
+    }
+
+    /// Refresh what the indexer derives from account state: Auction Houses, listing configs, trade
+    /// states closed outside of indexed instructions, escrow balances and listed mints.
+    pub fn apply_account(&mut self, account: &AccountRecord) -> Result<()> {
+        let db = self.conn.transaction()?;
+        let address = account.pubkey.to_string();
+
+        if account.owner == auction_house::id() {
+            let len = account.data.len();
+            if len == TRADE_STATE_SIZE || len == TRADE_STATE_WITH_EXPIRY_SIZE {
+                // A zeroed trade state is cancelled, one with its bump is still live.
+                if account.data[0] == 0 {
+                    close_trade_state(&db, &address, account.slot)?;
+                }
+            } else if len == TRADE_STATE_V2_SIZE {
+                // V2 trade states are zeroed from their discriminator on when closed.
+                if account.data[..8] == [0; 8] {
+                    close_trade_state(&db, &address, account.slot)?;
+                }
+            } else if let Ok(data) = decode_auction_house(&account.data) {
+                db.execute(
+                    "INSERT INTO auction_houses (address, authority, treasury_mint,
+                         seller_fee_basis_points, is_paused, updated_slot)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                     ON CONFLICT (address) DO UPDATE SET
+                         authority = excluded.authority,
+                         seller_fee_basis_points = excluded.seller_fee_basis_points,
+                         is_paused = excluded.is_paused,
+                         updated_slot = excluded.updated_slot",
+                    params![
+                        address,
+                        data.authority.to_string(),
+                        data.treasury_mint.to_string(),
+                        data.seller_fee_basis_points,
+                        data.is_paused,
+                        account.slot,
+                    ],
+                )?;
+            }
+        } else if account.owner == auctioneer::id() {
+            if let Ok(data) = decode_listing_config(&account.data) {
+                let highest_bid_trade_state = (data.highest_bid.amount > 0)
+                    .then(|| data.highest_bid.buyer_trade_state.to_string());
+                db.execute(
+                    "UPDATE listings SET start_time = ?2, end_time = ?3, reserve_price = ?4,
+                         min_bid_increment = ?5, highest_bid = ?6, highest_bid_trade_state = ?7,
+                         updated_slot = MAX(updated_slot, ?8)
+                     WHERE listing_config = ?1",
+                    params![
+                        address,
+                        data.start_time,
+                        data.end_time,
+                        data.reserve_price,
+                        data.min_bid_increment,
+                        data.highest_bid.amount,
+                        highest_bid_trade_state,
+                        account.slot,
+                    ],
+                )?;
+            }
+        } else if account.owner == system_program::id() {
+            // Native escrows hold the buyer's lamports directly.
+            update_escrow_balance(&db, &address, account.lamports, account.slot)?;
+        } else if is_token_program(&account.owner) && account.data.len() >= TOKEN_ACCOUNT_SIZE {
+            let mint = Pubkey::try_from(&account.data[..32])?.to_string();
+            let mut amount = [0u8; 8];
+            amount.copy_from_slice(&account.data[64..72]);
+            update_escrow_balance(&db, &address, u64::from_le_bytes(amount), account.slot)?;
+
+            for table in ["listings", "bids"] {
+                db.execute(
+                    &format!(
+                        "UPDATE {table} SET mint = ?2 WHERE token_account = ?1 AND mint IS NULL"
+                    ),
+                    params![address, mint],
+                )?;
+            }
+        }
+
+        db.commit()?;
+        Ok(())
+    }
+
+    /// Record that an account no longer exists.
+    pub fn apply_missing_account(&mut self, pubkey: &Pubkey, slot: u64) -> Result<()> {
+        let db = self.conn.transaction()?;
+        let address = pubkey.to_string();
+        close_trade_state(&db, &address, slot)?;
+        update_escrow_balance(&db, &address, 0, slot)?;
+
+        db.commit()?;
+        Ok(())
+    }
+
+    /// Accounts whose state the indexer tracks beyond what program accounts enumerate: live trade
+    /// states, escrows and token accounts of listings and bids with no known mint yet.
+    pub fn tracked_accounts(&self) -> Result<Vec<Pubkey>> {
+        let mut statement = self.conn.prepare(
+            "SELECT trade_state FROM listings WHERE status = 'active'
+             UNION SELECT trade_state FROM bids WHERE status = 'active'
+             UNION SELECT address FROM escrows
+             UNION SELECT token_account FROM listings WHERE mint IS NULL
+             UNION SELECT token_account FROM bids WHERE mint IS NULL",
+        )?;
+        let addresses = statement.query_map([], |row| row.get::<_, String>(0))?;
+
+        let mut accounts = Vec::new();
+        for address in addresses {
+            accounts.push(address?.parse()?);
+        }
+        Ok(accounts)
+    }
+
+    /// Newest indexed signature of `program_id`.
+    pub fn cursor(&self, program_id: &Pubkey) -> Result<Option<String>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT signature FROM cursors WHERE program_id = ?1",
+                params![program_id.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    pub fn set_cursor(&self, program_id: &Pubkey, signature: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO cursors (program_id, signature) VALUES (?1, ?2)
+             ON CONFLICT (program_id) DO UPDATE SET signature = excluded.signature",
+            params![program_id.to_string(), signature],
+        )?;
+        Ok(())
+    }
+}
+
+fn apply_instruction(
+    db: &Transaction,
+    tx: &TransactionRecord,
+    index: usize,
+    instruction: MarketInstruction,
+) -> Result<()> {
+    match instruction {
+        MarketInstruction::Listing {
+            auction_house,
+            seller,
+            token_account,
+            metadata,
+            trade_state,
+            price,
+            token_size,
+            listing_config,
+        } => {
+            db.execute(
+                "INSERT INTO listings (trade_state, auction_house, seller, token_account, metadata,
+                     price, token_size, listing_config, status, signature, updated_slot)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 'active', ?9, ?10)
+                 ON CONFLICT (trade_state) DO UPDATE SET
+                     status = 'active',
+                     signature = excluded.signature,
+                     updated_slot = excluded.updated_slot
+                 WHERE excluded.updated_slot >= listings.updated_slot",
+                params![
+                    trade_state.to_string(),
+                    auction_house.to_string(),
+                    seller.to_string(),
+                    token_account.to_string(),
+                    metadata.to_string(),
+                    price,
+                    token_size,
+                    listing_config.map(|key| key.to_string()),
+                    tx.signature,
+                    tx.slot,
+                ],
+            )?;
+        }
+        MarketInstruction::Bid {
+            auction_house,
+            buyer,
+            token_account,
+            metadata,
+            trade_state,
+            escrow_payment_account,
+            price,
+            token_size,
+            is_public,
+            listing_config,
+        } => {
+            db.execute(
+                "INSERT INTO bids (trade_state, auction_house, buyer, token_account, metadata,
+                     price, token_size, is_public, listing_config, escrow_payment_account, status,
+                     signature, updated_slot)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, 'active', ?11, ?12)
+                 ON CONFLICT (trade_state) DO UPDATE SET
+                     status = 'active',
+                     signature = excluded.signature,
+                     updated_slot = excluded.updated_slot
+                 WHERE excluded.updated_slot >= bids.updated_slot",
+                params![
+                    trade_state.to_string(),
+                    auction_house.to_string(),
+                    buyer.to_string(),
+                    token_account.to_string(),
+                    metadata.to_string(),
+                    price,
+                    token_size,
+                    is_public,
+                    listing_config.map(|key| key.to_string()),
+                    escrow_payment_account.to_string(),
+                    tx.signature,
+                    tx.slot,
+                ],
+            )?;
+            insert_escrow(db, &escrow_payment_account, &auction_house, &buyer)?;
+        }
+        MarketInstruction::Sale {
+            auction_house,
+            buyer,
+            seller,
+            token_mint,
+            metadata,
+            buyer_trade_state,
+            seller_trade_state,
+            escrow_payment_account,
+            price,
+            token_size,
+            is_partial,
+        } => {
+            db.execute(
+                "INSERT OR IGNORE INTO sales (signature, instruction_index, auction_house, buyer,
+                     seller, mint, price, token_size, buyer_trade_state, seller_trade_state, slot,
+                     block_time)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                params![
+                    tx.signature,
+                    index,
+                    auction_house.to_string(),
+                    buyer.to_string(),
+                    seller.to_string(),
+                    token_mint.to_string(),
+                    price,
+                    token_size,
+                    buyer_trade_state.to_string(),
+                    seller_trade_state.to_string(),
+                    tx.slot,
+                    tx.block_time,
+                ],
+            )?;
+            // The marketplace fee is taken from the price at the Auction House's current rate.
+            db.execute(
+                "INSERT OR IGNORE INTO fees (signature, instruction_index, auction_house, kind,
+                     amount, slot)
+                 SELECT ?1, ?2, address, 'sale', ?4 * seller_fee_basis_points / 10000, ?5
+                 FROM auction_houses WHERE address = ?3",
+                params![
+                    tx.signature,
+                    index,
+                    auction_house.to_string(),
+                    price,
+                    tx.slot
+                ],
+            )?;
+
+            update_status(db, "bids", &buyer_trade_state, "filled", tx)?;
+            // A partial sale leaves the rest of the listing up under a new trade state.
+            if !is_partial {
+                update_status(db, "listings", &seller_trade_state, "sold", tx)?;
+            }
+            for table in ["listings", "bids"] {
+                db.execute(
+                    &format!("UPDATE {table} SET mint = ?2 WHERE metadata = ?1 AND mint IS NULL"),
+                    params![metadata.to_string(), token_mint.to_string()],
+                )?;
+            }
+            insert_escrow(db, &escrow_payment_account, &auction_house, &buyer)?;
+        }
+        MarketInstruction::Cancel {
+            trade_state,
+            token_mint,
+            ..
+        } => {
+            for table in ["listings", "bids"] {
+                update_status(db, table, &trade_state, "cancelled", tx)?;
+                db.execute(
+                    &format!(
+                        "UPDATE {table} SET mint = ?2 WHERE trade_state = ?1 AND mint IS NULL"
+                    ),
+                    params![trade_state.to_string(), token_mint.to_string()],
+                )?;
+            }
+        }
+        MarketInstruction::Expiration { trade_state, .. } => {
+            for table in ["listings", "bids"] {
+                update_status(db, table, &trade_state, "expired", tx)?;
+            }
+        }
+        // Balances follow the escrow accounts, deposits and withdrawals only register them.
+        MarketInstruction::Deposit {
+            auction_house,
+            wallet,
+            escrow_payment_account,
+            ..
+        }
+        | MarketInstruction::Withdraw {
+            auction_house,
+            wallet,
+            escrow_payment_account,
+            ..
+        } => insert_escrow(db, &escrow_payment_account, &auction_house, &wallet)?,
+        MarketInstruction::FeeWithdrawal {
+            auction_house,
+            amount,
+        } => insert_fee(db, tx, index, &auction_house, "fee_withdrawal", amount)?,
+        MarketInstruction::TreasuryWithdrawal {
+            auction_house,
+            amount,
+        } => insert_fee(db, tx, index, &auction_house, "treasury_withdrawal", amount)?,
+    }
+
+    Ok(())
+}
+
+/// Move an active listing or bid to `status`.
+fn update_status(
+    db: &Transaction,
+    table: &str,
+    trade_state: &Pubkey,
+    status: &str,
+    tx: &TransactionRecord,
+) -> Result<()> {
+    db.execute(
+        &format!(
+            "UPDATE {table} SET status = ?2, signature = ?3, updated_slot = ?4
+             WHERE trade_state = ?1 AND status = 'active' AND updated_slot <= ?4"
+        ),
+        params![trade_state.to_string(), status, tx.signature, tx.slot],
+    )?;
+    Ok(())
+}
+
+/// Mark listings and bids whose trade state is gone, or was zeroed, as closed.
+fn close_trade_state(db: &Transaction, trade_state: &str, slot: u64) -> Result<()> {
+    for table in ["listings", "bids"] {
+        db.execute(
+            &format!(
+                "UPDATE {table} SET status = 'closed', updated_slot = ?2
+                 WHERE trade_state = ?1 AND status = 'active' AND updated_slot <= ?2"
+            ),
+            params![trade_state, slot],
+        )?;
+    }
+    Ok(())
+}
+
+fn insert_escrow(
+    db: &Transaction,
+    escrow_payment_account: &Pubkey,
+    auction_house: &Pubkey,
+    wallet: &Pubkey,
+) -> Result<()> {
+    db.execute(
+        "INSERT OR IGNORE INTO escrows (address, auction_house, wallet) VALUES (?1, ?2, ?3)",
+        params![
+            escrow_payment_account.to_string(),
+            auction_house.to_string(),
+            wallet.to_string(),
+        ],
+    )?;
+    Ok(())
+}
+
+fn update_escrow_balance(db: &Transaction, address: &str, balance: u64, slot: u64) -> Result<()> {
+    db.execute(
+        "UPDATE escrows SET balance = ?2, updated_slot = ?3
+         WHERE address = ?1 AND (updated_slot IS NULL OR updated_slot <= ?3)",
+        params![address, balance, slot],
+    )?;
+    Ok(())
+}
+
+fn insert_fee(
+    db: &Transaction,
+    tx: &TransactionRecord,
+    index: usize,
+    auction_house: &Pubkey,
+    kind: &str,
+    amount: u64,
+) -> Result<()> {
+    db.execute(
+        "INSERT OR IGNORE INTO fees (signature, instruction_index, auction_house, kind, amount,
+             slot)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            tx.signature,
+            index,
+            auction_house.to_string(),
+            kind,
+            amount,
+            tx.slot
+        ],
+    )?;
+    Ok(())
+}
+
+fn is_token_program(owner: &Pubkey) -> bool {
+    *owner == spl_token::id() || *owner == spl_token_2022::id()
+}
@@ -0,0 +1,477 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anchor_lang::{prelude::Pubkey, solana_program::instruction::Instruction, AccountSerialize};
+use auction_house::{
+    metadata::Royalties,
+    pda::{
+        find_auctioneer_trade_state_address, find_trade_state_address, find_trade_state_v2_address,
+    },
+    trade_state::TradeSide,
+    AuctionHouse,
+};
+use auctioneer::{
+    pda::find_listing_config_address,
+    sell::config::{Bid, ListingConfig, ListingConfigVersion},
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use nft_auction_client::{
+    accounts::TokenMetadata,
+    instructions::{self, auctioneer::AuctionSettings},
+    Token,
+};
+use rusqlite::{params, Connection};
+use serde_json::{json, Value};
+
+const START_TIME: i64 = 1_700_000_000;
+const END_TIME: i64 = START_TIME + 86_400;
+
+fn auction_house_data(treasury_mint: Pubkey) -> AuctionHouse {
+    AuctionHouse {
+        auction_house_fee_account: Pubkey::new_unique(),
+        auction_house_treasury: Pubkey::new_unique(),
+        treasury_withdrawal_destination: Pubkey::new_unique(),
+        fee_withdrawal_destination: Pubkey::new_unique(),
+        treasury_mint,
+        authority: Pubkey::new_unique(),
+        creator: Pubkey::new_unique(),
+        bump: 255,
+        treasury_bump: 254,
+        fee_payer_bump: 253,
+        seller_fee_basis_points: 100,
+        can_change_sale_price: false,
+        escrow_payment_bump: 0,
+        has_auctioneer: true,
+        auctioneer_address: Pubkey::new_unique(),
+        auctioneer_count: 1,
+        pending_authority: Pubkey::default(),
+        pending_treasury_withdrawal_destination: Pubkey::default(),
+        pending_fee_withdrawal_destination: Pubkey::default(),
+        has_withdrawal_policy: false,
+        is_paused: false,
+        has_collection_allowlist: false,
+        has_blocklist: false,
+        version: 1,
+    }
+}
+
+fn token() -> Token {
+    Token {
+        mint: Pubkey::new_unique(),
+        token_account: Pubkey::new_unique(),
+        metadata: Pubkey::new_unique(),
+        token_program: spl_token::id(),
+    }
+}
+
+fn token_metadata() -> TokenMetadata {
+    TokenMetadata {
+        collection: None,
+        royalties: Royalties {
+            seller_fee_basis_points: 0,
+            creators: None,
+        },
+    }
+}
+
+/// Empty scratch directory of a test, so reruns start from a new database.
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir =
+        std::env::temp_dir().join(format!("nft-auction-indexer-{}-{name}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// A `--transactions` dump line.
+fn transaction(signature: &str, slot: u64, err: Option<Value>, ixs: &[Instruction]) -> Value {
+    let instructions: Vec<Value> = ixs
+        .iter()
+        .map(|ix| {
+            let accounts: Vec<String> = ix.accounts.iter().map(|m| m.pubkey.to_string()).collect();
+            json!({
+                "program_id": ix.program_id.to_string(),
+                "accounts": accounts,
+                "data": STANDARD.encode(&ix.data),
+            })
+        })
+        .collect();
+
+    json!({
+        "signature": signature,
+        "slot": slot,
+        "block_time": START_TIME + slot as i64,
+        "err": err,
+        "instructions": instructions,
+    })
+}
+
+/// An `--accounts` dump line.
+fn account<T: AccountSerialize>(pubkey: &Pubkey, owner: &Pubkey, data: &T, slot: u64) -> Value {
+    let mut bytes = Vec::new();
+    data.try_serialize(&mut bytes).unwrap();
+
+    json!({
+        "pubkey": pubkey.to_string(),
+        "owner": owner.to_string(),
+        "lamports": 1_000_000,
+        "data": STANDARD.encode(bytes),
+        "slot": slot,
+    })
+}
+
+fn write_lines(path: &Path, lines: &[Value]) {
+    let contents: String = lines.iter().map(|line| format!("{line}\n")).collect();
+    fs::write(path, contents).unwrap();
+}
+
+/// Run the indexer binary against `database` and return its standard output.
+fn indexer(database: &Path, args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_nft-auction-indexer"))
+        .arg("--database")
+        .arg(database)
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn replay(database: &Path, flag: &str, dump: &Path) {
+    indexer(database, &["replay", flag, dump.to_str().unwrap()]);
+}
+
+#[test]
+fn replay_listings_bids_sales_and_active_auctions() {
+    let dir = scratch_dir("replay");
+    let database = dir.join("market.sqlite");
+
+    let auction_house = Pubkey::new_unique();
+    let treasury_mint = spl_token::native_mint::id();
+    let auction_house_data = auction_house_data(treasury_mint);
+    let seller = Pubkey::new_unique();
+    let buyer = Pubkey::new_unique();
+    let listed = token();
+    let auctioned = token();
+    let bid_on = token();
+    let unlisted = token();
+    let token_metadata = token_metadata();
+
+    // A listing of 10 tokens at 100 each, 4 of which are sold before the rest is cancelled.
+    let (listing, _) = find_trade_state_address(
+        &seller,
+        &auction_house,
+        &listed.token_account,
+        &treasury_mint,
+        &listed.mint,
+        1_000,
+        10,
+    );
+    let (partial_bid, _) = find_trade_state_address(
+        &buyer,
+        &auction_house,
+        &listed.token_account,
+        &treasury_mint,
+        &listed.mint,
+        400,
+        4,
+    );
+    let sell = instructions::auction_house::sell(
+        &auction_house,
+        &auction_house_data,
+        &seller,
+        &listed,
+        &token_metadata,
+        1_000,
+        10,
+        None,
+    );
+    let buy = instructions::auction_house::buy(
+        &auction_house,
+        &auction_house_data,
+        &buyer,
+        &listed,
+        &token_metadata,
+        &spl_token::id(),
+        400,
+        4,
+        None,
+    );
+    let partial_sale = instructions::auction_house::execute_sale(
+        &auction_house,
+        &auction_house_data,
+        &buyer,
+        &seller,
+        &listed,
+        &token_metadata,
+        &partial_bid,
+        &spl_token::id(),
+        1_000,
+        10,
+        Some(4),
+        Some(400),
+    );
+    let cancel = instructions::auction_house::cancel(
+        &auction_house,
+        &auction_house_data,
+        &seller,
+        &listed,
+        &listing,
+        1_000,
+        10,
+    );
+
+    // An auction, whose bidding state only shows up in its listing config account.
+    let (auction, _) = find_auctioneer_trade_state_address(
+        &seller,
+        &auction_house,
+        &auctioned.token_account,
+        &treasury_mint,
+        &auctioned.mint,
+        1,
+    );
+    let (listing_config, _) = find_listing_config_address(
+        &seller,
+        &auction_house,
+        &auctioned.token_account,
+        &treasury_mint,
+        &auctioned.mint,
+        1,
+    );
+    let auction_sell = instructions::auctioneer::sell(
+        &auction_house,
+        &auction_house_data,
+        &seller,
+        &auctioned,
+        &token_metadata,
+        1,
+        AuctionSettings {
+            start_time: START_TIME,
+            end_time: END_TIME,
+            reserve_price: Some(500),
+            min_bid_increment: Some(50),
+            ..Default::default()
+        },
+    );
+
+    // A bid closed once expired.
+    let (expiring_bid, _) = find_trade_state_v2_address(
+        &buyer,
+        &auction_house,
+        &bid_on.token_account,
+        TradeSide::Buy,
+    );
+    let buy_v2 = instructions::auction_house::buy_v2(
+        &auction_house,
+        &auction_house_data,
+        &buyer,
+        &bid_on,
+        &token_metadata,
+        &spl_token::id(),
+        700,
+        1,
+        Some(START_TIME + 100),
+    );
+    let close_expired =
+        instructions::auction_house::close_expired(&Pubkey::new_unique(), &expiring_bid, &buyer);
+
+    // A listing whose transaction failed.
+    let failed_sell = instructions::auction_house::sell(
+        &auction_house,
+        &auction_house_data,
+        &seller,
+        &unlisted,
+        &token_metadata,
+        1_000,
+        1,
+        None,
+    );
+
+    let auction_house_dump = dir.join("auction_house.jsonl");
+    write_lines(
+        &auction_house_dump,
+        &[account(
+            &auction_house,
+            &auction_house::id(),
+            &auction_house_data,
+            1,
+        )],
+    );
+
+    let transactions_dump = dir.join("transactions.jsonl");
+    let failed = json!({ "InstructionError": [0, { "Custom": 6000 }] });
+    write_lines(
+        &transactions_dump,
+        &[
+            transaction("sell", 10, None, &[sell]),
+            transaction("buy", 11, None, &[buy]),
+            transaction("partial-sale", 12, None, &[partial_sale.clone()]),
+            transaction("cancel", 13, None, &[cancel]),
+            transaction("auction", 14, None, &[auction_sell]),
+            transaction("expiring-bid", 15, None, &[buy_v2]),
+            transaction("close-expired", 16, None, &[close_expired]),
+            transaction("failed-sell", 17, Some(failed), &[failed_sell]),
+            // Transactions indexed twice are only counted once.
+            transaction("partial-sale", 12, None, &[partial_sale]),
+        ],
+    );
+
+    let highest_bid = Pubkey::new_unique();
+    let listing_config_dump = dir.join("listing_config.jsonl");
+    write_lines(
+        &listing_config_dump,
+        &[account(
+            &listing_config,
+            &auctioneer::id(),
+            &ListingConfig {
+                version: ListingConfigVersion::V0,
+                start_time: START_TIME,
+                end_time: END_TIME,
+                highest_bid: Bid {
+                    version: ListingConfigVersion::V0,
+                    amount: 600,
+                    buyer_trade_state: highest_bid,
+                },
+                bump: 255,
+                reserve_price: 500,
+                min_bid_increment: 50,
+                time_ext_period: 0,
+                time_ext_delta: 0,
+            },
+            20,
+        )],
+    );
+
+    // Fees need the Auction House row, so it is indexed first as when following a validator.
+    replay(&database, "--accounts", &auction_house_dump);
+    replay(&database, "--transactions", &transactions_dump);
+    replay(&database, "--accounts", &listing_config_dump);
+
+    let db = Connection::open(&database).unwrap();
+
+    let listings: Vec<(String, Option<i64>, i64, Option<String>, String)> = db
+        .prepare(
+            "SELECT trade_state, price, token_size, mint, status FROM listings ORDER BY updated_slot",
+        )
+        .unwrap()
+        .query_map([], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?))
+        })
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(
+        listings,
+        vec![
+            // The partial sale leaves the listing active until it is cancelled.
+            (
+                listing.to_string(),
+                Some(1_000),
+                10,
+                Some(listed.mint.to_string()),
+                "cancelled".to_string()
+            ),
+            (auction.to_string(), None, 1, None, "active".to_string()),
+        ]
+    );
+
+    let bids: Vec<(String, i64, i64, String)> = db
+        .prepare("SELECT trade_state, price, token_size, status FROM bids ORDER BY updated_slot")
+        .unwrap()
+        .query_map([], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(
+        bids,
+        vec![
+            (partial_bid.to_string(), 400, 4, "filled".to_string()),
+            (expiring_bid.to_string(), 700, 1, "expired".to_string()),
+        ]
+    );
+
+    let sales: Vec<(String, String, String, String, i64, i64, String, String)> = db
+        .prepare(
+            "SELECT signature, buyer, seller, mint, price, token_size, buyer_trade_state,
+                 seller_trade_state
+             FROM sales",
+        )
+        .unwrap()
+        .query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+                row.get(5)?,
+                row.get(6)?,
+                row.get(7)?,
+            ))
+        })
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(
+        sales,
+        vec![(
+            "partial-sale".to_string(),
+            buyer.to_string(),
+            seller.to_string(),
+            listed.mint.to_string(),
+            400,
+            4,
+            partial_bid.to_string(),
+            listing.to_string(),
+        )]
+    );
+
+    // 1% of the partial price.
+    let fee: i64 = db
+        .query_row(
+            "SELECT amount FROM fees WHERE signature = ?1 AND kind = 'sale'",
+            params!["partial-sale"],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(fee, 4);
+
+    let active_auctions: Value = serde_json::from_str(&indexer(
+        &database,
+        &[
+            "active-auctions",
+            "--auction-house",
+            &auction_house.to_string(),
+        ],
+    ))
+    .unwrap();
+    assert_eq!(
+        active_auctions,
+        json!([{
+            "seller_trade_state": auction.to_string(),
+            "seller": seller.to_string(),
+            "mint": null,
+            "metadata": auctioned.metadata.to_string(),
+            "token_size": 1,
+            "listing_config": listing_config.to_string(),
+            "start_time": START_TIME,
+            "end_time": END_TIME,
+            "reserve_price": 500,
+            "min_bid_increment": 50,
+            "highest_bid": 600,
+            "highest_bid_trade_state": highest_bid.to_string(),
+        }])
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,76 @@
+use anchor_lang::{
+    prelude::{AccountInfo, Pubkey},
+    AccountDeserialize, Result,
+};
+use auction_house::{
+    metadata::{assert_metadata_valid, get_royalties, get_verified_collection, Royalties},
+    trade_state::TradeStateV2,
+    AuctionHouse, Auctioneer,
+};
+use auctioneer::{authorize::AuctioneerAuthority, sell::config::ListingConfig};
+
+/// Decode an `AuctionHouse` account.
+///
+/// Houses created before the version byte fail to decode until `migrate_auction_house` is run.
+pub fn decode_auction_house(data: &[u8]) -> Result<AuctionHouse> {
+    AuctionHouse::try_deserialize(&mut &data[..])
+}
+
+/// Decode an `Auctioneer` delegate account of the Auction House program.
+pub fn decode_auctioneer(data: &[u8]) -> Result<Auctioneer> {
+    Auctioneer::try_deserialize(&mut &data[..])
+}
+
+/// Decode a v2 trade state account, holding the price and size of a listing or bid.
+pub fn decode_trade_state_v2(data: &[u8]) -> Result<TradeStateV2> {
+    TradeStateV2::try_deserialize(&mut &data[..])
+}
+
+/// Decode a `ListingConfig` account of the Auctioneer program.
+pub fn decode_listing_config(data: &[u8]) -> Result<ListingConfig> {
+    ListingConfig::try_deserialize(&mut &data[..])
+}
+
+/// Decode the `AuctioneerAuthority` account of the Auctioneer program.
+pub fn decode_auctioneer_authority(data: &[u8]) -> Result<AuctioneerAuthority> {
+    AuctioneerAuthority::try_deserialize(&mut &data[..])
+}
+
+/// Fields of an NFT metadata account the Auction House reads when trading.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TokenMetadata {
+    /// Verified collection, always `None` for Token-2022 token metadata.
+    pub collection: Option<Pubkey>,
+    pub royalties: Royalties,
+}
+
+/// Decode the metadata of `mint`, read the same way the Auction House program reads it.
+///
+/// `metadata` is the Metaplex metadata PDA or the mint itself for Token-2022 token metadata,
+/// `owner` and `data` are the fetched account's owner and data.
+pub fn decode_token_metadata(
+    metadata: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    data: &[u8],
+) -> Result<TokenMetadata> {
+    let mut lamports = 0;
+    let mut data = data.to_vec();
+    let metadata_info = AccountInfo::new(
+        metadata,
+        false,
+        false,
+        &mut lamports,
+        &mut data,
+        owner,
+        false,
+        0,
+    );
+
+    assert_metadata_valid(&metadata_info, mint)?;
+
+    Ok(TokenMetadata {
+        collection: get_verified_collection(&metadata_info)?,
+        royalties: get_royalties(&metadata_info)?,
+    })
+}
@@ -0,0 +1,354 @@
+use anchor_lang::{prelude::Pubkey, AnchorDeserialize, Discriminator};
+
+/// Marketplace activity of a top-level `auction_house` or `auctioneer` instruction.
+///
+/// Auctioneer instructions reach the Auction House through CPI, so only the outer instruction is
+/// decoded. Sell and buy instructions carry the metadata account rather than the mint, which
+/// identifies the NFT just as well.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MarketInstruction {
+    Listing {
+        auction_house: Pubkey,
+        seller: Pubkey,
+        token_account: Pubkey,
+        metadata: Pubkey,
+        trade_state: Pubkey,
+        /// `None` for auctions, whose price is set by the highest bid.
+        price: Option<u64>,
+        token_size: u64,
+        /// Auctioneer listing config, for auctions.
+        listing_config: Option<Pubkey>,
+    },
+    Bid {
+        auction_house: Pubkey,
+        buyer: Pubkey,
+        token_account: Pubkey,
+        metadata: Pubkey,
+        trade_state: Pubkey,
+        escrow_payment_account: Pubkey,
+        price: u64,
+        token_size: u64,
+        is_public: bool,
+        /// Auctioneer listing config, for bids on auctions.
+        listing_config: Option<Pubkey>,
+    },
+    Sale {
+        auction_house: Pubkey,
+        buyer: Pubkey,
+        seller: Pubkey,
+        token_mint: Pubkey,
+        metadata: Pubkey,
+        buyer_trade_state: Pubkey,
+        seller_trade_state: Pubkey,
+        escrow_payment_account: Pubkey,
+        /// Total paid by the buyer.
+        price: u64,
+        token_size: u64,
+        /// Whether only part of the listed tokens were bought.
+        is_partial: bool,
+    },
+    Cancel {
+        auction_house: Pubkey,
+        wallet: Pubkey,
+        token_mint: Pubkey,
+        trade_state: Pubkey,
+    },
+    /// Expired listing or bid closed by anyone for the bounty.
+    Expiration {
+        trade_state: Pubkey,
+        closer: Pubkey,
+    },
+    Deposit {
+        auction_house: Pubkey,
+        wallet: Pubkey,
+        escrow_payment_account: Pubkey,
+        amount: u64,
+    },
+    Withdraw {
+        auction_house: Pubkey,
+        wallet: Pubkey,
+        escrow_payment_account: Pubkey,
+        amount: u64,
+    },
+    FeeWithdrawal {
+        auction_house: Pubkey,
+        amount: u64,
+    },
+    TreasuryWithdrawal {
+        auction_house: Pubkey,
+        amount: u64,
+    },
+}
+
+/// Decode the marketplace activity of an instruction, `None` for other programs and instructions.
+///
+/// `accounts` are the instruction's account keys in order, which follows the field order of the
+/// program's `Accounts` structs.
+pub fn decode_instruction(
+    program_id: &Pubkey,
+    accounts: &[Pubkey],
+    data: &[u8],
+) -> Option<MarketInstruction> {
+    if *program_id == auction_house::id() {
+        decode_auction_house_instruction(accounts, data)
+    } else if *program_id == auctioneer::id() {
+        decode_auctioneer_instruction(accounts, data)
+    } else {
+        None
+    }
+}
+
+fn decode_auction_house_instruction(
+    accounts: &[Pubkey],
+    data: &[u8],
+) -> Option<MarketInstruction> {
+    use auction_house::instruction::*;
+
+    let key = |index: usize| accounts.get(index).copied();
+
+    // V2 instructions keep the account order of their legacy counterparts, less the free trade
+    // state of sales which comes after the decoded accounts.
+    let listing = match (
+        legacy_instruction_args::<Sell>(data),
+        instruction_args::<SellV2>(data),
+    ) {
+        (Some(args), _) => Some((args.buyer_price, args.token_size)),
+        (_, Some(args)) => Some((args.price, args.token_size)),
+        _ => None,
+    };
+    if let Some((price, token_size)) = listing {
+        return Some(MarketInstruction::Listing {
+            auction_house: key(4)?,
+            seller: key(0)?,
+            token_account: key(1)?,
+            metadata: key(2)?,
+            trade_state: key(6)?,
+            price: Some(price),
+            token_size,
+            listing_config: None,
+        });
+    }
+
+    let bid = match (
+        legacy_instruction_args::<Buy>(data),
+        legacy_instruction_args::<PublicBuy>(data),
+        instruction_args::<BuyV2>(data),
+        instruction_args::<PublicBuyV2>(data),
+    ) {
+        (Some(args), ..) => Some((args.buyer_price, args.token_size, false)),
+        (_, Some(args), ..) => Some((args.buyer_price, args.token_size, true)),
+        (_, _, Some(args), _) => Some((args.price, args.token_size, false)),
+        (.., Some(args)) => Some((args.price, args.token_size, true)),
+        _ => None,
+    };
+    if let Some((price, token_size, is_public)) = bid {
+        return Some(MarketInstruction::Bid {
+            auction_house: key(8)?,
+            buyer: key(0)?,
+            token_account: key(4)?,
+            metadata: key(5)?,
+            trade_state: key(10)?,
+            escrow_payment_account: key(6)?,
+            price,
+            token_size,
+            is_public,
+            listing_config: None,
+        });
+    }
+
+    let sale = match (
+        instruction_args::<ExecuteSale>(data),
+        instruction_args::<ExecuteSaleV2>(data),
+    ) {
+        (Some(args), _) => Some((
+            args.buyer_price,
+            args.token_size,
+            args.partial_order_size,
+            args.partial_order_price,
+        )),
+        (_, Some(args)) => Some((
+            args.buyer_price,
+            args.token_size,
+            args.partial_order_size,
+            args.partial_order_price,
+        )),
+        _ => None,
+    };
+    if let Some((buyer_price, listed_size, partial_order_size, partial_order_price)) = sale {
+        let (price, token_size, is_partial) = match (partial_order_size, partial_order_price) {
+            (Some(size), Some(price)) => (price, size, size < listed_size),
+            _ => (buyer_price, listed_size, false),
+        };
+
+        return Some(MarketInstruction::Sale {
+            auction_house: key(10)?,
+            buyer: key(0)?,
+            seller: key(1)?,
+            token_mint: key(3)?,
+            metadata: key(4)?,
+            buyer_trade_state: key(13)?,
+            seller_trade_state: key(14)?,
+            escrow_payment_account: key(6)?,
+            price,
+            token_size,
+            is_partial,
+        });
+    }
+
+    if instruction_args::<Cancel>(data).is_some() {
+        return Some(MarketInstruction::Cancel {
+            auction_house: key(4)?,
+            wallet: key(0)?,
+            token_mint: key(2)?,
+            trade_state: key(6)?,
+        });
+    }
+
+    if instruction_args::<CloseExpired>(data).is_some() {
+        return Some(MarketInstruction::Expiration {
+            trade_state: key(1)?,
+            closer: key(0)?,
+        });
+    }
+
+    if let Some(args) = instruction_args::<Deposit>(data) {
+        return Some(MarketInstruction::Deposit {
+            auction_house: key(6)?,
+            wallet: key(0)?,
+            escrow_payment_account: key(3)?,
+            amount: args.amount,
+        });
+    }
+
+    if let Some(args) = instruction_args::<Withdraw>(data) {
+        return Some(MarketInstruction::Withdraw {
+            auction_house: key(5)?,
+            wallet: key(0)?,
+            escrow_payment_account: key(2)?,
+            amount: args.amount,
+        });
+    }
+
+    if let Some(args) = instruction_args::<WithdrawFromFee>(data) {
+        return Some(MarketInstruction::FeeWithdrawal {
+            auction_house: key(3)?,
+            amount: args.amount,
+        });
+    }
+
+    if let Some(args) = instruction_args::<WithdrawFromTreasury>(data) {
+        return Some(MarketInstruction::TreasuryWithdrawal {
+            auction_house: key(4)?,
+            amount: args.amount,
+        });
+    }
+
+    None
+}
+
+fn decode_auctioneer_instruction(accounts: &[Pubkey], data: &[u8]) -> Option<MarketInstruction> {
+    use auctioneer::instruction::*;
+
+    let key = |index: usize| accounts.get(index).copied();
+
+    if let Some(args) = instruction_args::<Sell>(data) {
+        return Some(MarketInstruction::Listing {
+            auction_house: key(6)?,
+            seller: key(2)?,
+            token_account: key(3)?,
+            metadata: key(4)?,
+            trade_state: key(8)?,
+            price: None,
+            token_size: args.token_size,
+            listing_config: Some(key(1)?),
+        });
+    }
+
+    let bid = match (
+        instruction_args::<Buy>(data),
+        instruction_args::<PublicBuy>(data),
+    ) {
+        (Some(args), _) => Some((args.buyer_price, args.token_size, false)),
+        (_, Some(args)) => Some((args.buyer_price, args.token_size, true)),
+        _ => None,
+    };
+    if let Some((price, token_size, is_public)) = bid {
+        return Some(MarketInstruction::Bid {
+            auction_house: key(11)?,
+            buyer: key(3)?,
+            token_account: key(7)?,
+            metadata: key(8)?,
+            trade_state: key(13)?,
+            escrow_payment_account: key(9)?,
+            price,
+            token_size,
+            is_public,
+            listing_config: Some(key(1)?),
+        });
+    }
+
+    if let Some(args) = instruction_args::<ExecuteSale>(data) {
+        return Some(MarketInstruction::Sale {
+            auction_house: key(12)?,
+            buyer: key(2)?,
+            seller: key(3)?,
+            token_mint: key(5)?,
+            metadata: key(6)?,
+            buyer_trade_state: key(15)?,
+            seller_trade_state: key(16)?,
+            escrow_payment_account: key(8)?,
+            price: args.buyer_price,
+            token_size: args.token_size,
+            is_partial: false,
+        });
+    }
+
+    if instruction_args::<Cancel>(data).is_some() {
+        return Some(MarketInstruction::Cancel {
+            auction_house: key(7)?,
+            wallet: key(3)?,
+            token_mint: key(5)?,
+            trade_state: key(9)?,
+        });
+    }
+
+    if let Some(args) = instruction_args::<Deposit>(data) {
+        return Some(MarketInstruction::Deposit {
+            auction_house: key(7)?,
+            wallet: key(1)?,
+            escrow_payment_account: key(4)?,
+            amount: args.amount,
+        });
+    }
+
+    if let Some(args) = instruction_args::<Withdraw>(data) {
+        return Some(MarketInstruction::Withdraw {
+            auction_house: key(6)?,
+            wallet: key(1)?,
+            escrow_payment_account: key(3)?,
+            amount: args.amount,
+        });
+    }
+
+    None
+}
+
+/// Arguments of instruction `T` if `data` starts with its discriminator.
+fn instruction_args<T: AnchorDeserialize + Discriminator>(data: &[u8]) -> Option<T> {
+    if data.len() < 8 || data[..8] != T::DISCRIMINATOR {
+        return None;
+    }
+
+    T::deserialize(&mut &data[8..]).ok()
+}
+
+/// Arguments of legacy listing or bid instruction `T`, also decoding instructions sent before the
+/// trailing `expiry` argument was added.
+fn legacy_instruction_args<T: AnchorDeserialize + Discriminator>(data: &[u8]) -> Option<T> {
+    instruction_args(data).or_else(|| {
+        // A missing expiry is the `None` variant.
+        let mut data = data.to_vec();
+        data.push(0);
+        instruction_args(&data)
+    })
+}
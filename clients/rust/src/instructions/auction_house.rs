@@ -0,0 +1,841 @@
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{instruction::Instruction, system_program, sysvar},
+    InstructionData, ToAccountMetas,
+};
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id};
+use auction_house::{pda::*, trade_state::TradeSide, AuctionHouse, AuthorityScope};
+
+use crate::{
+    accounts::TokenMetadata,
+    instructions::treasury_payment_account,
+    remaining_accounts::{
+        execute_sale_remaining_accounts, listing_remaining_accounts,
+        withdrawal_policy_remaining_accounts,
+    },
+    Token,
+};
+
+/// Create an Auction House owned by `authority` for `treasury_mint`, paid for by `payer`.
+pub fn create_auction_house(
+    payer: &Pubkey,
+    authority: &Pubkey,
+    treasury_mint: &Pubkey,
+    treasury_token_program: &Pubkey,
+    fee_withdrawal_destination: &Pubkey,
+    treasury_withdrawal_destination_owner: &Pubkey,
+    seller_fee_basis_points: u16,
+    can_change_sale_price: bool,
+) -> Instruction {
+    let (auction_house, auction_house_bump) = find_auction_house_address(authority, treasury_mint);
+    let (auction_house_fee_account, fee_payer_bump) =
+        find_auction_house_fee_account_address(&auction_house);
+    let (auction_house_treasury, treasury_bump) =
+        find_auction_house_treasury_address(&auction_house);
+
+    let data = auction_house::instruction::CreateAuctionHouse {
+        _bump: auction_house_bump,
+        fee_payer_bump,
+        treasury_bump,
+        seller_fee_basis_points,
+        can_change_sale_price,
+    };
+
+    let accounts = auction_house::accounts::CreateAuctionHouse {
+        treasury_mint: *treasury_mint,
+        payer: *payer,
+        authority: *authority,
+        fee_withdrawal_destination: *fee_withdrawal_destination,
+        treasury_withdrawal_destination: treasury_payment_account(
+            treasury_withdrawal_destination_owner,
+            treasury_mint,
+            treasury_token_program,
+        ),
+        treasury_withdrawal_destination_owner: *treasury_withdrawal_destination_owner,
+        auction_house,
+        auction_house_fee_account,
+        auction_house_treasury,
+        token_program: *treasury_token_program,
+        system_program: system_program::id(),
+        associated_token_program: associated_token::ID,
+        rent: sysvar::rent::id(),
+    };
+
+    Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    }
+}
+
+/// Delegate the `scopes` of an Auction House to `auctioneer_authority`.
+pub fn delegate_auctioneer(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    auctioneer_authority: &Pubkey,
+    scopes: Vec<AuthorityScope>,
+) -> Instruction {
+    let (auctioneer, _) = find_auctioneer_address(auction_house, auctioneer_authority);
+
+    let data = auction_house::instruction::DelegateAuctioneer { scopes };
+
+    let accounts = auction_house::accounts::DelegateAuctioneer {
+        auction_house: *auction_house,
+        authority: auction_house_data.authority,
+        auctioneer_authority: *auctioneer_authority,
+        auctioneer,
+        system_program: system_program::id(),
+    };
+
+    Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    }
+}
+
+/// Withdraw `amount` from the fee account of an Auction House to its fee withdrawal destination.
+pub fn withdraw_from_fee(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    amount: u64,
+) -> Instruction {
+    let accounts = auction_house::accounts::WithdrawFromFee {
+        authority: auction_house_data.authority,
+        fee_withdrawal_destination: auction_house_data.fee_withdrawal_destination,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        auction_house: *auction_house,
+        system_program: system_program::id(),
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(withdrawal_policy_remaining_accounts(
+        auction_house,
+        auction_house_data,
+    ));
+
+    Instruction {
+        program_id: auction_house::id(),
+        data: auction_house::instruction::WithdrawFromFee { amount }.data(),
+        accounts: account_metas,
+    }
+}
+
+/// Withdraw `amount` from the treasury of an Auction House to its treasury withdrawal destination.
+pub fn withdraw_from_treasury(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    treasury_token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let accounts = auction_house::accounts::WithdrawFromTreasury {
+        treasury_mint: auction_house_data.treasury_mint,
+        authority: auction_house_data.authority,
+        treasury_withdrawal_destination: auction_house_data.treasury_withdrawal_destination,
+        auction_house_treasury: auction_house_data.auction_house_treasury,
+        auction_house: *auction_house,
+        token_program: *treasury_token_program,
+        system_program: system_program::id(),
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(withdrawal_policy_remaining_accounts(
+        auction_house,
+        auction_house_data,
+    ));
+
+    Instruction {
+        program_id: auction_house::id(),
+        data: auction_house::instruction::WithdrawFromTreasury { amount }.data(),
+        accounts: account_metas,
+    }
+}
+
+/// List `token_size` of `token` at `buyer_price` until `expiry`, signed by `wallet`, the token
+/// account owner.
+pub fn sell(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    wallet: &Pubkey,
+    token: &Token,
+    token_metadata: &TokenMetadata,
+    buyer_price: u64,
+    token_size: u64,
+    expiry: Option<i64>,
+) -> Instruction {
+    let (seller_trade_state, trade_state_bump) = find_trade_state_address(
+        wallet,
+        auction_house,
+        &token.token_account,
+        &auction_house_data.treasury_mint,
+        &token.mint,
+        buyer_price,
+        token_size,
+    );
+    let (free_seller_trade_state, free_trade_state_bump) = find_trade_state_address(
+        wallet,
+        auction_house,
+        &token.token_account,
+        &auction_house_data.treasury_mint,
+        &token.mint,
+        0,
+        token_size,
+    );
+    let (program_as_signer, program_as_signer_bump) = find_program_as_signer_address();
+
+    let data = auction_house::instruction::Sell {
+        trade_state_bump,
+        free_trade_state_bump,
+        program_as_signer_bump,
+        buyer_price,
+        token_size,
+        expiry,
+    };
+
+    let accounts = auction_house::accounts::Sell {
+        wallet: *wallet,
+        token_account: token.token_account,
+        metadata: token.metadata,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        seller_trade_state,
+        free_seller_trade_state,
+        program_as_signer,
+        token_program: token.token_program,
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(listing_remaining_accounts(
+        auction_house,
+        auction_house_data,
+        wallet,
+        token_metadata,
+    ));
+
+    Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: account_metas,
+    }
+}
+
+/// Place a private bid of `buyer_price` on `token_size` of `token` from `wallet` until `expiry`,
+/// funding the escrow from its treasury payment account.
+pub fn buy(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    wallet: &Pubkey,
+    token: &Token,
+    token_metadata: &TokenMetadata,
+    treasury_token_program: &Pubkey,
+    buyer_price: u64,
+    token_size: u64,
+    expiry: Option<i64>,
+) -> Instruction {
+    let (buyer_trade_state, trade_state_bump) = find_trade_state_address(
+        wallet,
+        auction_house,
+        &token.token_account,
+        &auction_house_data.treasury_mint,
+        &token.mint,
+        buyer_price,
+        token_size,
+    );
+    let (escrow_payment_account, escrow_payment_bump) =
+        find_escrow_payment_account_address(auction_house, wallet);
+
+    let data = auction_house::instruction::Buy {
+        trade_state_bump,
+        escrow_payment_bump,
+        buyer_price,
+        token_size,
+        expiry,
+    };
+
+    let accounts = auction_house::accounts::Buy {
+        wallet: *wallet,
+        payment_account: treasury_payment_account(
+            wallet,
+            &auction_house_data.treasury_mint,
+            treasury_token_program,
+        ),
+        transfer_authority: *wallet,
+        treasury_mint: auction_house_data.treasury_mint,
+        token_account: token.token_account,
+        metadata: token.metadata,
+        escrow_payment_account,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        buyer_trade_state,
+        token_program: *treasury_token_program,
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(listing_remaining_accounts(
+        auction_house,
+        auction_house_data,
+        wallet,
+        token_metadata,
+    ));
+
+    Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: account_metas,
+    }
+}
+
+/// Place a public bid of `buyer_price` on `token_size` of `token` until `expiry`, open to any
+/// seller.
+pub fn public_buy(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    wallet: &Pubkey,
+    token: &Token,
+    token_metadata: &TokenMetadata,
+    treasury_token_program: &Pubkey,
+    buyer_price: u64,
+    token_size: u64,
+    expiry: Option<i64>,
+) -> Instruction {
+    let (buyer_trade_state, trade_state_bump) = find_public_bid_trade_state_address(
+        wallet,
+        auction_house,
+        &auction_house_data.treasury_mint,
+        &token.mint,
+        buyer_price,
+        token_size,
+    );
+    let (escrow_payment_account, escrow_payment_bump) =
+        find_escrow_payment_account_address(auction_house, wallet);
+
+    let data = auction_house::instruction::PublicBuy {
+        trade_state_bump,
+        escrow_payment_bump,
+        buyer_price,
+        token_size,
+        expiry,
+    };
+
+    let accounts = auction_house::accounts::PublicBuy {
+        wallet: *wallet,
+        payment_account: treasury_payment_account(
+            wallet,
+            &auction_house_data.treasury_mint,
+            treasury_token_program,
+        ),
+        transfer_authority: *wallet,
+        treasury_mint: auction_house_data.treasury_mint,
+        token_account: token.token_account,
+        metadata: token.metadata,
+        escrow_payment_account,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        buyer_trade_state,
+        token_program: *treasury_token_program,
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(listing_remaining_accounts(
+        auction_house,
+        auction_house_data,
+        wallet,
+        token_metadata,
+    ));
+
+    Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: account_metas,
+    }
+}
+
+/// Match the `seller` listing of `token` at `buyer_price` with the bid at `buyer_trade_state`.
+///
+/// The creator accounts royalties are paid to are computed from `token_metadata`.
+pub fn execute_sale(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    buyer: &Pubkey,
+    seller: &Pubkey,
+    token: &Token,
+    token_metadata: &TokenMetadata,
+    buyer_trade_state: &Pubkey,
+    treasury_token_program: &Pubkey,
+    buyer_price: u64,
+    token_size: u64,
+    partial_order_size: Option<u64>,
+    partial_order_price: Option<u64>,
+) -> Instruction {
+    let (seller_trade_state, _) = find_trade_state_address(
+        seller,
+        auction_house,
+        &token.token_account,
+        &auction_house_data.treasury_mint,
+        &token.mint,
+        buyer_price,
+        token_size,
+    );
+    let (free_trade_state, free_trade_state_bump) = find_trade_state_address(
+        seller,
+        auction_house,
+        &token.token_account,
+        &auction_house_data.treasury_mint,
+        &token.mint,
+        0,
+        token_size,
+    );
+    let (escrow_payment_account, escrow_payment_bump) =
+        find_escrow_payment_account_address(auction_house, buyer);
+    let (program_as_signer, program_as_signer_bump) = find_program_as_signer_address();
+
+    let data = auction_house::instruction::ExecuteSale {
+        escrow_payment_bump,
+        _free_trade_state_bump: free_trade_state_bump,
+        program_as_signer_bump,
+        buyer_price,
+        token_size,
+        partial_order_size,
+        partial_order_price,
+    };
+
+    let accounts = auction_house::accounts::ExecuteSale {
+        buyer: *buyer,
+        seller: *seller,
+        token_account: token.token_account,
+        token_mint: token.mint,
+        metadata: token.metadata,
+        treasury_mint: auction_house_data.treasury_mint,
+        escrow_payment_account,
+        seller_payment_receipt_account: treasury_payment_account(
+            seller,
+            &auction_house_data.treasury_mint,
+            treasury_token_program,
+        ),
+        buyer_receipt_token_account: get_associated_token_address_with_program_id(
+            buyer,
+            &token.mint,
+            &token.token_program,
+        ),
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        auction_house_treasury: auction_house_data.auction_house_treasury,
+        buyer_trade_state: *buyer_trade_state,
+        seller_trade_state,
+        free_trade_state,
+        token_program: token.token_program,
+        system_program: system_program::id(),
+        associated_token_program: associated_token::ID,
+        program_as_signer,
+        rent: sysvar::rent::id(),
+        treasury_token_program: *treasury_token_program,
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(execute_sale_remaining_accounts(
+        auction_house,
+        auction_house_data,
+        buyer,
+        seller,
+        treasury_token_program,
+        token_metadata,
+    ));
+
+    Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: account_metas,
+    }
+}
+
+/// Deposit `amount` from the treasury payment account of `wallet` into its escrow.
+pub fn deposit(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    wallet: &Pubkey,
+    treasury_token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (escrow_payment_account, escrow_payment_bump) =
+        find_escrow_payment_account_address(auction_house, wallet);
+
+    let data = auction_house::instruction::Deposit {
+        escrow_payment_bump,
+        amount,
+    };
+
+    let accounts = auction_house::accounts::Deposit {
+        wallet: *wallet,
+        payment_account: treasury_payment_account(
+            wallet,
+            &auction_house_data.treasury_mint,
+            treasury_token_program,
+        ),
+        transfer_authority: *wallet,
+        escrow_payment_account,
+        treasury_mint: auction_house_data.treasury_mint,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        token_program: *treasury_token_program,
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+    };
+
+    Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    }
+}
+
+/// Withdraw `amount` from the escrow of `wallet` back to its treasury payment account.
+pub fn withdraw(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    wallet: &Pubkey,
+    treasury_token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (escrow_payment_account, escrow_payment_bump) =
+        find_escrow_payment_account_address(auction_house, wallet);
+    let (escrow_lock, _) = find_escrow_lock_address(auction_house, wallet);
+
+    let data = auction_house::instruction::Withdraw {
+        escrow_payment_bump,
+        amount,
+    };
+
+    let accounts = auction_house::accounts::Withdraw {
+        wallet: *wallet,
+        receipt_account: treasury_payment_account(
+            wallet,
+            &auction_house_data.treasury_mint,
+            treasury_token_program,
+        ),
+        escrow_payment_account,
+        treasury_mint: auction_house_data.treasury_mint,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        token_program: *treasury_token_program,
+        system_program: system_program::id(),
+        associated_token_program: associated_token::ID,
+        rent: sysvar::rent::id(),
+        escrow_lock,
+    };
+
+    Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    }
+}
+
+/// Cancel the listing or bid of `wallet` at `trade_state`.
+pub fn cancel(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    wallet: &Pubkey,
+    token: &Token,
+    trade_state: &Pubkey,
+    buyer_price: u64,
+    token_size: u64,
+) -> Instruction {
+    let data = auction_house::instruction::Cancel {
+        buyer_price,
+        token_size,
+    };
+
+    let accounts = auction_house::accounts::Cancel {
+        wallet: *wallet,
+        token_account: token.token_account,
+        token_mint: token.mint,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        trade_state: *trade_state,
+        token_program: token.token_program,
+    };
+
+    Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    }
+}
+
+/// List `token_size` of `token` at `price` until `expiry` in the v2 trade state of `wallet`,
+/// amending the open listing in place if there is one.
+pub fn sell_v2(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    wallet: &Pubkey,
+    token: &Token,
+    token_metadata: &TokenMetadata,
+    price: u64,
+    token_size: u64,
+    expiry: Option<i64>,
+) -> Instruction {
+    let (seller_trade_state, _) =
+        find_trade_state_v2_address(wallet, auction_house, &token.token_account, TradeSide::Sell);
+    let (program_as_signer, _) = find_program_as_signer_address();
+
+    let data = auction_house::instruction::SellV2 {
+        price,
+        token_size,
+        expiry,
+    };
+
+    let accounts = auction_house::accounts::SellV2 {
+        wallet: *wallet,
+        token_account: token.token_account,
+        metadata: token.metadata,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        seller_trade_state,
+        program_as_signer,
+        token_program: token.token_program,
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(listing_remaining_accounts(
+        auction_house,
+        auction_house_data,
+        wallet,
+        token_metadata,
+    ));
+
+    Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: account_metas,
+    }
+}
+
+/// Place or amend the private v2 bid of `wallet` on `token` until `expiry`, funding the escrow as `buy` does.
+pub fn buy_v2(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    wallet: &Pubkey,
+    token: &Token,
+    token_metadata: &TokenMetadata,
+    treasury_token_program: &Pubkey,
+    price: u64,
+    token_size: u64,
+    expiry: Option<i64>,
+) -> Instruction {
+    let (buyer_trade_state, _) =
+        find_trade_state_v2_address(wallet, auction_house, &token.token_account, TradeSide::Buy);
+    let (escrow_payment_account, _) = find_escrow_payment_account_address(auction_house, wallet);
+
+    let data = auction_house::instruction::BuyV2 {
+        price,
+        token_size,
+        expiry,
+    };
+
+    let accounts = auction_house::accounts::BuyV2 {
+        wallet: *wallet,
+        payment_account: treasury_payment_account(
+            wallet,
+            &auction_house_data.treasury_mint,
+            treasury_token_program,
+        ),
+        transfer_authority: *wallet,
+        treasury_mint: auction_house_data.treasury_mint,
+        token_account: token.token_account,
+        metadata: token.metadata,
+        escrow_payment_account,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        buyer_trade_state,
+        token_program: *treasury_token_program,
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(listing_remaining_accounts(
+        auction_house,
+        auction_house_data,
+        wallet,
+        token_metadata,
+    ));
+
+    Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: account_metas,
+    }
+}
+
+/// Place or amend the public v2 bid of `wallet` on the mint of `token` until `expiry`, open to
+/// any seller.
+pub fn public_buy_v2(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    wallet: &Pubkey,
+    token: &Token,
+    token_metadata: &TokenMetadata,
+    treasury_token_program: &Pubkey,
+    price: u64,
+    token_size: u64,
+    expiry: Option<i64>,
+) -> Instruction {
+    let (buyer_trade_state, _) =
+        find_trade_state_v2_address(wallet, auction_house, &token.mint, TradeSide::Buy);
+    let (escrow_payment_account, _) = find_escrow_payment_account_address(auction_house, wallet);
+
+    let data = auction_house::instruction::PublicBuyV2 {
+        price,
+        token_size,
+        expiry,
+    };
+
+    let accounts = auction_house::accounts::PublicBuyV2 {
+        wallet: *wallet,
+        payment_account: treasury_payment_account(
+            wallet,
+            &auction_house_data.treasury_mint,
+            treasury_token_program,
+        ),
+        transfer_authority: *wallet,
+        treasury_mint: auction_house_data.treasury_mint,
+        token_account: token.token_account,
+        metadata: token.metadata,
+        escrow_payment_account,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        buyer_trade_state,
+        token_program: *treasury_token_program,
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(listing_remaining_accounts(
+        auction_house,
+        auction_house_data,
+        wallet,
+        token_metadata,
+    ));
+
+    Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: account_metas,
+    }
+}
+
+/// Match the v2 listing of `seller` on `token`, which must hold `buyer_price` and `token_size`,
+/// with the bid at `buyer_trade_state`.
+///
+/// The creator accounts royalties are paid to are computed from `token_metadata`.
+pub fn execute_sale_v2(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    buyer: &Pubkey,
+    seller: &Pubkey,
+    token: &Token,
+    token_metadata: &TokenMetadata,
+    buyer_trade_state: &Pubkey,
+    treasury_token_program: &Pubkey,
+    buyer_price: u64,
+    token_size: u64,
+    partial_order_size: Option<u64>,
+    partial_order_price: Option<u64>,
+) -> Instruction {
+    let (seller_trade_state, _) =
+        find_trade_state_v2_address(seller, auction_house, &token.token_account, TradeSide::Sell);
+    let (escrow_payment_account, _) = find_escrow_payment_account_address(auction_house, buyer);
+    let (program_as_signer, _) = find_program_as_signer_address();
+
+    let data = auction_house::instruction::ExecuteSaleV2 {
+        buyer_price,
+        token_size,
+        partial_order_size,
+        partial_order_price,
+    };
+
+    let accounts = auction_house::accounts::ExecuteSaleV2 {
+        buyer: *buyer,
+        seller: *seller,
+        token_account: token.token_account,
+        token_mint: token.mint,
+        metadata: token.metadata,
+        treasury_mint: auction_house_data.treasury_mint,
+        escrow_payment_account,
+        seller_payment_receipt_account: treasury_payment_account(
+            seller,
+            &auction_house_data.treasury_mint,
+            treasury_token_program,
+        ),
+        buyer_receipt_token_account: get_associated_token_address_with_program_id(
+            buyer,
+            &token.mint,
+            &token.token_program,
+        ),
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        auction_house_treasury: auction_house_data.auction_house_treasury,
+        buyer_trade_state: *buyer_trade_state,
+        seller_trade_state,
+        token_program: token.token_program,
+        system_program: system_program::id(),
+        associated_token_program: associated_token::ID,
+        program_as_signer,
+        rent: sysvar::rent::id(),
+        treasury_token_program: *treasury_token_program,
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(execute_sale_remaining_accounts(
+        auction_house,
+        auction_house_data,
+        buyer,
+        seller,
+        treasury_token_program,
+        token_metadata,
+    ));
+
+    Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: account_metas,
+    }
+}
+
+/// Close the expired v2 listing or bid `trade_state`, paying `closer` the bounty and refunding the
+/// rest of the rent to `fee_payer`, the account recorded in the trade state.
+pub fn close_expired(closer: &Pubkey, trade_state: &Pubkey, fee_payer: &Pubkey) -> Instruction {
+    let data = auction_house::instruction::CloseExpired {};
+
+    let accounts = auction_house::accounts::CloseExpired {
+        closer: *closer,
+        trade_state: *trade_state,
+        fee_payer: *fee_payer,
+    };
+
+    Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    }
+}
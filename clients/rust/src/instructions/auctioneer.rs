@@ -0,0 +1,680 @@
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{instruction::Instruction, system_program, sysvar},
+    InstructionData, ToAccountMetas,
+};
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id};
+use auction_house::{pda::*, AuctionHouse};
+use auctioneer::pda::*;
+
+use crate::{
+    accounts::TokenMetadata,
+    instructions::treasury_payment_account,
+    remaining_accounts::{execute_sale_remaining_accounts, listing_remaining_accounts},
+    Token,
+};
+
+/// Timing and bidding rules of an auction.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AuctionSettings {
+    pub start_time: i64,
+    pub end_time: i64,
+    pub reserve_price: Option<u64>,
+    pub min_bid_increment: Option<u64>,
+    pub time_ext_period: Option<u32>,
+    pub time_ext_delta: Option<u32>,
+}
+
+/// Create the Auctioneer authority PDA of an Auction House, paid for by `wallet`.
+pub fn authorize(wallet: &Pubkey, auction_house: &Pubkey) -> Instruction {
+    let (auctioneer_authority, _) = find_auctioneer_authority_address(auction_house);
+
+    let accounts = auctioneer::accounts::AuctioneerAuthorize {
+        wallet: *wallet,
+        auction_house: *auction_house,
+        auctioneer_authority,
+        system_program: system_program::id(),
+    };
+
+    Instruction {
+        program_id: auctioneer::id(),
+        data: auctioneer::instruction::Authorize {}.data(),
+        accounts: accounts.to_account_metas(None),
+    }
+}
+
+/// Start an auction of `token_size` of `token`, signed by `wallet`, the token account owner.
+pub fn sell(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    wallet: &Pubkey,
+    token: &Token,
+    token_metadata: &TokenMetadata,
+    token_size: u64,
+    settings: AuctionSettings,
+) -> Instruction {
+    let (seller_trade_state, trade_state_bump) = find_auctioneer_trade_state_address(
+        wallet,
+        auction_house,
+        &token.token_account,
+        &auction_house_data.treasury_mint,
+        &token.mint,
+        token_size,
+    );
+    let (free_seller_trade_state, free_trade_state_bump) = find_trade_state_address(
+        wallet,
+        auction_house,
+        &token.token_account,
+        &auction_house_data.treasury_mint,
+        &token.mint,
+        0,
+        token_size,
+    );
+    let (listing_config, _) = find_listing_config_address(
+        wallet,
+        auction_house,
+        &token.token_account,
+        &auction_house_data.treasury_mint,
+        &token.mint,
+        token_size,
+    );
+    let (program_as_signer, program_as_signer_bump) = find_program_as_signer_address();
+    let (auctioneer_authority, auctioneer_authority_bump) =
+        find_auctioneer_authority_address(auction_house);
+    let (auctioneer, _) = find_auctioneer_address(auction_house, &auctioneer_authority);
+
+    let data = auctioneer::instruction::Sell {
+        trade_state_bump,
+        free_trade_state_bump,
+        program_as_signer_bump,
+        auctioneer_authority_bump,
+        token_size,
+        start_time: settings.start_time,
+        end_time: settings.end_time,
+        reserve_price: settings.reserve_price,
+        min_bid_increment: settings.min_bid_increment,
+        time_ext_period: settings.time_ext_period,
+        time_ext_delta: settings.time_ext_delta,
+    };
+
+    let accounts = auctioneer::accounts::AuctioneerSell {
+        auction_house_program: auction_house::id(),
+        listing_config,
+        wallet: *wallet,
+        token_account: token.token_account,
+        metadata: token.metadata,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        seller_trade_state,
+        free_seller_trade_state,
+        auctioneer_authority,
+        auctioneer,
+        program_as_signer,
+        token_program: token.token_program,
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(listing_remaining_accounts(
+        auction_house,
+        auction_house_data,
+        wallet,
+        token_metadata,
+    ));
+
+    Instruction {
+        program_id: auctioneer::id(),
+        data: data.data(),
+        accounts: account_metas,
+    }
+}
+
+/// Bid `buyer_price` from `wallet` on the `seller` auction of `token_size` of `token`.
+///
+/// `highest_bidder` is the wallet holding the auction's current highest bid, if any, whose escrow
+/// reservation moves to the new bid.
+pub fn buy(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    wallet: &Pubkey,
+    seller: &Pubkey,
+    token: &Token,
+    token_metadata: &TokenMetadata,
+    treasury_token_program: &Pubkey,
+    highest_bidder: Option<&Pubkey>,
+    buyer_price: u64,
+    token_size: u64,
+) -> Instruction {
+    let (buyer_trade_state, trade_state_bump) = find_trade_state_address(
+        wallet,
+        auction_house,
+        &token.token_account,
+        &auction_house_data.treasury_mint,
+        &token.mint,
+        buyer_price,
+        token_size,
+    );
+    let (escrow_payment_account, escrow_payment_bump) =
+        find_escrow_payment_account_address(auction_house, wallet);
+    let (listing_config, _) = find_listing_config_address(
+        seller,
+        auction_house,
+        &token.token_account,
+        &auction_house_data.treasury_mint,
+        &token.mint,
+        token_size,
+    );
+    let (auctioneer_authority, auctioneer_authority_bump) =
+        find_auctioneer_authority_address(auction_house);
+    let (auctioneer, _) = find_auctioneer_address(auction_house, &auctioneer_authority);
+
+    let (escrow_lock, _) = find_escrow_lock_address(auction_house, wallet);
+    let highest_bidder_escrow_lock =
+        highest_bidder.map(|bidder| find_escrow_lock_address(auction_house, bidder).0);
+
+    let data = auctioneer::instruction::Buy {
+        trade_state_bump,
+        escrow_payment_bump,
+        auctioneer_authority_bump,
+        buyer_price,
+        token_size,
+    };
+
+    let accounts = auctioneer::accounts::AuctioneerBuy {
+        auction_house_program: auction_house::id(),
+        listing_config,
+        seller: *seller,
+        wallet: *wallet,
+        payment_account: treasury_payment_account(
+            wallet,
+            &auction_house_data.treasury_mint,
+            treasury_token_program,
+        ),
+        transfer_authority: *wallet,
+        treasury_mint: auction_house_data.treasury_mint,
+        token_account: token.token_account,
+        metadata: token.metadata,
+        escrow_payment_account,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        buyer_trade_state,
+        auctioneer_authority,
+        auctioneer,
+        token_program: *treasury_token_program,
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+        escrow_lock,
+        highest_bidder: highest_bidder.copied(),
+        highest_bidder_escrow_lock,
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(listing_remaining_accounts(
+        auction_house,
+        auction_house_data,
+        wallet,
+        token_metadata,
+    ));
+
+    Instruction {
+        program_id: auctioneer::id(),
+        data: data.data(),
+        accounts: account_metas,
+    }
+}
+
+/// Place a public bid of `buyer_price` from `wallet` on the `seller` auction of `token`.
+///
+/// `highest_bidder` is the wallet holding the auction's current highest bid, if any.
+pub fn public_buy(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    wallet: &Pubkey,
+    seller: &Pubkey,
+    token: &Token,
+    token_metadata: &TokenMetadata,
+    treasury_token_program: &Pubkey,
+    highest_bidder: Option<&Pubkey>,
+    buyer_price: u64,
+    token_size: u64,
+) -> Instruction {
+    let (buyer_trade_state, trade_state_bump) = find_public_bid_trade_state_address(
+        wallet,
+        auction_house,
+        &auction_house_data.treasury_mint,
+        &token.mint,
+        buyer_price,
+        token_size,
+    );
+    let (escrow_payment_account, escrow_payment_bump) =
+        find_escrow_payment_account_address(auction_house, wallet);
+    let (listing_config, _) = find_listing_config_address(
+        seller,
+        auction_house,
+        &token.token_account,
+        &auction_house_data.treasury_mint,
+        &token.mint,
+        token_size,
+    );
+    let (auctioneer_authority, auctioneer_authority_bump) =
+        find_auctioneer_authority_address(auction_house);
+    let (auctioneer, _) = find_auctioneer_address(auction_house, &auctioneer_authority);
+
+    let (escrow_lock, _) = find_escrow_lock_address(auction_house, wallet);
+    let highest_bidder_escrow_lock =
+        highest_bidder.map(|bidder| find_escrow_lock_address(auction_house, bidder).0);
+
+    let data = auctioneer::instruction::PublicBuy {
+        trade_state_bump,
+        escrow_payment_bump,
+        auctioneer_authority_bump,
+        buyer_price,
+        token_size,
+    };
+
+    let accounts = auctioneer::accounts::AuctioneerPublicBuy {
+        auction_house_program: auction_house::id(),
+        listing_config,
+        seller: *seller,
+        wallet: *wallet,
+        payment_account: treasury_payment_account(
+            wallet,
+            &auction_house_data.treasury_mint,
+            treasury_token_program,
+        ),
+        transfer_authority: *wallet,
+        treasury_mint: auction_house_data.treasury_mint,
+        token_account: token.token_account,
+        metadata: token.metadata,
+        escrow_payment_account,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        buyer_trade_state,
+        auctioneer_authority,
+        auctioneer,
+        token_program: *treasury_token_program,
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+        escrow_lock,
+        highest_bidder: highest_bidder.copied(),
+        highest_bidder_escrow_lock,
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(listing_remaining_accounts(
+        auction_house,
+        auction_house_data,
+        wallet,
+        token_metadata,
+    ));
+
+    Instruction {
+        program_id: auctioneer::id(),
+        data: data.data(),
+        accounts: account_metas,
+    }
+}
+
+/// Settle the `seller` auction of `token` with the winning bid at `buyer_trade_state`.
+///
+/// The creator accounts royalties are paid to are computed from `token_metadata`.
+pub fn execute_sale(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    buyer: &Pubkey,
+    seller: &Pubkey,
+    token: &Token,
+    token_metadata: &TokenMetadata,
+    buyer_trade_state: &Pubkey,
+    treasury_token_program: &Pubkey,
+    buyer_price: u64,
+    token_size: u64,
+) -> Instruction {
+    let (seller_trade_state, _) = find_auctioneer_trade_state_address(
+        seller,
+        auction_house,
+        &token.token_account,
+        &auction_house_data.treasury_mint,
+        &token.mint,
+        token_size,
+    );
+    let (free_trade_state, free_trade_state_bump) = find_trade_state_address(
+        seller,
+        auction_house,
+        &token.token_account,
+        &auction_house_data.treasury_mint,
+        &token.mint,
+        0,
+        token_size,
+    );
+    let (listing_config, _) = find_listing_config_address(
+        seller,
+        auction_house,
+        &token.token_account,
+        &auction_house_data.treasury_mint,
+        &token.mint,
+        token_size,
+    );
+    let (escrow_payment_account, escrow_payment_bump) =
+        find_escrow_payment_account_address(auction_house, buyer);
+    let (program_as_signer, program_as_signer_bump) = find_program_as_signer_address();
+    let (auctioneer_authority, auctioneer_authority_bump) =
+        find_auctioneer_authority_address(auction_house);
+    let (auctioneer, _) = find_auctioneer_address(auction_house, &auctioneer_authority);
+    let (escrow_lock, _) = find_escrow_lock_address(auction_house, buyer);
+
+    let data = auctioneer::instruction::ExecuteSale {
+        escrow_payment_bump,
+        free_trade_state_bump,
+        program_as_signer_bump,
+        auctioneer_authority_bump,
+        buyer_price,
+        token_size,
+    };
+
+    let accounts = auctioneer::accounts::AuctioneerExecuteSale {
+        auction_house_program: auction_house::id(),
+        listing_config,
+        buyer: *buyer,
+        seller: *seller,
+        token_account: token.token_account,
+        token_mint: token.mint,
+        metadata: token.metadata,
+        treasury_mint: auction_house_data.treasury_mint,
+        escrow_payment_account,
+        seller_payment_receipt_account: treasury_payment_account(
+            seller,
+            &auction_house_data.treasury_mint,
+            treasury_token_program,
+        ),
+        buyer_receipt_token_account: get_associated_token_address_with_program_id(
+            buyer,
+            &token.mint,
+            &token.token_program,
+        ),
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        auction_house_treasury: auction_house_data.auction_house_treasury,
+        buyer_trade_state: *buyer_trade_state,
+        seller_trade_state,
+        free_trade_state,
+        auctioneer_authority,
+        auctioneer,
+        program_as_signer,
+        token_program: token.token_program,
+        system_program: system_program::id(),
+        associated_token_program: associated_token::ID,
+        rent: sysvar::rent::id(),
+        treasury_token_program: *treasury_token_program,
+        escrow_lock,
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(execute_sale_remaining_accounts(
+        auction_house,
+        auction_house_data,
+        buyer,
+        seller,
+        treasury_token_program,
+        token_metadata,
+    ));
+
+    Instruction {
+        program_id: auctioneer::id(),
+        data: data.data(),
+        accounts: account_metas,
+    }
+}
+
+/// Deposit `amount` from the treasury payment account of `wallet` into its escrow.
+pub fn deposit(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    wallet: &Pubkey,
+    treasury_token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (escrow_payment_account, escrow_payment_bump) =
+        find_escrow_payment_account_address(auction_house, wallet);
+    let (auctioneer_authority, auctioneer_authority_bump) =
+        find_auctioneer_authority_address(auction_house);
+    let (auctioneer, _) = find_auctioneer_address(auction_house, &auctioneer_authority);
+
+    let data = auctioneer::instruction::Deposit {
+        escrow_payment_bump,
+        auctioneer_authority_bump,
+        amount,
+    };
+
+    let accounts = auctioneer::accounts::AuctioneerDeposit {
+        auction_house_program: auction_house::id(),
+        wallet: *wallet,
+        payment_account: treasury_payment_account(
+            wallet,
+            &auction_house_data.treasury_mint,
+            treasury_token_program,
+        ),
+        transfer_authority: *wallet,
+        escrow_payment_account,
+        treasury_mint: auction_house_data.treasury_mint,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        auctioneer_authority,
+        auctioneer,
+        token_program: *treasury_token_program,
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+    };
+
+    Instruction {
+        program_id: auctioneer::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    }
+}
+
+/// Withdraw `amount` from the escrow of `wallet` back to its treasury payment account.
+pub fn withdraw(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    wallet: &Pubkey,
+    treasury_token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (escrow_payment_account, escrow_payment_bump) =
+        find_escrow_payment_account_address(auction_house, wallet);
+    let (auctioneer_authority, auctioneer_authority_bump) =
+        find_auctioneer_authority_address(auction_house);
+    let (auctioneer, _) = find_auctioneer_address(auction_house, &auctioneer_authority);
+    let (escrow_lock, _) = find_escrow_lock_address(auction_house, wallet);
+
+    let data = auctioneer::instruction::Withdraw {
+        escrow_payment_bump,
+        auctioneer_authority_bump,
+        amount,
+    };
+
+    let accounts = auctioneer::accounts::AuctioneerWithdraw {
+        auction_house_program: auction_house::id(),
+        wallet: *wallet,
+        receipt_account: treasury_payment_account(
+            wallet,
+            &auction_house_data.treasury_mint,
+            treasury_token_program,
+        ),
+        escrow_payment_account,
+        treasury_mint: auction_house_data.treasury_mint,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        auctioneer_authority,
+        auctioneer,
+        token_program: *treasury_token_program,
+        system_program: system_program::id(),
+        associated_token_program: associated_token::ID,
+        rent: sysvar::rent::id(),
+        escrow_lock,
+    };
+
+    Instruction {
+        program_id: auctioneer::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    }
+}
+
+/// Close the escrow of `wallet`, returning its balance to its treasury payment account. Fails while
+/// the wallet holds the highest bid of an auction.
+pub fn close_escrow_account(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    wallet: &Pubkey,
+    treasury_token_program: &Pubkey,
+) -> Instruction {
+    let (escrow_payment_account, escrow_payment_bump) =
+        find_escrow_payment_account_address(auction_house, wallet);
+    let (auctioneer_authority, auctioneer_authority_bump) =
+        find_auctioneer_authority_address(auction_house);
+    let (auctioneer, _) = find_auctioneer_address(auction_house, &auctioneer_authority);
+    let (escrow_lock, _) = find_escrow_lock_address(auction_house, wallet);
+
+    let data = auctioneer::instruction::CloseEscrowAccount {
+        escrow_payment_bump,
+        auctioneer_authority_bump,
+    };
+
+    let accounts = auctioneer::accounts::AuctioneerCloseEscrowAccount {
+        auction_house_program: auction_house::id(),
+        wallet: *wallet,
+        receipt_account: treasury_payment_account(
+            wallet,
+            &auction_house_data.treasury_mint,
+            treasury_token_program,
+        ),
+        escrow_payment_account,
+        treasury_mint: auction_house_data.treasury_mint,
+        auction_house: *auction_house,
+        auctioneer_authority,
+        auctioneer,
+        token_program: *treasury_token_program,
+        system_program: system_program::id(),
+        associated_token_program: associated_token::ID,
+        rent: sysvar::rent::id(),
+        escrow_lock,
+    };
+
+    Instruction {
+        program_id: auctioneer::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    }
+}
+
+/// Cancel the auction or bid of `wallet` at `trade_state` on the `seller` auction of `token`.
+///
+/// Auctions are cancelled with a `buyer_price` of `u64::MAX`, bids with their price.
+/// Sellers cancelling an auction with bids pass its `highest_bidder`, whose escrow reservation is
+/// released.
+pub fn cancel(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    wallet: &Pubkey,
+    seller: &Pubkey,
+    token: &Token,
+    trade_state: &Pubkey,
+    highest_bidder: Option<&Pubkey>,
+    buyer_price: u64,
+    token_size: u64,
+) -> Instruction {
+    let (listing_config, _) = find_listing_config_address(
+        seller,
+        auction_house,
+        &token.token_account,
+        &auction_house_data.treasury_mint,
+        &token.mint,
+        token_size,
+    );
+    let (auctioneer_authority, auctioneer_authority_bump) =
+        find_auctioneer_authority_address(auction_house);
+    let (auctioneer, _) = find_auctioneer_address(auction_house, &auctioneer_authority);
+    let highest_bidder_escrow_lock =
+        highest_bidder.map(|bidder| find_escrow_lock_address(auction_house, bidder).0);
+
+    let data = auctioneer::instruction::Cancel {
+        auctioneer_authority_bump,
+        buyer_price,
+        token_size,
+    };
+
+    let accounts = auctioneer::accounts::AuctioneerCancel {
+        auction_house_program: auction_house::id(),
+        listing_config,
+        seller: *seller,
+        wallet: *wallet,
+        token_account: token.token_account,
+        token_mint: token.mint,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        trade_state: *trade_state,
+        auctioneer_authority,
+        auctioneer,
+        token_program: token.token_program,
+        highest_bidder: highest_bidder.copied(),
+        highest_bidder_escrow_lock,
+    };
+
+    Instruction {
+        program_id: auctioneer::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    }
+}
+
+/// Release the escrow funds locked by the highest bid of `highest_bidder` on the `seller` auction
+/// of `token`, once the grace period after the auction end has passed without a sale.
+pub fn release_escrow_lock(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    seller: &Pubkey,
+    token: &Token,
+    highest_bidder: &Pubkey,
+    token_size: u64,
+) -> Instruction {
+    let (listing_config, _) = find_listing_config_address(
+        seller,
+        auction_house,
+        &token.token_account,
+        &auction_house_data.treasury_mint,
+        &token.mint,
+        token_size,
+    );
+    let (highest_bidder_escrow_lock, _) = find_escrow_lock_address(auction_house, highest_bidder);
+    let (auctioneer_authority, _) = find_auctioneer_authority_address(auction_house);
+    let (auctioneer, _) = find_auctioneer_address(auction_house, &auctioneer_authority);
+
+    let accounts = auctioneer::accounts::ReleaseEscrowLock {
+        auction_house_program: auction_house::id(),
+        listing_config,
+        seller: *seller,
+        token_account: token.token_account,
+        token_mint: token.mint,
+        auction_house: *auction_house,
+        highest_bidder: *highest_bidder,
+        highest_bidder_escrow_lock,
+        auctioneer_authority,
+        auctioneer,
+    };
+
+    Instruction {
+        program_id: auctioneer::id(),
+        data: auctioneer::instruction::ReleaseEscrowLock { token_size }.data(),
+        accounts: accounts.to_account_metas(None),
+    }
+}
@@ -0,0 +1,28 @@
+//! Instruction builders, one module per program.
+//!
+//! Builders taking an `AuctionHouse` read the treasury mint, authority and fee accounts from it
+//! and append the remaining accounts its allowlist and blocklist flags require. Extra remaining
+//! accounts, such as programmable NFT accounts for `sell`, are pushed by the caller.
+
+pub mod auction_house;
+pub mod auctioneer;
+pub mod nft_minter;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+
+use crate::is_native_treasury;
+
+/// Account paying or receiving treasury funds for `wallet`: the wallet itself for native
+/// treasuries, its treasury mint associated token account otherwise.
+pub fn treasury_payment_account(
+    wallet: &Pubkey,
+    treasury_mint: &Pubkey,
+    treasury_token_program: &Pubkey,
+) -> Pubkey {
+    if is_native_treasury(treasury_mint) {
+        *wallet
+    } else {
+        get_associated_token_address_with_program_id(wallet, treasury_mint, treasury_token_program)
+    }
+}
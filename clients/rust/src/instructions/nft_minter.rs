@@ -0,0 +1,78 @@
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{instruction::Instruction, system_program, sysvar},
+    InstructionData, ToAccountMetas,
+};
+use anchor_spl::{associated_token, token};
+use nft_minter::{
+    pda::{find_master_edition_account, find_metadata_account},
+    utils::{token_metadata_program_id, Creator},
+};
+
+/// Create the Metaplex metadata of `mint`, a new mint account signing the transaction.
+pub fn create_token(
+    payer: &Pubkey,
+    mint: &Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+    creators: Option<Vec<Creator>>,
+    seller_fee_basis_points: u16,
+    is_mutable: bool,
+) -> Instruction {
+    let (metadata_account, _) = find_metadata_account(mint);
+
+    let data = nft_minter::instruction::CreateToken {
+        name,
+        symbol,
+        uri,
+        creators,
+        seller_fee_basis_points,
+        is_mutable,
+    };
+
+    let accounts = nft_minter::accounts::CreateToken {
+        payer: *payer,
+        mint_account: *mint,
+        mint_authority: *payer,
+        update_authority: *payer,
+        metadata_account,
+        token_metadata_program: token_metadata_program_id(),
+        system_program: system_program::id(),
+        token_program: token::ID,
+        rent: sysvar::rent::id(),
+    };
+
+    Instruction {
+        program_id: nft_minter::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    }
+}
+
+/// Mint `mint` to the associated token account of `payer` and create its master edition.
+pub fn mint_token(payer: &Pubkey, mint: &Pubkey, max_supply: Option<u64>) -> Instruction {
+    let (metadata_account, _) = find_metadata_account(mint);
+    let (edition_account, _) = find_master_edition_account(mint);
+
+    let accounts = nft_minter::accounts::MintToken {
+        payer: *payer,
+        mint_account: *mint,
+        mint_authority: *payer,
+        update_authority: *payer,
+        associated_token_account: associated_token::get_associated_token_address(payer, mint),
+        metadata_account,
+        edition_account,
+        token_metadata_program: token_metadata_program_id(),
+        system_program: system_program::id(),
+        token_program: token::ID,
+        associated_token_program: associated_token::ID,
+        rent: sysvar::rent::id(),
+    };
+
+    Instruction {
+        program_id: nft_minter::id(),
+        data: nft_minter::instruction::MintToken { max_supply }.data(),
+        accounts: accounts.to_account_metas(None),
+    }
+}
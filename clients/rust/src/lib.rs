@@ -0,0 +1,33 @@
+//! Client for the `auction_house`, `auctioneer` and `nft_minter` programs.
+//!
+//! Instruction builders derive every PDA and bump through the programs' `pda` modules, so callers
+//! only provide the wallets, the token and the prices. Account decoders read the program
+//! accounts fetched over RPC.
+
+#![allow(clippy::result_large_err)]
+#![allow(clippy::too_many_arguments)]
+
+pub mod accounts;
+pub mod decode;
+pub mod instructions;
+pub mod remaining_accounts;
+
+use anchor_lang::prelude::Pubkey;
+
+/// NFT being listed, bid on or sold.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Token {
+    /// Mint of the NFT.
+    pub mint: Pubkey,
+    /// Seller token account holding the NFT.
+    pub token_account: Pubkey,
+    /// Metaplex metadata PDA, or the mint itself for Token-2022 token metadata.
+    pub metadata: Pubkey,
+    /// Token program owning the mint, either SPL Token or Token-2022.
+    pub token_program: Pubkey,
+}
+
+/// Whether the treasury of an Auction House is native SOL.
+pub fn is_native_treasury(treasury_mint: &Pubkey) -> bool {
+    *treasury_mint == spl_token::native_mint::id()
+}
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use auction_house::{
+    metadata::Royalties,
+    pda::{
+        find_allowed_collection_address, find_blocked_wallet_address, find_royalty_vault_address,
+        find_withdrawal_policy_address,
+    },
+    AuctionHouse,
+};
+
+use crate::{accounts::TokenMetadata, is_native_treasury};
+
+/// Remaining accounts of `sell`, `buy` and `public_buy`: the allowed collection PDA if the
+/// Auction House has a collection allowlist, then the wallet's blocked wallet PDA if it has a
+/// blocklist.
+pub fn listing_remaining_accounts(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    wallet: &Pubkey,
+    token_metadata: &TokenMetadata,
+) -> Vec<AccountMeta> {
+    let mut accounts = Vec::new();
+
+    if auction_house_data.has_collection_allowlist {
+        // NFTs without a verified collection are rejected whatever is passed.
+        let collection = token_metadata.collection.unwrap_or_default();
+        accounts.push(AccountMeta::new_readonly(
+            find_allowed_collection_address(auction_house, &collection).0,
+            false,
+        ));
+    }
+
+    if auction_house_data.has_blocklist {
+        accounts.push(AccountMeta::new_readonly(
+            find_blocked_wallet_address(auction_house, wallet).0,
+            false,
+        ));
+    }
+
+    accounts
+}
+
+/// Remaining accounts of `withdraw_from_fee` and `withdraw_from_treasury`: the withdrawal policy
+/// PDA if the Auction House has one.
+pub fn withdrawal_policy_remaining_accounts(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+) -> Vec<AccountMeta> {
+    if !auction_house_data.has_withdrawal_policy {
+        return Vec::new();
+    }
+
+    vec![AccountMeta::new(
+        find_withdrawal_policy_address(auction_house).0,
+        false,
+    )]
+}
+
+/// Remaining accounts of `execute_sale`: the buyer and seller blocked wallet PDAs if the Auction
+/// House has a blocklist, then the royalty accounts of every creator.
+pub fn execute_sale_remaining_accounts(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    buyer: &Pubkey,
+    seller: &Pubkey,
+    treasury_token_program: &Pubkey,
+    token_metadata: &TokenMetadata,
+) -> Vec<AccountMeta> {
+    let mut accounts = Vec::new();
+
+    if auction_house_data.has_blocklist {
+        for wallet in [buyer, seller] {
+            accounts.push(AccountMeta::new_readonly(
+                find_blocked_wallet_address(auction_house, wallet).0,
+                false,
+            ));
+        }
+    }
+
+    accounts.extend(royalty_accounts(
+        auction_house,
+        &auction_house_data.treasury_mint,
+        treasury_token_program,
+        &token_metadata.royalties,
+    ));
+
+    accounts
+}
+
+/// Each creator followed by its royalty vault for native treasuries, or by its treasury mint
+/// associated token account otherwise, in metadata order.
+pub fn royalty_accounts(
+    auction_house: &Pubkey,
+    treasury_mint: &Pubkey,
+    treasury_token_program: &Pubkey,
+    royalties: &Royalties,
+) -> Vec<AccountMeta> {
+    let is_native = is_native_treasury(treasury_mint);
+
+    royalties
+        .creators
+        .iter()
+        .flatten()
+        .flat_map(|creator| {
+            let creator_account = if is_native {
+                find_royalty_vault_address(auction_house, &creator.address).0
+            } else {
+                get_associated_token_address_with_program_id(
+                    &creator.address,
+                    treasury_mint,
+                    treasury_token_program,
+                )
+            };
+
+            [
+                AccountMeta::new(creator.address, false),
+                AccountMeta::new(creator_account, false),
+            ]
+        })
+        .collect()
+}
@@ -0,0 +1,326 @@
+use anchor_lang::prelude::Pubkey;
+use auction_house::{
+    pda::{
+        find_escrow_payment_account_address, find_trade_state_address, find_trade_state_v2_address,
+    },
+    trade_state::TradeSide,
+};
+use auctioneer::pda::find_listing_config_address;
+use nft_auction_client::{
+    accounts::TokenMetadata,
+    decode::{decode_instruction, MarketInstruction},
+    instructions,
+};
+
+mod utils;
+use utils::setup_functions::*;
+
+fn token_metadata() -> TokenMetadata {
+    TokenMetadata {
+        collection: None,
+        royalties: auction_house::metadata::Royalties {
+            seller_fee_basis_points: 0,
+            creators: None,
+        },
+    }
+}
+
+fn decode(ix: &anchor_lang::solana_program::instruction::Instruction) -> Option<MarketInstruction> {
+    let accounts: Vec<Pubkey> = ix.accounts.iter().map(|account| account.pubkey).collect();
+    decode_instruction(&ix.program_id, &accounts, &ix.data)
+}
+
+#[test]
+fn decode_auction_house_sell_and_partial_sale() {
+    let auction_house = Pubkey::new_unique();
+    let auction_house_data = auction_house_data(spl_token::native_mint::id(), false);
+    let seller = Pubkey::new_unique();
+    let buyer = Pubkey::new_unique();
+    let token = token();
+
+    let ix = instructions::auction_house::sell(
+        &auction_house,
+        &auction_house_data,
+        &seller,
+        &token,
+        &token_metadata(),
+        1_000,
+        10,
+        None,
+    );
+    let (seller_trade_state, _) = find_trade_state_address(
+        &seller,
+        &auction_house,
+        &token.token_account,
+        &auction_house_data.treasury_mint,
+        &token.mint,
+        1_000,
+        10,
+    );
+
+    let listing = Some(MarketInstruction::Listing {
+        auction_house,
+        seller,
+        token_account: token.token_account,
+        metadata: token.metadata,
+        trade_state: seller_trade_state,
+        price: Some(1_000),
+        token_size: 10,
+        listing_config: None,
+    });
+    assert_eq!(decode(&ix), listing);
+
+    // Listings sent before the expiry argument existed end after the token size.
+    let mut ix_without_expiry = ix.clone();
+    ix_without_expiry.data.pop();
+    assert_eq!(decode(&ix_without_expiry), listing);
+
+    let buyer_trade_state = Pubkey::new_unique();
+    let ix = instructions::auction_house::execute_sale(
+        &auction_house,
+        &auction_house_data,
+        &buyer,
+        &seller,
+        &token,
+        &token_metadata(),
+        &buyer_trade_state,
+        &spl_token::id(),
+        1_000,
+        10,
+        Some(4),
+        Some(400),
+    );
+
+    assert_eq!(
+        decode(&ix),
+        Some(MarketInstruction::Sale {
+            auction_house,
+            buyer,
+            seller,
+            token_mint: token.mint,
+            metadata: token.metadata,
+            buyer_trade_state,
+            seller_trade_state,
+            escrow_payment_account: find_escrow_payment_account_address(&auction_house, &buyer).0,
+            price: 400,
+            token_size: 4,
+            is_partial: true,
+        })
+    );
+}
+
+#[test]
+fn decode_auction_house_v2_listing_bid_sale_and_expiration() {
+    let auction_house = Pubkey::new_unique();
+    let auction_house_data = auction_house_data(spl_token::native_mint::id(), false);
+    let seller = Pubkey::new_unique();
+    let buyer = Pubkey::new_unique();
+    let token = token();
+
+    let ix = instructions::auction_house::sell_v2(
+        &auction_house,
+        &auction_house_data,
+        &seller,
+        &token,
+        &token_metadata(),
+        1_000,
+        1,
+        Some(1_700_000_000),
+    );
+    let (seller_trade_state, _) = find_trade_state_v2_address(
+        &seller,
+        &auction_house,
+        &token.token_account,
+        TradeSide::Sell,
+    );
+
+    assert_eq!(
+        decode(&ix),
+        Some(MarketInstruction::Listing {
+            auction_house,
+            seller,
+            token_account: token.token_account,
+            metadata: token.metadata,
+            trade_state: seller_trade_state,
+            price: Some(1_000),
+            token_size: 1,
+            listing_config: None,
+        })
+    );
+
+    let ix = instructions::auction_house::public_buy_v2(
+        &auction_house,
+        &auction_house_data,
+        &buyer,
+        &token,
+        &token_metadata(),
+        &spl_token::id(),
+        1_000,
+        1,
+        None,
+    );
+    let (buyer_trade_state, _) =
+        find_trade_state_v2_address(&buyer, &auction_house, &token.mint, TradeSide::Buy);
+    let escrow_payment_account = find_escrow_payment_account_address(&auction_house, &buyer).0;
+
+    assert_eq!(
+        decode(&ix),
+        Some(MarketInstruction::Bid {
+            auction_house,
+            buyer,
+            token_account: token.token_account,
+            metadata: token.metadata,
+            trade_state: buyer_trade_state,
+            escrow_payment_account,
+            price: 1_000,
+            token_size: 1,
+            is_public: true,
+            listing_config: None,
+        })
+    );
+
+    let ix = instructions::auction_house::execute_sale_v2(
+        &auction_house,
+        &auction_house_data,
+        &buyer,
+        &seller,
+        &token,
+        &token_metadata(),
+        &buyer_trade_state,
+        &spl_token::id(),
+        1_000,
+        1,
+        None,
+        None,
+    );
+
+    assert_eq!(
+        decode(&ix),
+        Some(MarketInstruction::Sale {
+            auction_house,
+            buyer,
+            seller,
+            token_mint: token.mint,
+            metadata: token.metadata,
+            buyer_trade_state,
+            seller_trade_state,
+            escrow_payment_account,
+            price: 1_000,
+            token_size: 1,
+            is_partial: false,
+        })
+    );
+
+    let closer = Pubkey::new_unique();
+    let ix = instructions::auction_house::close_expired(
+        &closer,
+        &buyer_trade_state,
+        &Pubkey::new_unique(),
+    );
+
+    assert_eq!(
+        decode(&ix),
+        Some(MarketInstruction::Expiration {
+            trade_state: buyer_trade_state,
+            closer,
+        })
+    );
+}
+
+#[test]
+fn decode_auctioneer_bid_and_withdraw() {
+    let auction_house = Pubkey::new_unique();
+    let auction_house_data = auction_house_data(spl_token::native_mint::id(), false);
+    let seller = Pubkey::new_unique();
+    let buyer = Pubkey::new_unique();
+    let token = token();
+
+    let ix = instructions::auctioneer::buy(
+        &auction_house,
+        &auction_house_data,
+        &buyer,
+        &seller,
+        &token,
+        &token_metadata(),
+        &spl_token::id(),
+        None,
+        500,
+        1,
+    );
+    let (escrow_payment_account, _) = find_escrow_payment_account_address(&auction_house, &buyer);
+
+    assert_eq!(
+        decode(&ix),
+        Some(MarketInstruction::Bid {
+            auction_house,
+            buyer,
+            token_account: token.token_account,
+            metadata: token.metadata,
+            trade_state: find_trade_state_address(
+                &buyer,
+                &auction_house,
+                &token.token_account,
+                &auction_house_data.treasury_mint,
+                &token.mint,
+                500,
+                1,
+            )
+            .0,
+            escrow_payment_account,
+            price: 500,
+            token_size: 1,
+            is_public: false,
+            listing_config: Some(
+                find_listing_config_address(
+                    &seller,
+                    &auction_house,
+                    &token.token_account,
+                    &auction_house_data.treasury_mint,
+                    &token.mint,
+                    1,
+                )
+                .0
+            ),
+        })
+    );
+
+    let ix = instructions::auctioneer::withdraw(
+        &auction_house,
+        &auction_house_data,
+        &buyer,
+        &spl_token::id(),
+        200,
+    );
+
+    assert_eq!(
+        decode(&ix),
+        Some(MarketInstruction::Withdraw {
+            auction_house,
+            wallet: buyer,
+            escrow_payment_account,
+            amount: 200,
+        })
+    );
+}
+
+#[test]
+fn decode_other_instructions() {
+    let auction_house = Pubkey::new_unique();
+    let auction_house_data = auction_house_data(spl_token::native_mint::id(), false);
+
+    // Admin instructions are not marketplace activity.
+    let ix = instructions::auctioneer::authorize(&Pubkey::new_unique(), &auction_house);
+    assert_eq!(decode(&ix), None);
+
+    // Nor are instructions of other programs with the same data.
+    let mut ix = instructions::auction_house::deposit(
+        &auction_house,
+        &auction_house_data,
+        &Pubkey::new_unique(),
+        &spl_token::id(),
+        100,
+    );
+    assert!(decode(&ix).is_some());
+    ix.program_id = spl_token::id();
+    assert_eq!(decode(&ix), None);
+}
@@ -0,0 +1,121 @@
+use anchor_lang::{prelude::Pubkey, AccountSerialize};
+use anchor_spl::associated_token::get_associated_token_address;
+use auction_house::{
+    metadata::{Royalties, RoyaltyCreator},
+    pda::{find_blocked_wallet_address, find_royalty_vault_address},
+    Auctioneer,
+};
+use nft_auction_client::{
+    accounts::{decode_auction_house, decode_auctioneer, TokenMetadata},
+    remaining_accounts::{execute_sale_remaining_accounts, royalty_accounts},
+};
+
+mod utils;
+use utils::setup_functions::*;
+
+fn royalties(creators: &[Pubkey]) -> Royalties {
+    Royalties {
+        seller_fee_basis_points: 500,
+        creators: Some(
+            creators
+                .iter()
+                .map(|address| RoyaltyCreator {
+                    address: *address,
+                    share: (100 / creators.len()) as u8,
+                })
+                .collect(),
+        ),
+    }
+}
+
+#[test]
+fn royalty_accounts_native_treasury() {
+    let auction_house = Pubkey::new_unique();
+    let creators = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+    let accounts = royalty_accounts(
+        &auction_house,
+        &spl_token::native_mint::id(),
+        &spl_token::id(),
+        &royalties(&creators),
+    );
+
+    let keys: Vec<Pubkey> = accounts.iter().map(|account| account.pubkey).collect();
+    assert_eq!(
+        keys,
+        vec![
+            creators[0],
+            find_royalty_vault_address(&auction_house, &creators[0]).0,
+            creators[1],
+            find_royalty_vault_address(&auction_house, &creators[1]).0,
+        ]
+    );
+    assert!(accounts
+        .iter()
+        .all(|account| account.is_writable && !account.is_signer));
+}
+
+#[test]
+fn execute_sale_remaining_accounts_spl_treasury_with_blocklist() {
+    let auction_house = Pubkey::new_unique();
+    let treasury_mint = Pubkey::new_unique();
+    let auction_house_data = auction_house_data(treasury_mint, true);
+    let buyer = Pubkey::new_unique();
+    let seller = Pubkey::new_unique();
+    let creator = Pubkey::new_unique();
+    let token_metadata = TokenMetadata {
+        collection: None,
+        royalties: royalties(&[creator]),
+    };
+
+    let accounts = execute_sale_remaining_accounts(
+        &auction_house,
+        &auction_house_data,
+        &buyer,
+        &seller,
+        &spl_token::id(),
+        &token_metadata,
+    );
+
+    let keys: Vec<Pubkey> = accounts.iter().map(|account| account.pubkey).collect();
+    assert_eq!(
+        keys,
+        vec![
+            find_blocked_wallet_address(&auction_house, &buyer).0,
+            find_blocked_wallet_address(&auction_house, &seller).0,
+            creator,
+            get_associated_token_address(&creator, &treasury_mint),
+        ]
+    );
+}
+
+#[test]
+fn decode_program_accounts() {
+    let auction_house_data = auction_house_data(spl_token::native_mint::id(), false);
+    let mut data = Vec::new();
+    auction_house_data.try_serialize(&mut data).unwrap();
+
+    let decoded = decode_auction_house(&data).unwrap();
+    assert_eq!(decoded.authority, auction_house_data.authority);
+    assert_eq!(decoded.version, auction_house_data.version);
+
+    // Discriminators keep one account type from decoding as another.
+    assert!(decode_auctioneer(&data).is_err());
+
+    let auctioneer = Auctioneer {
+        auctioneer_authority: Pubkey::new_unique(),
+        auction_house: Pubkey::new_unique(),
+        bump: 255,
+        scopes: 0b11,
+        locked_amount: 0,
+    };
+    let mut data = Vec::new();
+    auctioneer.try_serialize(&mut data).unwrap();
+
+    let decoded = decode_auctioneer(&data).unwrap();
+    assert_eq!(
+        decoded.auctioneer_authority,
+        auctioneer.auctioneer_authority
+    );
+    assert_eq!(decoded.scopes, auctioneer.scopes);
+}
@@ -0,0 +1,43 @@
+#![allow(dead_code)]
+
+use anchor_lang::prelude::Pubkey;
+use auction_house::AuctionHouse;
+use nft_auction_client::Token;
+
+pub fn auction_house_data(treasury_mint: Pubkey, has_blocklist: bool) -> AuctionHouse {
+    AuctionHouse {
+        auction_house_fee_account: Pubkey::new_unique(),
+        auction_house_treasury: Pubkey::new_unique(),
+        treasury_withdrawal_destination: Pubkey::new_unique(),
+        fee_withdrawal_destination: Pubkey::new_unique(),
+        treasury_mint,
+        authority: Pubkey::new_unique(),
+        creator: Pubkey::new_unique(),
+        bump: 255,
+        treasury_bump: 254,
+        fee_payer_bump: 253,
+        seller_fee_basis_points: 100,
+        can_change_sale_price: false,
+        escrow_payment_bump: 0,
+        has_auctioneer: false,
+        auctioneer_address: Pubkey::default(),
+        auctioneer_count: 0,
+        pending_authority: Pubkey::default(),
+        pending_treasury_withdrawal_destination: Pubkey::default(),
+        pending_fee_withdrawal_destination: Pubkey::default(),
+        has_withdrawal_policy: false,
+        is_paused: false,
+        has_collection_allowlist: false,
+        has_blocklist,
+        version: 1,
+    }
+}
+
+pub fn token() -> Token {
+    Token {
+        mint: Pubkey::new_unique(),
+        token_account: Pubkey::new_unique(),
+        metadata: Pubkey::new_unique(),
+        token_program: spl_token::id(),
+    }
+}
@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
 
-use crate::{constants::*, errors::AuctionHouseError, AuctionHouse, Auctioneer};
+use crate::{
+    constants::*, errors::AuctionHouseError, events::AuctioneerDelegated, AuctionHouse, Auctioneer,
+    AuthorityScope,
+};
 
 #[derive(Accounts)]
 pub struct DelegateAuctioneer<'info> {
@@ -43,13 +46,18 @@ pub struct DelegateAuctioneer<'info> {
 
 pub fn delegate_auctioneer<'info>(
     ctx: Context<'_, '_, '_, 'info, DelegateAuctioneer<'info>>,
+    scopes: Vec<AuthorityScope>,
 ) -> Result<()> {
     let auction_house = &mut ctx.accounts.auction_house;
 
-    if auction_house.has_auctioneer {
-        return Err(AuctionHouseError::AuctionHouseAlreadyDelegated.into());
+    if scopes.is_empty() {
+        return Err(AuctionHouseError::MissingAuctioneerScopes.into());
     }
 
+    auction_house.auctioneer_count = auction_house
+        .delegated_auctioneer_count()
+        .checked_add(1)
+        .ok_or(AuctionHouseError::NumericalOverflow)?;
     auction_house.has_auctioneer = true;
     auction_house.auctioneer_address = ctx.accounts.auctioneer.key();
 
@@ -60,6 +68,13 @@ pub fn delegate_auctioneer<'info>(
         .bumps
         .get("auctioneer")
         .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+    auctioneer.set_scopes(&scopes);
+
+    emit!(AuctioneerDelegated {
+        auction_house: auctioneer.auction_house,
+        auctioneer_authority: auctioneer.auctioneer_authority,
+        scopes,
+    });
 
     Ok(())
 }
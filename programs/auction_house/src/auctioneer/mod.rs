@@ -1,2 +1,6 @@
 pub mod delegate;
+pub mod undelegate;
+pub mod update;
 pub use delegate::*;
+pub use undelegate::*;
+pub use update::*;
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*, errors::AuctionHouseError, events::AuctioneerUndelegated, AuctionHouse,
+    Auctioneer,
+};
+
+#[derive(Accounts)]
+pub struct UndelegateAuctioneer<'info> {
+    // Auction House instance PDA account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump = auction_house.bump,
+        has_one = authority
+    )]
+    pub auction_house: Account<'info, AuctionHouse>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Verified in auctioneer seeds check.
+    /// The auctioneer authority - the program PDA running this auction.
+    pub auctioneer_authority: UncheckedAccount<'info>,
+
+    /// The auctioneer PDA owned by Auction House, closed to the authority.
+    #[account(
+        mut,
+        close = authority,
+        seeds = [
+            AUCTIONEER.as_bytes(),
+            auction_house.key().as_ref(),
+            auctioneer_authority.key().as_ref()
+        ],
+        bump = auctioneer.bump
+    )]
+    pub auctioneer: Account<'info, Auctioneer>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn undelegate_auctioneer<'info>(
+    ctx: Context<'_, '_, '_, 'info, UndelegateAuctioneer<'info>>,
+) -> Result<()> {
+    let auction_house = &mut ctx.accounts.auction_house;
+
+    if !auction_house.has_auctioneer {
+        return Err(AuctionHouseError::AuctionHouseNotDelegated.into());
+    }
+
+    // Escrow locks are only released by the auctioneer that placed them.
+    if ctx.accounts.auctioneer.locked_amount > 0 {
+        return Err(AuctionHouseError::AuctioneerHoldsEscrowLocks.into());
+    }
+
+    auction_house.auctioneer_count = auction_house
+        .delegated_auctioneer_count()
+        .checked_sub(1)
+        .ok_or(AuctionHouseError::NumericalOverflow)?;
+    auction_house.has_auctioneer = auction_house.auctioneer_count > 0;
+
+    if auction_house.auctioneer_address == ctx.accounts.auctioneer.key() {
+        auction_house.auctioneer_address = Pubkey::default();
+    }
+
+    emit!(AuctioneerUndelegated {
+        auction_house: auction_house.key(),
+        auctioneer_authority: ctx.accounts.auctioneer_authority.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*, errors::AuctionHouseError, events::AuctioneerUpdated, AuctionHouse, Auctioneer,
+    AuthorityScope,
+};
+
+#[derive(Accounts)]
+pub struct UpdateAuctioneer<'info> {
+    // Auction House instance PDA account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump = auction_house.bump,
+        has_one = authority
+    )]
+    pub auction_house: Account<'info, AuctionHouse>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Verified in auctioneer seeds check.
+    /// The currently delegated auctioneer authority.
+    pub auctioneer_authority: UncheckedAccount<'info>,
+
+    /// The currently delegated auctioneer PDA, closed to the authority.
+    #[account(
+        mut,
+        close = authority,
+        seeds = [
+            AUCTIONEER.as_bytes(),
+            auction_house.key().as_ref(),
+            auctioneer_authority.key().as_ref()
+        ],
+        bump = auctioneer.bump
+    )]
+    pub auctioneer: Account<'info, Auctioneer>,
+
+    /// CHECK: The auction house authority can set this to whatever external address they wish.
+    /// The new auctioneer authority - the program PDA that will run auctions from now on.
+    pub new_auctioneer_authority: UncheckedAccount<'info>,
+
+    /// The new auctioneer PDA owned by Auction House.
+    #[account(
+        init,
+        payer = authority,
+        space = AUCTIONEER_SIZE,
+        seeds = [
+            AUCTIONEER.as_bytes(),
+            auction_house.key().as_ref(),
+            new_auctioneer_authority.key().as_ref()
+        ],
+        bump
+    )]
+    pub new_auctioneer: Account<'info, Auctioneer>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn update_auctioneer<'info>(
+    ctx: Context<'_, '_, '_, 'info, UpdateAuctioneer<'info>>,
+    scopes: Vec<AuthorityScope>,
+) -> Result<()> {
+    let auction_house = &mut ctx.accounts.auction_house;
+
+    if !auction_house.has_auctioneer {
+        return Err(AuctionHouseError::AuctionHouseNotDelegated.into());
+    }
+
+    // Escrow locks are only released by the auctioneer that placed them.
+    if ctx.accounts.auctioneer.locked_amount > 0 {
+        return Err(AuctionHouseError::AuctioneerHoldsEscrowLocks.into());
+    }
+
+    if scopes.is_empty() {
+        return Err(AuctionHouseError::MissingAuctioneerScopes.into());
+    }
+
+    if auction_house.auctioneer_address == ctx.accounts.auctioneer.key() {
+        auction_house.auctioneer_address = ctx.accounts.new_auctioneer.key();
+    }
+
+    let new_auctioneer = &mut ctx.accounts.new_auctioneer;
+    new_auctioneer.auctioneer_authority = ctx.accounts.new_auctioneer_authority.key();
+    new_auctioneer.auction_house = ctx.accounts.auction_house.key();
+    new_auctioneer.bump = *ctx
+        .bumps
+        .get("new_auctioneer")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+    new_auctioneer.set_scopes(&scopes);
+
+    emit!(AuctioneerUpdated {
+        auction_house: new_auctioneer.auction_house,
+        auctioneer_authority: ctx.accounts.auctioneer_authority.key(),
+        new_auctioneer_authority: new_auctioneer.auctioneer_authority,
+        scopes,
+    });
+
+    Ok(())
+}
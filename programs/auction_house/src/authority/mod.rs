@@ -0,0 +1,206 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenInterface},
+};
+
+use crate::{
+    constants::*,
+    errors::AuctionHouseError,
+    events::{AuctionHouseUpdated, AuthorityChangeProposed},
+    utils::*,
+    AuctionHouse,
+};
+
+/// Accounts for the [`propose_authority` handler](auction_house/fn.propose_authority.html).
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    /// Treasury mint account, either native SOL mint or a SPL Token or Token-2022 mint.
+    pub treasury_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Authority key for the Auction House.
+    pub authority: Signer<'info>,
+
+    /// CHECK: Has to sign `accept_authority` before it takes over.
+    /// Proposed authority key for the Auction House.
+    pub new_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Has to sign `accept_authority` before it is used.
+    /// Proposed account to receive Auction House fee withdrawals.
+    pub fee_withdrawal_destination: UncheckedAccount<'info>,
+
+    /// CHECK: Its owner has to sign `accept_authority` before it is used.
+    /// Proposed SOL or SPL token account to receive Auction House treasury withdrawals.
+    pub treasury_withdrawal_destination: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref(), treasury_mint.key().as_ref()], bump=auction_house.bump, has_one=authority, has_one=treasury_mint)]
+    pub auction_house: Account<'info, AuctionHouse>,
+}
+
+/// Accounts for the [`accept_authority` handler](auction_house/fn.accept_authority.html).
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    /// Treasury mint account, either native SOL mint or a SPL Token or Token-2022 mint.
+    pub treasury_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Key paying SOL fees for creating the treasury withdrawal destination token account.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Has to match and sign for a pending authority.
+    /// Authority key for the Auction House, or the pending one if it is being changed.
+    pub new_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Has to match and sign for a pending fee withdrawal destination.
+    /// Account to receive Auction House fee withdrawals.
+    pub fee_withdrawal_destination: UncheckedAccount<'info>,
+
+    /// CHECK: Has to match a pending treasury withdrawal destination.
+    /// SOL or SPL token account to receive Auction House treasury withdrawals.
+    #[account(mut)]
+    pub treasury_withdrawal_destination: UncheckedAccount<'info>,
+
+    /// CHECK: Has to sign for a pending treasury withdrawal destination.
+    /// Owner of the `treasury_withdrawal_destination` account or the same address if the `treasury_mint` is native.
+    pub treasury_withdrawal_destination_owner: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref(), treasury_mint.key().as_ref()], bump=auction_house.bump, has_one=treasury_mint)]
+    pub auction_house: Account<'info, AuctionHouse>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn propose_authority<'info>(
+    ctx: Context<'_, '_, '_, 'info, ProposeAuthority<'info>>,
+) -> Result<()> {
+    let auction_house = &mut ctx.accounts.auction_house;
+
+    // Keys left as they are clear any earlier proposal for them.
+    auction_house.pending_authority =
+        pending_key(ctx.accounts.new_authority.key(), auction_house.authority);
+    auction_house.pending_fee_withdrawal_destination = pending_key(
+        ctx.accounts.fee_withdrawal_destination.key(),
+        auction_house.fee_withdrawal_destination,
+    );
+    auction_house.pending_treasury_withdrawal_destination = pending_key(
+        ctx.accounts.treasury_withdrawal_destination.key(),
+        auction_house.treasury_withdrawal_destination,
+    );
+
+    emit!(AuthorityChangeProposed {
+        auction_house: auction_house.key(),
+        pending_authority: auction_house.pending_authority,
+        pending_treasury_withdrawal_destination: auction_house
+            .pending_treasury_withdrawal_destination,
+        pending_fee_withdrawal_destination: auction_house.pending_fee_withdrawal_destination,
+    });
+
+    Ok(())
+}
+
+pub fn accept_authority<'info>(
+    ctx: Context<'_, '_, '_, 'info, AcceptAuthority<'info>>,
+) -> Result<()> {
+    let treasury_mint = &ctx.accounts.treasury_mint;
+    let payer = &ctx.accounts.payer;
+    let new_authority = &ctx.accounts.new_authority;
+    let fee_withdrawal_destination = &ctx.accounts.fee_withdrawal_destination;
+    let treasury_withdrawal_destination = &ctx.accounts.treasury_withdrawal_destination;
+    let treasury_withdrawal_destination_owner = &ctx.accounts.treasury_withdrawal_destination_owner;
+    let auction_house = &mut ctx.accounts.auction_house;
+
+    if auction_house.pending_authority == Pubkey::default()
+        && auction_house.pending_fee_withdrawal_destination == Pubkey::default()
+        && auction_house.pending_treasury_withdrawal_destination == Pubkey::default()
+    {
+        return Err(AuctionHouseError::NoPendingAuthorityChange.into());
+    }
+
+    if auction_house.pending_authority != Pubkey::default() {
+        assert_keys_equal(new_authority.key(), auction_house.pending_authority)?;
+        assert_signer(new_authority)?;
+        auction_house.authority = new_authority.key();
+    }
+
+    if auction_house.pending_fee_withdrawal_destination != Pubkey::default() {
+        assert_keys_equal(
+            fee_withdrawal_destination.key(),
+            auction_house.pending_fee_withdrawal_destination,
+        )?;
+        assert_signer(fee_withdrawal_destination)?;
+        auction_house.fee_withdrawal_destination = fee_withdrawal_destination.key();
+    }
+
+    if auction_house.pending_treasury_withdrawal_destination != Pubkey::default() {
+        assert_keys_equal(
+            treasury_withdrawal_destination.key(),
+            auction_house.pending_treasury_withdrawal_destination,
+        )?;
+        assert_signer(treasury_withdrawal_destination_owner)?;
+
+        if treasury_mint.key() != spl_token::native_mint::id() {
+            if treasury_withdrawal_destination.data_is_empty() {
+                make_ata(
+                    treasury_withdrawal_destination.to_account_info(),
+                    treasury_withdrawal_destination_owner.to_account_info(),
+                    treasury_mint.to_account_info(),
+                    payer.to_account_info(),
+                    ctx.accounts.associated_token_program.to_account_info(),
+                    ctx.accounts.token_program.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                    ctx.accounts.rent.to_account_info(),
+                    &[],
+                )?;
+            }
+
+            assert_is_ata(
+                &treasury_withdrawal_destination.to_account_info(),
+                &treasury_withdrawal_destination_owner.key(),
+                &treasury_mint.key(),
+            )?;
+        } else {
+            assert_keys_equal(
+                treasury_withdrawal_destination.key(),
+                treasury_withdrawal_destination_owner.key(),
+            )?;
+        }
+        auction_house.treasury_withdrawal_destination = treasury_withdrawal_destination.key();
+    }
+
+    auction_house.pending_authority = Pubkey::default();
+    auction_house.pending_fee_withdrawal_destination = Pubkey::default();
+    auction_house.pending_treasury_withdrawal_destination = Pubkey::default();
+
+    emit!(AuctionHouseUpdated {
+        auction_house: auction_house.key(),
+        authority: auction_house.authority,
+        treasury_withdrawal_destination: auction_house.treasury_withdrawal_destination,
+        fee_withdrawal_destination: auction_house.fee_withdrawal_destination,
+        seller_fee_basis_points: auction_house.seller_fee_basis_points,
+        can_change_sale_price: auction_house.can_change_sale_price,
+    });
+
+    Ok(())
+}
+
+/// The key to store as pending, or the default key if it is not being changed.
+fn pending_key(proposed: Pubkey, current: Pubkey) -> Pubkey {
+    if proposed == current {
+        Pubkey::default()
+    } else {
+        proposed
+    }
+}
+
+fn assert_signer(account: &AccountInfo) -> Result<()> {
+    if !account.is_signer {
+        return Err(AuctionHouseError::NoValidSignerPresent.into());
+    }
+
+    Ok(())
+}
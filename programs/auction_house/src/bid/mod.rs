@@ -4,12 +4,444 @@ use anchor_lang::{
     solana_program::{program::invoke, system_instruction},
     AnchorDeserialize,
 };
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
-    constants::*, errors::AuctionHouseError, utils::*, AuctionHouse, Auctioneer, TRADE_STATE_SIZE,
+    blocklist::assert_wallet_not_blocked, collection_allowlist::assert_collection_allowed,
+    constants::*, errors::AuctionHouseError, events::BidPlaced, metadata::assert_metadata_valid,
+    trade_state::*, utils::*, AuctionHouse, Auctioneer, AuthorityScope, TRADE_STATE_SIZE,
 };
 
+#[derive(Accounts)]
+#[instruction(
+    trade_state_bump: u8,
+    escrow_payment_bump: u8,
+    buyer_price: u64,
+    token_size: u64
+)]
+pub struct PublicBuy<'info> {
+    /// User wallet account.
+    wallet: Signer<'info>,
+
+    /// CHECK: Validated in bid_logic.
+    /// User SOL or SPL account to transfer funds from.
+    #[account(mut)]
+    payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in bid_logic.
+    /// SPL token account transfer authority.
+    transfer_authority: UncheckedAccount<'info>,
+
+    /// Auction House instance treasury mint account.
+    treasury_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// SPL token account.
+    token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Validated in bid_logic.
+    /// SPL token account metadata.
+    metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref()
+        ],
+        bump
+    )]
+    escrow_payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Verified with has_one constraint on auction house account.
+    authority: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+            ],
+        bump = auction_house.bump,
+        has_one = authority,
+        has_one = treasury_mint,
+        has_one = auction_house_fee_account
+    )]
+    auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Auction House instance fee account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            FEE_PAYER.as_bytes()
+        ],
+        bump = auction_house.fee_payer_bump
+    )]
+    auction_house_fee_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer public trade state PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            wallet.key().as_ref(),
+            auction_house.key().as_ref(),
+            treasury_mint.key().as_ref(),
+            token_account.mint.as_ref(),
+            buyer_price.to_le_bytes().as_ref(),
+            token_size.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    buyer_trade_state: UncheckedAccount<'info>,
+
+    token_program: Interface<'info, TokenInterface>,
+    system_program: Program<'info, System>,
+    rent: Sysvar<'info, Rent>,
+}
+
+pub fn public_bid<'info>(
+    ctx: Context<'_, '_, '_, 'info, PublicBuy<'info>>,
+    trade_state_bump: u8,
+    escrow_payment_bump: u8,
+    buyer_price: u64,
+    token_size: u64,
+    expiry: Option<i64>,
+) -> Result<()> {
+    if ctx.accounts.auction_house.has_auctioneer {
+        return Err(AuctionHouseError::MustUseAuctioneerHandler.into());
+    }
+
+    bid_logic(
+        ctx.accounts.wallet.to_owned(),
+        ctx.accounts.payment_account.to_owned(),
+        ctx.accounts.transfer_authority.to_owned(),
+        *ctx.accounts.treasury_mint.to_owned(),
+        *ctx.accounts.token_account.to_owned(),
+        ctx.accounts.metadata.to_owned(),
+        ctx.accounts.escrow_payment_account.to_owned(),
+        &mut ctx.accounts.auction_house,
+        ctx.accounts.auction_house_fee_account.to_owned(),
+        ctx.accounts.buyer_trade_state.to_owned(),
+        ctx.accounts.authority.to_owned(),
+        ctx.accounts.token_program.to_owned(),
+        ctx.accounts.system_program.to_owned(),
+        ctx.accounts.rent.to_owned(),
+        ctx.remaining_accounts,
+        trade_state_bump,
+        escrow_payment_bump,
+        buyer_price,
+        token_size,
+        expiry,
+        true,
+        *ctx.bumps
+            .get("escrow_payment_account")
+            .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?,
+        *ctx.bumps
+            .get("buyer_trade_state")
+            .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?,
+    )
+}
+
+#[derive(Accounts)]
+#[instruction(
+    trade_state_bump: u8,
+    escrow_payment_bump: u8,
+    buyer_price: u64,
+    token_size: u64
+)]
+pub struct Buy<'info> {
+    /// User wallet account.
+    wallet: Signer<'info>,
+
+    /// CHECK: Validated in bid_logic.
+    /// User SOL or SPL account to transfer funds from.
+    #[account(mut)]
+    payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in bid_logic.
+    /// SPL token account transfer authority.
+    transfer_authority: UncheckedAccount<'info>,
+
+    /// Auction House instance treasury mint account.
+    treasury_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// SPL token account.
+    token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Validated in bid_logic.
+    /// SPL token account metadata.
+    metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref()
+        ],
+        bump
+    )]
+    escrow_payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Verified with has_one constraint on auction house account.
+    authority: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+            ],
+        bump = auction_house.bump,
+        has_one = authority,
+        has_one = treasury_mint,
+        has_one = auction_house_fee_account
+    )]
+    auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Auction House instance fee account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            FEE_PAYER.as_bytes()
+        ],
+        bump = auction_house.fee_payer_bump
+    )]
+    auction_house_fee_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer trade state PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            wallet.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_account.key().as_ref(),
+            treasury_mint.key().as_ref(),
+            token_account.mint.as_ref(),
+            buyer_price.to_le_bytes().as_ref(),
+            token_size.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    buyer_trade_state: UncheckedAccount<'info>,
+
+    token_program: Interface<'info, TokenInterface>,
+    system_program: Program<'info, System>,
+    rent: Sysvar<'info, Rent>,
+}
+
+pub fn private_bid<'info>(
+    ctx: Context<'_, '_, '_, 'info, Buy<'info>>,
+    trade_state_bump: u8,
+    escrow_payment_bump: u8,
+    buyer_price: u64,
+    token_size: u64,
+    expiry: Option<i64>,
+) -> Result<()> {
+    if ctx.accounts.auction_house.has_auctioneer {
+        return Err(AuctionHouseError::MustUseAuctioneerHandler.into());
+    }
+
+    bid_logic(
+        ctx.accounts.wallet.to_owned(),
+        ctx.accounts.payment_account.to_owned(),
+        ctx.accounts.transfer_authority.to_owned(),
+        *ctx.accounts.treasury_mint.to_owned(),
+        *ctx.accounts.token_account.to_owned(),
+        ctx.accounts.metadata.to_owned(),
+        ctx.accounts.escrow_payment_account.to_owned(),
+        &mut ctx.accounts.auction_house,
+        ctx.accounts.auction_house_fee_account.to_owned(),
+        ctx.accounts.buyer_trade_state.to_owned(),
+        ctx.accounts.authority.to_owned(),
+        ctx.accounts.token_program.to_owned(),
+        ctx.accounts.system_program.to_owned(),
+        ctx.accounts.rent.to_owned(),
+        ctx.remaining_accounts,
+        trade_state_bump,
+        escrow_payment_bump,
+        buyer_price,
+        token_size,
+        expiry,
+        false,
+        *ctx.bumps
+            .get("escrow_payment_account")
+            .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?,
+        *ctx.bumps
+            .get("buyer_trade_state")
+            .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?,
+    )
+}
+
+#[derive(Accounts)]
+#[instruction(
+    trade_state_bump: u8,
+    escrow_payment_bump: u8,
+    buyer_price: u64,
+    token_size: u64
+)]
+pub struct AuctioneerPublicBuy<'info> {
+    /// User wallet account.
+    wallet: Signer<'info>,
+
+    /// CHECK: Validated in bid_logic.
+    /// User SOL or SPL account to transfer funds from.
+    #[account(mut)]
+    payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in bid_logic.
+    /// SPL token account transfer authority.
+    transfer_authority: UncheckedAccount<'info>,
+
+    /// Auction House instance treasury mint account.
+    treasury_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// SPL token account.
+    token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Validated in bid_logic.
+    /// SPL token account metadata.
+    metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref()
+        ],
+        bump
+    )]
+    escrow_payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Verified with has_one constraint on auction house account.
+    authority: UncheckedAccount<'info>,
+
+    /// CHECK: Verified in auctioneer seeds check.
+    /// The auctioneer authority - typically a PDA of the Auctioneer program running this action.
+    auctioneer_authority: Signer<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+            ],
+        bump = auction_house.bump,
+        has_one = authority,
+        has_one = treasury_mint,
+        has_one = auction_house_fee_account
+    )]
+    auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Auction House instance fee account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            FEE_PAYER.as_bytes()
+        ],
+        bump = auction_house.fee_payer_bump
+    )]
+    auction_house_fee_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer public trade state PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            wallet.key().as_ref(),
+            auction_house.key().as_ref(),
+            treasury_mint.key().as_ref(),
+            token_account.mint.as_ref(),
+            buyer_price.to_le_bytes().as_ref(),
+            token_size.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    buyer_trade_state: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            AUCTIONEER.as_bytes(),
+            auction_house.key().as_ref(),
+            auctioneer_authority.key().as_ref()
+        ],
+        bump = auctioneer.bump,
+    )]
+    pub auctioneer: Account<'info, Auctioneer>,
+
+    token_program: Interface<'info, TokenInterface>,
+    system_program: Program<'info, System>,
+    rent: Sysvar<'info, Rent>,
+}
+
+pub fn auctioneer_public_bid<'info>(
+    ctx: Context<'_, '_, '_, 'info, AuctioneerPublicBuy<'info>>,
+    trade_state_bump: u8,
+    escrow_payment_bump: u8,
+    buyer_price: u64,
+    token_size: u64,
+) -> Result<()> {
+    assert_valid_auctioneer_and_scope(
+        &ctx.accounts.auction_house,
+        &ctx.accounts.auctioneer_authority.key(),
+        &ctx.accounts.auctioneer,
+        AuthorityScope::PublicBuy,
+    )?;
+
+    bid_logic(
+        ctx.accounts.wallet.to_owned(),
+        ctx.accounts.payment_account.to_owned(),
+        ctx.accounts.transfer_authority.to_owned(),
+        *ctx.accounts.treasury_mint.to_owned(),
+        *ctx.accounts.token_account.to_owned(),
+        ctx.accounts.metadata.to_owned(),
+        ctx.accounts.escrow_payment_account.to_owned(),
+        &mut ctx.accounts.auction_house,
+        ctx.accounts.auction_house_fee_account.to_owned(),
+        ctx.accounts.buyer_trade_state.to_owned(),
+        ctx.accounts.authority.to_owned(),
+        ctx.accounts.token_program.to_owned(),
+        ctx.accounts.system_program.to_owned(),
+        ctx.accounts.rent.to_owned(),
+        ctx.remaining_accounts,
+        trade_state_bump,
+        escrow_payment_bump,
+        buyer_price,
+        token_size,
+        None,
+        true,
+        *ctx.bumps
+            .get("escrow_payment_account")
+            .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?,
+        *ctx.bumps
+            .get("buyer_trade_state")
+            .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?,
+    )
+}
+
 #[derive(Accounts)]
 #[instruction(
     trade_state_bump: u8,
@@ -21,22 +453,321 @@ pub struct AuctioneerBuy<'info> {
     /// User wallet account.
     wallet: Signer<'info>,
 
-    /// CHECK: Validated in bid_logic.
+    /// CHECK: Validated in bid_logic.
+    /// User SOL or SPL account to transfer funds from.
+    #[account(mut)]
+    payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in bid_logic.
+    /// SPL token account transfer authority.
+    transfer_authority: UncheckedAccount<'info>,
+
+    /// Auction House instance treasury mint account.
+    treasury_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// SPL token account.
+    token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Validated in bid_logic.
+    /// SPL token account metadata.
+    metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref()
+        ],
+        bump
+    )]
+    escrow_payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Verified with has_one constraint on auction house account.
+    authority: UncheckedAccount<'info>,
+
+    /// CHECK: Verified in auctioneer seeds check.
+    /// The auctioneer authority - typically a PDA of the Auctioneer program running this action.
+    auctioneer_authority: Signer<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+            ],
+        bump = auction_house.bump,
+        has_one = authority,
+        has_one = treasury_mint,
+        has_one = auction_house_fee_account
+    )]
+    auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Auction House instance fee account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            FEE_PAYER.as_bytes()
+        ],
+        bump = auction_house.fee_payer_bump
+    )]
+    auction_house_fee_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer trade state PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            wallet.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_account.key().as_ref(),
+            treasury_mint.key().as_ref(),
+            token_account.mint.as_ref(),
+            buyer_price.to_le_bytes().as_ref(),
+            token_size.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    buyer_trade_state: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            AUCTIONEER.as_bytes(),
+            auction_house.key().as_ref(),
+            auctioneer_authority.key().as_ref()
+        ],
+        bump = auctioneer.bump,
+    )]
+    pub auctioneer: Account<'info, Auctioneer>,
+
+    token_program: Interface<'info, TokenInterface>,
+    system_program: Program<'info, System>,
+    rent: Sysvar<'info, Rent>,
+}
+
+pub fn auctioneer_private_bid<'info>(
+    ctx: Context<'_, '_, '_, 'info, AuctioneerBuy<'info>>,
+    trade_state_bump: u8,
+    escrow_payment_bump: u8,
+    buyer_price: u64,
+    token_size: u64,
+) -> Result<()> {
+    assert_valid_auctioneer_and_scope(
+        &ctx.accounts.auction_house,
+        &ctx.accounts.auctioneer_authority.key(),
+        &ctx.accounts.auctioneer,
+        AuthorityScope::Buy,
+    )?;
+
+    bid_logic(
+        ctx.accounts.wallet.to_owned(),
+        ctx.accounts.payment_account.to_owned(),
+        ctx.accounts.transfer_authority.to_owned(),
+        *ctx.accounts.treasury_mint.to_owned(),
+        *ctx.accounts.token_account.to_owned(),
+        ctx.accounts.metadata.to_owned(),
+        ctx.accounts.escrow_payment_account.to_owned(),
+        &mut ctx.accounts.auction_house,
+        ctx.accounts.auction_house_fee_account.to_owned(),
+        ctx.accounts.buyer_trade_state.to_owned(),
+        ctx.accounts.authority.to_owned(),
+        ctx.accounts.token_program.to_owned(),
+        ctx.accounts.system_program.to_owned(),
+        ctx.accounts.rent.to_owned(),
+        ctx.remaining_accounts,
+        trade_state_bump,
+        escrow_payment_bump,
+        buyer_price,
+        token_size,
+        None,
+        false,
+        *ctx.bumps
+            .get("escrow_payment_account")
+            .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?,
+        *ctx.bumps
+            .get("buyer_trade_state")
+            .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn bid_logic<'info>(
+    wallet: Signer<'info>,
+    payment_account: UncheckedAccount<'info>,
+    transfer_authority: UncheckedAccount<'info>,
+    treasury_mint: InterfaceAccount<'info, Mint>,
+    token_account: InterfaceAccount<'info, TokenAccount>,
+    metadata: UncheckedAccount<'info>,
+    escrow_payment_account: UncheckedAccount<'info>,
+    auction_house: &mut Box<Account<'info, AuctionHouse>>,
+    auction_house_fee_account: UncheckedAccount<'info>,
+    buyer_trade_state: UncheckedAccount<'info>,
+    authority: UncheckedAccount<'info>,
+    token_program: Interface<'info, TokenInterface>,
+    system_program: Program<'info, System>,
+    rent: Sysvar<'info, Rent>,
+    remaining_accounts: &[AccountInfo<'info>],
+    trade_state_bump: u8,
+    escrow_payment_bump: u8,
+    buyer_price: u64,
+    token_size: u64,
+    expiry: Option<i64>,
+    public: bool,
+    escrow_canonical_bump: u8,
+    trade_state_canonical_bump: u8,
+) -> Result<()> {
+    if (escrow_canonical_bump != escrow_payment_bump)
+        || (trade_state_canonical_bump != trade_state_bump)
+    {
+        return Err(AuctionHouseError::BumpSeedNotInHashMap.into());
+    }
+
+    assert_not_paused(auction_house)?;
+    assert_valid_expiry(expiry)?;
+
+    assert_valid_trade_state(
+        &wallet.key(),
+        auction_house,
+        buyer_price,
+        token_size,
+        &buyer_trade_state,
+        &token_account.mint.key(),
+        &token_account.key(),
+        trade_state_bump,
+    )?;
+    let auction_house_key = auction_house.key();
+    let seeds = [
+        PREFIX.as_bytes(),
+        auction_house_key.as_ref(),
+        FEE_PAYER.as_bytes(),
+        &[auction_house.fee_payer_bump],
+    ];
+    let (fee_payer, fee_seeds) = get_fee_payer(
+        &authority,
+        wallet.to_account_info(),
+        auction_house_fee_account.to_account_info(),
+        &seeds,
+    )?;
+
+    fund_escrow(
+        &wallet,
+        &payment_account,
+        &transfer_authority,
+        &treasury_mint,
+        &escrow_payment_account,
+        auction_house,
+        &fee_payer,
+        fee_seeds,
+        &token_program,
+        &system_program,
+        &rent,
+        escrow_payment_bump,
+        buyer_price,
+    )?;
+    assert_metadata_valid(&metadata, &token_account.mint)?;
+    let remaining_accounts = &mut remaining_accounts.iter();
+    assert_collection_allowed(auction_house, &metadata, remaining_accounts)?;
+    assert_wallet_not_blocked(auction_house, &wallet.key(), remaining_accounts)?;
+
+    let ts_info = buyer_trade_state.to_account_info();
+    let created = ts_info.data_is_empty();
+    if created {
+        let wallet_key = wallet.key();
+        let token_account_key = token_account.key();
+        if public {
+            create_or_allocate_account_raw(
+                crate::id(),
+                &ts_info,
+                &rent.to_account_info(),
+                &system_program,
+                &fee_payer,
+                legacy_trade_state_size(expiry),
+                fee_seeds,
+                &[
+                    PREFIX.as_bytes(),
+                    wallet_key.as_ref(),
+                    auction_house_key.as_ref(),
+                    auction_house.treasury_mint.as_ref(),
+                    token_account.mint.as_ref(),
+                    &buyer_price.to_le_bytes(),
+                    &token_size.to_le_bytes(),
+                    &[trade_state_bump],
+                ],
+            )?;
+        } else {
+            create_or_allocate_account_raw(
+                crate::id(),
+                &ts_info,
+                &rent.to_account_info(),
+                &system_program,
+                &fee_payer,
+                legacy_trade_state_size(expiry),
+                fee_seeds,
+                &[
+                    PREFIX.as_bytes(),
+                    wallet_key.as_ref(),
+                    auction_house_key.as_ref(),
+                    token_account_key.as_ref(),
+                    auction_house.treasury_mint.as_ref(),
+                    token_account.mint.as_ref(),
+                    &buyer_price.to_le_bytes(),
+                    &token_size.to_le_bytes(),
+                    &[trade_state_bump],
+                ],
+            )?;
+        }
+        #[allow(clippy::explicit_auto_deref)]
+        sol_memset(
+            *ts_info.try_borrow_mut_data()?,
+            trade_state_bump,
+            TRADE_STATE_SIZE,
+        );
+    }
+    write_legacy_trade_state_expiry(&ts_info, created, expiry)?;
+
+    emit!(BidPlaced {
+        auction_house: auction_house_key,
+        buyer: wallet.key(),
+        token_mint: token_account.mint,
+        token_account: token_account.key(),
+        trade_state: buyer_trade_state.key(),
+        price: buyer_price,
+        token_size,
+        public,
+    });
+
+    // Allow The same bid to be sent with no issues
+    Ok(())
+}
+
+/// Accounts for the [`public_buy_v2` handler](auction_house/fn.public_buy_v2.html).
+#[derive(Accounts)]
+pub struct PublicBuyV2<'info> {
+    /// User wallet account.
+    wallet: Signer<'info>,
+
+    /// CHECK: Validated in fund_escrow.
     /// User SOL or SPL account to transfer funds from.
     #[account(mut)]
     payment_account: UncheckedAccount<'info>,
 
-    /// CHECK: Validated in bid_logic.
+    /// CHECK: Validated in fund_escrow.
     /// SPL token account transfer authority.
     transfer_authority: UncheckedAccount<'info>,
 
     /// Auction House instance treasury mint account.
-    treasury_mint: Box<Account<'info, Mint>>,
+    treasury_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// SPL token account.
-    token_account: Box<Account<'info, TokenAccount>>,
+    token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// CHECK: Validated in bid_logic.
+    /// CHECK: Validated by assert_metadata_valid.
     /// SPL token account metadata.
     metadata: UncheckedAccount<'info>,
 
@@ -56,17 +787,13 @@ pub struct AuctioneerBuy<'info> {
     /// CHECK: Verified with has_one constraint on auction house account.
     authority: UncheckedAccount<'info>,
 
-    /// CHECK: Verified in auctioneer seeds check.
-    /// The auctioneer authority - typically a PDA of the Auctioneer program running this action.
-    auctioneer_authority: Signer<'info>,
-
     /// Auction House instance PDA account.
     #[account(
         seeds = [
             PREFIX.as_bytes(),
             auction_house.creator.as_ref(),
             auction_house.treasury_mint.as_ref()
-            ],
+        ],
         bump = auction_house.bump,
         has_one = authority,
         has_one = treasury_mint,
@@ -88,117 +815,227 @@ pub struct AuctioneerBuy<'info> {
     auction_house_fee_account: UncheckedAccount<'info>,
 
     /// CHECK: Not dangerous. Account seeds checked in constraint.
-    /// Buyer trade state PDA.
+    /// Buyer public v2 trade state PDA, keyed by the token mint.
     #[account(
         mut,
         seeds = [
             PREFIX.as_bytes(),
+            TRADE_STATE_V2.as_bytes(),
             wallet.key().as_ref(),
             auction_house.key().as_ref(),
-            token_account.key().as_ref(),
-            treasury_mint.key().as_ref(),
             token_account.mint.as_ref(),
-            buyer_price.to_le_bytes().as_ref(),
-            token_size.to_le_bytes().as_ref()
+            &TradeSide::Buy.seed()
         ],
         bump
     )]
     buyer_trade_state: UncheckedAccount<'info>,
+
+    token_program: Interface<'info, TokenInterface>,
+    system_program: Program<'info, System>,
+    rent: Sysvar<'info, Rent>,
+}
+
+/// Accounts for the [`buy_v2` handler](auction_house/fn.buy_v2.html).
+#[derive(Accounts)]
+pub struct BuyV2<'info> {
+    /// User wallet account.
+    wallet: Signer<'info>,
+
+    /// CHECK: Validated in fund_escrow.
+    /// User SOL or SPL account to transfer funds from.
+    #[account(mut)]
+    payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in fund_escrow.
+    /// SPL token account transfer authority.
+    transfer_authority: UncheckedAccount<'info>,
+
+    /// Auction House instance treasury mint account.
+    treasury_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// SPL token account.
+    token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Validated by assert_metadata_valid.
+    /// SPL token account metadata.
+    metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer escrow payment account PDA.
     #[account(
+        mut,
         seeds = [
-            AUCTIONEER.as_bytes(),
+            PREFIX.as_bytes(),
             auction_house.key().as_ref(),
-            auctioneer_authority.key().as_ref()
+            wallet.key().as_ref()
         ],
-        bump = auctioneer.bump,
+        bump
     )]
-    pub auctioneer: Account<'info, Auctioneer>,
+    escrow_payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Verified with has_one constraint on auction house account.
+    authority: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump = auction_house.bump,
+        has_one = authority,
+        has_one = treasury_mint,
+        has_one = auction_house_fee_account
+    )]
+    auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Auction House instance fee account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            FEE_PAYER.as_bytes()
+        ],
+        bump = auction_house.fee_payer_bump
+    )]
+    auction_house_fee_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer v2 trade state PDA, keyed by the token account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            TRADE_STATE_V2.as_bytes(),
+            wallet.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_account.key().as_ref(),
+            &TradeSide::Buy.seed()
+        ],
+        bump
+    )]
+    buyer_trade_state: UncheckedAccount<'info>,
 
-    token_program: Program<'info, Token>,
+    token_program: Interface<'info, TokenInterface>,
     system_program: Program<'info, System>,
     rent: Sysvar<'info, Rent>,
 }
 
-pub fn auctioneer_private_bid<'info>(
-    ctx: Context<'_, '_, '_, 'info, AuctioneerBuy<'info>>,
-    trade_state_bump: u8,
-    escrow_payment_bump: u8,
-    buyer_price: u64,
+/// Bid `price` for `token_size` of any token account of the mint until `expiry` in a v2 trade state, or amend the open bid in place.
+pub fn public_buy_v2<'info>(
+    ctx: Context<'_, '_, '_, 'info, PublicBuyV2<'info>>,
+    price: u64,
     token_size: u64,
+    expiry: Option<i64>,
 ) -> Result<()> {
-    auctioneer_bid_logic(
-        ctx.accounts.wallet.to_owned(),
-        ctx.accounts.payment_account.to_owned(),
-        ctx.accounts.transfer_authority.to_owned(),
-        *ctx.accounts.treasury_mint.to_owned(),
-        *ctx.accounts.token_account.to_owned(),
-        ctx.accounts.metadata.to_owned(),
-        ctx.accounts.escrow_payment_account.to_owned(),
-        &mut ctx.accounts.auction_house,
-        ctx.accounts.auction_house_fee_account.to_owned(),
-        ctx.accounts.buyer_trade_state.to_owned(),
-        ctx.accounts.authority.to_owned(),
-        ctx.accounts.token_program.to_owned(),
-        ctx.accounts.system_program.to_owned(),
-        ctx.accounts.rent.to_owned(),
+    let trade_state_bump = *ctx
+        .bumps
+        .get("buyer_trade_state")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+    let escrow_payment_bump = *ctx
+        .bumps
+        .get("escrow_payment_account")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+
+    bid_v2_logic(
+        &ctx.accounts.wallet,
+        &ctx.accounts.payment_account,
+        &ctx.accounts.transfer_authority,
+        &ctx.accounts.treasury_mint,
+        &ctx.accounts.token_account,
+        &ctx.accounts.metadata,
+        &ctx.accounts.escrow_payment_account,
+        &ctx.accounts.auction_house,
+        &ctx.accounts.auction_house_fee_account,
+        &ctx.accounts.buyer_trade_state,
+        &ctx.accounts.authority,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+        &ctx.accounts.rent,
+        ctx.remaining_accounts,
         trade_state_bump,
         escrow_payment_bump,
-        buyer_price,
+        price,
+        token_size,
+        expiry,
+        true,
+    )
+}
+
+/// Bid `price` for `token_size` of the token account until `expiry` in a v2 trade state, or amend the open bid in place.
+pub fn buy_v2<'info>(
+    ctx: Context<'_, '_, '_, 'info, BuyV2<'info>>,
+    price: u64,
+    token_size: u64,
+    expiry: Option<i64>,
+) -> Result<()> {
+    let trade_state_bump = *ctx
+        .bumps
+        .get("buyer_trade_state")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+    let escrow_payment_bump = *ctx
+        .bumps
+        .get("escrow_payment_account")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+
+    bid_v2_logic(
+        &ctx.accounts.wallet,
+        &ctx.accounts.payment_account,
+        &ctx.accounts.transfer_authority,
+        &ctx.accounts.treasury_mint,
+        &ctx.accounts.token_account,
+        &ctx.accounts.metadata,
+        &ctx.accounts.escrow_payment_account,
+        &ctx.accounts.auction_house,
+        &ctx.accounts.auction_house_fee_account,
+        &ctx.accounts.buyer_trade_state,
+        &ctx.accounts.authority,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+        &ctx.accounts.rent,
+        ctx.remaining_accounts,
+        trade_state_bump,
+        escrow_payment_bump,
+        price,
         token_size,
+        expiry,
         false,
-        *ctx.bumps
-            .get("escrow_payment_account")
-            .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?,
-        *ctx.bumps
-            .get("buyer_trade_state")
-            .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?,
     )
 }
 
-#[allow(clippy::too_many_arguments)]
-pub fn auctioneer_bid_logic<'info>(
-    wallet: Signer<'info>,
-    payment_account: UncheckedAccount<'info>,
-    transfer_authority: UncheckedAccount<'info>,
-    treasury_mint: Account<'info, Mint>,
-    token_account: Account<'info, TokenAccount>,
-    metadata: UncheckedAccount<'info>,
-    escrow_payment_account: UncheckedAccount<'info>,
-    auction_house: &mut Box<Account<'info, AuctionHouse>>,
-    auction_house_fee_account: UncheckedAccount<'info>,
-    buyer_trade_state: UncheckedAccount<'info>,
-    authority: UncheckedAccount<'info>,
-    token_program: Program<'info, Token>,
-    system_program: Program<'info, System>,
-    rent: Sysvar<'info, Rent>,
+fn bid_v2_logic<'info>(
+    wallet: &Signer<'info>,
+    payment_account: &UncheckedAccount<'info>,
+    transfer_authority: &UncheckedAccount<'info>,
+    treasury_mint: &InterfaceAccount<'info, Mint>,
+    token_account: &InterfaceAccount<'info, TokenAccount>,
+    metadata: &UncheckedAccount<'info>,
+    escrow_payment_account: &UncheckedAccount<'info>,
+    auction_house: &Account<'info, AuctionHouse>,
+    auction_house_fee_account: &UncheckedAccount<'info>,
+    buyer_trade_state: &UncheckedAccount<'info>,
+    authority: &UncheckedAccount<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    system_program: &Program<'info, System>,
+    rent: &Sysvar<'info, Rent>,
+    remaining_accounts: &[AccountInfo<'info>],
     trade_state_bump: u8,
     escrow_payment_bump: u8,
-    buyer_price: u64,
+    price: u64,
     token_size: u64,
+    expiry: Option<i64>,
     public: bool,
-    escrow_canonical_bump: u8,
-    trade_state_canonical_bump: u8,
 ) -> Result<()> {
-    if !auction_house.has_auctioneer {
-        return Err(AuctionHouseError::NoAuctioneerProgramSet.into());
+    if auction_house.has_auctioneer {
+        return Err(AuctionHouseError::MustUseAuctioneerHandler.into());
     }
 
-    if (escrow_canonical_bump != escrow_payment_bump)
-        || (trade_state_canonical_bump != trade_state_bump)
-    {
-        return Err(AuctionHouseError::BumpSeedNotInHashMap.into());
-    }
+    assert_not_paused(auction_house)?;
+    assert_valid_expiry(expiry)?;
 
-    assert_valid_trade_state(
-        &wallet.key(),
-        auction_house,
-        buyer_price,
-        token_size,
-        &buyer_trade_state,
-        &token_account.mint.key(),
-        &token_account.key(),
-        trade_state_bump,
-    )?;
     let auction_house_key = auction_house.key();
     let seeds = [
         PREFIX.as_bytes(),
@@ -207,12 +1044,104 @@ pub fn auctioneer_bid_logic<'info>(
         &[auction_house.fee_payer_bump],
     ];
     let (fee_payer, fee_seeds) = get_fee_payer(
-        &authority,
+        authority,
         wallet.to_account_info(),
         auction_house_fee_account.to_account_info(),
         &seeds,
     )?;
 
+    fund_escrow(
+        wallet,
+        payment_account,
+        transfer_authority,
+        treasury_mint,
+        escrow_payment_account,
+        auction_house,
+        &fee_payer,
+        fee_seeds,
+        token_program,
+        system_program,
+        rent,
+        escrow_payment_bump,
+        price,
+    )?;
+    assert_metadata_valid(metadata, &token_account.mint)?;
+    let remaining_accounts = &mut remaining_accounts.iter();
+    assert_collection_allowed(auction_house, metadata, remaining_accounts)?;
+    assert_wallet_not_blocked(auction_house, &wallet.key(), remaining_accounts)?;
+
+    // Public bids are keyed by the mint and match any token account holding it.
+    let (trade_type, trade_state_key) = if public {
+        (TradeType::Public, token_account.mint)
+    } else {
+        (TradeType::Private, token_account.key())
+    };
+    let wallet_key = wallet.key();
+    write_trade_state_v2(
+        &buyer_trade_state.to_account_info(),
+        &fee_payer,
+        fee_seeds,
+        &rent.to_account_info(),
+        &system_program.to_account_info(),
+        &[
+            PREFIX.as_bytes(),
+            TRADE_STATE_V2.as_bytes(),
+            wallet_key.as_ref(),
+            auction_house_key.as_ref(),
+            trade_state_key.as_ref(),
+            &TradeSide::Buy.seed(),
+            &[trade_state_bump],
+        ],
+        TradeStateV2 {
+            auction_house: auction_house_key,
+            wallet: wallet_key,
+            token_account: if public {
+                Pubkey::default()
+            } else {
+                token_account.key()
+            },
+            token_mint: token_account.mint,
+            side: TradeSide::Buy,
+            trade_type,
+            price,
+            token_size,
+            created_slot: Clock::get()?.slot,
+            bump: trade_state_bump,
+            expiry,
+            fee_payer: fee_payer.key(),
+        },
+    )?;
+
+    emit!(BidPlaced {
+        auction_house: auction_house_key,
+        buyer: wallet_key,
+        token_mint: token_account.mint,
+        token_account: token_account.key(),
+        trade_state: buyer_trade_state.key(),
+        price,
+        token_size,
+        public,
+    });
+
+    Ok(())
+}
+
+/// Create the escrow of `wallet` if missing and top it up from `payment_account` to cover `buyer_price`.
+fn fund_escrow<'info>(
+    wallet: &Signer<'info>,
+    payment_account: &UncheckedAccount<'info>,
+    transfer_authority: &UncheckedAccount<'info>,
+    treasury_mint: &InterfaceAccount<'info, Mint>,
+    escrow_payment_account: &UncheckedAccount<'info>,
+    auction_house: &Account<'info, AuctionHouse>,
+    fee_payer: &AccountInfo<'info>,
+    fee_seeds: &[&[u8]],
+    token_program: &Interface<'info, TokenInterface>,
+    system_program: &Program<'info, System>,
+    rent: &Sysvar<'info, Rent>,
+    escrow_payment_bump: u8,
+    buyer_price: u64,
+) -> Result<()> {
     let is_native = treasury_mint.key() == spl_token::native_mint::id();
 
     let auction_house_key = auction_house.key();
@@ -224,13 +1153,13 @@ pub fn auctioneer_bid_logic<'info>(
         &[escrow_payment_bump],
     ];
     create_program_token_account_if_not_present(
-        &escrow_payment_account,
-        &system_program,
-        &fee_payer,
-        &token_program,
-        &treasury_mint,
+        escrow_payment_account,
+        system_program,
+        fee_payer,
+        token_program,
+        treasury_mint,
         &auction_house.to_account_info(),
-        &rent,
+        rent,
         &escrow_signer_seeds,
         fee_seeds,
         is_native,
@@ -263,86 +1192,26 @@ pub fn auctioneer_bid_logic<'info>(
             )?;
         }
     } else {
-        let escrow_payment_loaded: spl_token::state::Account =
-            assert_initialized(&escrow_payment_account)?;
+        let escrow_payment_loaded = unpack_token_account(escrow_payment_account)?;
 
         if escrow_payment_loaded.amount < buyer_price {
             let diff = buyer_price
                 .checked_sub(escrow_payment_loaded.amount)
                 .ok_or(AuctionHouseError::NumericalOverflow)?;
-            invoke(
-                &spl_token::instruction::transfer(
-                    &token_program.key(),
-                    &payment_account.key(),
-                    &escrow_payment_account.key(),
-                    &transfer_authority.key(),
-                    &[],
-                    diff,
-                )?,
-                &[
-                    transfer_authority.to_account_info(),
-                    payment_account.to_account_info(),
-                    escrow_payment_account.to_account_info(),
-                    token_program.to_account_info(),
-                ],
-            )?;
-        }
-    }
-    assert_metadata_valid(&metadata, &token_account)?;
 
-    let ts_info = buyer_trade_state.to_account_info();
-    if ts_info.data_is_empty() {
-        let wallet_key = wallet.key();
-        let token_account_key = token_account.key();
-        if public {
-            create_or_allocate_account_raw(
-                crate::id(),
-                &ts_info,
-                &rent.to_account_info(),
-                &system_program,
-                &fee_payer,
-                TRADE_STATE_SIZE,
-                fee_seeds,
-                &[
-                    PREFIX.as_bytes(),
-                    wallet_key.as_ref(),
-                    auction_house_key.as_ref(),
-                    auction_house.treasury_mint.as_ref(),
-                    token_account.mint.as_ref(),
-                    &buyer_price.to_le_bytes(),
-                    &token_size.to_le_bytes(),
-                    &[trade_state_bump],
-                ],
-            )?;
-        } else {
-            create_or_allocate_account_raw(
-                crate::id(),
-                &ts_info,
-                &rent.to_account_info(),
-                &system_program,
-                &fee_payer,
-                TRADE_STATE_SIZE,
-                fee_seeds,
-                &[
-                    PREFIX.as_bytes(),
-                    wallet_key.as_ref(),
-                    auction_house_key.as_ref(),
-                    token_account_key.as_ref(),
-                    auction_house.treasury_mint.as_ref(),
-                    token_account.mint.as_ref(),
-                    &buyer_price.to_le_bytes(),
-                    &token_size.to_le_bytes(),
-                    &[trade_state_bump],
-                ],
+            // Gross up for any transfer fee so the escrow ends up holding `buyer_price`.
+            let treasury_mint_info = treasury_mint.to_account_info();
+            transfer_tokens_checked(
+                &token_program.to_account_info(),
+                payment_account,
+                &treasury_mint_info,
+                escrow_payment_account,
+                transfer_authority,
+                get_amount_with_transfer_fee(&treasury_mint_info, diff)?,
+                &[],
             )?;
         }
-        #[allow(clippy::explicit_auto_deref)]
-        sol_memset(
-            *ts_info.try_borrow_mut_data()?,
-            trade_state_bump,
-            TRADE_STATE_SIZE,
-        );
     }
-    // Allow The same bid to be sent with no issues
+
     Ok(())
 }
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use std::slice::Iter;
+
+use crate::{
+    constants::*,
+    errors::AuctionHouseError,
+    events::{WalletBlocked, WalletUnblocked},
+    pda::find_blocked_wallet_address,
+    utils::assert_keys_equal,
+    AuctionHouse,
+};
+
+/// Marks a wallet as blocked from trading on an Auction House.
+#[account]
+pub struct BlockedWallet {
+    pub auction_house: Pubkey,
+    pub wallet: Pubkey,
+    pub bump: u8,
+}
+
+/// Accounts for the [`block_wallet` handler](auction_house/fn.block_wallet.html).
+#[derive(Accounts)]
+pub struct BlockWallet<'info> {
+    /// Authority key for the Auction House, paying for the blocked wallet account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Only its key is stored.
+    /// Wallet to block.
+    pub wallet: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref(), auction_house.treasury_mint.as_ref()], bump=auction_house.bump, has_one=authority)]
+    pub auction_house: Account<'info, AuctionHouse>,
+
+    /// Blocked wallet PDA account.
+    #[account(init, seeds=[PREFIX.as_bytes(), auction_house.key().as_ref(), BLOCKED_WALLET.as_bytes(), wallet.key().as_ref()], bump, space=BLOCKED_WALLET_SIZE, payer=authority)]
+    pub blocked_wallet: Account<'info, BlockedWallet>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts for the [`unblock_wallet` handler](auction_house/fn.unblock_wallet.html).
+#[derive(Accounts)]
+pub struct UnblockWallet<'info> {
+    /// Authority key for the Auction House, refunded the blocked wallet rent.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref(), auction_house.treasury_mint.as_ref()], bump=auction_house.bump, has_one=authority)]
+    pub auction_house: Account<'info, AuctionHouse>,
+
+    /// Blocked wallet PDA account.
+    #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.key().as_ref(), BLOCKED_WALLET.as_bytes(), blocked_wallet.wallet.as_ref()], bump=blocked_wallet.bump, has_one=auction_house, close=authority)]
+    pub blocked_wallet: Account<'info, BlockedWallet>,
+}
+
+pub fn block_wallet(ctx: Context<BlockWallet>) -> Result<()> {
+    let blocked_wallet = &mut ctx.accounts.blocked_wallet;
+    blocked_wallet.auction_house = ctx.accounts.auction_house.key();
+    blocked_wallet.wallet = ctx.accounts.wallet.key();
+    blocked_wallet.bump = *ctx
+        .bumps
+        .get("blocked_wallet")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+
+    ctx.accounts.auction_house.has_blocklist = true;
+
+    emit!(WalletBlocked {
+        auction_house: blocked_wallet.auction_house,
+        wallet: blocked_wallet.wallet,
+    });
+
+    Ok(())
+}
+
+pub fn unblock_wallet(ctx: Context<UnblockWallet>) -> Result<()> {
+    emit!(WalletUnblocked {
+        auction_house: ctx.accounts.auction_house.key(),
+        wallet: ctx.accounts.blocked_wallet.wallet,
+    });
+
+    Ok(())
+}
+
+/// Assert `wallet` is not on the blocklist of the Auction House.
+///
+/// When the Auction House has a blocklist, the `BlockedWallet` PDA of the wallet is taken from
+/// `remaining_accounts` whether or not it exists. Only this program can create accounts at that
+/// address, so any data there means the wallet is blocked.
+pub fn assert_wallet_not_blocked<'a>(
+    auction_house: &Account<AuctionHouse>,
+    wallet: &Pubkey,
+    remaining_accounts: &mut Iter<AccountInfo<'a>>,
+) -> Result<()> {
+    if !auction_house.has_blocklist {
+        return Ok(());
+    }
+
+    let blocked_wallet = next_account_info(remaining_accounts)?;
+    assert_keys_equal(
+        blocked_wallet.key(),
+        find_blocked_wallet_address(&auction_house.key(), wallet).0,
+    )?;
+
+    if !blocked_wallet.data_is_empty() {
+        return Err(AuctionHouseError::WalletBlocked.into());
+    }
+
+    Ok(())
+}
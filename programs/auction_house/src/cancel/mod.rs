@@ -1,7 +1,75 @@
 use crate::{constants::*, errors::*, utils::*, AuctionHouse, *};
-use anchor_lang::solana_program::program_memory::sol_memset;
 use anchor_lang::{prelude::*, solana_program::program::invoke, AnchorDeserialize};
-use spl_token::instruction::revoke;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use spl_token_2022::instruction::revoke;
+
+#[derive(Accounts, Clone)]
+#[instruction(buyer_price: u64, token_size: u64)]
+pub struct Cancel<'info> {
+    /// CHECK: Wallet validated as owner in cancel logic.
+    /// User wallet account.
+    #[account(mut)]
+    pub wallet: UncheckedAccount<'info>,
+
+    /// SPL token account containing the token of the sale to be canceled.
+    #[account(mut)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token mint account of SPL token.
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: Validated as a signer in cancel_logic.
+    /// Auction House instance authority account.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump=auction_house.bump,
+        has_one=authority,
+        has_one=auction_house_fee_account
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Validated in cancel_logic.
+    /// Auction House instance fee account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            FEE_PAYER.as_bytes()
+        ],
+        bump=auction_house.fee_payer_bump
+    )]
+    pub auction_house_fee_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in cancel_logic.
+    /// Trade state PDA account representing the bid or ask to be canceled.
+    #[account(mut)]
+    pub trade_state: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> From<AuctioneerCancel<'info>> for Cancel<'info> {
+    fn from(a: AuctioneerCancel<'info>) -> Cancel<'info> {
+        Cancel {
+            wallet: a.wallet,
+            token_account: a.token_account,
+            token_mint: a.token_mint,
+            authority: a.authority,
+            auction_house: a.auction_house,
+            auction_house_fee_account: a.auction_house_fee_account,
+            trade_state: a.trade_state,
+            token_program: a.token_program,
+        }
+    }
+}
 
 #[derive(Accounts, Clone)]
 #[instruction(buyer_price: u64, token_size: u64)]
@@ -13,10 +81,10 @@ pub struct AuctioneerCancel<'info> {
 
     /// SPL token account containing the token of the sale to be canceled.
     #[account(mut)]
-    pub token_account: Box<Account<'info, TokenAccount>>,
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// Token mint account of SPL token.
-    pub token_mint: Box<Account<'info, Mint>>,
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// CHECK: Validated as a signer in cancel_logic.
     /// Auction House instance authority account.
@@ -69,7 +137,27 @@ pub struct AuctioneerCancel<'info> {
     )]
     pub auctioneer: Account<'info, Auctioneer>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Cancel a bid or ask by revoking the token delegate, transferring all lamports from the trade state account to the fee payer, and setting the trade state account data to zero so it can be garbage collected.
+pub fn cancel<'info>(
+    ctx: Context<'_, '_, '_, 'info, Cancel<'info>>,
+    buyer_price: u64,
+    token_size: u64,
+) -> Result<()> {
+    let auction_house = &ctx.accounts.auction_house;
+
+    if auction_house.has_auctioneer {
+        return Err(AuctionHouseError::MustUseAuctioneerHandler.into());
+    }
+
+    cancel_logic(
+        ctx.accounts,
+        ctx.remaining_accounts,
+        buyer_price,
+        token_size,
+    )
 }
 
 pub fn auctioneer_cancel<'info>(
@@ -79,19 +167,27 @@ pub fn auctioneer_cancel<'info>(
 ) -> Result<()> {
     let auction_house = &ctx.accounts.auction_house;
 
-    if !auction_house.has_auctioneer {
-        return Err(AuctionHouseError::NoAuctioneerProgramSet.into());
-    }
+    assert_valid_auctioneer_and_scope(
+        auction_house,
+        &ctx.accounts.auctioneer_authority.key(),
+        &ctx.accounts.auctioneer,
+        AuthorityScope::Cancel,
+    )?;
 
-    let mut accounts: AuctioneerCancel<'info> = (*ctx.accounts).clone();
+    let mut accounts: Cancel<'info> = (*ctx.accounts).clone().into();
 
-    cancel_logic(&mut accounts, buyer_price, token_size)
+    cancel_logic(
+        &mut accounts,
+        ctx.remaining_accounts,
+        buyer_price,
+        token_size,
+    )
 }
 
 #[allow(clippy::needless_lifetimes)]
-#[allow(clippy::extra_unused_lifetimes)]
 fn cancel_logic<'c, 'info>(
-    accounts: &mut AuctioneerCancel<'info>,
+    accounts: &mut Cancel<'info>,
+    remaining_accounts: &'c [AccountInfo<'info>],
     buyer_price: u64,
     token_size: u64,
 ) -> Result<()> {
@@ -104,7 +200,13 @@ fn cancel_logic<'c, 'info>(
     let trade_state = &accounts.trade_state;
     let token_program = &accounts.token_program;
 
-    let ts_bump = trade_state.try_borrow_data()?[0];
+    // V2 trade states are cancelled whatever their current price and size.
+    let (buyer_price, token_size) = match load_trade_state_v2(trade_state)? {
+        Some(trade_state_v2) => (trade_state_v2.price, trade_state_v2.token_size),
+        None => (buyer_price, token_size),
+    };
+
+    let ts_bump = trade_state_bump(trade_state)?;
     assert_valid_trade_state(
         &wallet.key(),
         auction_house,
@@ -135,7 +237,32 @@ fn cancel_logic<'c, 'info>(
         &seeds,
     )?;
 
-    if token_account.owner == wallet.key() && wallet.is_signer {
+    // Programmable NFTs pass their metadata as the second remaining account.
+    let programmable = match remaining_accounts.get(1) {
+        Some(metadata) => {
+            assert_derivation(
+                &mpl_token_metadata::id(),
+                metadata,
+                &[
+                    mpl_token_metadata::state::PREFIX.as_bytes(),
+                    mpl_token_metadata::id().as_ref(),
+                    token_mint.key().as_ref(),
+                ],
+            )?;
+            is_programmable(metadata)?
+        }
+        None => false,
+    };
+
+    if token_account.owner == wallet.key() && wallet.is_signer && programmable {
+        revoke_programmable(
+            &mut remaining_accounts.iter(),
+            &token_account.to_account_info(),
+            &token_mint.to_account_info(),
+            wallet,
+            token_program,
+        )?;
+    } else if token_account.owner == wallet.key() && wallet.is_signer {
         invoke(
             &revoke(
                 &token_program.key(),
@@ -152,16 +279,17 @@ fn cancel_logic<'c, 'info>(
         )?;
     }
 
-    let curr_lamp = trade_state.lamports();
-    **trade_state.lamports.borrow_mut() = 0;
+    close_account(&trade_state.to_account_info(), &fee_payer)?;
 
-    **fee_payer.lamports.borrow_mut() = fee_payer
-        .lamports()
-        .checked_add(curr_lamp)
-        .ok_or(AuctionHouseError::NumericalOverflow)?;
-
-    #[allow(clippy::explicit_auto_deref)]
-    sol_memset(*trade_state.try_borrow_mut_data()?, 0, TRADE_STATE_SIZE);
+    emit!(TradeStateCancelled {
+        auction_house: auction_house_key,
+        wallet: wallet.key(),
+        token_mint: token_mint.key(),
+        token_account: token_account.key(),
+        trade_state: trade_state.key(),
+        price: buyer_price,
+        token_size,
+    });
 
     Ok(())
 }
@@ -0,0 +1,186 @@
+use anchor_lang::{prelude::*, solana_program::program::invoke_signed, Discriminator};
+use anchor_spl::token_interface::{Mint, TokenInterface};
+use spl_token_2022::instruction::close_account as close_token_account;
+
+use crate::{
+    blocklist::BlockedWallet,
+    collection_allowlist::AllowedCollection,
+    constants::*,
+    errors::AuctionHouseError,
+    events::AuctionHouseClosed,
+    utils::*,
+    withdrawal_policy::{QueuedWithdrawal, WithdrawalPolicy},
+    AuctionHouse,
+};
+
+/// Accounts for the [`close_auction_house` handler](auction_house/fn.close_auction_house.html).
+#[derive(Accounts)]
+pub struct CloseAuctionHouse<'info> {
+    /// Treasury mint account, either native SOL mint or a SPL Token or Token-2022 mint.
+    pub treasury_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Authority key for the Auction House, receiving the rent of the closed accounts.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Verified with has_one constraint on auction house account.
+    /// SOL or SPL token account to receive the remaining treasury.
+    #[account(mut)]
+    pub treasury_withdrawal_destination: UncheckedAccount<'info>,
+
+    /// CHECK: Verified with has_one constraint on auction house account.
+    /// Account to receive the remaining fee account lamports.
+    #[account(mut)]
+    pub fee_withdrawal_destination: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Auction House treasury PDA account.
+    #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.key().as_ref(), TREASURY.as_bytes()], bump=auction_house.treasury_bump)]
+    pub auction_house_treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Auction House instance fee account.
+    #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.key().as_ref(), FEE_PAYER.as_bytes()], bump=auction_house.fee_payer_bump)]
+    pub auction_house_fee_account: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref(), treasury_mint.key().as_ref()], bump=auction_house.bump, has_one=authority, has_one=treasury_mint, has_one=treasury_withdrawal_destination, has_one=fee_withdrawal_destination, has_one=auction_house_treasury, has_one=auction_house_fee_account, close=authority)]
+    pub auction_house: Account<'info, AuctionHouse>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn close_auction_house<'info>(
+    ctx: Context<'_, '_, '_, 'info, CloseAuctionHouse<'info>>,
+) -> Result<()> {
+    let treasury_mint = &ctx.accounts.treasury_mint;
+    let authority = &ctx.accounts.authority;
+    let treasury_withdrawal_destination = &ctx.accounts.treasury_withdrawal_destination;
+    let fee_withdrawal_destination = &ctx.accounts.fee_withdrawal_destination;
+    let auction_house_treasury = &ctx.accounts.auction_house_treasury;
+    let auction_house_fee_account = &ctx.accounts.auction_house_fee_account;
+    let auction_house = &ctx.accounts.auction_house;
+    let token_program = &ctx.accounts.token_program;
+    let system_program = &ctx.accounts.system_program;
+
+    if auction_house.has_auctioneer {
+        return Err(AuctionHouseError::AuctioneersStillDelegated.into());
+    }
+
+    // The address is derived from the creator, so only the creator could recreate the house to
+    // recover the escrows and trade states left behind.
+    if authority.key() != auction_house.creator {
+        return Err(AuctionHouseError::AuthorityNotCreator.into());
+    }
+
+    let is_native = treasury_mint.key() == spl_token::native_mint::id();
+    let treasury_balance = if is_native {
+        auction_house_treasury.lamports()
+    } else {
+        unpack_token_account(auction_house_treasury)?.amount
+    };
+    let fee_balance = auction_house_fee_account.lamports();
+
+    // Sweeping would get around the epoch caps, so the policy has to be used to drain them.
+    if auction_house.has_withdrawal_policy && (treasury_balance > 0 || fee_balance > 0) {
+        return Err(AuctionHouseError::AuctionHouseNotDrained.into());
+    }
+
+    if treasury_balance > 0 {
+        pay_from_treasury(
+            auction_house,
+            &treasury_mint.to_account_info(),
+            auction_house_treasury,
+            treasury_withdrawal_destination,
+            token_program,
+            system_program,
+            treasury_balance,
+        )?;
+    }
+
+    if !is_native {
+        let auction_house_seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref(),
+            &[auction_house.bump],
+        ];
+
+        invoke_signed(
+            &close_token_account(
+                token_program.key,
+                auction_house_treasury.key,
+                authority.key,
+                &auction_house.key(),
+                &[],
+            )?,
+            &[
+                auction_house_treasury.to_account_info(),
+                authority.to_account_info(),
+                auction_house.to_account_info(),
+                token_program.to_account_info(),
+            ],
+            &[&auction_house_seeds],
+        )?;
+    }
+
+    if fee_balance > 0 {
+        pay_from_fee_account(
+            auction_house,
+            auction_house_fee_account,
+            fee_withdrawal_destination,
+            system_program,
+            fee_balance,
+        )?;
+    }
+
+    close_owned_accounts(
+        &auction_house.key(),
+        ctx.remaining_accounts,
+        &authority.to_account_info(),
+    )?;
+
+    emit!(AuctionHouseClosed {
+        auction_house: auction_house.key(),
+        authority: authority.key(),
+    });
+
+    Ok(())
+}
+
+/// Close withdrawal policy, queued withdrawal, allowed collection and blocked wallet accounts of
+/// the Auction House passed as remaining accounts, which could not be closed once it is gone.
+fn close_owned_accounts<'info>(
+    auction_house_key: &Pubkey,
+    remaining_accounts: &[AccountInfo<'info>],
+    receiver: &AccountInfo<'info>,
+) -> Result<()> {
+    let discriminators = [
+        WithdrawalPolicy::discriminator(),
+        QueuedWithdrawal::discriminator(),
+        AllowedCollection::discriminator(),
+        BlockedWallet::discriminator(),
+    ];
+
+    for account in remaining_accounts {
+        if *account.owner != crate::id() {
+            return Err(AuctionHouseError::IncorrectOwner.into());
+        }
+
+        // All of them start with the Auction House key right after the discriminator.
+        {
+            let data = account.try_borrow_data()?;
+            if data.len() < 40
+                || !discriminators.iter().any(|d| d[..] == data[..8])
+                || data[8..40] != auction_house_key.as_ref()[..]
+            {
+                return Err(AuctionHouseError::PublicKeyMismatch.into());
+            }
+        }
+
+        close_account(account, receiver)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use std::slice::Iter;
+
+use crate::{
+    constants::*,
+    errors::AuctionHouseError,
+    events::{CollectionAllowed, CollectionDisallowed},
+    metadata::get_verified_collection,
+    AuctionHouse,
+};
+
+/// Marks a verified Metaplex collection as tradable on an Auction House with an allowlist.
+#[account]
+pub struct AllowedCollection {
+    pub auction_house: Pubkey,
+    pub collection: Pubkey,
+    pub bump: u8,
+}
+
+/// Accounts for the [`add_allowed_collection` handler](auction_house/fn.add_allowed_collection.html).
+#[derive(Accounts)]
+pub struct AddAllowedCollection<'info> {
+    /// Authority key for the Auction House, paying for the allowed collection account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Only its key is stored, NFTs have to be verified members of it.
+    /// Mint of the collection NFT.
+    pub collection: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref(), auction_house.treasury_mint.as_ref()], bump=auction_house.bump, has_one=authority)]
+    pub auction_house: Account<'info, AuctionHouse>,
+
+    /// Allowed collection PDA account.
+    #[account(init, seeds=[PREFIX.as_bytes(), auction_house.key().as_ref(), ALLOWED_COLLECTION.as_bytes(), collection.key().as_ref()], bump, space=ALLOWED_COLLECTION_SIZE, payer=authority)]
+    pub allowed_collection: Account<'info, AllowedCollection>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts for the [`remove_allowed_collection` handler](auction_house/fn.remove_allowed_collection.html).
+#[derive(Accounts)]
+pub struct RemoveAllowedCollection<'info> {
+    /// Authority key for the Auction House, refunded the allowed collection rent.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref(), auction_house.treasury_mint.as_ref()], bump=auction_house.bump, has_one=authority)]
+    pub auction_house: Account<'info, AuctionHouse>,
+
+    /// Allowed collection PDA account.
+    #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.key().as_ref(), ALLOWED_COLLECTION.as_bytes(), allowed_collection.collection.as_ref()], bump=allowed_collection.bump, has_one=auction_house, close=authority)]
+    pub allowed_collection: Account<'info, AllowedCollection>,
+}
+
+/// The first allowed collection turns the allowlist on for good, removing every collection leaves
+/// nothing tradable rather than everything.
+pub fn add_allowed_collection(ctx: Context<AddAllowedCollection>) -> Result<()> {
+    let allowed_collection = &mut ctx.accounts.allowed_collection;
+    allowed_collection.auction_house = ctx.accounts.auction_house.key();
+    allowed_collection.collection = ctx.accounts.collection.key();
+    allowed_collection.bump = *ctx
+        .bumps
+        .get("allowed_collection")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+
+    ctx.accounts.auction_house.has_collection_allowlist = true;
+
+    emit!(CollectionAllowed {
+        auction_house: allowed_collection.auction_house,
+        collection: allowed_collection.collection,
+    });
+
+    Ok(())
+}
+
+pub fn remove_allowed_collection(ctx: Context<RemoveAllowedCollection>) -> Result<()> {
+    emit!(CollectionDisallowed {
+        auction_house: ctx.accounts.auction_house.key(),
+        collection: ctx.accounts.allowed_collection.collection,
+    });
+
+    Ok(())
+}
+
+/// Assert the NFT belongs to a verified collection on the allowlist of the Auction House.
+///
+/// When the Auction House has an allowlist, its `AllowedCollection` account for the collection is
+/// taken from `remaining_accounts`, ahead of any accounts used later in the instruction.
+pub fn assert_collection_allowed<'a>(
+    auction_house: &Account<AuctionHouse>,
+    metadata: &AccountInfo,
+    remaining_accounts: &mut Iter<AccountInfo<'a>>,
+) -> Result<()> {
+    if !auction_house.has_collection_allowlist {
+        return Ok(());
+    }
+
+    let allowed_collection_info =
+        next_account_info(remaining_accounts).or(Err(AuctionHouseError::CollectionNotAllowed))?;
+    let collection =
+        get_verified_collection(metadata)?.ok_or(AuctionHouseError::CollectionNotAllowed)?;
+
+    // A closed account fails to deserialize, so removed collections are rejected here too.
+    let allowed_collection = Account::<AllowedCollection>::try_from(allowed_collection_info)
+        .or(Err(AuctionHouseError::CollectionNotAllowed))?;
+    if allowed_collection.auction_house != auction_house.key()
+        || allowed_collection.collection != collection
+    {
+        return Err(AuctionHouseError::CollectionNotAllowed.into());
+    }
+
+    Ok(())
+}
@@ -3,12 +3,45 @@ pub const FEE_PAYER: &str = "fee_payer";
 pub const TREASURY: &str = "treasury";
 pub const SIGNER: &str = "signer";
 pub const AUCTIONEER: &str = "auctioneer";
+pub const ROYALTY_VAULT: &str = "royalty_vault";
+pub const WITHDRAWAL_POLICY: &str = "withdrawal_policy";
+pub const QUEUED_WITHDRAWAL: &str = "queued_withdrawal";
+pub const ALLOWED_COLLECTION: &str = "allowed_collection";
+pub const BLOCKED_WALLET: &str = "blocked_wallet";
+pub const ESCROW_LOCK: &str = "escrow_lock";
+pub const LISTING_RECEIPT_PREFIX: &str = "listing_receipt";
+pub const BID_RECEIPT_PREFIX: &str = "bid_receipt";
+pub const PURCHASE_RECEIPT_PREFIX: &str = "purchase_receipt";
+pub const TRADE_STATE_V2: &str = "trade_state";
 pub const TRADE_STATE_SIZE: usize = 1;
+/// Legacy trade state placed with an expiry, the bump followed by the expiry timestamp.
+pub const TRADE_STATE_WITH_EXPIRY_SIZE: usize = TRADE_STATE_SIZE + 8;
+pub const TRADE_STATE_V2_SIZE: usize = 8 +                  // Anchor discriminator/sighash
+32 +                                                        // auction house
+32 +                                                        // wallet
+32 +                                                        // token account
+32 +                                                        // token mint
+1 +                                                         // side
+1 +                                                         // trade type
+8 +                                                         // price
+8 +                                                         // token size
+8 +                                                         // created slot
+1 +                                                         // bump
+9 +                                                         // expiry
+32 +                                                        // fee payer
+23                                                          // padding
+;
+/// Share of an expired trade state's rent paid to whoever closes it, in basis points.
+pub const CLOSE_EXPIRED_BOUNTY_BPS: u64 = 1000;
+/// Layout version of newly created Auction Houses, older ones are upgraded by `migrate_auction_house`.
+pub const AUCTION_HOUSE_VERSION: u8 = 1;
 pub const AUCTIONEER_SIZE: usize = 8 +                      // Anchor discriminator/sighash
 32 +                                                        // Auctioneer authority
 32 +                                                        // Auction house instance
 1 +                                                         // bump
-63                                                          // Padding
+1 +                                                         // scopes bitmask
+8 +                                                         // locked amount
+54                                                          // Padding
 ;
 
 pub const AUCTION_HOUSE_SIZE: usize = 8 +                   // key
@@ -24,8 +57,109 @@ pub const AUCTION_HOUSE_SIZE: usize = 8 +                   // key
 1 +                                                         // fee_payer_bump
 2 +                                                         // seller fee basis points
 1 +                                                         // can change sale price
-8 +                                                         // escrow payment bump
+1 +                                                         // escrow payment bump
 1 +                                                         // has external auctioneer program as an authority
 32 +                                                         // auctioneer address
-172                                                         // padding
+2 +                                                         // number of delegated auctioneers
+32 +                                                        // pending authority
+32 +                                                        // pending treasury withdrawal destination
+32 +                                                        // pending fee withdrawal destination
+1 +                                                         // has withdrawal policy
+1 +                                                         // is paused
+1 +                                                         // has collection allowlist
+1 +                                                         // has blocklist
+1 +                                                         // version
+76                                                          // padding
+;
+
+pub const LISTING_RECEIPT_SIZE: usize = 8 +                 // Anchor discriminator/sighash
+32 +                                                        // trade state
+32 +                                                        // bookkeeper
+32 +                                                        // auction house
+32 +                                                        // seller
+32 +                                                        // metadata
+32 +                                                        // token mint
+1 + 32 +                                                    // purchase receipt (optional)
+8 +                                                         // price
+8 +                                                         // token size
+1 +                                                         // bump
+1 +                                                         // trade state bump
+8 +                                                         // created at
+1 + 8                                                       // canceled at (optional)
+;
+
+pub const BID_RECEIPT_SIZE: usize = 8 +                     // Anchor discriminator/sighash
+32 +                                                        // trade state
+32 +                                                        // bookkeeper
+32 +                                                        // auction house
+32 +                                                        // buyer
+32 +                                                        // metadata
+32 +                                                        // token mint
+1 + 32 +                                                    // token account (optional, private bids only)
+1 + 32 +                                                    // purchase receipt (optional)
+8 +                                                         // price
+8 +                                                         // token size
+1 +                                                         // bump
+1 +                                                         // trade state bump
+8 +                                                         // created at
+1 + 8                                                       // canceled at (optional)
+;
+
+pub const PURCHASE_RECEIPT_SIZE: usize = 8 +                // Anchor discriminator/sighash
+32 +                                                        // bookkeeper
+32 +                                                        // buyer
+32 +                                                        // seller
+32 +                                                        // auction house
+32 +                                                        // metadata
+32 +                                                        // token mint
+8 +                                                         // token size
+8 +                                                         // price
+1 +                                                         // bump
+8                                                           // created at
 ;
+
+pub const WITHDRAWAL_POLICY_SIZE: usize = 8 +              // Anchor discriminator/sighash
+32 +                                                        // auction house
+8 +                                                         // treasury epoch cap
+8 +                                                         // fee epoch cap
+8 +                                                         // timelock
+8 +                                                         // epoch
+8 +                                                         // treasury withdrawn
+8 +                                                         // fee withdrawn
+8 +                                                         // next withdrawal id
+1 +                                                         // bump
+64                                                          // padding
+;
+
+pub const QUEUED_WITHDRAWAL_SIZE: usize = 8 +               // Anchor discriminator/sighash
+32 +                                                        // auction house
+8 +                                                         // id
+1 +                                                         // source
+8 +                                                         // amount
+8 +                                                         // executable at
+1                                                           // bump
+;
+
+pub const ALLOWED_COLLECTION_SIZE: usize = 8 +             // Anchor discriminator/sighash
+32 +                                                        // auction house
+32 +                                                        // collection
+1                                                           // bump
+;
+
+pub const BLOCKED_WALLET_SIZE: usize = 8 +                 // Anchor discriminator/sighash
+32 +                                                        // auction house
+32 +                                                        // wallet
+1                                                           // bump
+;
+
+pub const ESCROW_LOCK_SIZE: usize = 8 +                    // Anchor discriminator/sighash
+32 +                                                        // auction house
+32 +                                                        // wallet
+8 +                                                         // locked amount
+1                                                           // bump
+;
+
+/// Token-2022 token-metadata `additional_metadata` key holding the royalty in basis points.
+pub const SELLER_FEE_BASIS_POINTS_KEY: &str = "seller_fee_basis_points";
+/// Token-2022 token-metadata `additional_metadata` key holding comma separated `<address>:<share>` creators.
+pub const CREATORS_KEY: &str = "creators";
@@ -1,7 +1,93 @@
 use anchor_lang::{prelude::*, solana_program::program::invoke, AnchorDeserialize};
+use anchor_spl::token_interface::{Mint, TokenInterface};
 
 use crate::{constants::*, errors::*, utils::*, AuctionHouse, *};
 
+#[derive(Accounts, Clone)]
+#[instruction(escrow_payment_bump: u8)]
+pub struct Deposit<'info> {
+    /// User wallet account.
+    pub wallet: Signer<'info>,
+
+    /// CHECK: Validated in deposit_logic.
+    /// User SOL or SPL account to transfer funds from.
+    #[account(mut)]
+    pub payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in deposit_logic.
+    /// SPL token account transfer authority.
+    pub transfer_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_payment_account: UncheckedAccount<'info>,
+
+    /// Auction House instance treasury mint account.
+    pub treasury_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: Validated in deposit_logic.
+    /// Auction House instance authority account.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump=auction_house.bump,
+        has_one=authority,
+        has_one=treasury_mint,
+        has_one=auction_house_fee_account
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Auction House instance fee account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            FEE_PAYER.as_bytes()
+        ],
+        bump=auction_house.fee_payer_bump
+    )]
+    pub auction_house_fee_account: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> From<AuctioneerDeposit<'info>> for Deposit<'info> {
+    fn from(a: AuctioneerDeposit<'info>) -> Deposit<'info> {
+        Deposit {
+            wallet: a.wallet,
+            payment_account: a.payment_account,
+            transfer_authority: a.transfer_authority,
+            escrow_payment_account: a.escrow_payment_account,
+            treasury_mint: a.treasury_mint,
+            authority: a.authority,
+            auction_house: a.auction_house,
+            auction_house_fee_account: a.auction_house_fee_account,
+            token_program: a.token_program,
+            system_program: a.system_program,
+            rent: a.rent,
+        }
+    }
+}
+
 #[derive(Accounts, Clone)]
 #[instruction(escrow_payment_bump: u8)]
 pub struct AuctioneerDeposit<'info> {
@@ -31,7 +117,7 @@ pub struct AuctioneerDeposit<'info> {
     pub escrow_payment_account: UncheckedAccount<'info>,
 
     /// Auction House instance treasury mint account.
-    pub treasury_mint: Box<Account<'info, Mint>>,
+    pub treasury_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// CHECK: Validated in deposit_logic.
     /// Auction House instance authority account.
@@ -80,11 +166,35 @@ pub struct AuctioneerDeposit<'info> {
     )]
     pub auctioneer: Account<'info, Auctioneer>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
+/// Deposit `amount` into the escrow payment account for your specific wallet.
+pub fn deposit<'info>(
+    ctx: Context<'_, '_, '_, 'info, Deposit<'info>>,
+    escrow_payment_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    let auction_house = &ctx.accounts.auction_house;
+
+    if auction_house.has_auctioneer {
+        return Err(AuctionHouseError::MustUseAuctioneerHandler.into());
+    }
+
+    if escrow_payment_bump
+        != *ctx
+            .bumps
+            .get("escrow_payment_account")
+            .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?
+    {
+        return Err(AuctionHouseError::BumpSeedNotInHashMap.into());
+    }
+
+    deposit_logic(ctx.accounts, escrow_payment_bump, amount)
+}
+
 pub fn auctioneer_deposit<'info>(
     ctx: Context<'_, '_, '_, 'info, AuctioneerDeposit<'info>>,
     escrow_payment_bump: u8,
@@ -92,9 +202,12 @@ pub fn auctioneer_deposit<'info>(
 ) -> Result<()> {
     let auction_house = &ctx.accounts.auction_house;
 
-    if !auction_house.has_auctioneer {
-        return Err(AuctionHouseError::NoAuctioneerProgramSet.into());
-    }
+    assert_valid_auctioneer_and_scope(
+        auction_house,
+        &ctx.accounts.auctioneer_authority.key(),
+        &ctx.accounts.auctioneer,
+        AuthorityScope::Deposit,
+    )?;
 
     if escrow_payment_bump
         != *ctx
@@ -105,7 +218,7 @@ pub fn auctioneer_deposit<'info>(
         return Err(AuctionHouseError::BumpSeedNotInHashMap.into());
     }
 
-    let mut accounts: AuctioneerDeposit<'info> = (*ctx.accounts).clone();
+    let mut accounts: Deposit<'info> = (*ctx.accounts).clone().into();
 
     deposit_logic(&mut accounts, escrow_payment_bump, amount)
 }
@@ -113,7 +226,7 @@ pub fn auctioneer_deposit<'info>(
 #[allow(clippy::needless_lifetimes)]
 /// Deposit `amount` into the escrow payment account for your specific wallet.
 fn deposit_logic<'info>(
-    accounts: &mut AuctioneerDeposit<'info>,
+    accounts: &mut Deposit<'info>,
     escrow_payment_bump: u8,
     amount: u64,
 ) -> Result<()> {
@@ -169,21 +282,18 @@ fn deposit_logic<'info>(
 
     if !is_native {
         assert_is_ata(payment_account, &wallet.key(), &treasury_mint.key())?;
-        invoke(
-            &spl_token::instruction::transfer(
-                token_program.key,
-                &payment_account.key(),
-                &escrow_payment_account.key(),
-                &transfer_authority.key(),
-                &[],
-                amount,
-            )?,
-            &[
-                escrow_payment_account.to_account_info(),
-                payment_account.to_account_info(),
-                token_program.to_account_info(),
-                transfer_authority.to_account_info(),
-            ],
+
+        // Gross up for any transfer fee so the escrow is credited the full `amount`.
+        let treasury_mint_info = treasury_mint.to_account_info();
+        let gross_amount = get_amount_with_transfer_fee(&treasury_mint_info, amount)?;
+        transfer_tokens_checked(
+            &token_program.to_account_info(),
+            payment_account,
+            &treasury_mint_info,
+            escrow_payment_account,
+            transfer_authority,
+            gross_amount,
+            &[],
         )?;
     } else {
         assert_keys_equal(payment_account.key(), wallet.key())?;
@@ -208,5 +318,12 @@ fn deposit_logic<'info>(
         )?;
     }
 
+    emit!(EscrowDeposited {
+        auction_house: auction_house_key,
+        wallet: wallet_key,
+        escrow_payment_account: escrow_payment_account.key(),
+        amount,
+    });
+
     Ok(())
 }
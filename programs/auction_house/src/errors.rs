@@ -89,4 +89,116 @@ pub enum AuctionHouseError {
     // 6021
     #[msg("Insufficient funds in escrow account.")]
     InsufficientFunds,
+
+    // 6022
+    #[msg("Auction House has an auctioneer delegated, must use auctioneer handler.")]
+    MustUseAuctioneerHandler,
+
+    // 6023
+    #[msg("The auctioneer does not have the required scope for this action.")]
+    MissingAuctioneerScope,
+
+    // 6024
+    #[msg("The previous instruction does not match the receipt being printed.")]
+    InstructionMismatch,
+
+    // 6025
+    #[msg("Both partial order size and partial order price must be provided.")]
+    MissingElementForPartialOrder,
+
+    // 6026
+    #[msg("Partial order price does not match the listing price for the partial order size.")]
+    PartialPriceMismatch,
+
+    // 6027
+    #[msg("Partial order size must be greater than zero and no larger than the listed size.")]
+    InvalidPartialOrderSize,
+
+    // 6028
+    #[msg("Failed to build the Token Metadata instruction.")]
+    InstructionBuilderFailed,
+
+    // 6029
+    #[msg("Token metadata extension is missing or malformed.")]
+    InvalidTokenMetadata,
+
+    // 6030
+    #[msg("The royalty vault has no royalties to claim.")]
+    NoRoyaltiesToClaim,
+
+    // 6031
+    #[msg("There is no pending authority change to accept.")]
+    NoPendingAuthorityChange,
+
+    // 6032
+    #[msg("Withdrawal policy account is missing, invalid or would be loosened.")]
+    InvalidWithdrawalPolicy,
+
+    // 6033
+    #[msg("Withdrawal exceeds the epoch cap, queue it instead.")]
+    WithdrawalExceedsEpochCap,
+
+    // 6034
+    #[msg("Queued withdrawal is still timelocked.")]
+    WithdrawalTimelocked,
+
+    // 6035
+    #[msg("Auction House is paused.")]
+    AuctionHousePaused,
+
+    // 6036
+    #[msg("NFT is not part of a verified collection allowed on this Auction House.")]
+    CollectionNotAllowed,
+
+    // 6037
+    #[msg("Wallet is blocked from trading on this Auction House.")]
+    WalletBlocked,
+
+    // 6038
+    #[msg("Auctioneers have to be undelegated before the Auction House is closed.")]
+    AuctioneersStillDelegated,
+
+    // 6039
+    #[msg("Treasury and fee accounts have to be drained through the withdrawal policy first.")]
+    AuctionHouseNotDrained,
+
+    // 6040
+    #[msg("Auction House is already on the current layout version.")]
+    AuctionHouseAlreadyMigrated,
+
+    // 6041
+    #[msg("Trade state does not match the order.")]
+    TradeStateMismatch,
+
+    // 6042
+    #[msg("Trade state has expired.")]
+    TradeStateExpired,
+
+    // 6043
+    #[msg("Expiry has to be in the future.")]
+    InvalidExpiry,
+
+    // 6044
+    #[msg("Trade state has not expired yet.")]
+    TradeStateNotExpired,
+
+    // 6045
+    #[msg("At least one auctioneer scope is required.")]
+    MissingAuctioneerScopes,
+
+    // 6046
+    #[msg("Receipt has already been printed.")]
+    ReceiptAlreadyPrinted,
+
+    // 6047
+    #[msg("The escrow does not hold enough funds beyond those locked by highest auction bids.")]
+    EscrowFundsLocked,
+
+    // 6048
+    #[msg("The auctioneer still holds escrow funds locked and cannot be undelegated or replaced.")]
+    AuctioneerHoldsEscrowLocks,
+
+    // 6049
+    #[msg("The Auction House can only be closed by its creator, who can recreate it at the same address.")]
+    AuthorityNotCreator,
 }
@@ -0,0 +1,203 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*, errors::AuctionHouseError, utils::*, AuctionHouse, Auctioneer, AuthorityScope,
+};
+
+/// Escrow funds of a wallet reserved by auctioneers for the auctions it is the highest bidder of.
+/// The escrow is shared by all bids of the wallet, so every withdrawal path may only take what is
+/// not locked.
+#[account]
+pub struct EscrowLock {
+    pub auction_house: Pubkey,
+    pub wallet: Pubkey,
+    /// Sum of the wallet's current highest bids.
+    pub locked_amount: u64,
+    pub bump: u8,
+}
+
+/// Accounts for the [`auctioneer_lock_escrow` handler](auction_house/fn.auctioneer_lock_escrow.html).
+#[derive(Accounts)]
+pub struct AuctioneerLockEscrow<'info> {
+    /// Bidding wallet, paying for its escrow lock.
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump = auction_house.bump
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// The auctioneer authority - typically a PDA of the Auctioneer program running this action.
+    pub auctioneer_authority: Signer<'info>,
+
+    /// The auctioneer PDA owned by Auction House, counting the funds it locks.
+    #[account(
+        mut,
+        seeds = [
+            AUCTIONEER.as_bytes(),
+            auction_house.key().as_ref(),
+            auctioneer_authority.key().as_ref()
+        ],
+        bump = auctioneer.bump
+    )]
+    pub auctioneer: Account<'info, Auctioneer>,
+
+    /// Escrow lock PDA account of the wallet.
+    #[account(
+        init_if_needed,
+        payer = wallet,
+        space = ESCROW_LOCK_SIZE,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            ESCROW_LOCK.as_bytes(),
+            wallet.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_lock: Account<'info, EscrowLock>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts for the [`auctioneer_release_escrow` handler](auction_house/fn.auctioneer_release_escrow.html).
+#[derive(Accounts)]
+pub struct AuctioneerReleaseEscrow<'info> {
+    /// CHECK: Only used for the escrow lock seeds.
+    /// Wallet whose escrow funds are released.
+    pub wallet: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump = auction_house.bump
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// The auctioneer authority - typically a PDA of the Auctioneer program running this action.
+    pub auctioneer_authority: Signer<'info>,
+
+    /// The auctioneer PDA owned by Auction House, counting the funds it locks.
+    #[account(
+        mut,
+        seeds = [
+            AUCTIONEER.as_bytes(),
+            auction_house.key().as_ref(),
+            auctioneer_authority.key().as_ref()
+        ],
+        bump = auctioneer.bump
+    )]
+    pub auctioneer: Account<'info, Auctioneer>,
+
+    /// CHECK: Account seeds checked in constraint, may not exist for bids placed before escrow locking.
+    /// Escrow lock PDA account of the wallet.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            ESCROW_LOCK.as_bytes(),
+            wallet.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_lock: UncheckedAccount<'info>,
+}
+
+/// Reserve `amount` of the wallet's escrow for a highest bid placed through the auctioneer.
+pub fn auctioneer_lock_escrow(ctx: Context<AuctioneerLockEscrow>, amount: u64) -> Result<()> {
+    let auctioneer = &mut ctx.accounts.auctioneer;
+    assert_valid_auctioneer(
+        &ctx.accounts.auction_house,
+        &ctx.accounts.auctioneer_authority.key(),
+        auctioneer,
+    )?;
+    if !auctioneer.has_scope(AuthorityScope::Buy)
+        && !auctioneer.has_scope(AuthorityScope::PublicBuy)
+    {
+        return Err(AuctionHouseError::MissingAuctioneerScope.into());
+    }
+
+    let escrow_lock = &mut ctx.accounts.escrow_lock;
+    // First lock of the wallet, the account was just created.
+    if escrow_lock.wallet == Pubkey::default() {
+        escrow_lock.auction_house = ctx.accounts.auction_house.key();
+        escrow_lock.wallet = ctx.accounts.wallet.key();
+        escrow_lock.bump = *ctx
+            .bumps
+            .get("escrow_lock")
+            .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+    }
+
+    escrow_lock.locked_amount = escrow_lock
+        .locked_amount
+        .checked_add(amount)
+        .ok_or(AuctionHouseError::NumericalOverflow)?;
+    auctioneer.locked_amount = auctioneer
+        .locked_amount
+        .checked_add(amount)
+        .ok_or(AuctionHouseError::NumericalOverflow)?;
+
+    Ok(())
+}
+
+/// Release up to `amount` of the wallet's locked escrow funds as the auctioneer's highest bid is
+/// outbid, settled or cancelled.
+pub fn auctioneer_release_escrow(ctx: Context<AuctioneerReleaseEscrow>, amount: u64) -> Result<()> {
+    let auctioneer = &mut ctx.accounts.auctioneer;
+    assert_valid_auctioneer(
+        &ctx.accounts.auction_house,
+        &ctx.accounts.auctioneer_authority.key(),
+        auctioneer,
+    )?;
+
+    // Wallets that only bid before escrow locking have nothing to release.
+    let escrow_lock = &ctx.accounts.escrow_lock;
+    if escrow_lock.data_is_empty() {
+        return Ok(());
+    }
+
+    let mut data = escrow_lock.try_borrow_mut_data()?;
+    let mut lock = EscrowLock::try_deserialize(&mut &data[..])?;
+    // Capped, as a bid placed before the lock existed was never added to it.
+    let released = amount.min(lock.locked_amount);
+    lock.locked_amount -= released;
+    lock.try_serialize(&mut &mut data[..])?;
+
+    auctioneer.locked_amount = auctioneer.locked_amount.saturating_sub(released);
+
+    Ok(())
+}
+
+/// Amount locked by the escrow lock account of a wallet, whose address was already checked. Zero
+/// if no auctioneer ever locked its funds.
+pub fn locked_amount(escrow_lock: &AccountInfo) -> Result<u64> {
+    if escrow_lock.data_is_empty() {
+        return Ok(0);
+    }
+
+    assert_owned_by(escrow_lock, &crate::id())?;
+    let data = escrow_lock.try_borrow_data()?;
+    Ok(EscrowLock::try_deserialize(&mut &data[..])?.locked_amount)
+}
+
+/// Check `amount` can leave an escrow holding `balance` without taking funds its lock reserves.
+pub fn assert_unlocked_funds(escrow_lock: &AccountInfo, balance: u64, amount: u64) -> Result<()> {
+    let locked_amount = locked_amount(escrow_lock)?;
+    if locked_amount > 0 && amount > balance.saturating_sub(locked_amount) {
+        return Err(AuctionHouseError::EscrowFundsLocked.into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,211 @@
+use anchor_lang::prelude::*;
+
+use crate::{withdrawal_policy::WithdrawalSource, AuthorityScope};
+
+/// Emitted when a seller lists tokens, including relistings of the same trade state.
+#[event]
+pub struct ListingCreated {
+    pub auction_house: Pubkey,
+    pub seller: Pubkey,
+    pub token_mint: Pubkey,
+    pub token_account: Pubkey,
+    pub trade_state: Pubkey,
+    pub price: u64,
+    pub token_size: u64,
+}
+
+/// Emitted when a buyer places a private or public bid.
+#[event]
+pub struct BidPlaced {
+    pub auction_house: Pubkey,
+    pub buyer: Pubkey,
+    pub token_mint: Pubkey,
+    /// The token account bid on, only part of the trade state seeds for private bids.
+    pub token_account: Pubkey,
+    pub trade_state: Pubkey,
+    pub price: u64,
+    pub token_size: u64,
+    pub public: bool,
+}
+
+/// Emitted when a listing or bid is cancelled and its trade state closed.
+#[event]
+pub struct TradeStateCancelled {
+    pub auction_house: Pubkey,
+    pub wallet: Pubkey,
+    pub token_mint: Pubkey,
+    pub token_account: Pubkey,
+    pub trade_state: Pubkey,
+    pub price: u64,
+    pub token_size: u64,
+}
+
+/// Emitted when an expired listing or bid is closed, with the bounty paid to the closer.
+#[event]
+pub struct TradeStateExpired {
+    pub auction_house: Pubkey,
+    pub wallet: Pubkey,
+    pub token_mint: Pubkey,
+    pub trade_state: Pubkey,
+    pub closer: Pubkey,
+    pub bounty: u64,
+}
+
+/// Emitted when funds are deposited into a buyer escrow.
+#[event]
+pub struct EscrowDeposited {
+    pub auction_house: Pubkey,
+    pub wallet: Pubkey,
+    pub escrow_payment_account: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when funds are withdrawn from a buyer escrow.
+#[event]
+pub struct EscrowWithdrawn {
+    pub auction_house: Pubkey,
+    pub wallet: Pubkey,
+    pub escrow_payment_account: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when a sale is executed, with how the price was split. Payouts are the amounts each party
+/// received, net of any Token-2022 transfer fee withheld on the way.
+#[event]
+pub struct SaleExecuted {
+    pub auction_house: Pubkey,
+    pub buyer: Pubkey,
+    pub seller: Pubkey,
+    pub token_mint: Pubkey,
+    pub buyer_trade_state: Pubkey,
+    pub seller_trade_state: Pubkey,
+    pub price: u64,
+    pub token_size: u64,
+    /// Royalties paid to creators or credited to their royalty vaults.
+    pub royalties: u64,
+    pub auction_house_fee: u64,
+    pub seller_proceeds: u64,
+    /// Whether the listing stays open for the remaining tokens.
+    pub partial: bool,
+}
+
+/// Emitted when an auctioneer is delegated.
+#[event]
+pub struct AuctioneerDelegated {
+    pub auction_house: Pubkey,
+    pub auctioneer_authority: Pubkey,
+    pub scopes: Vec<AuthorityScope>,
+}
+
+/// Emitted when an auctioneer delegation is replaced by a new authority and scopes.
+#[event]
+pub struct AuctioneerUpdated {
+    pub auction_house: Pubkey,
+    pub auctioneer_authority: Pubkey,
+    pub new_auctioneer_authority: Pubkey,
+    pub scopes: Vec<AuthorityScope>,
+}
+
+/// Emitted when an auctioneer delegation is removed.
+#[event]
+pub struct AuctioneerUndelegated {
+    pub auction_house: Pubkey,
+    pub auctioneer_authority: Pubkey,
+}
+
+/// Emitted when the Auction House settings are updated.
+#[event]
+pub struct AuctionHouseUpdated {
+    pub auction_house: Pubkey,
+    pub authority: Pubkey,
+    pub treasury_withdrawal_destination: Pubkey,
+    pub fee_withdrawal_destination: Pubkey,
+    pub seller_fee_basis_points: u16,
+    pub can_change_sale_price: bool,
+}
+
+/// Emitted when an Auction House is closed and its rent returned to the authority.
+#[event]
+pub struct AuctionHouseClosed {
+    pub auction_house: Pubkey,
+    pub authority: Pubkey,
+}
+
+/// Emitted when an Auction House account is upgraded to a new layout version.
+#[event]
+pub struct AuctionHouseMigrated {
+    pub auction_house: Pubkey,
+    pub previous_version: u8,
+    pub version: u8,
+}
+
+/// Emitted when trading on the Auction House is paused or resumed.
+#[event]
+pub struct PauseStateChanged {
+    pub auction_house: Pubkey,
+    pub is_paused: bool,
+}
+
+/// Emitted when a collection is added to the allowlist.
+#[event]
+pub struct CollectionAllowed {
+    pub auction_house: Pubkey,
+    pub collection: Pubkey,
+}
+
+/// Emitted when a collection is removed from the allowlist.
+#[event]
+pub struct CollectionDisallowed {
+    pub auction_house: Pubkey,
+    pub collection: Pubkey,
+}
+
+/// Emitted when a wallet is added to the blocklist.
+#[event]
+pub struct WalletBlocked {
+    pub auction_house: Pubkey,
+    pub wallet: Pubkey,
+}
+
+/// Emitted when a wallet is removed from the blocklist.
+#[event]
+pub struct WalletUnblocked {
+    pub auction_house: Pubkey,
+    pub wallet: Pubkey,
+}
+
+/// Emitted when an authority or withdrawal destination change is proposed. Default keys are not
+/// being changed.
+#[event]
+pub struct AuthorityChangeProposed {
+    pub auction_house: Pubkey,
+    pub pending_authority: Pubkey,
+    pub pending_treasury_withdrawal_destination: Pubkey,
+    pub pending_fee_withdrawal_destination: Pubkey,
+}
+
+/// Emitted when a withdrawal above the epoch cap is queued.
+#[event]
+pub struct WithdrawalQueued {
+    pub auction_house: Pubkey,
+    pub queued_withdrawal: Pubkey,
+    pub source: WithdrawalSource,
+    pub amount: u64,
+    pub executable_at: i64,
+}
+
+/// Emitted when a queued withdrawal is paid out after its timelock.
+#[event]
+pub struct QueuedWithdrawalExecuted {
+    pub auction_house: Pubkey,
+    pub queued_withdrawal: Pubkey,
+    pub source: WithdrawalSource,
+    pub amount: u64,
+}
+
+/// Emitted when a queued withdrawal is cancelled.
+#[event]
+pub struct QueuedWithdrawalCancelled {
+    pub auction_house: Pubkey,
+    pub queued_withdrawal: Pubkey,
+}
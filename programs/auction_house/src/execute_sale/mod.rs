@@ -1,8 +1,217 @@
 use crate::{constants::*, errors::*, utils::*, AuctionHouse, Auctioneer, *};
-use anchor_lang::{prelude::*, solana_program::program_pack::Pack, AnchorDeserialize};
-use spl_token::state::Account as SplAccount;
+use anchor_lang::{prelude::*, AnchorDeserialize};
+use anchor_spl::token_interface::TokenInterface;
 
-#[derive(Accounts)]
+#[derive(Accounts, Clone)]
+#[instruction(
+    escrow_payment_bump: u8,
+    free_trade_state_bump: u8,
+    program_as_signer_bump: u8,
+    buyer_price: u64,
+    token_size: u64
+)]
+pub struct ExecuteSale<'info> {
+    /// CHECK: Validated in execute_sale_logic.
+    /// Buyer user wallet account.
+    #[account(mut)]
+    pub buyer: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in execute_sale_logic.
+    /// Seller user wallet account.
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in execute_sale_logic.
+    // cannot mark these as real Accounts or else we blow stack size limit
+    ///Token account where the SPL token is stored.
+    #[account(mut)]
+    pub token_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in execute_sale_logic.
+    /// Token mint account for the SPL token.
+    pub token_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in execute_sale_logic.
+    /// Metaplex metadata account decorating SPL mint account.
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in execute_sale_logic.
+    // cannot mark these as real Accounts or else we blow stack size limit
+    /// Auction House treasury mint account.
+    pub treasury_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer escrow payment account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            buyer.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in execute_sale_logic.
+    /// Seller SOL or SPL account to receive payment at.
+    #[account(mut)]
+    pub seller_payment_receipt_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in execute_sale_logic.
+    /// Buyer SPL token account to receive purchased item at.
+    #[account(mut)]
+    pub buyer_receipt_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in execute_sale_logic.
+    /// Auction House instance authority.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump=auction_house.bump,
+        has_one=authority,
+        has_one=treasury_mint,
+        has_one=auction_house_treasury,
+        has_one=auction_house_fee_account
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Auction House instance fee account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            FEE_PAYER.as_bytes()
+        ],
+        bump=auction_house.fee_payer_bump
+    )]
+    pub auction_house_fee_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Auction House instance treasury account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            TREASURY.as_bytes()
+        ],
+        bump=auction_house.treasury_bump
+    )]
+    pub auction_house_treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in execute_sale_logic.
+    /// Buyer trade state PDA account encoding the buy order.
+    #[account(mut)]
+    pub buyer_trade_state: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Seller trade state PDA account encoding the sell order.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            seller.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_account.key().as_ref(),
+            auction_house.treasury_mint.as_ref(),
+            token_mint.key().as_ref(),
+            &buyer_price.to_le_bytes(),
+            &token_size.to_le_bytes()
+        ],
+        bump=seller_trade_state.to_account_info().data.borrow()[0]
+    )]
+    pub seller_trade_state: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Free seller trade state PDA account encoding a free sell order.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            seller.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_account.key().as_ref(),
+            auction_house.treasury_mint.as_ref(),
+            token_mint.key().as_ref(),
+            &0u64.to_le_bytes(),
+            &token_size.to_le_bytes()
+        ],
+        bump
+    )]
+    pub free_trade_state: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(
+        seeds=[
+            PREFIX.as_bytes(), SIGNER.as_bytes()
+        ],
+        bump
+    )]
+    pub program_as_signer: UncheckedAccount<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    /// Token program owning the treasury mint, either SPL Token or Token-2022.
+    pub treasury_token_program: Interface<'info, TokenInterface>,
+    // Remaining accounts (Provide buyer and seller blocked wallet PDAs first if the Auction House
+    // has a blocklist)
+    // Remaining accounts (Provide only if token metadata has creators, each followed by its
+    // treasury mint token account, or by its royalty vault for native treasuries)
+    //    #[account(mut)]
+    //    pub creator1: Option<UncheckedAccount<'info>>,
+    //    #[account(mut)]
+    //    pub creator2: Option<UncheckedAccount<'info>>,
+    //    #[account(mut)]
+    //    pub creator3: Option<UncheckedAccount<'info>>,
+    //    #[account(mut)]
+    //    pub creator4: Option<UncheckedAccount<'info>>,
+    //    #[account(mut)]
+    //    pub creator5: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> From<AuctioneerExecuteSale<'info>> for ExecuteSale<'info> {
+    fn from(a: AuctioneerExecuteSale<'info>) -> ExecuteSale<'info> {
+        ExecuteSale {
+            buyer: a.buyer,
+            seller: a.seller,
+            token_account: a.token_account,
+            token_mint: a.token_mint,
+            metadata: a.metadata,
+            treasury_mint: a.treasury_mint,
+            escrow_payment_account: a.escrow_payment_account,
+            seller_payment_receipt_account: a.seller_payment_receipt_account,
+            buyer_receipt_token_account: a.buyer_receipt_token_account,
+            authority: a.authority,
+            auction_house: a.auction_house,
+            auction_house_fee_account: a.auction_house_fee_account,
+            auction_house_treasury: a.auction_house_treasury,
+            buyer_trade_state: a.buyer_trade_state,
+            seller_trade_state: a.seller_trade_state,
+            free_trade_state: a.free_trade_state,
+            token_program: a.token_program,
+            system_program: a.system_program,
+            associated_token_program: a.associated_token_program,
+            program_as_signer: a.program_as_signer,
+            rent: a.rent,
+            treasury_token_program: a.treasury_token_program,
+        }
+    }
+}
+
+#[derive(Accounts, Clone)]
 #[instruction(
     escrow_payment_bump: u8,
     free_trade_state_bump: u8,
@@ -165,7 +374,7 @@ pub struct AuctioneerExecuteSale<'info> {
     )]
     pub auctioneer: Box<Account<'info, Auctioneer>>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 
@@ -179,7 +388,13 @@ pub struct AuctioneerExecuteSale<'info> {
     pub program_as_signer: UncheckedAccount<'info>,
 
     pub rent: Sysvar<'info, Rent>,
-    // Remaining accounts (Provide only if token metadata has creators)
+
+    /// Token program owning the treasury mint, either SPL Token or Token-2022.
+    pub treasury_token_program: Interface<'info, TokenInterface>,
+    // Remaining accounts (Provide buyer and seller blocked wallet PDAs first if the Auction House
+    // has a blocklist)
+    // Remaining accounts (Provide only if token metadata has creators, each followed by its
+    // treasury mint token account, or by its royalty vault for native treasuries)
     //    #[account(mut)]
     //    pub creator1: Option<UncheckedAccount<'info>>,
     //    #[account(mut)]
@@ -192,18 +407,207 @@ pub struct AuctioneerExecuteSale<'info> {
     //    pub creator5: Option<UncheckedAccount<'info>>,
 }
 
-pub fn auctioneer_execute_sale<'info>(
-    ctx: Context<'_, '_, '_, 'info, AuctioneerExecuteSale<'info>>,
+/// Accounts for the [`execute_sale_v2` handler](auction_house/fn.execute_sale_v2.html).
+#[derive(Accounts, Clone)]
+pub struct ExecuteSaleV2<'info> {
+    /// CHECK: Validated in execute_sale_logic.
+    /// Buyer user wallet account.
+    #[account(mut)]
+    pub buyer: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in execute_sale_logic.
+    /// Seller user wallet account.
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in execute_sale_logic.
+    // cannot mark these as real Accounts or else we blow stack size limit
+    ///Token account where the SPL token is stored.
+    #[account(mut)]
+    pub token_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in execute_sale_logic.
+    /// Token mint account for the SPL token.
+    pub token_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in execute_sale_logic.
+    /// Metaplex metadata account decorating SPL mint account.
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in execute_sale_logic.
+    // cannot mark these as real Accounts or else we blow stack size limit
+    /// Auction House treasury mint account.
+    pub treasury_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer escrow payment account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            buyer.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in execute_sale_logic.
+    /// Seller SOL or SPL account to receive payment at.
+    #[account(mut)]
+    pub seller_payment_receipt_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in execute_sale_logic.
+    /// Buyer SPL token account to receive purchased item at.
+    #[account(mut)]
+    pub buyer_receipt_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in execute_sale_logic.
+    /// Auction House instance authority.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump=auction_house.bump,
+        has_one=authority,
+        has_one=treasury_mint,
+        has_one=auction_house_treasury,
+        has_one=auction_house_fee_account
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Auction House instance fee account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            FEE_PAYER.as_bytes()
+        ],
+        bump=auction_house.fee_payer_bump
+    )]
+    pub auction_house_fee_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Auction House instance treasury account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            TREASURY.as_bytes()
+        ],
+        bump=auction_house.treasury_bump
+    )]
+    pub auction_house_treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in execute_sale_logic.
+    /// Buyer trade state PDA account encoding the buy order.
+    #[account(mut)]
+    pub buyer_trade_state: UncheckedAccount<'info>,
+
+    /// CHECK: Account seeds checked in constraint, order validated in execute_sale_v2.
+    /// Seller v2 trade state PDA account holding the listing.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            TRADE_STATE_V2.as_bytes(),
+            seller.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_account.key().as_ref(),
+            &TradeSide::Sell.seed()
+        ],
+        bump
+    )]
+    pub seller_trade_state: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(
+        seeds=[
+            PREFIX.as_bytes(), SIGNER.as_bytes()
+        ],
+        bump
+    )]
+    pub program_as_signer: UncheckedAccount<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    /// Token program owning the treasury mint, either SPL Token or Token-2022.
+    pub treasury_token_program: Interface<'info, TokenInterface>,
+    // Remaining accounts (Provide buyer and seller blocked wallet PDAs first if the Auction House
+    // has a blocklist)
+    // Remaining accounts (Provide only if token metadata has creators, each followed by its
+    // treasury mint token account, or by its royalty vault for native treasuries)
+    //    #[account(mut)]
+    //    pub creator1: Option<UncheckedAccount<'info>>,
+    //    #[account(mut)]
+    //    pub creator2: Option<UncheckedAccount<'info>>,
+    //    #[account(mut)]
+    //    pub creator3: Option<UncheckedAccount<'info>>,
+    //    #[account(mut)]
+    //    pub creator4: Option<UncheckedAccount<'info>>,
+    //    #[account(mut)]
+    //    pub creator5: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> From<ExecuteSaleV2<'info>> for ExecuteSale<'info> {
+    fn from(a: ExecuteSaleV2<'info>) -> ExecuteSale<'info> {
+        ExecuteSale {
+            buyer: a.buyer,
+            seller: a.seller,
+            token_account: a.token_account,
+            token_mint: a.token_mint,
+            metadata: a.metadata,
+            treasury_mint: a.treasury_mint,
+            escrow_payment_account: a.escrow_payment_account,
+            seller_payment_receipt_account: a.seller_payment_receipt_account,
+            buyer_receipt_token_account: a.buyer_receipt_token_account,
+            authority: a.authority,
+            auction_house: a.auction_house,
+            auction_house_fee_account: a.auction_house_fee_account,
+            auction_house_treasury: a.auction_house_treasury,
+            buyer_trade_state: a.buyer_trade_state,
+            // V2 listings have no free trade state. Once the seller trade state is closed this
+            // alias has no lamports left, so it is skipped.
+            free_trade_state: a.seller_trade_state.clone(),
+            seller_trade_state: a.seller_trade_state,
+            token_program: a.token_program,
+            system_program: a.system_program,
+            associated_token_program: a.associated_token_program,
+            program_as_signer: a.program_as_signer,
+            rent: a.rent,
+            treasury_token_program: a.treasury_token_program,
+        }
+    }
+}
+
+/// Execute sale between provided buyer and seller trade state accounts transferring funds to seller wallet and token to buyer wallet.
+#[inline(never)]
+pub fn execute_sale<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteSale<'info>>,
     escrow_payment_bump: u8,
     free_trade_state_bump: u8,
     program_as_signer_bump: u8,
     buyer_price: u64,
     token_size: u64,
+    partial_order_size: Option<u64>,
+    partial_order_price: Option<u64>,
 ) -> Result<()> {
     let auction_house = &ctx.accounts.auction_house;
 
-    if !auction_house.has_auctioneer {
-        return Err(AuctionHouseError::NoAuctioneerProgramSet.into());
+    if auction_house.has_auctioneer {
+        return Err(AuctionHouseError::MustUseAuctioneerHandler.into());
     }
 
     let escrow_canonical_bump = *ctx
@@ -226,7 +630,7 @@ pub fn auctioneer_execute_sale<'info>(
         return Err(AuctionHouseError::BumpSeedNotInHashMap.into());
     }
 
-    auctioneer_execute_sale_logic(
+    execute_sale_logic(
         ctx.accounts,
         ctx.remaining_accounts,
         escrow_payment_bump,
@@ -234,19 +638,152 @@ pub fn auctioneer_execute_sale<'info>(
         program_as_signer_bump,
         buyer_price,
         token_size,
+        partial_order_size,
+        partial_order_price,
+    )
+}
+
+pub fn auctioneer_execute_sale<'info>(
+    ctx: Context<'_, '_, '_, 'info, AuctioneerExecuteSale<'info>>,
+    escrow_payment_bump: u8,
+    free_trade_state_bump: u8,
+    program_as_signer_bump: u8,
+    buyer_price: u64,
+    token_size: u64,
+    partial_order_size: Option<u64>,
+    partial_order_price: Option<u64>,
+) -> Result<()> {
+    let auction_house = &ctx.accounts.auction_house;
+
+    assert_valid_auctioneer_and_scope(
+        auction_house,
+        &ctx.accounts.auctioneer_authority.key(),
+        &ctx.accounts.auctioneer,
+        AuthorityScope::ExecuteSale,
+    )?;
+
+    let escrow_canonical_bump = *ctx
+        .bumps
+        .get("escrow_payment_account")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+    let free_trade_state_canonical_bump = *ctx
+        .bumps
+        .get("free_trade_state")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+    let program_as_signer_canonical_bump = *ctx
+        .bumps
+        .get("program_as_signer")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+
+    if (escrow_canonical_bump != escrow_payment_bump)
+        || (free_trade_state_canonical_bump != free_trade_state_bump)
+        || (program_as_signer_canonical_bump != program_as_signer_bump)
+    {
+        return Err(AuctionHouseError::BumpSeedNotInHashMap.into());
+    }
+
+    let mut accounts: ExecuteSale<'info> = (*ctx.accounts).clone().into();
+
+    execute_sale_logic(
+        &mut accounts,
+        ctx.remaining_accounts,
+        escrow_payment_bump,
+        free_trade_state_bump,
+        program_as_signer_bump,
+        buyer_price,
+        token_size,
+        partial_order_size,
+        partial_order_price,
     )
 }
 
+/// Execute sale against a v2 listing at its current price, then shrink the listing by a partial order.
+#[inline(never)]
+pub fn execute_sale_v2<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteSaleV2<'info>>,
+    buyer_price: u64,
+    token_size: u64,
+    partial_order_size: Option<u64>,
+    partial_order_price: Option<u64>,
+) -> Result<()> {
+    let auction_house = &ctx.accounts.auction_house;
+
+    if auction_house.has_auctioneer {
+        return Err(AuctionHouseError::MustUseAuctioneerHandler.into());
+    }
+
+    let seller_trade_state = &ctx.accounts.seller_trade_state;
+    let listing =
+        load_trade_state_v2(seller_trade_state)?.ok_or(AuctionHouseError::TradeStateMismatch)?;
+    if listing.side != TradeSide::Sell {
+        return Err(AuctionHouseError::TradeStateMismatch.into());
+    }
+    listing.assert_order(
+        &ctx.accounts.seller.key(),
+        &auction_house.key(),
+        &ctx.accounts.token_account.key(),
+        &ctx.accounts.token_mint.key(),
+        buyer_price,
+        token_size,
+    )?;
+
+    let escrow_payment_bump = *ctx
+        .bumps
+        .get("escrow_payment_account")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+    let program_as_signer_bump = *ctx
+        .bumps
+        .get("program_as_signer")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+
+    let seller_trade_state = seller_trade_state.to_account_info();
+    let mut accounts: ExecuteSale<'info> = (*ctx.accounts).clone().into();
+
+    execute_sale_logic(
+        &mut accounts,
+        ctx.remaining_accounts,
+        escrow_payment_bump,
+        0,
+        program_as_signer_bump,
+        buyer_price,
+        token_size,
+        partial_order_size,
+        partial_order_price,
+    )?;
+
+    // The rest of a partially filled listing stays open at the same per-token price.
+    if let (Some(size), Some(price)) = (partial_order_size, partial_order_price) {
+        if seller_trade_state.lamports() > 0 {
+            let mut listing = listing;
+            listing.token_size = listing
+                .token_size
+                .checked_sub(size)
+                .ok_or(AuctionHouseError::NumericalOverflow)?;
+            listing.price = listing
+                .price
+                .checked_sub(price)
+                .ok_or(AuctionHouseError::NumericalOverflow)?;
+
+            let mut data = seller_trade_state.try_borrow_mut_data()?;
+            listing.try_serialize(&mut &mut data[..])?;
+        }
+    }
+
+    Ok(())
+}
+
 /// Execute sale between provided buyer and seller trade state accounts transferring funds to seller wallet and token to buyer wallet.
 #[inline(never)]
-fn auctioneer_execute_sale_logic<'c, 'info>(
-    accounts: &mut AuctioneerExecuteSale<'info>,
+fn execute_sale_logic<'c, 'info>(
+    accounts: &mut ExecuteSale<'info>,
     remaining_accounts: &'c [AccountInfo<'info>],
     escrow_payment_bump: u8,
     _free_trade_state_bump: u8,
     program_as_signer_bump: u8,
     buyer_price: u64,
     token_size: u64,
+    partial_order_size: Option<u64>,
+    partial_order_price: Option<u64>,
 ) -> Result<()> {
     let buyer = &accounts.buyer;
     let seller = &accounts.seller;
@@ -269,12 +806,14 @@ fn auctioneer_execute_sale_logic<'c, 'info>(
     let associated_token_program = &accounts.associated_token_program;
     let program_as_signer = &accounts.program_as_signer;
     let rent = &accounts.rent;
+    let treasury_token_program = &accounts.treasury_token_program;
 
     let metadata_clone = metadata.to_account_info();
     let escrow_clone = escrow_payment_account.to_account_info();
     let auction_house_clone = auction_house.to_account_info();
     let ata_clone = associated_token_program.to_account_info();
     let token_clone = token_program.to_account_info();
+    let treasury_token_clone = treasury_token_program.to_account_info();
     let sys_clone = system_program.to_account_info();
     let rent_clone = rent.to_account_info();
     let treasury_clone = auction_house_treasury.to_account_info();
@@ -284,6 +823,12 @@ fn auctioneer_execute_sale_logic<'c, 'info>(
 
     let is_native = treasury_mint.key() == spl_token::native_mint::id();
 
+    assert_not_paused(auction_house)?;
+
+    let remaining_accounts = &mut remaining_accounts.iter();
+    assert_wallet_not_blocked(auction_house, &buyer.key(), remaining_accounts)?;
+    assert_wallet_not_blocked(auction_house, &seller.key(), remaining_accounts)?;
+
     if buyer_price == 0 && !authority_clone.is_signer && !seller.is_signer {
         return Err(
             AuctionHouseError::CannotMatchFreeSalesWithoutAuctionHouseOrSellerSignoff.into(),
@@ -302,32 +847,72 @@ fn auctioneer_execute_sale_logic<'c, 'info>(
     }
 
     let ts_bump = if buyer_trade_state.data_len() > 0 {
-        buyer_trade_state.try_borrow_data()?[0]
+        trade_state_bump(buyer_trade_state)?
     } else {
         return Err(AuctionHouseError::BuyerTradeStateNotValid.into());
     };
 
-    if ts_bump == 0
-        || seller_trade_state.data_len() == 0
-        || seller_trade_state.try_borrow_data()?[0] == 0
-    {
+    if ts_bump == 0 || trade_state_bump(seller_trade_state)? == 0 {
         return Err(AuctionHouseError::BothPartiesNeedToAgreeToSale.into());
     }
 
-    let token_account_data = SplAccount::unpack(&token_account.data.borrow())?;
+    // A listing can't be passed off as the bid, both carry the seller's token account.
+    if let Some(trade_state_v2) = load_trade_state_v2(buyer_trade_state)? {
+        if trade_state_v2.side != TradeSide::Buy {
+            return Err(AuctionHouseError::BuyerTradeStateNotValid.into());
+        }
+        trade_state_v2.assert_not_expired()?;
+    } else {
+        assert_legacy_trade_state_not_expired(buyer_trade_state)?;
+    }
+    if let Some(trade_state_v2) = load_trade_state_v2(seller_trade_state)? {
+        trade_state_v2.assert_not_expired()?;
+    } else {
+        assert_legacy_trade_state_not_expired(seller_trade_state)?;
+    }
+
+    let token_account_data = unpack_token_account(&token_account_clone)?;
+
+    // A partial order buys `size` of the listed tokens at the listing's per-token price, rounded
+    // down. The seller trade state and delegate are left in place for the remainder. A v2 listing
+    // then keeps the rest of the price, so its partial fills add up to the listing price. A legacy
+    // listing records no fills and prices each one from the whole listing, so every partial fill
+    // of it can round off up to a lamport (or smallest treasury mint unit) from the seller.
+    let (price, size) = match (partial_order_size, partial_order_price) {
+        (Some(size), Some(price)) => {
+            if size == 0 || size > token_size {
+                return Err(AuctionHouseError::InvalidPartialOrderSize.into());
+            }
+
+            let expected_price = (buyer_price as u128)
+                .checked_mul(size as u128)
+                .ok_or(AuctionHouseError::NumericalOverflow)?
+                .checked_div(token_size as u128)
+                .ok_or(AuctionHouseError::NumericalOverflow)?;
+            let expected_price =
+                u64::try_from(expected_price).map_err(|_| AuctionHouseError::NumericalOverflow)?;
+            if price != expected_price {
+                return Err(AuctionHouseError::PartialPriceMismatch.into());
+            }
+
+            (price, size)
+        }
+        (None, None) => (buyer_price, token_size),
+        _ => return Err(AuctionHouseError::MissingElementForPartialOrder.into()),
+    };
 
     assert_valid_trade_state(
         &buyer.key(),
         auction_house,
-        buyer_price,
-        token_size,
+        price,
+        size,
         buyer_trade_state,
         &token_mint.key(),
         &token_account.key(),
         ts_bump,
     )?;
 
-    if token_account_data.amount < token_size {
+    if token_account_data.amount < size {
         return Err(AuctionHouseError::NotEnoughTokensAvailableForPurchase.into());
     };
 
@@ -354,23 +939,14 @@ fn auctioneer_execute_sale_logic<'c, 'info>(
         &seller.key(),
         &token_account_mint,
     )?;
-    assert_derivation(
-        &mpl_token_metadata::id(),
-        &metadata.to_account_info(),
-        &[
-            mpl_token_metadata::state::PREFIX.as_bytes(),
-            mpl_token_metadata::id().as_ref(),
-            token_account_mint.as_ref(),
-        ],
-    )?;
+    assert_metadata_valid(&metadata_clone, &token_account_mint)?;
 
     // For native purchases, verify that the amount in escrow is sufficient to actually purchase the
     // token.  This is intended to cover the migration from pre-rent-exemption checked accounts to
     // rent-exemption checked accounts.  The fee payer makes up the shortfall up to the amount of
     // rent for an empty account.
     if is_native {
-        let rent_shortfall =
-            verify_withdrawal(escrow_payment_account.to_account_info(), buyer_price)?;
+        let rent_shortfall = verify_withdrawal(escrow_payment_account.to_account_info(), price)?;
         if rent_shortfall > 0 {
             invoke_signed(
                 &system_instruction::transfer(
@@ -416,33 +992,34 @@ fn auctioneer_execute_sale_logic<'c, 'info>(
         ah_seeds
     };
 
-    let remaining_accounts = &mut remaining_accounts.iter();
-
-    let buyer_leftover_after_royalties = pay_creator_fees(
+    let (buyer_leftover_after_royalties, royalties_received) = pay_creator_fees(
         remaining_accounts,
         &metadata_clone,
+        auction_house,
+        &auction_house_fee_account.to_account_info(),
         &escrow_clone,
         &auction_house_clone,
         &fee_payer_clone,
         treasury_mint,
         &ata_clone,
-        &token_clone,
+        &treasury_token_clone,
         &sys_clone,
         &rent_clone,
         &signer_seeds_for_royalties,
         fee_payer_seeds,
-        buyer_price,
+        price,
         is_native,
     )?;
 
-    let auction_house_fee_paid = pay_auction_house_fees(
+    let (auction_house_fee_paid, auction_house_fee_received) = pay_auction_house_fees(
         auction_house,
         &treasury_clone,
         &escrow_clone,
-        &token_clone,
+        treasury_mint,
+        &treasury_token_clone,
         &sys_clone,
         &signer_seeds_for_royalties,
-        buyer_price,
+        price,
         is_native,
     )?;
 
@@ -450,7 +1027,7 @@ fn auctioneer_execute_sale_logic<'c, 'info>(
         .checked_sub(auction_house_fee_paid)
         .ok_or(AuctionHouseError::NumericalOverflow)?;
 
-    if !is_native {
+    let seller_proceeds = if !is_native {
         if seller_payment_receipt_account.data_is_empty() {
             make_ata(
                 seller_payment_receipt_account.to_account_info(),
@@ -458,7 +1035,7 @@ fn auctioneer_execute_sale_logic<'c, 'info>(
                 treasury_mint.to_account_info(),
                 fee_payer.to_account_info(),
                 associated_token_program.to_account_info(),
-                token_program.to_account_info(),
+                treasury_token_clone.clone(),
                 system_program.to_account_info(),
                 rent.to_account_info(),
                 fee_payer_seeds,
@@ -476,23 +1053,17 @@ fn auctioneer_execute_sale_logic<'c, 'info>(
             return Err(AuctionHouseError::SellerATACannotHaveDelegate.into());
         }
 
-        invoke_signed(
-            &spl_token::instruction::transfer(
-                token_program.key,
-                &escrow_payment_account.key(),
-                &seller_payment_receipt_account.key(),
-                &auction_house.key(),
-                &[],
-                buyer_leftover_after_royalties_and_house_fee,
-            )?,
-            &[
-                escrow_payment_account.to_account_info(),
-                seller_payment_receipt_account.to_account_info(),
-                token_program.to_account_info(),
-                auction_house.to_account_info(),
-            ],
+        // Escrow debits match the computed split exactly; a Token-2022 transfer fee is withheld
+        // from what each recipient receives rather than taken out of the escrow.
+        transfer_tokens_checked(
+            &treasury_token_clone,
+            &escrow_clone,
+            treasury_mint,
+            seller_payment_receipt_account,
+            &auction_house_clone,
+            buyer_leftover_after_royalties_and_house_fee,
             &[&ah_seeds],
-        )?;
+        )?
     } else {
         assert_keys_equal(seller_payment_receipt_account.key(), seller.key())?;
         invoke_signed(
@@ -508,7 +1079,8 @@ fn auctioneer_execute_sale_logic<'c, 'info>(
             ],
             &[&escrow_signer_seeds],
         )?;
-    }
+        buyer_leftover_after_royalties_and_house_fee
+    };
 
     if buyer_receipt_token_account.data_is_empty() {
         make_ata(
@@ -523,8 +1095,7 @@ fn auctioneer_execute_sale_logic<'c, 'info>(
             fee_payer_seeds,
         )?;
     } else {
-        let data = buyer_receipt_token_account.try_borrow_data()?;
-        let token_account = TokenAccount::try_deserialize(&mut data.as_ref())?;
+        let token_account = unpack_token_account(&buyer_receipt_clone)?;
         if &token_account.owner != buyer.key {
             return Err(AuctionHouseError::IncorrectOwner.into());
         }
@@ -543,23 +1114,36 @@ fn auctioneer_execute_sale_logic<'c, 'info>(
         &[program_as_signer_bump],
     ];
 
-    invoke_signed(
-        &spl_token::instruction::transfer(
-            token_program.key,
-            &token_account.key(),
-            &buyer_receipt_token_account.key(),
-            &program_as_signer.key(),
-            &[],
-            token_size,
-        )?,
-        &[
-            token_account.to_account_info(),
-            buyer_receipt_clone,
-            program_as_signer.to_account_info(),
-            token_clone,
-        ],
-        &[&program_as_signer_seeds],
-    )?;
+    // Programmable NFTs are frozen, so they have to be moved through Token Metadata.
+    if is_programmable(&metadata_clone)? {
+        transfer_programmable(
+            remaining_accounts,
+            &metadata_clone,
+            &token_account_clone,
+            seller,
+            &buyer_receipt_clone,
+            buyer,
+            token_mint,
+            program_as_signer,
+            &fee_payer_clone,
+            &sys_clone,
+            &token_clone,
+            &ata_clone,
+            &program_as_signer_seeds,
+            fee_payer_seeds,
+            size,
+        )?;
+    } else {
+        transfer_tokens_checked(
+            &token_clone,
+            &token_account_clone,
+            token_mint,
+            &buyer_receipt_clone,
+            program_as_signer,
+            size,
+            &[&program_as_signer_seeds],
+        )?;
+    }
 
     // Close the buyer trade state account if the rest of execute sale was successful.
     close_account(
@@ -567,8 +1151,9 @@ fn auctioneer_execute_sale_logic<'c, 'info>(
         &fee_payer.to_account_info(),
     )?;
 
-    let token_account_data = SplAccount::unpack(&token_account.data.borrow())?;
-    if token_account_data.delegated_amount == 0 {
+    let token_account_data = unpack_token_account(&token_account_clone)?;
+    let partial = token_account_data.delegated_amount > 0;
+    if !partial {
         close_account(
             &seller_trade_state.to_account_info(),
             &fee_payer.to_account_info(),
@@ -581,5 +1166,21 @@ fn auctioneer_execute_sale_logic<'c, 'info>(
             )?;
         }
     }
+
+    emit!(SaleExecuted {
+        auction_house: auction_house_key,
+        buyer: buyer.key(),
+        seller: seller.key(),
+        token_mint: token_mint.key(),
+        buyer_trade_state: buyer_trade_state.key(),
+        seller_trade_state: seller_trade_state.key(),
+        price,
+        token_size: size,
+        royalties: royalties_received,
+        auction_house_fee: auction_house_fee_received,
+        seller_proceeds,
+        partial,
+    });
+
     Ok(())
 }
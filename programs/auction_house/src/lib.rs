@@ -1,23 +1,37 @@
 #![allow(clippy::result_large_err)]
 #![allow(clippy::too_many_arguments)]
 pub mod auctioneer;
+pub mod authority;
 pub mod bid;
+pub mod blocklist;
 pub mod cancel;
+pub mod close;
+pub mod collection_allowlist;
 pub mod constants;
 pub mod deposit;
 pub mod errors;
+pub mod escrow_lock;
+pub mod events;
 pub mod execute_sale;
+pub mod metadata;
+pub mod migrate;
 pub mod pda;
+pub mod receipt;
+pub mod royalty;
 pub mod sell;
 pub mod state;
+pub mod trade_state;
 pub mod utils;
 pub mod withdraw;
+pub mod withdrawal_policy;
 
 pub use state::*;
 
 use crate::{
-    auctioneer::*, bid::*, cancel::*, constants::*, deposit::*, errors::AuctionHouseError,
-    execute_sale::*, sell::*, utils::*, withdraw::*,
+    auctioneer::*, authority::*, bid::*, blocklist::*, cancel::*, close::*,
+    collection_allowlist::*, constants::*, deposit::*, errors::AuctionHouseError, escrow_lock::*,
+    events::*, execute_sale::*, metadata::*, migrate::*, receipt::*, royalty::*, sell::*,
+    trade_state::*, utils::*, withdraw::*, withdrawal_policy::*,
 };
 
 use anchor_lang::{
@@ -27,7 +41,7 @@ use anchor_lang::{
 };
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount},
+    token_interface::{Mint, TokenInterface},
 };
 
 anchor_lang::declare_id!("FMrPvDk4xZNykJ2aWCmyKCzQ12qhJ6SR9tS67fhLbx8x");
@@ -37,123 +51,65 @@ pub mod auction_house {
     use super::*;
 
     /// Withdraw `amount` from the Auction House Fee Account to a provided destination account.
+    /// If the Auction House has a withdrawal policy it has to be passed as the first remaining account.
     pub fn withdraw_from_fee<'info>(
         ctx: Context<'_, '_, '_, 'info, WithdrawFromFee<'info>>,
         amount: u64,
     ) -> Result<()> {
-        let auction_house_fee_account = &ctx.accounts.auction_house_fee_account;
-        let fee_withdrawal_destination = &ctx.accounts.fee_withdrawal_destination;
         let auction_house = &ctx.accounts.auction_house;
-        let system_program = &ctx.accounts.system_program;
-
-        let auction_house_key = auction_house.key();
-        let seeds = [
-            PREFIX.as_bytes(),
-            auction_house_key.as_ref(),
-            FEE_PAYER.as_bytes(),
-            &[auction_house.fee_payer_bump],
-        ];
 
-        invoke_signed(
-            &system_instruction::transfer(
-                &auction_house_fee_account.key(),
-                &fee_withdrawal_destination.key(),
-                amount,
-            ),
-            &[
-                auction_house_fee_account.to_account_info(),
-                fee_withdrawal_destination.to_account_info(),
-                system_program.to_account_info(),
-            ],
-            &[&seeds],
+        record_policy_withdrawal(
+            auction_house,
+            ctx.remaining_accounts,
+            WithdrawalSource::Fee,
+            amount,
         )?;
 
-        Ok(())
+        pay_from_fee_account(
+            auction_house,
+            &ctx.accounts.auction_house_fee_account.to_account_info(),
+            &ctx.accounts.fee_withdrawal_destination.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            amount,
+        )
     }
 
     /// Withdraw `amount` from the Auction House Treasury Account to a provided destination account.
+    /// If the Auction House has a withdrawal policy it has to be passed as the first remaining account.
     pub fn withdraw_from_treasury<'info>(
         ctx: Context<'_, '_, '_, 'info, WithdrawFromTreasury<'info>>,
         amount: u64,
     ) -> Result<()> {
-        let treasury_mint = &ctx.accounts.treasury_mint;
-        let treasury_withdrawal_destination = &ctx.accounts.treasury_withdrawal_destination;
-        let auction_house_treasury = &ctx.accounts.auction_house_treasury;
         let auction_house = &ctx.accounts.auction_house;
-        let token_program = &ctx.accounts.token_program;
-        let system_program = &ctx.accounts.system_program;
 
-        let is_native = treasury_mint.key() == spl_token::native_mint::id();
-        let auction_house_seeds = [
-            PREFIX.as_bytes(),
-            auction_house.creator.as_ref(),
-            auction_house.treasury_mint.as_ref(),
-            &[auction_house.bump],
-        ];
-
-        let ah_key = auction_house.key();
-        let auction_house_treasury_seeds = [
-            PREFIX.as_bytes(),
-            ah_key.as_ref(),
-            TREASURY.as_bytes(),
-            &[auction_house.treasury_bump],
-        ];
-        if !is_native {
-            invoke_signed(
-                &spl_token::instruction::transfer(
-                    token_program.key,
-                    &auction_house_treasury.key(),
-                    &treasury_withdrawal_destination.key(),
-                    &auction_house.key(),
-                    &[],
-                    amount,
-                )?,
-                &[
-                    auction_house_treasury.to_account_info(),
-                    treasury_withdrawal_destination.to_account_info(),
-                    token_program.to_account_info(),
-                    auction_house.to_account_info(),
-                ],
-                &[&auction_house_seeds],
-            )?;
-        } else {
-            invoke_signed(
-                &system_instruction::transfer(
-                    &auction_house_treasury.key(),
-                    &treasury_withdrawal_destination.key(),
-                    amount,
-                ),
-                &[
-                    auction_house_treasury.to_account_info(),
-                    treasury_withdrawal_destination.to_account_info(),
-                    system_program.to_account_info(),
-                ],
-                &[&auction_house_treasury_seeds],
-            )?;
-        }
+        record_policy_withdrawal(
+            auction_house,
+            ctx.remaining_accounts,
+            WithdrawalSource::Treasury,
+            amount,
+        )?;
 
-        Ok(())
+        pay_from_treasury(
+            auction_house,
+            &ctx.accounts.treasury_mint.to_account_info(),
+            &ctx.accounts.auction_house_treasury.to_account_info(),
+            &ctx.accounts
+                .treasury_withdrawal_destination
+                .to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            amount,
+        )
     }
 
-    /// Update Auction House values such as seller fee basis points, update authority, treasury account, etc.
+    /// Update Auction House values such as seller fee basis points and whether the sale price can change.
+    /// The authority and withdrawal destinations are changed with `propose_authority` and `accept_authority`.
     pub fn update_auction_house<'info>(
         ctx: Context<'_, '_, '_, 'info, UpdateAuctionHouse<'info>>,
         seller_fee_basis_points: Option<u16>,
         can_change_sale_price: Option<bool>,
     ) -> Result<()> {
-        let treasury_mint = &ctx.accounts.treasury_mint;
-        let payer = &ctx.accounts.payer;
-        let new_authority = &ctx.accounts.new_authority;
         let auction_house = &mut ctx.accounts.auction_house;
-        let fee_withdrawal_destination = &ctx.accounts.fee_withdrawal_destination;
-        let treasury_withdrawal_destination_owner =
-            &ctx.accounts.treasury_withdrawal_destination_owner;
-        let treasury_withdrawal_destination = &ctx.accounts.treasury_withdrawal_destination;
-        let token_program = &ctx.accounts.token_program;
-        let system_program = &ctx.accounts.system_program;
-        let associated_token_program = &ctx.accounts.associated_token_program;
-        let rent = &ctx.accounts.rent;
-        let is_native = treasury_mint.key() == spl_token::native_mint::id();
 
         if let Some(sfbp) = seller_fee_basis_points {
             if sfbp > 10000 {
@@ -167,36 +123,31 @@ pub mod auction_house {
             auction_house.can_change_sale_price = chsp;
         }
 
-        auction_house.authority = new_authority.key();
-        auction_house.treasury_withdrawal_destination = treasury_withdrawal_destination.key();
-        auction_house.fee_withdrawal_destination = fee_withdrawal_destination.key();
+        emit!(AuctionHouseUpdated {
+            auction_house: auction_house.key(),
+            authority: auction_house.authority,
+            treasury_withdrawal_destination: auction_house.treasury_withdrawal_destination,
+            fee_withdrawal_destination: auction_house.fee_withdrawal_destination,
+            seller_fee_basis_points: auction_house.seller_fee_basis_points,
+            can_change_sale_price: auction_house.can_change_sale_price,
+        });
 
-        if !is_native {
-            if treasury_withdrawal_destination.data_is_empty() {
-                make_ata(
-                    treasury_withdrawal_destination.to_account_info(),
-                    treasury_withdrawal_destination_owner.to_account_info(),
-                    treasury_mint.to_account_info(),
-                    payer.to_account_info(),
-                    associated_token_program.to_account_info(),
-                    token_program.to_account_info(),
-                    system_program.to_account_info(),
-                    rent.to_account_info(),
-                    &[],
-                )?;
-            }
+        Ok(())
+    }
 
-            assert_is_ata(
-                &treasury_withdrawal_destination.to_account_info(),
-                &treasury_withdrawal_destination_owner.key(),
-                &treasury_mint.key(),
-            )?;
-        } else {
-            assert_keys_equal(
-                treasury_withdrawal_destination.key(),
-                treasury_withdrawal_destination_owner.key(),
-            )?;
-        }
+    /// Pause or resume trading. While paused, sells, bids and sales are rejected but cancels and
+    /// escrow withdrawals still work.
+    pub fn set_pause_state<'info>(
+        ctx: Context<'_, '_, '_, 'info, SetPauseState<'info>>,
+        paused: bool,
+    ) -> Result<()> {
+        let auction_house = &mut ctx.accounts.auction_house;
+        auction_house.is_paused = paused;
+
+        emit!(PauseStateChanged {
+            auction_house: auction_house.key(),
+            is_paused: paused,
+        });
 
         Ok(())
     }
@@ -263,6 +214,7 @@ pub mod auction_house {
         auction_house.auction_house_treasury = auction_house_treasury.key();
         auction_house.treasury_withdrawal_destination = treasury_withdrawal_destination.key();
         auction_house.fee_withdrawal_destination = fee_withdrawal_destination.key();
+        auction_house.version = AUCTION_HOUSE_VERSION;
 
         let is_native = treasury_mint.key() == spl_token::native_mint::id();
 
@@ -318,6 +270,129 @@ pub mod auction_house {
         Ok(())
     }
 
+    /// Create a private buy bid by creating a `buyer_trade_state` account and an `escrow_payment` account and funding the escrow with the necessary SOL or SPL token amount.
+    /// Past the optional `expiry` timestamp the bid can't be filled, and the buyer cancels it.
+    /// Leading remaining accounts: the `AllowedCollection` account of the NFT collection if the Auction House has a collection allowlist,
+    /// then the `BlockedWallet` PDA of the wallet if it has a blocklist.
+    pub fn buy<'info>(
+        ctx: Context<'_, '_, '_, 'info, Buy<'info>>,
+        trade_state_bump: u8,
+        escrow_payment_bump: u8,
+        buyer_price: u64,
+        token_size: u64,
+        expiry: Option<i64>,
+    ) -> Result<()> {
+        bid::private_bid(
+            ctx,
+            trade_state_bump,
+            escrow_payment_bump,
+            buyer_price,
+            token_size,
+            expiry,
+        )
+    }
+
+    /// Create a public buy bid by creating a `public_buyer_trade_state` account and an `escrow_payment` account and funding the escrow with the necessary SOL or SPL token amount.
+    /// Past the optional `expiry` timestamp the bid can't be filled, and the buyer cancels it.
+    /// Leading remaining accounts: the `AllowedCollection` account of the NFT collection if the Auction House has a collection allowlist,
+    /// then the `BlockedWallet` PDA of the wallet if it has a blocklist.
+    pub fn public_buy<'info>(
+        ctx: Context<'_, '_, '_, 'info, PublicBuy<'info>>,
+        trade_state_bump: u8,
+        escrow_payment_bump: u8,
+        buyer_price: u64,
+        token_size: u64,
+        expiry: Option<i64>,
+    ) -> Result<()> {
+        bid::public_bid(
+            ctx,
+            trade_state_bump,
+            escrow_payment_bump,
+            buyer_price,
+            token_size,
+            expiry,
+        )
+    }
+
+    /// Cancel a bid or ask by revoking the token delegate, transferring all lamports from the trade state account to the fee payer, and setting the trade state account data to zero so it can be garbage collected.
+    pub fn cancel<'info>(
+        ctx: Context<'_, '_, '_, 'info, Cancel<'info>>,
+        buyer_price: u64,
+        token_size: u64,
+    ) -> Result<()> {
+        cancel::cancel(ctx, buyer_price, token_size)
+    }
+
+    /// Deposit `amount` into the escrow payment account for your specific wallet.
+    pub fn deposit<'info>(
+        ctx: Context<'_, '_, '_, 'info, Deposit<'info>>,
+        escrow_payment_bump: u8,
+        amount: u64,
+    ) -> Result<()> {
+        deposit::deposit(ctx, escrow_payment_bump, amount)
+    }
+
+    /// Execute sale between provided buyer and seller trade state accounts transferring funds to seller wallet and token to buyer wallet.
+    /// Pass `partial_order_size` and `partial_order_price` to buy only part of a listing; the listing stays open for the remainder.
+    /// Each partial fill of a legacy listing is priced from the whole listing and rounded down, so the fills can add up to less than
+    /// the listing price. Use `execute_sale_v2`, whose listings keep the rest of the price, for exact totals.
+    /// If the Auction House has a blocklist, the `BlockedWallet` PDAs of the buyer and seller lead the remaining accounts.
+    #[inline(never)]
+    pub fn execute_sale<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteSale<'info>>,
+        escrow_payment_bump: u8,
+        _free_trade_state_bump: u8,
+        program_as_signer_bump: u8,
+        buyer_price: u64,
+        token_size: u64,
+        partial_order_size: Option<u64>,
+        partial_order_price: Option<u64>,
+    ) -> Result<()> {
+        execute_sale::execute_sale(
+            ctx,
+            escrow_payment_bump,
+            _free_trade_state_bump,
+            program_as_signer_bump,
+            buyer_price,
+            token_size,
+            partial_order_size,
+            partial_order_price,
+        )
+    }
+
+    /// Create a sell bid by creating a `seller_trade_state` account and approving the program as the token delegate.
+    /// Past the optional `expiry` timestamp the listing can't be sold, and the seller cancels it.
+    /// Leading remaining accounts: the `AllowedCollection` account of the NFT collection if the Auction House has a collection allowlist,
+    /// then the `BlockedWallet` PDA of the wallet if it has a blocklist.
+    pub fn sell<'info>(
+        ctx: Context<'_, '_, '_, 'info, Sell<'info>>,
+        trade_state_bump: u8,
+        free_trade_state_bump: u8,
+        program_as_signer_bump: u8,
+        buyer_price: u64,
+        token_size: u64,
+        expiry: Option<i64>,
+    ) -> Result<()> {
+        sell::sell(
+            ctx,
+            trade_state_bump,
+            free_trade_state_bump,
+            program_as_signer_bump,
+            buyer_price,
+            token_size,
+            expiry,
+        )
+    }
+
+    /// Withdraw `amount` from the escrow payment account for your specific wallet.
+    pub fn withdraw<'info>(
+        ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>,
+        escrow_payment_bump: u8,
+        amount: u64,
+    ) -> Result<()> {
+        withdraw::withdraw(ctx, escrow_payment_bump, amount)
+    }
+
     pub fn auctioneer_buy<'info>(
         ctx: Context<'_, '_, '_, 'info, AuctioneerBuy<'info>>,
         trade_state_bump: u8,
@@ -334,6 +409,23 @@ pub mod auction_house {
         )
     }
 
+    /// Create a public buy bid, but with an auctioneer
+    pub fn auctioneer_public_buy<'info>(
+        ctx: Context<'_, '_, '_, 'info, AuctioneerPublicBuy<'info>>,
+        trade_state_bump: u8,
+        escrow_payment_bump: u8,
+        buyer_price: u64,
+        token_size: u64,
+    ) -> Result<()> {
+        bid::auctioneer_public_bid(
+            ctx,
+            trade_state_bump,
+            escrow_payment_bump,
+            buyer_price,
+            token_size,
+        )
+    }
+
     /// Cancel, but with an auctioneer
     pub fn auctioneer_cancel<'info>(
         ctx: Context<'_, '_, '_, 'info, AuctioneerCancel<'info>>,
@@ -359,6 +451,8 @@ pub mod auction_house {
         program_as_signer_bump: u8,
         buyer_price: u64,
         token_size: u64,
+        partial_order_size: Option<u64>,
+        partial_order_price: Option<u64>,
     ) -> Result<()> {
         execute_sale::auctioneer_execute_sale(
             ctx,
@@ -367,6 +461,8 @@ pub mod auction_house {
             program_as_signer_bump,
             buyer_price,
             token_size,
+            partial_order_size,
+            partial_order_price,
         )
     }
 
@@ -395,41 +491,278 @@ pub mod auction_house {
         withdraw::auctioneer_withdraw(ctx, escrow_payment_bump, amount)
     }
 
-    /// Close the escrow account of the user.
+    /// Close the escrow account of the user, returning its balance to the wallet. For SPL treasuries
+    /// the tokens go to the wallet's associated token account, created if missing.
     pub fn close_escrow_account<'info>(
         ctx: Context<'_, '_, '_, 'info, CloseEscrowAccount<'info>>,
         escrow_payment_bump: u8,
     ) -> Result<()> {
-        let auction_house_key = ctx.accounts.auction_house.key();
-        let wallet_key = ctx.accounts.wallet.key();
+        withdraw::close_escrow_account(ctx, escrow_payment_bump)
+    }
 
-        let escrow_signer_seeds = [
-            PREFIX.as_bytes(),
-            auction_house_key.as_ref(),
-            wallet_key.as_ref(),
-            &[escrow_payment_bump],
-        ];
+    /// Close the escrow account of the user through the auctioneer. Fails while any of its funds
+    /// are locked by highest auction bids.
+    pub fn auctioneer_close_escrow_account<'info>(
+        ctx: Context<'_, '_, '_, 'info, AuctioneerCloseEscrowAccount<'info>>,
+        escrow_payment_bump: u8,
+    ) -> Result<()> {
+        withdraw::auctioneer_close_escrow_account(ctx, escrow_payment_bump)
+    }
 
-        invoke_signed(
-            &system_instruction::transfer(
-                &ctx.accounts.escrow_payment_account.key(),
-                &ctx.accounts.wallet.key(),
-                ctx.accounts.escrow_payment_account.lamports(),
-            ),
-            &[
-                ctx.accounts.escrow_payment_account.to_account_info(),
-                ctx.accounts.wallet.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
-            &[&escrow_signer_seeds],
-        )?;
-        Ok(())
+    /// Lock `amount` of the wallet's escrow for a highest auction bid. Withdrawals and escrow
+    /// closing through any path leave locked funds in the escrow.
+    pub fn auctioneer_lock_escrow<'info>(
+        ctx: Context<'_, '_, '_, 'info, AuctioneerLockEscrow<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        escrow_lock::auctioneer_lock_escrow(ctx, amount)
+    }
+
+    /// Release `amount` of the wallet's escrow locked by the auctioneer.
+    pub fn auctioneer_release_escrow<'info>(
+        ctx: Context<'_, '_, '_, 'info, AuctioneerReleaseEscrow<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        escrow_lock::auctioneer_release_escrow(ctx, amount)
     }
 
+    /// Delegate an auctioneer with the given scopes. Several auctioneers may be delegated at once.
     pub fn delegate_auctioneer<'info>(
         ctx: Context<'_, '_, '_, 'info, DelegateAuctioneer<'info>>,
+        scopes: Vec<AuthorityScope>,
+    ) -> Result<()> {
+        auctioneer::delegate_auctioneer(ctx, scopes)
+    }
+
+    /// Create a listing receipt. Must directly follow a `sell` or `sell_v2` instruction in the same transaction.
+    /// A canceled or sold receipt of the same trade state is overwritten, a live one fails unless `sell_v2` amended it.
+    pub fn print_listing_receipt<'info>(
+        ctx: Context<'_, '_, '_, 'info, PrintListingReceipt<'info>>,
+    ) -> Result<()> {
+        receipt::print_listing_receipt(ctx)
+    }
+
+    /// Mark a listing receipt as canceled. Must directly follow a `cancel` instruction in the same transaction.
+    pub fn cancel_listing_receipt<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelListingReceipt<'info>>,
+    ) -> Result<()> {
+        receipt::cancel_listing_receipt(ctx)
+    }
+
+    /// Create a bid receipt. Must directly follow a `buy`, `public_buy`, `buy_v2` or `public_buy_v2` instruction in the same transaction.
+    /// A canceled or filled receipt of the same trade state is overwritten, a live one fails unless a v2 bid amended it.
+    pub fn print_bid_receipt<'info>(
+        ctx: Context<'_, '_, '_, 'info, PrintBidReceipt<'info>>,
+    ) -> Result<()> {
+        receipt::print_bid_receipt(ctx)
+    }
+
+    /// Mark a bid receipt as canceled. Must directly follow a `cancel` instruction in the same transaction.
+    pub fn cancel_bid_receipt<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelBidReceipt<'info>>,
+    ) -> Result<()> {
+        receipt::cancel_bid_receipt(ctx)
+    }
+
+    /// Create a purchase receipt and link it to the listing and bid receipts. Must directly follow an `execute_sale` or `execute_sale_v2` instruction in the same transaction.
+    /// Fails if the receipt was already printed.
+    pub fn print_purchase_receipt<'info>(
+        ctx: Context<'_, '_, '_, 'info, PrintPurchaseReceipt<'info>>,
+    ) -> Result<()> {
+        receipt::print_purchase_receipt(ctx)
+    }
+
+    /// Remove the auctioneer delegation and close the `Auctioneer` PDA. Fails while the auctioneer
+    /// holds escrow funds locked.
+    pub fn undelegate_auctioneer<'info>(
+        ctx: Context<'_, '_, '_, 'info, UndelegateAuctioneer<'info>>,
+    ) -> Result<()> {
+        auctioneer::undelegate_auctioneer(ctx)
+    }
+
+    /// Replace a delegated auctioneer with a new auctioneer authority holding the given scopes.
+    /// Fails while the auctioneer holds escrow funds locked.
+    pub fn update_auctioneer<'info>(
+        ctx: Context<'_, '_, '_, 'info, UpdateAuctioneer<'info>>,
+        scopes: Vec<AuthorityScope>,
+    ) -> Result<()> {
+        auctioneer::update_auctioneer(ctx, scopes)
+    }
+
+    /// Withdraw the royalties credited to a creator's royalty vault to the creator wallet.
+    pub fn claim_royalties<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimRoyalties<'info>>,
+    ) -> Result<()> {
+        royalty::claim_royalties(ctx)
+    }
+
+    /// Propose a new authority and withdrawal destinations. Keys that stay the same are left unchanged.
+    pub fn propose_authority<'info>(
+        ctx: Context<'_, '_, '_, 'info, ProposeAuthority<'info>>,
+    ) -> Result<()> {
+        authority::propose_authority(ctx)
+    }
+
+    /// Apply a proposed authority and withdrawal destinations once each new key has signed.
+    pub fn accept_authority<'info>(
+        ctx: Context<'_, '_, '_, 'info, AcceptAuthority<'info>>,
+    ) -> Result<()> {
+        authority::accept_authority(ctx)
+    }
+
+    /// Attach a withdrawal policy capping immediate treasury and fee withdrawals per epoch.
+    pub fn create_withdrawal_policy(
+        ctx: Context<CreateWithdrawalPolicy>,
+        treasury_epoch_cap: u64,
+        fee_epoch_cap: u64,
+        timelock: i64,
+    ) -> Result<()> {
+        withdrawal_policy::create_withdrawal_policy(
+            ctx,
+            treasury_epoch_cap,
+            fee_epoch_cap,
+            timelock,
+        )
+    }
+
+    /// Lower the epoch caps or lengthen the timelock of the withdrawal policy.
+    pub fn update_withdrawal_policy(
+        ctx: Context<UpdateWithdrawalPolicy>,
+        treasury_epoch_cap: u64,
+        fee_epoch_cap: u64,
+        timelock: i64,
+    ) -> Result<()> {
+        withdrawal_policy::update_withdrawal_policy(
+            ctx,
+            treasury_epoch_cap,
+            fee_epoch_cap,
+            timelock,
+        )
+    }
+
+    /// Queue a withdrawal of `amount` that can be executed once the policy timelock has passed.
+    pub fn queue_withdrawal(
+        ctx: Context<QueueWithdrawal>,
+        source: WithdrawalSource,
+        amount: u64,
+    ) -> Result<()> {
+        withdrawal_policy::queue_withdrawal(ctx, source, amount)
+    }
+
+    /// Pay out a queued withdrawal whose timelock has passed.
+    pub fn execute_queued_withdrawal<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteQueuedWithdrawal<'info>>,
+    ) -> Result<()> {
+        withdrawal_policy::execute_queued_withdrawal(ctx)
+    }
+
+    /// Cancel a queued withdrawal before it is executed.
+    pub fn cancel_queued_withdrawal(ctx: Context<CancelQueuedWithdrawal>) -> Result<()> {
+        withdrawal_policy::cancel_queued_withdrawal(ctx)
+    }
+
+    /// Allow NFTs of a verified collection to be listed and bid on. The first allowed collection
+    /// restricts the Auction House to its allowlist.
+    pub fn add_allowed_collection(ctx: Context<AddAllowedCollection>) -> Result<()> {
+        collection_allowlist::add_allowed_collection(ctx)
+    }
+
+    /// Remove a collection from the allowlist. Existing listings and bids are not affected.
+    pub fn remove_allowed_collection(ctx: Context<RemoveAllowedCollection>) -> Result<()> {
+        collection_allowlist::remove_allowed_collection(ctx)
+    }
+
+    /// Block a wallet from listing, bidding and buying. The first blocked wallet makes every trade
+    /// check the blocklist.
+    pub fn block_wallet(ctx: Context<BlockWallet>) -> Result<()> {
+        blocklist::block_wallet(ctx)
+    }
+
+    /// Remove a wallet from the blocklist.
+    pub fn unblock_wallet(ctx: Context<UnblockWallet>) -> Result<()> {
+        blocklist::unblock_wallet(ctx)
+    }
+
+    /// Close the Auction House, sweeping its treasury and fee account to the withdrawal destinations
+    /// and returning the rent to the authority. Its withdrawal policy, queued withdrawals, allowed
+    /// collections and blocked wallets can be passed as remaining accounts to close them as well.
+    /// Escrows and trade states stay tied to the Auction House address, so buyers should withdraw
+    /// and sellers cancel first. Those left behind are recovered by recreating the Auction House
+    /// from the original creator with the same treasury mint, which gives it back the same address.
+    /// A transferred authority has to be handed back to the creator before the house is closed.
+    pub fn close_auction_house<'info>(
+        ctx: Context<'_, '_, '_, 'info, CloseAuctionHouse<'info>>,
+    ) -> Result<()> {
+        close::close_auction_house(ctx)
+    }
+
+    /// Upgrade an Auction House account to the current layout version, growing it if needed.
+    pub fn migrate_auction_house(ctx: Context<MigrateAuctionHouse>) -> Result<()> {
+        migrate::migrate_auction_house(ctx)
+    }
+
+    /// List `token_size` of a token account at `price` in a v2 trade state keyed by the token account
+    /// rather than the price. Listing again while the trade state is open amends its price, size and expiry in place.
+    /// Past the optional `expiry` timestamp the listing can't be sold and anyone can close it with `close_expired`.
+    /// Leading remaining accounts: the `AllowedCollection` account of the NFT collection if the Auction House has a collection allowlist,
+    /// then the `BlockedWallet` PDA of the wallet if it has a blocklist.
+    pub fn sell_v2<'info>(
+        ctx: Context<'_, '_, '_, 'info, SellV2<'info>>,
+        price: u64,
+        token_size: u64,
+        expiry: Option<i64>,
+    ) -> Result<()> {
+        sell::sell_v2(ctx, price, token_size, expiry)
+    }
+
+    /// Bid on a token account in a v2 trade state keyed by the token account rather than the price,
+    /// funding the escrow as `buy` does. Bidding again while the trade state is open amends it in place.
+    /// Past the optional `expiry` timestamp the bid can't be filled and anyone can close it with `close_expired`.
+    /// Leading remaining accounts: the `AllowedCollection` account of the NFT collection if the Auction House has a collection allowlist,
+    /// then the `BlockedWallet` PDA of the wallet if it has a blocklist.
+    pub fn buy_v2<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuyV2<'info>>,
+        price: u64,
+        token_size: u64,
+        expiry: Option<i64>,
     ) -> Result<()> {
-        auctioneer::delegate_auctioneer(ctx)
+        bid::buy_v2(ctx, price, token_size, expiry)
+    }
+
+    /// Bid on any token account of a mint in a v2 trade state keyed by the mint, see `buy_v2`.
+    pub fn public_buy_v2<'info>(
+        ctx: Context<'_, '_, '_, 'info, PublicBuyV2<'info>>,
+        price: u64,
+        token_size: u64,
+        expiry: Option<i64>,
+    ) -> Result<()> {
+        bid::public_buy_v2(ctx, price, token_size, expiry)
+    }
+
+    /// Close an expired v2 listing or bid. The closer is paid a bounty out of the trade state rent and
+    /// the rest is refunded to the account that paid it. A closed listing leaves its delegate on the
+    /// seller's token account for the seller to revoke. Expired legacy trade states record no fee
+    /// payer to refund, so their owner closes them with `cancel`.
+    pub fn close_expired(ctx: Context<CloseExpired>) -> Result<()> {
+        trade_state::close_expired(ctx)
+    }
+
+    /// Execute sale against a v2 listing, with a v2 or legacy buyer trade state. The listing must hold
+    /// `buyer_price` and `token_size`; after a partial order it stays open for the remainder.
+    pub fn execute_sale_v2<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteSaleV2<'info>>,
+        buyer_price: u64,
+        token_size: u64,
+        partial_order_size: Option<u64>,
+        partial_order_price: Option<u64>,
+    ) -> Result<()> {
+        execute_sale::execute_sale_v2(
+            ctx,
+            buyer_price,
+            token_size,
+            partial_order_size,
+            partial_order_price,
+        )
     }
 }
 
@@ -437,8 +770,8 @@ pub mod auction_house {
 #[derive(Accounts)]
 #[instruction(bump: u8, fee_payer_bump: u8, treasury_bump: u8)]
 pub struct CreateAuctionHouse<'info> {
-    /// Treasury mint account, either native SOL mint or a SPL token mint.
-    pub treasury_mint: Account<'info, Mint>,
+    /// Treasury mint account, either native SOL mint or a SPL Token or Token-2022 mint.
+    pub treasury_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// Key paying SOL fees for setting up the Auction House.
     #[account(mut)]
@@ -476,7 +809,7 @@ pub struct CreateAuctionHouse<'info> {
     #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.key().as_ref(), TREASURY.as_bytes()], bump)]
     pub auction_house_treasury: UncheckedAccount<'info>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
@@ -485,48 +818,33 @@ pub struct CreateAuctionHouse<'info> {
 /// Accounts for the [`update_auction_house` handler](auction_house/fn.update_auction_house.html).
 #[derive(Accounts)]
 pub struct UpdateAuctionHouse<'info> {
-    /// Treasury mint account, either native SOL mint or a SPL token mint.
-    pub treasury_mint: Account<'info, Mint>,
-
-    /// Key paying SOL fees for setting up the Auction House.
-    pub payer: Signer<'info>,
+    /// Treasury mint account, either native SOL mint or a SPL Token or Token-2022 mint.
+    pub treasury_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// Authority key for the Auction House.
     pub authority: Signer<'info>,
 
-    /// CHECK: User can use whatever they want for updating this.
-    /// New authority key for the Auction House.
-    pub new_authority: UncheckedAccount<'info>,
-
-    /// CHECK: User can use whatever they want for updating this.
-    /// Account that pays for fees if the marketplace executes sales.
-    #[account(mut)]
-    pub fee_withdrawal_destination: UncheckedAccount<'info>,
-
-    /// CHECK: User can use whatever they want for updating this.
-    /// SOL or SPL token account to receive Auction House fees. If treasury mint is native this will be the same as the `treasury_withdrawl_destination_owner`.
-    #[account(mut)]
-    pub treasury_withdrawal_destination: UncheckedAccount<'info>,
-
-    /// CHECK: User can use whatever they want for updating this.
-    /// Owner of the `treasury_withdrawal_destination` account or the same address if the `treasury_mint` is native.
-    pub treasury_withdrawal_destination_owner: UncheckedAccount<'info>,
-
     /// Auction House instance PDA account.
     #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref(), treasury_mint.key().as_ref()], bump=auction_house.bump, has_one=authority, has_one=treasury_mint)]
     pub auction_house: Account<'info, AuctionHouse>,
+}
 
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub rent: Sysvar<'info, Rent>,
+/// Accounts for the [`set_pause_state` handler](auction_house/fn.set_pause_state.html).
+#[derive(Accounts)]
+pub struct SetPauseState<'info> {
+    /// Authority key for the Auction House.
+    pub authority: Signer<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref(), auction_house.treasury_mint.as_ref()], bump=auction_house.bump, has_one=authority)]
+    pub auction_house: Account<'info, AuctionHouse>,
 }
 
 /// Accounts for the [`withdraw_from_treasury` handler](auction_house/fn.withdraw_from_treasury.html).
 #[derive(Accounts)]
 pub struct WithdrawFromTreasury<'info> {
-    /// Treasury mint account, either native SOL mint or a SPL token mint.
-    pub treasury_mint: Account<'info, Mint>,
+    /// Treasury mint account, either native SOL mint or a SPL Token or Token-2022 mint.
+    pub treasury_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// Authority key for the Auction House.
     pub authority: Signer<'info>,
@@ -545,7 +863,7 @@ pub struct WithdrawFromTreasury<'info> {
     #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref(), treasury_mint.key().as_ref()], bump=auction_house.bump, has_one=authority, has_one=treasury_mint, has_one=treasury_withdrawal_destination, has_one=auction_house_treasury)]
     pub auction_house: Account<'info, AuctionHouse>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
@@ -571,36 +889,3 @@ pub struct WithdrawFromFee<'info> {
 
     pub system_program: Program<'info, System>,
 }
-
-#[derive(Accounts)]
-#[instruction(escrow_payment_bump: u8)]
-pub struct CloseEscrowAccount<'info> {
-    /// User wallet account.
-    #[account(mut)]
-    pub wallet: Signer<'info>,
-
-    /// CHECK: Account seeds checked in constraint.
-    /// Buyer escrow payment account PDA.
-    #[account(
-        mut,
-        seeds = [
-            PREFIX.as_bytes(),
-            auction_house.key().as_ref(),
-            wallet.key().as_ref()
-        ],
-        bump = escrow_payment_bump
-    )]
-    pub escrow_payment_account: UncheckedAccount<'info>,
-
-    /// Auction House instance PDA account.
-    #[account(
-        seeds = [
-            PREFIX.as_bytes(),
-            auction_house.creator.as_ref(),
-            auction_house.treasury_mint.as_ref()
-        ],
-        bump = auction_house.bump
-    )]
-    pub auction_house: Account<'info, AuctionHouse>,
-    pub system_program: Program<'info, System>,
-}
@@ -0,0 +1,218 @@
+use crate::{
+    constants::{CREATORS_KEY, SELLER_FEE_BASIS_POINTS_KEY},
+    errors::AuctionHouseError,
+    utils::assert_derivation,
+};
+
+use anchor_lang::{prelude::*, AnchorDeserialize};
+use arrayref::array_ref;
+use mpl_token_metadata::state::{Metadata, TokenMetadataAccount};
+use std::str::FromStr;
+
+// The spl-token-2022 version this program builds against predates the metadata-pointer and
+// token-metadata extensions, so their TLV entries are located by hand.
+const ACCOUNT_TYPE_OFFSET: usize = 165;
+const MINT_ACCOUNT_TYPE: u8 = 1;
+const METADATA_POINTER_EXTENSION: u16 = 18;
+const TOKEN_METADATA_EXTENSION: u16 = 19;
+
+/// Where the metadata of a tradable NFT lives.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetadataSource {
+    /// A Metaplex Token Metadata account derived from the mint.
+    Metaplex,
+    /// The token-metadata extension of a Token-2022 mint whose metadata pointer targets itself.
+    TokenMetadataExtension,
+}
+
+/// A creator entitled to a share of the royalties.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoyaltyCreator {
+    pub address: Pubkey,
+    pub share: u8,
+}
+
+/// Royalty configuration read from either metadata source.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Royalties {
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<RoyaltyCreator>>,
+}
+
+/// Borsh layout of the token-metadata extension.
+#[derive(AnchorDeserialize)]
+struct TokenMetadata {
+    _update_authority: Pubkey,
+    mint: Pubkey,
+    _name: String,
+    _symbol: String,
+    _uri: String,
+    additional_metadata: Vec<(String, String)>,
+}
+
+/// Assert `metadata` is the metadata of `mint`, either the Metaplex PDA or a Token-2022 mint
+/// carrying its own token metadata.
+pub fn assert_metadata_valid(metadata: &AccountInfo, mint: &Pubkey) -> Result<MetadataSource> {
+    if metadata.key == mint && *metadata.owner == spl_token_2022::id() {
+        let data = metadata.try_borrow_data()?;
+        let pointer = get_mint_extension(&data, METADATA_POINTER_EXTENSION)
+            .ok_or(AuctionHouseError::InvalidTokenMetadata)?;
+        // MetadataPointer layout: authority(32), metadata_address(32)
+        if pointer.len() != 64 || array_ref![pointer, 32, 32] != mint.as_ref() {
+            return Err(AuctionHouseError::InvalidTokenMetadata.into());
+        }
+
+        let token_metadata = unpack_token_metadata(&data)?;
+        if token_metadata.mint != *mint {
+            return Err(AuctionHouseError::InvalidTokenMetadata.into());
+        }
+
+        return Ok(MetadataSource::TokenMetadataExtension);
+    }
+
+    assert_derivation(
+        &mpl_token_metadata::id(),
+        metadata,
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            mint.as_ref(),
+        ],
+    )?;
+
+    if metadata.data_is_empty() {
+        return Err(AuctionHouseError::MetadataDoesntExist.into());
+    }
+
+    Ok(MetadataSource::Metaplex)
+}
+
+/// Read the royalty basis points and creators from an account already checked with
+/// [`assert_metadata_valid`].
+///
+/// Token-2022 token metadata has no royalty fields, so they are read from the
+/// `seller_fee_basis_points` and `creators` additional metadata entries. Missing entries mean no
+/// royalties.
+pub fn get_royalties(metadata: &AccountInfo) -> Result<Royalties> {
+    if *metadata.owner != spl_token_2022::id() {
+        let metadata = Metadata::from_account_info(metadata)?;
+
+        return Ok(Royalties {
+            seller_fee_basis_points: metadata.data.seller_fee_basis_points,
+            creators: metadata.data.creators.map(|creators| {
+                creators
+                    .into_iter()
+                    .map(|creator| RoyaltyCreator {
+                        address: creator.address,
+                        share: creator.share,
+                    })
+                    .collect()
+            }),
+        });
+    }
+
+    let data = metadata.try_borrow_data()?;
+    let token_metadata = unpack_token_metadata(&data)?;
+
+    let mut royalties = Royalties {
+        seller_fee_basis_points: 0,
+        creators: None,
+    };
+    for (key, value) in token_metadata.additional_metadata.iter() {
+        if key == SELLER_FEE_BASIS_POINTS_KEY {
+            royalties.seller_fee_basis_points = value
+                .trim()
+                .parse::<u16>()
+                .map_err(|_| AuctionHouseError::InvalidTokenMetadata)?;
+        } else if key == CREATORS_KEY {
+            royalties.creators = Some(parse_creators(value)?);
+        }
+    }
+
+    if royalties.seller_fee_basis_points > 10000 {
+        return Err(AuctionHouseError::InvalidBasisPoints.into());
+    }
+
+    Ok(royalties)
+}
+
+/// Read the verified collection from an account already checked with [`assert_metadata_valid`].
+///
+/// Token-2022 token metadata has no collection field, so those NFTs never have one.
+pub fn get_verified_collection(metadata: &AccountInfo) -> Result<Option<Pubkey>> {
+    if *metadata.owner == spl_token_2022::id() {
+        return Ok(None);
+    }
+
+    let metadata = Metadata::from_account_info(metadata)?;
+
+    Ok(metadata
+        .collection
+        .filter(|collection| collection.verified)
+        .map(|collection| collection.key))
+}
+
+/// Parse a comma separated list of `<address>:<share>` creators whose shares add up to 100.
+fn parse_creators(value: &str) -> Result<Vec<RoyaltyCreator>> {
+    let mut creators = Vec::new();
+    let mut total_share: u16 = 0;
+
+    for entry in value.split(',') {
+        let (address, share) = entry
+            .trim()
+            .split_once(':')
+            .ok_or(AuctionHouseError::InvalidTokenMetadata)?;
+        let address =
+            Pubkey::from_str(address).map_err(|_| AuctionHouseError::InvalidTokenMetadata)?;
+        let share = share
+            .parse::<u8>()
+            .map_err(|_| AuctionHouseError::InvalidTokenMetadata)?;
+
+        total_share = total_share
+            .checked_add(share as u16)
+            .ok_or(AuctionHouseError::NumericalOverflow)?;
+        creators.push(RoyaltyCreator { address, share });
+    }
+
+    if total_share != 100 {
+        return Err(AuctionHouseError::InvalidTokenMetadata.into());
+    }
+
+    Ok(creators)
+}
+
+fn unpack_token_metadata(mint_data: &[u8]) -> Result<TokenMetadata> {
+    let value = get_mint_extension(mint_data, TOKEN_METADATA_EXTENSION)
+        .ok_or(AuctionHouseError::InvalidTokenMetadata)?;
+
+    Ok(TokenMetadata::deserialize(&mut &value[..])
+        .map_err(|_| AuctionHouseError::InvalidTokenMetadata)?)
+}
+
+/// Find the value of a TLV extension entry on a Token-2022 mint.
+fn get_mint_extension(mint_data: &[u8], extension_type: u16) -> Option<&[u8]> {
+    if mint_data.len() <= ACCOUNT_TYPE_OFFSET || mint_data[ACCOUNT_TYPE_OFFSET] != MINT_ACCOUNT_TYPE
+    {
+        return None;
+    }
+
+    // TLV entry layout: type(2), length(2), value(length)
+    let mut offset = ACCOUNT_TYPE_OFFSET + 1;
+    while offset + 4 <= mint_data.len() {
+        let entry_type = u16::from_le_bytes(*array_ref![mint_data, offset, 2]);
+        let length = u16::from_le_bytes(*array_ref![mint_data, offset + 2, 2]) as usize;
+        let value_start = offset + 4;
+        let value_end = value_start.checked_add(length)?;
+
+        if entry_type == 0 || value_end > mint_data.len() {
+            return None;
+        }
+        if entry_type == extension_type {
+            return Some(&mint_data[value_start..value_end]);
+        }
+
+        offset = value_end;
+    }
+
+    None
+}
@@ -0,0 +1,90 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{program::invoke, system_instruction},
+};
+
+use crate::{
+    constants::*, errors::AuctionHouseError, events::AuctionHouseMigrated, utils::*, AuctionHouse,
+};
+
+/// Accounts for the [`migrate_auction_house` handler](auction_house/fn.migrate_auction_house.html).
+#[derive(Accounts)]
+pub struct MigrateAuctionHouse<'info> {
+    /// Authority key for the Auction House, paying for any extra rent.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Deserialized and checked in migrate_auction_house, older layouts may be too short
+    /// to load as an `Account`.
+    /// Auction House instance PDA account.
+    #[account(mut, owner=crate::id())]
+    pub auction_house: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_auction_house(ctx: Context<MigrateAuctionHouse>) -> Result<()> {
+    let authority = &ctx.accounts.authority;
+    let auction_house_info = ctx.accounts.auction_house.to_account_info();
+    let system_program = &ctx.accounts.system_program;
+
+    // Grow the account first, new fields are zeroed so they load as their defaults.
+    if auction_house_info.data_len() < AUCTION_HOUSE_SIZE {
+        let rent_shortfall = Rent::get()?
+            .minimum_balance(AUCTION_HOUSE_SIZE)
+            .saturating_sub(auction_house_info.lamports());
+        if rent_shortfall > 0 {
+            invoke(
+                &system_instruction::transfer(
+                    authority.key,
+                    auction_house_info.key,
+                    rent_shortfall,
+                ),
+                &[
+                    authority.to_account_info(),
+                    auction_house_info.clone(),
+                    system_program.to_account_info(),
+                ],
+            )?;
+        }
+
+        auction_house_info.realloc(AUCTION_HOUSE_SIZE, true)?;
+    }
+
+    let mut auction_house =
+        AuctionHouse::try_deserialize(&mut &auction_house_info.try_borrow_data()?[..])?;
+
+    assert_keys_equal(auction_house.authority, authority.key())?;
+    let auction_house_key = Pubkey::create_program_address(
+        &[
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref(),
+            &[auction_house.bump],
+        ],
+        &crate::id(),
+    )
+    .map_err(|_| AuctionHouseError::DerivedKeyInvalid)?;
+    assert_keys_equal(auction_house_key, auction_house_info.key())?;
+
+    if auction_house.version >= AUCTION_HOUSE_VERSION {
+        return Err(AuctionHouseError::AuctionHouseAlreadyMigrated.into());
+    }
+
+    // Other fields added so far default to zero. Houses delegated before `auctioneer_count` was
+    // tracked count their single auctioneer. Later layouts that need real upgrades add them here,
+    // keyed on `auction_house.version`.
+    auction_house.auctioneer_count = auction_house.delegated_auctioneer_count();
+    let previous_version = auction_house.version;
+    auction_house.version = AUCTION_HOUSE_VERSION;
+
+    auction_house.try_serialize(&mut &mut auction_house_info.try_borrow_mut_data()?[..])?;
+
+    emit!(AuctionHouseMigrated {
+        auction_house: auction_house_info.key(),
+        previous_version,
+        version: AUCTION_HOUSE_VERSION,
+    });
+
+    Ok(())
+}
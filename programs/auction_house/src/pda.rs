@@ -1,8 +1,13 @@
 use anchor_lang::prelude::Pubkey;
 
 use crate::{
-    constants::{AUCTIONEER, FEE_PAYER, PREFIX, SIGNER, TREASURY},
+    constants::{
+        ALLOWED_COLLECTION, AUCTIONEER, BID_RECEIPT_PREFIX, BLOCKED_WALLET, ESCROW_LOCK, FEE_PAYER,
+        LISTING_RECEIPT_PREFIX, PREFIX, PURCHASE_RECEIPT_PREFIX, QUEUED_WITHDRAWAL, ROYALTY_VAULT,
+        SIGNER, TRADE_STATE_V2, TREASURY, WITHDRAWAL_POLICY,
+    },
     id,
+    trade_state::TradeSide,
 };
 
 pub fn find_program_as_signer_address() -> (Pubkey, u8) {
@@ -33,6 +38,28 @@ pub fn find_trade_state_address(
     )
 }
 
+pub fn find_public_bid_trade_state_address(
+    wallet: &Pubkey,
+    auction_house: &Pubkey,
+    treasury_mint: &Pubkey,
+    token_mint: &Pubkey,
+    price: u64,
+    token_size: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            wallet.as_ref(),
+            auction_house.as_ref(),
+            treasury_mint.as_ref(),
+            token_mint.as_ref(),
+            &price.to_le_bytes(),
+            &token_size.to_le_bytes(),
+        ],
+        &id(),
+    )
+}
+
 pub fn find_auctioneer_trade_state_address(
     wallet: &Pubkey,
     auction_house: &Pubkey,
@@ -56,6 +83,25 @@ pub fn find_auctioneer_trade_state_address(
     )
 }
 
+pub fn find_trade_state_v2_address(
+    wallet: &Pubkey,
+    auction_house: &Pubkey,
+    token_account_or_mint: &Pubkey,
+    side: TradeSide,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            TRADE_STATE_V2.as_bytes(),
+            wallet.as_ref(),
+            auction_house.as_ref(),
+            token_account_or_mint.as_ref(),
+            &side.seed(),
+        ],
+        &id(),
+    )
+}
+
 pub fn find_auction_house_address(authority: &Pubkey, mint_address: &Pubkey) -> (Pubkey, u8) {
     let auction_house_seeds = &[PREFIX.as_bytes(), authority.as_ref(), mint_address.as_ref()];
     Pubkey::find_program_address(auction_house_seeds, &id())
@@ -102,3 +148,108 @@ pub fn find_auctioneer_address(
         &id(),
     )
 }
+
+pub fn find_listing_receipt_address(seller_trade_state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            LISTING_RECEIPT_PREFIX.as_bytes(),
+            seller_trade_state.as_ref(),
+        ],
+        &id(),
+    )
+}
+
+pub fn find_bid_receipt_address(buyer_trade_state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[BID_RECEIPT_PREFIX.as_bytes(), buyer_trade_state.as_ref()],
+        &id(),
+    )
+}
+
+pub fn find_purchase_receipt_address(
+    seller_trade_state: &Pubkey,
+    buyer_trade_state: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PURCHASE_RECEIPT_PREFIX.as_bytes(),
+            seller_trade_state.as_ref(),
+            buyer_trade_state.as_ref(),
+        ],
+        &id(),
+    )
+}
+
+pub fn find_royalty_vault_address(auction_house: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            auction_house.as_ref(),
+            ROYALTY_VAULT.as_bytes(),
+            creator.as_ref(),
+        ],
+        &id(),
+    )
+}
+
+pub fn find_allowed_collection_address(
+    auction_house: &Pubkey,
+    collection: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            auction_house.as_ref(),
+            ALLOWED_COLLECTION.as_bytes(),
+            collection.as_ref(),
+        ],
+        &id(),
+    )
+}
+
+pub fn find_blocked_wallet_address(auction_house: &Pubkey, wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            auction_house.as_ref(),
+            BLOCKED_WALLET.as_bytes(),
+            wallet.as_ref(),
+        ],
+        &id(),
+    )
+}
+
+pub fn find_escrow_lock_address(auction_house: &Pubkey, wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            auction_house.as_ref(),
+            ESCROW_LOCK.as_bytes(),
+            wallet.as_ref(),
+        ],
+        &id(),
+    )
+}
+
+pub fn find_withdrawal_policy_address(auction_house: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            auction_house.as_ref(),
+            WITHDRAWAL_POLICY.as_bytes(),
+        ],
+        &id(),
+    )
+}
+
+pub fn find_queued_withdrawal_address(auction_house: &Pubkey, withdrawal_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            auction_house.as_ref(),
+            QUEUED_WITHDRAWAL.as_bytes(),
+            &withdrawal_id.to_le_bytes(),
+        ],
+        &id(),
+    )
+}
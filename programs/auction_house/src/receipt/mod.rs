@@ -0,0 +1,631 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::AccountMeta,
+        sysvar::{self, instructions::get_instruction_relative},
+    },
+    Discriminator,
+};
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{
+    constants::*, errors::AuctionHouseError, instruction, pda::find_trade_state_v2_address,
+    trade_state::TradeSide, utils::*,
+};
+
+// Positions of the accounts read from the instruction a receipt follows, in the field order of its
+// accounts struct. The v2 accounts structs keep the legacy positions. Receipts only follow direct
+// calls: an instruction reached through an auctioneer CPI is not visible in the instructions sysvar.
+
+/// `wallet` in [`Sell`](crate::Sell) and [`SellV2`](crate::SellV2).
+pub const SELL_WALLET_INDEX: usize = 0;
+/// `token_account` in [`Sell`](crate::Sell) and [`SellV2`](crate::SellV2).
+pub const SELL_TOKEN_ACCOUNT_INDEX: usize = 1;
+/// `metadata` in [`Sell`](crate::Sell) and [`SellV2`](crate::SellV2).
+pub const SELL_METADATA_INDEX: usize = 2;
+/// `auction_house` in [`Sell`](crate::Sell) and [`SellV2`](crate::SellV2).
+pub const SELL_AUCTION_HOUSE_INDEX: usize = 4;
+/// `seller_trade_state` in [`Sell`](crate::Sell) and [`SellV2`](crate::SellV2).
+pub const SELL_TRADE_STATE_INDEX: usize = 6;
+
+/// `wallet` in [`Buy`](crate::Buy), [`PublicBuy`](crate::PublicBuy) and their v2 versions.
+pub const BUY_WALLET_INDEX: usize = 0;
+/// `token_account` in [`Buy`](crate::Buy), [`PublicBuy`](crate::PublicBuy) and their v2 versions.
+pub const BUY_TOKEN_ACCOUNT_INDEX: usize = 4;
+/// `metadata` in [`Buy`](crate::Buy), [`PublicBuy`](crate::PublicBuy) and their v2 versions.
+pub const BUY_METADATA_INDEX: usize = 5;
+/// `auction_house` in [`Buy`](crate::Buy), [`PublicBuy`](crate::PublicBuy) and their v2 versions.
+pub const BUY_AUCTION_HOUSE_INDEX: usize = 8;
+/// `buyer_trade_state` in [`Buy`](crate::Buy), [`PublicBuy`](crate::PublicBuy) and their v2 versions.
+pub const BUY_TRADE_STATE_INDEX: usize = 10;
+
+/// `buyer` in [`ExecuteSale`](crate::ExecuteSale) and [`ExecuteSaleV2`](crate::ExecuteSaleV2).
+pub const EXECUTE_SALE_BUYER_INDEX: usize = 0;
+/// `seller` in [`ExecuteSale`](crate::ExecuteSale) and [`ExecuteSaleV2`](crate::ExecuteSaleV2).
+pub const EXECUTE_SALE_SELLER_INDEX: usize = 1;
+/// `token_mint` in [`ExecuteSale`](crate::ExecuteSale) and [`ExecuteSaleV2`](crate::ExecuteSaleV2).
+pub const EXECUTE_SALE_TOKEN_MINT_INDEX: usize = 3;
+/// `metadata` in [`ExecuteSale`](crate::ExecuteSale) and [`ExecuteSaleV2`](crate::ExecuteSaleV2).
+pub const EXECUTE_SALE_METADATA_INDEX: usize = 4;
+/// `auction_house` in [`ExecuteSale`](crate::ExecuteSale) and [`ExecuteSaleV2`](crate::ExecuteSaleV2).
+pub const EXECUTE_SALE_AUCTION_HOUSE_INDEX: usize = 10;
+/// `buyer_trade_state` in [`ExecuteSale`](crate::ExecuteSale) and [`ExecuteSaleV2`](crate::ExecuteSaleV2).
+pub const EXECUTE_SALE_BUYER_TRADE_STATE_INDEX: usize = 13;
+/// `seller_trade_state` in [`ExecuteSale`](crate::ExecuteSale) and [`ExecuteSaleV2`](crate::ExecuteSaleV2).
+pub const EXECUTE_SALE_SELLER_TRADE_STATE_INDEX: usize = 14;
+
+/// `trade_state` in [`Cancel`](crate::Cancel).
+pub const CANCEL_TRADE_STATE_INDEX: usize = 6;
+
+/// Durable record of a listing, created right after a `sell` or `sell_v2` instruction.
+#[account]
+pub struct ListingReceipt {
+    pub trade_state: Pubkey,
+    pub bookkeeper: Pubkey,
+    pub auction_house: Pubkey,
+    pub seller: Pubkey,
+    pub metadata: Pubkey,
+    pub token_mint: Pubkey,
+    pub purchase_receipt: Option<Pubkey>,
+    pub price: u64,
+    pub token_size: u64,
+    pub bump: u8,
+    pub trade_state_bump: u8,
+    pub created_at: i64,
+    pub canceled_at: Option<i64>,
+}
+
+/// Durable record of a bid, created right after a `buy`, `public_buy` or v2 bid instruction.
+#[account]
+pub struct BidReceipt {
+    pub trade_state: Pubkey,
+    pub bookkeeper: Pubkey,
+    pub auction_house: Pubkey,
+    pub buyer: Pubkey,
+    pub metadata: Pubkey,
+    pub token_mint: Pubkey,
+    pub token_account: Option<Pubkey>,
+    pub purchase_receipt: Option<Pubkey>,
+    pub price: u64,
+    pub token_size: u64,
+    pub bump: u8,
+    pub trade_state_bump: u8,
+    pub created_at: i64,
+    pub canceled_at: Option<i64>,
+}
+
+/// Durable record of a sale, created right after an `execute_sale` or `execute_sale_v2` instruction.
+#[account]
+pub struct PurchaseReceipt {
+    pub bookkeeper: Pubkey,
+    pub buyer: Pubkey,
+    pub seller: Pubkey,
+    pub auction_house: Pubkey,
+    pub metadata: Pubkey,
+    pub token_mint: Pubkey,
+    pub token_size: u64,
+    pub price: u64,
+    pub bump: u8,
+    pub created_at: i64,
+}
+
+/// Accounts for the [`print_listing_receipt` handler](auction_house/fn.print_listing_receipt.html).
+#[derive(Accounts)]
+pub struct PrintListingReceipt<'info> {
+    /// CHECK: Receipt seeds are checked in print_listing_receipt.
+    /// Listing receipt PDA.
+    #[account(mut)]
+    pub receipt: UncheckedAccount<'info>,
+
+    /// Account paying for the receipt.
+    #[account(mut)]
+    pub bookkeeper: Signer<'info>,
+
+    /// SPL token account listed by the previous instruction.
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: Address checked in constraint.
+    /// Instructions sysvar account.
+    #[account(address = sysvar::instructions::id())]
+    pub instruction: UncheckedAccount<'info>,
+}
+
+/// Accounts for the [`print_bid_receipt` handler](auction_house/fn.print_bid_receipt.html).
+#[derive(Accounts)]
+pub struct PrintBidReceipt<'info> {
+    /// CHECK: Receipt seeds are checked in print_bid_receipt.
+    /// Bid receipt PDA.
+    #[account(mut)]
+    pub receipt: UncheckedAccount<'info>,
+
+    /// Account paying for the receipt.
+    #[account(mut)]
+    pub bookkeeper: Signer<'info>,
+
+    /// SPL token account bid on by the previous instruction.
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: Address checked in constraint.
+    /// Instructions sysvar account.
+    #[account(address = sysvar::instructions::id())]
+    pub instruction: UncheckedAccount<'info>,
+}
+
+/// Accounts for the [`print_purchase_receipt` handler](auction_house/fn.print_purchase_receipt.html).
+#[derive(Accounts)]
+pub struct PrintPurchaseReceipt<'info> {
+    /// CHECK: Receipt seeds are checked in print_purchase_receipt.
+    /// Purchase receipt PDA.
+    #[account(mut)]
+    pub purchase_receipt: UncheckedAccount<'info>,
+
+    /// Receipt of the listing that was filled.
+    #[account(
+        mut,
+        seeds = [
+            LISTING_RECEIPT_PREFIX.as_bytes(),
+            listing_receipt.trade_state.as_ref()
+        ],
+        bump = listing_receipt.bump
+    )]
+    pub listing_receipt: Box<Account<'info, ListingReceipt>>,
+
+    /// Receipt of the bid that was filled.
+    #[account(
+        mut,
+        seeds = [
+            BID_RECEIPT_PREFIX.as_bytes(),
+            bid_receipt.trade_state.as_ref()
+        ],
+        bump = bid_receipt.bump
+    )]
+    pub bid_receipt: Box<Account<'info, BidReceipt>>,
+
+    /// Account paying for the receipt.
+    #[account(mut)]
+    pub bookkeeper: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: Address checked in constraint.
+    /// Instructions sysvar account.
+    #[account(address = sysvar::instructions::id())]
+    pub instruction: UncheckedAccount<'info>,
+}
+
+/// Accounts for the [`cancel_listing_receipt` handler](auction_house/fn.cancel_listing_receipt.html).
+#[derive(Accounts)]
+pub struct CancelListingReceipt<'info> {
+    /// Listing receipt PDA.
+    #[account(
+        mut,
+        seeds = [
+            LISTING_RECEIPT_PREFIX.as_bytes(),
+            receipt.trade_state.as_ref()
+        ],
+        bump = receipt.bump
+    )]
+    pub receipt: Account<'info, ListingReceipt>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Address checked in constraint.
+    /// Instructions sysvar account.
+    #[account(address = sysvar::instructions::id())]
+    pub instruction: UncheckedAccount<'info>,
+}
+
+/// Accounts for the [`cancel_bid_receipt` handler](auction_house/fn.cancel_bid_receipt.html).
+#[derive(Accounts)]
+pub struct CancelBidReceipt<'info> {
+    /// Bid receipt PDA.
+    #[account(
+        mut,
+        seeds = [
+            BID_RECEIPT_PREFIX.as_bytes(),
+            receipt.trade_state.as_ref()
+        ],
+        bump = receipt.bump
+    )]
+    pub receipt: Account<'info, BidReceipt>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Address checked in constraint.
+    /// Instructions sysvar account.
+    #[account(address = sysvar::instructions::id())]
+    pub instruction: UncheckedAccount<'info>,
+}
+
+pub fn print_listing_receipt<'info>(
+    ctx: Context<'_, '_, '_, 'info, PrintListingReceipt<'info>>,
+) -> Result<()> {
+    let receipt_account = &ctx.accounts.receipt;
+    let bookkeeper = &ctx.accounts.bookkeeper;
+    let token_account = &ctx.accounts.token_account;
+    let system_program = &ctx.accounts.system_program;
+    let rent = &ctx.accounts.rent;
+
+    let prev_instruction = get_instruction_relative(-1, &ctx.accounts.instruction)?;
+    if prev_instruction.program_id != crate::id() {
+        return Err(AuctionHouseError::InstructionMismatch.into());
+    }
+
+    let data = &prev_instruction.data;
+    let accounts = &prev_instruction.accounts;
+    let seller = account_key(accounts, SELL_WALLET_INDEX)?;
+    let auction_house = account_key(accounts, SELL_AUCTION_HOUSE_INDEX)?;
+    let discriminator = instruction_discriminator(data)?;
+
+    assert_keys_equal(
+        token_account.key(),
+        account_key(accounts, SELL_TOKEN_ACCOUNT_INDEX)?,
+    )?;
+    let trade_state = account_key(accounts, SELL_TRADE_STATE_INDEX)?;
+
+    // `sell_v2` amends its listing in place, so it rewrites the receipt of the live listing.
+    let (price, token_size, trade_state_bump, amends) =
+        if discriminator == instruction::Sell::DISCRIMINATOR {
+            let args: instruction::Sell = instruction_args(data)?;
+            (
+                args.buyer_price,
+                args.token_size,
+                args.trade_state_bump,
+                false,
+            )
+        } else if discriminator == instruction::SellV2::DISCRIMINATOR {
+            let args: instruction::SellV2 = instruction_args(data)?;
+            let (_, trade_state_bump) = find_trade_state_v2_address(
+                &seller,
+                &auction_house,
+                &token_account.key(),
+                TradeSide::Sell,
+            );
+            (args.price, args.token_size, trade_state_bump, true)
+        } else {
+            return Err(AuctionHouseError::InstructionMismatch.into());
+        };
+
+    let receipt_seeds = [LISTING_RECEIPT_PREFIX.as_bytes(), trade_state.as_ref()];
+    let bump = assert_derivation(&crate::id(), receipt_account, &receipt_seeds)?;
+
+    if receipt_account.data_is_empty() {
+        create_or_allocate_account_raw(
+            crate::id(),
+            receipt_account,
+            &rent.to_account_info(),
+            system_program,
+            bookkeeper,
+            LISTING_RECEIPT_SIZE,
+            &[],
+            &[
+                LISTING_RECEIPT_PREFIX.as_bytes(),
+                trade_state.as_ref(),
+                &[bump],
+            ],
+        )?;
+    } else {
+        // Relisting at the same price reuses the trade state, and so the receipt once the previous
+        // listing was canceled or sold.
+        let existing =
+            ListingReceipt::try_deserialize(&mut &receipt_account.try_borrow_data()?[..])?;
+        if !amends {
+            assert_receipt_closed(existing.canceled_at, existing.purchase_receipt)?;
+        }
+    }
+
+    let receipt = ListingReceipt {
+        trade_state,
+        bookkeeper: bookkeeper.key(),
+        auction_house,
+        seller,
+        metadata: account_key(accounts, SELL_METADATA_INDEX)?,
+        token_mint: token_account.mint,
+        purchase_receipt: None,
+        price,
+        token_size,
+        bump,
+        trade_state_bump,
+        created_at: Clock::get()?.unix_timestamp,
+        canceled_at: None,
+    };
+
+    write_receipt(receipt_account, &receipt)
+}
+
+pub fn print_bid_receipt<'info>(
+    ctx: Context<'_, '_, '_, 'info, PrintBidReceipt<'info>>,
+) -> Result<()> {
+    let receipt_account = &ctx.accounts.receipt;
+    let bookkeeper = &ctx.accounts.bookkeeper;
+    let token_account = &ctx.accounts.token_account;
+    let system_program = &ctx.accounts.system_program;
+    let rent = &ctx.accounts.rent;
+
+    let prev_instruction = get_instruction_relative(-1, &ctx.accounts.instruction)?;
+    if prev_instruction.program_id != crate::id() {
+        return Err(AuctionHouseError::InstructionMismatch.into());
+    }
+
+    let data = &prev_instruction.data;
+    let accounts = &prev_instruction.accounts;
+    let buyer = account_key(accounts, BUY_WALLET_INDEX)?;
+    let auction_house = account_key(accounts, BUY_AUCTION_HOUSE_INDEX)?;
+    let discriminator = instruction_discriminator(data)?;
+
+    // Private and public bids share their accounts. The v2 bids amend their bid in place, so they
+    // rewrite the receipt of the live bid.
+    let (price, token_size, trade_state_bump, public, amends) =
+        if discriminator == instruction::Buy::DISCRIMINATOR {
+            let args: instruction::Buy = instruction_args(data)?;
+            (
+                args.buyer_price,
+                args.token_size,
+                args.trade_state_bump,
+                false,
+                false,
+            )
+        } else if discriminator == instruction::PublicBuy::DISCRIMINATOR {
+            let args: instruction::PublicBuy = instruction_args(data)?;
+            (
+                args.buyer_price,
+                args.token_size,
+                args.trade_state_bump,
+                true,
+                false,
+            )
+        } else if discriminator == instruction::BuyV2::DISCRIMINATOR {
+            let args: instruction::BuyV2 = instruction_args(data)?;
+            let (_, trade_state_bump) = find_trade_state_v2_address(
+                &buyer,
+                &auction_house,
+                &token_account.key(),
+                TradeSide::Buy,
+            );
+            (args.price, args.token_size, trade_state_bump, false, true)
+        } else if discriminator == instruction::PublicBuyV2::DISCRIMINATOR {
+            let args: instruction::PublicBuyV2 = instruction_args(data)?;
+            let (_, trade_state_bump) = find_trade_state_v2_address(
+                &buyer,
+                &auction_house,
+                &token_account.mint,
+                TradeSide::Buy,
+            );
+            (args.price, args.token_size, trade_state_bump, true, true)
+        } else {
+            return Err(AuctionHouseError::InstructionMismatch.into());
+        };
+
+    assert_keys_equal(
+        token_account.key(),
+        account_key(accounts, BUY_TOKEN_ACCOUNT_INDEX)?,
+    )?;
+    let trade_state = account_key(accounts, BUY_TRADE_STATE_INDEX)?;
+
+    let receipt_seeds = [BID_RECEIPT_PREFIX.as_bytes(), trade_state.as_ref()];
+    let bump = assert_derivation(&crate::id(), receipt_account, &receipt_seeds)?;
+
+    if receipt_account.data_is_empty() {
+        create_or_allocate_account_raw(
+            crate::id(),
+            receipt_account,
+            &rent.to_account_info(),
+            system_program,
+            bookkeeper,
+            BID_RECEIPT_SIZE,
+            &[],
+            &[BID_RECEIPT_PREFIX.as_bytes(), trade_state.as_ref(), &[bump]],
+        )?;
+    } else {
+        // Bidding again at the same price reuses the trade state, and so the receipt once the
+        // previous bid was canceled or filled.
+        let existing = BidReceipt::try_deserialize(&mut &receipt_account.try_borrow_data()?[..])?;
+        if !amends {
+            assert_receipt_closed(existing.canceled_at, existing.purchase_receipt)?;
+        }
+    }
+
+    let receipt = BidReceipt {
+        trade_state,
+        bookkeeper: bookkeeper.key(),
+        auction_house,
+        buyer,
+        metadata: account_key(accounts, BUY_METADATA_INDEX)?,
+        token_mint: token_account.mint,
+        token_account: if public {
+            None
+        } else {
+            Some(token_account.key())
+        },
+        purchase_receipt: None,
+        price,
+        token_size,
+        bump,
+        trade_state_bump,
+        created_at: Clock::get()?.unix_timestamp,
+        canceled_at: None,
+    };
+
+    write_receipt(receipt_account, &receipt)
+}
+
+pub fn print_purchase_receipt<'info>(
+    ctx: Context<'_, '_, '_, 'info, PrintPurchaseReceipt<'info>>,
+) -> Result<()> {
+    let purchase_receipt_account = &ctx.accounts.purchase_receipt;
+    let listing_receipt = &mut ctx.accounts.listing_receipt;
+    let bid_receipt = &mut ctx.accounts.bid_receipt;
+    let bookkeeper = &ctx.accounts.bookkeeper;
+    let system_program = &ctx.accounts.system_program;
+    let rent = &ctx.accounts.rent;
+
+    let prev_instruction = get_instruction_relative(-1, &ctx.accounts.instruction)?;
+    if prev_instruction.program_id != crate::id() {
+        return Err(AuctionHouseError::InstructionMismatch.into());
+    }
+
+    let data = &prev_instruction.data;
+    let accounts = &prev_instruction.accounts;
+    let discriminator = instruction_discriminator(data)?;
+
+    // Legacy and v2 sales share their accounts and arguments.
+    let (buyer_price, token_size, partial_order_size, partial_order_price) =
+        if discriminator == instruction::ExecuteSale::DISCRIMINATOR {
+            let args: instruction::ExecuteSale = instruction_args(data)?;
+            (
+                args.buyer_price,
+                args.token_size,
+                args.partial_order_size,
+                args.partial_order_price,
+            )
+        } else if discriminator == instruction::ExecuteSaleV2::DISCRIMINATOR {
+            let args: instruction::ExecuteSaleV2 = instruction_args(data)?;
+            (
+                args.buyer_price,
+                args.token_size,
+                args.partial_order_size,
+                args.partial_order_price,
+            )
+        } else {
+            return Err(AuctionHouseError::InstructionMismatch.into());
+        };
+
+    // A partial order records the part that was bought.
+    let (price, token_size) = match (partial_order_size, partial_order_price) {
+        (Some(size), Some(price)) => (price, size),
+        _ => (buyer_price, token_size),
+    };
+
+    let buyer_trade_state = account_key(accounts, EXECUTE_SALE_BUYER_TRADE_STATE_INDEX)?;
+    let seller_trade_state = account_key(accounts, EXECUTE_SALE_SELLER_TRADE_STATE_INDEX)?;
+    assert_keys_equal(bid_receipt.trade_state, buyer_trade_state)?;
+    assert_keys_equal(listing_receipt.trade_state, seller_trade_state)?;
+
+    let receipt_seeds = [
+        PURCHASE_RECEIPT_PREFIX.as_bytes(),
+        seller_trade_state.as_ref(),
+        buyer_trade_state.as_ref(),
+    ];
+    let bump = assert_derivation(&crate::id(), purchase_receipt_account, &receipt_seeds)?;
+
+    if !purchase_receipt_account.data_is_empty() {
+        return Err(AuctionHouseError::ReceiptAlreadyPrinted.into());
+    }
+    create_or_allocate_account_raw(
+        crate::id(),
+        purchase_receipt_account,
+        &rent.to_account_info(),
+        system_program,
+        bookkeeper,
+        PURCHASE_RECEIPT_SIZE,
+        &[],
+        &[
+            PURCHASE_RECEIPT_PREFIX.as_bytes(),
+            seller_trade_state.as_ref(),
+            buyer_trade_state.as_ref(),
+            &[bump],
+        ],
+    )?;
+
+    let receipt = PurchaseReceipt {
+        bookkeeper: bookkeeper.key(),
+        buyer: account_key(accounts, EXECUTE_SALE_BUYER_INDEX)?,
+        seller: account_key(accounts, EXECUTE_SALE_SELLER_INDEX)?,
+        auction_house: account_key(accounts, EXECUTE_SALE_AUCTION_HOUSE_INDEX)?,
+        metadata: account_key(accounts, EXECUTE_SALE_METADATA_INDEX)?,
+        token_mint: account_key(accounts, EXECUTE_SALE_TOKEN_MINT_INDEX)?,
+        token_size,
+        price,
+        bump,
+        created_at: Clock::get()?.unix_timestamp,
+    };
+
+    write_receipt(purchase_receipt_account, &receipt)?;
+
+    listing_receipt.purchase_receipt = Some(purchase_receipt_account.key());
+    bid_receipt.purchase_receipt = Some(purchase_receipt_account.key());
+
+    Ok(())
+}
+
+pub fn cancel_listing_receipt<'info>(
+    ctx: Context<'_, '_, '_, 'info, CancelListingReceipt<'info>>,
+) -> Result<()> {
+    let trade_state = cancelled_trade_state(&ctx.accounts.instruction)?;
+    let receipt = &mut ctx.accounts.receipt;
+
+    assert_keys_equal(receipt.trade_state, trade_state)?;
+    receipt.canceled_at = Some(Clock::get()?.unix_timestamp);
+
+    Ok(())
+}
+
+pub fn cancel_bid_receipt<'info>(
+    ctx: Context<'_, '_, '_, 'info, CancelBidReceipt<'info>>,
+) -> Result<()> {
+    let trade_state = cancelled_trade_state(&ctx.accounts.instruction)?;
+    let receipt = &mut ctx.accounts.receipt;
+
+    assert_keys_equal(receipt.trade_state, trade_state)?;
+    receipt.canceled_at = Some(Clock::get()?.unix_timestamp);
+
+    Ok(())
+}
+
+/// Returns the trade state closed by the previous `cancel` instruction.
+fn cancelled_trade_state(instruction_account: &AccountInfo) -> Result<Pubkey> {
+    let prev_instruction = get_instruction_relative(-1, instruction_account)?;
+    if prev_instruction.program_id != crate::id() {
+        return Err(AuctionHouseError::InstructionMismatch.into());
+    }
+
+    if instruction_discriminator(&prev_instruction.data)? != instruction::Cancel::DISCRIMINATOR {
+        return Err(AuctionHouseError::InstructionMismatch.into());
+    }
+
+    account_key(&prev_instruction.accounts, CANCEL_TRADE_STATE_INDEX)
+}
+
+/// A listing or bid receipt is only printed again once its order was canceled or filled.
+fn assert_receipt_closed(canceled_at: Option<i64>, purchase_receipt: Option<Pubkey>) -> Result<()> {
+    if canceled_at.is_none() && purchase_receipt.is_none() {
+        return Err(AuctionHouseError::ReceiptAlreadyPrinted.into());
+    }
+
+    Ok(())
+}
+
+fn instruction_discriminator(data: &[u8]) -> Result<[u8; 8]> {
+    data.get(..8)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| AuctionHouseError::InstructionMismatch.into())
+}
+
+/// Decode the arguments of instruction `T` from its data, checking the discriminator.
+fn instruction_args<T: Discriminator + AnchorDeserialize>(data: &[u8]) -> Result<T> {
+    if instruction_discriminator(data)? != T::DISCRIMINATOR {
+        return Err(AuctionHouseError::InstructionMismatch.into());
+    }
+
+    T::deserialize(&mut &data[8..]).map_err(|_| AuctionHouseError::InstructionMismatch.into())
+}
+
+fn account_key(accounts: &[AccountMeta], index: usize) -> Result<Pubkey> {
+    accounts
+        .get(index)
+        .map(|meta| meta.pubkey)
+        .ok_or_else(|| AuctionHouseError::InstructionMismatch.into())
+}
+
+fn write_receipt<T: AccountSerialize>(receipt_account: &AccountInfo, receipt: &T) -> Result<()> {
+    let mut data = receipt_account.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data;
+    receipt.try_serialize(&mut writer)
+}
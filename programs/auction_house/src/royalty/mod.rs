@@ -0,0 +1,82 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{program::invoke_signed, system_instruction},
+};
+
+use crate::{constants::*, errors::AuctionHouseError, AuctionHouse};
+
+#[derive(Accounts)]
+pub struct ClaimRoyalties<'info> {
+    /// Creator claiming the royalties credited to their vault.
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump = auction_house.bump
+    )]
+    pub auction_house: Account<'info, AuctionHouse>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Royalty vault PDA holding the creator's unpaid royalties in SOL.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            ROYALTY_VAULT.as_bytes(),
+            creator.key().as_ref()
+        ],
+        bump
+    )]
+    pub royalty_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn claim_royalties<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClaimRoyalties<'info>>,
+) -> Result<()> {
+    let creator = &ctx.accounts.creator;
+    let auction_house = &ctx.accounts.auction_house;
+    let royalty_vault = &ctx.accounts.royalty_vault;
+    let system_program = &ctx.accounts.system_program;
+
+    // The vault keeps its rent-exempt reserve so it can be credited again later.
+    let amount = royalty_vault
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(royalty_vault.data_len()));
+    if amount == 0 {
+        return Err(AuctionHouseError::NoRoyaltiesToClaim.into());
+    }
+
+    let auction_house_key = auction_house.key();
+    let creator_key = creator.key();
+    let royalty_vault_seeds = [
+        PREFIX.as_bytes(),
+        auction_house_key.as_ref(),
+        ROYALTY_VAULT.as_bytes(),
+        creator_key.as_ref(),
+        &[*ctx
+            .bumps
+            .get("royalty_vault")
+            .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?],
+    ];
+
+    invoke_signed(
+        &system_instruction::transfer(&royalty_vault.key(), &creator.key(), amount),
+        &[
+            royalty_vault.to_account_info(),
+            creator.to_account_info(),
+            system_program.to_account_info(),
+        ],
+        &[&royalty_vault_seeds],
+    )?;
+
+    Ok(())
+}
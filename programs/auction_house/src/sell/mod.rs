@@ -1,8 +1,125 @@
 use anchor_lang::{prelude::*, solana_program::program::invoke, AnchorDeserialize};
-use spl_token::instruction::approve;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
+use spl_token_2022::instruction::approve;
 
 use crate::{constants::*, errors::*, utils::*, AuctionHouse, *};
 
+#[derive(Accounts, Clone)]
+#[instruction(
+    trade_state_bump: u8,
+    free_trade_state_bump: u8,
+    program_as_signer_bump: u8,
+    buyer_price: u64,
+    token_size: u64
+)]
+pub struct Sell<'info> {
+    /// CHECK: Wallet is validated as a signer in sell_logic.
+    /// User wallet account.
+    #[account(mut)]
+    pub wallet: UncheckedAccount<'info>,
+
+    /// SPL token account containing token for sale.
+    #[account(mut)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Validated by assert_metadata_valid.
+    /// Metaplex metadata account decorating SPL mint account.
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Verified with has_one constraint on auction house account.
+    /// Auction House authority account.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump=auction_house.bump,
+        has_one=authority,
+        has_one=auction_house_fee_account
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Auction House instance fee account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            FEE_PAYER.as_bytes()
+        ],
+        bump=auction_house.fee_payer_bump
+    )]
+    pub auction_house_fee_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Seller trade state PDA account encoding the sell order.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            wallet.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_account.key().as_ref(),
+            auction_house.treasury_mint.as_ref(),
+            token_account.mint.as_ref(),
+            &buyer_price.to_le_bytes(),
+            &token_size.to_le_bytes()
+        ],
+        bump
+    )]
+    pub seller_trade_state: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Free seller trade state PDA account encoding a free sell order.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            wallet.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_account.key().as_ref(),
+            auction_house.treasury_mint.as_ref(),
+            token_account.mint.as_ref(),
+            &0u64.to_le_bytes(),
+            &token_size.to_le_bytes()
+        ],
+        bump
+    )]
+    pub free_seller_trade_state: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(seeds=[PREFIX.as_bytes(), SIGNER.as_bytes()], bump)]
+    pub program_as_signer: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> From<AuctioneerSell<'info>> for Sell<'info> {
+    fn from(a: AuctioneerSell<'info>) -> Sell<'info> {
+        Sell {
+            wallet: a.wallet,
+            token_account: a.token_account,
+            metadata: a.metadata,
+            authority: a.authority,
+            auction_house: a.auction_house,
+            auction_house_fee_account: a.auction_house_fee_account,
+            seller_trade_state: a.seller_trade_state,
+            free_seller_trade_state: a.free_seller_trade_state,
+            program_as_signer: a.program_as_signer,
+            token_program: a.token_program,
+            system_program: a.system_program,
+            rent: a.rent,
+        }
+    }
+}
+
 #[derive(Accounts, Clone)]
 #[instruction(
     trade_state_bump: u8,
@@ -18,7 +135,7 @@ pub struct AuctioneerSell<'info> {
 
     /// SPL token account containing token for sale.
     #[account(mut)]
-    pub token_account: Box<Account<'info, TokenAccount>>,
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// CHECK: Validated by assert_metadata_valid.
     /// Metaplex metadata account decorating SPL mint account.
@@ -110,11 +227,130 @@ pub struct AuctioneerSell<'info> {
     #[account(seeds=[PREFIX.as_bytes(), SIGNER.as_bytes()], bump)]
     pub program_as_signer: UncheckedAccount<'info>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Accounts for the [`sell_v2` handler](auction_house/fn.sell_v2.html).
+#[derive(Accounts, Clone)]
+pub struct SellV2<'info> {
+    /// User wallet account.
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    /// SPL token account containing token for sale.
+    #[account(mut)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Validated by assert_metadata_valid.
+    /// Metaplex metadata account decorating SPL mint account.
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Verified with has_one constraint on auction house account.
+    /// Auction House authority account.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump=auction_house.bump,
+        has_one=authority,
+        has_one=auction_house_fee_account
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Auction House instance fee account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            FEE_PAYER.as_bytes()
+        ],
+        bump=auction_house.fee_payer_bump
+    )]
+    pub auction_house_fee_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Seller v2 trade state PDA account holding the listing.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            TRADE_STATE_V2.as_bytes(),
+            wallet.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_account.key().as_ref(),
+            &TradeSide::Sell.seed()
+        ],
+        bump
+    )]
+    pub seller_trade_state: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(seeds=[PREFIX.as_bytes(), SIGNER.as_bytes()], bump)]
+    pub program_as_signer: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
+/// Create a sell bid by creating a `seller_trade_state` account and approving the program as the token delegate.
+pub fn sell<'info>(
+    ctx: Context<'_, '_, '_, 'info, Sell<'info>>,
+    trade_state_bump: u8,
+    free_trade_state_bump: u8,
+    program_as_signer_bump: u8,
+    buyer_price: u64,
+    token_size: u64,
+    expiry: Option<i64>,
+) -> Result<()> {
+    let auction_house = &ctx.accounts.auction_house;
+
+    if auction_house.has_auctioneer {
+        return Err(AuctionHouseError::MustUseAuctioneerHandler.into());
+    }
+
+    let trade_state_canonical_bump = *ctx
+        .bumps
+        .get("seller_trade_state")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+    let free_trade_state_canonical_bump = *ctx
+        .bumps
+        .get("free_seller_trade_state")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+    let program_as_signer_canonical_bump = *ctx
+        .bumps
+        .get("program_as_signer")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+
+    if (trade_state_canonical_bump != trade_state_bump)
+        || (free_trade_state_canonical_bump != free_trade_state_bump)
+        || (program_as_signer_canonical_bump != program_as_signer_bump)
+    {
+        return Err(AuctionHouseError::BumpSeedNotInHashMap.into());
+    }
+
+    sell_logic(
+        ctx.accounts,
+        ctx.program_id,
+        ctx.remaining_accounts,
+        trade_state_bump,
+        free_trade_state_bump,
+        program_as_signer_bump,
+        buyer_price,
+        token_size,
+        expiry,
+    )
+}
+
 /// Create a sell bid by creating a `seller_trade_state` account and approving the program as the token delegate.
 pub fn auctioneer_sell<'info>(
     ctx: Context<'_, '_, '_, 'info, AuctioneerSell<'info>>,
@@ -125,9 +361,12 @@ pub fn auctioneer_sell<'info>(
 ) -> Result<()> {
     let auction_house = &ctx.accounts.auction_house;
 
-    if !auction_house.has_auctioneer {
-        return Err(AuctionHouseError::NoAuctioneerProgramSet.into());
-    }
+    assert_valid_auctioneer_and_scope(
+        auction_house,
+        &ctx.accounts.auctioneer_authority.key(),
+        &ctx.accounts.auctioneer,
+        AuthorityScope::Sell,
+    )?;
 
     let trade_state_canonical_bump = *ctx
         .bumps
@@ -149,29 +388,129 @@ pub fn auctioneer_sell<'info>(
         return Err(AuctionHouseError::BumpSeedNotInHashMap.into());
     }
 
-    let mut accounts: AuctioneerSell<'info> = (*ctx.accounts).clone();
+    let mut accounts: Sell<'info> = (*ctx.accounts).clone().into();
 
     sell_logic(
         &mut accounts,
         ctx.program_id,
+        ctx.remaining_accounts,
         trade_state_bump,
         free_trade_state_bump,
         program_as_signer_bump,
         u64::MAX,
         token_size,
+        None,
     )
 }
 
+/// List `token_size` of the token account at `price` until `expiry` in a v2 trade state, or amend the open listing in place.
+pub fn sell_v2<'info>(
+    ctx: Context<'_, '_, '_, 'info, SellV2<'info>>,
+    price: u64,
+    token_size: u64,
+    expiry: Option<i64>,
+) -> Result<()> {
+    let wallet = &ctx.accounts.wallet;
+    let token_account = &ctx.accounts.token_account;
+    let auction_house = &ctx.accounts.auction_house;
+    let seller_trade_state = &ctx.accounts.seller_trade_state;
+
+    if auction_house.has_auctioneer {
+        return Err(AuctionHouseError::MustUseAuctioneerHandler.into());
+    }
+
+    assert_not_paused(auction_house)?;
+    assert_valid_expiry(expiry)?;
+
+    let trade_state_bump = *ctx
+        .bumps
+        .get("seller_trade_state")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+
+    let auction_house_key = auction_house.key();
+    let seeds = [
+        PREFIX.as_bytes(),
+        auction_house_key.as_ref(),
+        FEE_PAYER.as_bytes(),
+        &[auction_house.fee_payer_bump],
+    ];
+    let (fee_payer, fee_seeds) = get_fee_payer(
+        &ctx.accounts.authority,
+        wallet.to_account_info(),
+        ctx.accounts.auction_house_fee_account.to_account_info(),
+        &seeds,
+    )?;
+
+    approve_listing(
+        &wallet.to_account_info(),
+        token_account,
+        &ctx.accounts.metadata,
+        auction_house,
+        &ctx.accounts.program_as_signer,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+        ctx.remaining_accounts,
+        token_size,
+    )?;
+
+    let wallet_key = wallet.key();
+    let token_account_key = token_account.key();
+    write_trade_state_v2(
+        &seller_trade_state.to_account_info(),
+        &fee_payer,
+        fee_seeds,
+        &ctx.accounts.rent.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &[
+            PREFIX.as_bytes(),
+            TRADE_STATE_V2.as_bytes(),
+            wallet_key.as_ref(),
+            auction_house_key.as_ref(),
+            token_account_key.as_ref(),
+            &TradeSide::Sell.seed(),
+            &[trade_state_bump],
+        ],
+        TradeStateV2 {
+            auction_house: auction_house_key,
+            wallet: wallet_key,
+            token_account: token_account_key,
+            token_mint: token_account.mint,
+            side: TradeSide::Sell,
+            trade_type: TradeType::Private,
+            price,
+            token_size,
+            created_slot: Clock::get()?.slot,
+            bump: trade_state_bump,
+            expiry,
+            fee_payer: fee_payer.key(),
+        },
+    )?;
+
+    emit!(ListingCreated {
+        auction_house: auction_house_key,
+        seller: wallet_key,
+        token_mint: token_account.mint,
+        token_account: token_account_key,
+        trade_state: seller_trade_state.key(),
+        price,
+        token_size,
+    });
+
+    Ok(())
+}
+
 /// Create a sell bid by creating a `seller_trade_state` account and approving the program as the token delegate.
-#[allow(clippy::extra_unused_lifetimes)]
+#[allow(clippy::needless_lifetimes)]
 fn sell_logic<'c, 'info>(
-    accounts: &mut AuctioneerSell<'info>,
+    accounts: &mut Sell<'info>,
     program_id: &Pubkey,
+    remaining_accounts: &'c [AccountInfo<'info>],
     trade_state_bump: u8,
     _free_trade_state_bump: u8,
     _program_as_signer_bump: u8,
     buyer_price: u64,
     token_size: u64,
+    expiry: Option<i64>,
 ) -> Result<()> {
     let wallet = &accounts.wallet;
     let token_account = &accounts.token_account;
@@ -186,6 +525,9 @@ fn sell_logic<'c, 'info>(
     let program_as_signer = &accounts.program_as_signer;
     let rent = &accounts.rent;
 
+    assert_not_paused(auction_house)?;
+    assert_valid_expiry(expiry)?;
+
     // 1. The wallet being a signer is the only condition in which an NFT can sell at a price of 0.
     //    If the user does list at 0 then auction house can change the sale price if the 'can_change_sale_price' option is true.
     // 2. If the trade is not priced at 0, the wallet holder has to be a signer since auction house cannot sign if listing over 0.
@@ -214,19 +556,103 @@ fn sell_logic<'c, 'info>(
         auction_house_fee_account.to_account_info(),
         &seeds,
     )?;
+    approve_listing(
+        &wallet.to_account_info(),
+        token_account,
+        metadata,
+        auction_house,
+        program_as_signer,
+        token_program,
+        system_program,
+        remaining_accounts,
+        token_size,
+    )?;
+
+    let ts_info = seller_trade_state.to_account_info();
+    let created = ts_info.data_is_empty();
+    if created {
+        let token_account_key = token_account.key();
+        let wallet_key = wallet.key();
+        let ts_seeds = [
+            PREFIX.as_bytes(),
+            wallet_key.as_ref(),
+            auction_house_key.as_ref(),
+            token_account_key.as_ref(),
+            auction_house.treasury_mint.as_ref(),
+            token_account.mint.as_ref(),
+            &buyer_price.to_le_bytes(),
+            &token_size.to_le_bytes(),
+            &[trade_state_bump],
+        ];
+        create_or_allocate_account_raw(
+            *program_id,
+            &ts_info,
+            &rent.to_account_info(),
+            system_program,
+            &fee_payer,
+            legacy_trade_state_size(expiry),
+            fee_seeds,
+            &ts_seeds,
+        )?;
+    }
+
+    ts_info.try_borrow_mut_data()?[0] = trade_state_bump;
+    write_legacy_trade_state_expiry(&ts_info, created, expiry)?;
+
+    emit!(ListingCreated {
+        auction_house: auction_house_key,
+        seller: wallet.key(),
+        token_mint: token_account.mint,
+        token_account: token_account.key(),
+        trade_state: seller_trade_state.key(),
+        price: buyer_price,
+        token_size,
+    });
+
+    Ok(())
+}
+
+/// Check `token_size` of `token_account` can be listed and approve the program as its delegate.
+fn approve_listing<'info>(
+    wallet: &AccountInfo<'info>,
+    token_account: &InterfaceAccount<'info, TokenAccount>,
+    metadata: &AccountInfo<'info>,
+    auction_house: &Account<'info, AuctionHouse>,
+    program_as_signer: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    token_size: u64,
+) -> Result<()> {
     assert_is_ata(
         &token_account.to_account_info(),
         &wallet.key(),
         &token_account.mint,
     )?;
 
-    assert_metadata_valid(metadata, token_account)?;
+    assert_metadata_valid(metadata, &token_account.mint)?;
+
+    let remaining_accounts = &mut remaining_accounts.iter();
+    assert_collection_allowed(auction_house, metadata, remaining_accounts)?;
+    assert_wallet_not_blocked(auction_house, &wallet.key(), remaining_accounts)?;
 
     if token_size > token_account.amount {
         return Err(AuctionHouseError::InvalidTokenAmount.into());
     }
 
-    if wallet.is_signer {
+    // Programmable NFTs are frozen, so the delegate has to be set through Token Metadata.
+    if wallet.is_signer && is_programmable(metadata)? {
+        delegate_programmable(
+            remaining_accounts,
+            metadata,
+            &token_account.to_account_info(),
+            wallet,
+            program_as_signer,
+            system_program,
+            token_program,
+            token_size,
+        )?;
+    } else if wallet.is_signer {
         invoke(
             &approve(
                 &token_program.key(),
@@ -246,35 +672,5 @@ fn sell_logic<'c, 'info>(
         )?;
     }
 
-    let ts_info = seller_trade_state.to_account_info();
-    if ts_info.data_is_empty() {
-        let token_account_key = token_account.key();
-        let wallet_key = wallet.key();
-        let ts_seeds = [
-            PREFIX.as_bytes(),
-            wallet_key.as_ref(),
-            auction_house_key.as_ref(),
-            token_account_key.as_ref(),
-            auction_house.treasury_mint.as_ref(),
-            token_account.mint.as_ref(),
-            &buyer_price.to_le_bytes(),
-            &token_size.to_le_bytes(),
-            &[trade_state_bump],
-        ];
-        create_or_allocate_account_raw(
-            *program_id,
-            &ts_info,
-            &rent.to_account_info(),
-            system_program,
-            &fee_payer,
-            TRADE_STATE_SIZE,
-            fee_seeds,
-            &ts_seeds,
-        )?;
-    }
-
-    let data = &mut ts_info.data.borrow_mut();
-    data[0] = trade_state_bump;
-
     Ok(())
 }
@@ -17,6 +17,28 @@ pub struct AuctionHouse {
     pub escrow_payment_bump: u8,
     pub has_auctioneer: bool,
     pub auctioneer_address: Pubkey,
+    pub auctioneer_count: u16,
+    /// Authority proposed with `propose_authority`, or the default key if none is pending.
+    pub pending_authority: Pubkey,
+    pub pending_treasury_withdrawal_destination: Pubkey,
+    pub pending_fee_withdrawal_destination: Pubkey,
+    pub has_withdrawal_policy: bool,
+    /// Set with `set_pause_state` to reject new sells, bids and sales.
+    pub is_paused: bool,
+    /// Set by `add_allowed_collection` to only trade NFTs of allowed verified collections.
+    pub has_collection_allowlist: bool,
+    /// Set by `block_wallet` to check buyers and sellers against the blocklist.
+    pub has_blocklist: bool,
+    /// Layout version, zero for houses created before it was tracked.
+    pub version: u8,
+}
+
+impl AuctionHouse {
+    /// Delegated auctioneers, counting the single auctioneer of houses delegated before
+    /// `auctioneer_count` was tracked.
+    pub fn delegated_auctioneer_count(&self) -> u16 {
+        self.auctioneer_count.max(self.has_auctioneer as u16)
+    }
 }
 
 #[account]
@@ -24,4 +46,37 @@ pub struct Auctioneer {
     pub auctioneer_authority: Pubkey,
     pub auction_house: Pubkey,
     pub bump: u8,
+    pub scopes: u8,
+    /// Escrow funds the auctioneer holds locked across wallets, released before it can be
+    /// undelegated or replaced.
+    pub locked_amount: u64,
+}
+
+impl Auctioneer {
+    pub fn set_scopes(&mut self, scopes: &[AuthorityScope]) {
+        self.scopes = scopes.iter().fold(0, |mask, scope| mask | scope.mask());
+    }
+
+    /// Auctioneers delegated before scopes were stored have an empty mask and keep every scope.
+    pub fn has_scope(&self, scope: AuthorityScope) -> bool {
+        self.scopes == 0 || self.scopes & scope.mask() != 0
+    }
+}
+
+/// Operations an auctioneer may perform on behalf of an Auction House.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuthorityScope {
+    Deposit = 0,
+    Buy = 1,
+    PublicBuy = 2,
+    ExecuteSale = 3,
+    Sell = 4,
+    Cancel = 5,
+    Withdraw = 6,
+}
+
+impl AuthorityScope {
+    pub fn mask(self) -> u8 {
+        1 << self as u8
+    }
 }
@@ -0,0 +1,278 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*,
+    errors::AuctionHouseError,
+    events::TradeStateExpired,
+    utils::{assert_keys_equal, close_account, create_or_allocate_account_raw},
+};
+
+/// Side of the order a v2 trade state encodes, part of its seeds.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TradeSide {
+    Sell = 0,
+    Buy = 1,
+}
+
+impl TradeSide {
+    pub fn seed(self) -> [u8; 1] {
+        [self as u8]
+    }
+}
+
+/// Whether a v2 trade state is tied to one token account, or to any token account of the mint.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TradeType {
+    Private = 0,
+    Public = 1,
+}
+
+/// Listing or bid keyed by wallet, token account (or mint for public bids) and side rather than
+/// by price, so the order is amended in place and validated without re-deriving its address.
+#[account]
+pub struct TradeStateV2 {
+    pub auction_house: Pubkey,
+    pub wallet: Pubkey,
+    /// Token account listed or bid on, the default key for public bids.
+    pub token_account: Pubkey,
+    pub token_mint: Pubkey,
+    pub side: TradeSide,
+    pub trade_type: TradeType,
+    pub price: u64,
+    pub token_size: u64,
+    /// Slot the order was first placed at, kept across amendments.
+    pub created_slot: u64,
+    pub bump: u8,
+    /// Unix timestamp from which the order can no longer be filled, `None` if it never expires.
+    pub expiry: Option<i64>,
+    /// Account that paid the rent, refunded when the expired trade state is closed.
+    pub fee_payer: Pubkey,
+}
+
+impl TradeStateV2 {
+    /// Check the trade state is the order of `wallet` for `token_size` of `token_mint` at `price`.
+    /// Public bids match any `token_account` of the mint.
+    pub fn assert_order(
+        &self,
+        wallet: &Pubkey,
+        auction_house: &Pubkey,
+        token_account: &Pubkey,
+        token_mint: &Pubkey,
+        price: u64,
+        token_size: u64,
+    ) -> Result<()> {
+        if self.wallet != *wallet
+            || self.auction_house != *auction_house
+            || self.token_mint != *token_mint
+            || (self.trade_type == TradeType::Private && self.token_account != *token_account)
+            || self.price != price
+            || self.token_size != token_size
+        {
+            return Err(AuctionHouseError::TradeStateMismatch.into());
+        }
+
+        Ok(())
+    }
+
+    /// Whether the order expired at unix timestamp `now`.
+    pub fn is_expired(&self, now: i64) -> bool {
+        matches!(self.expiry, Some(expiry) if now >= expiry)
+    }
+
+    /// Reject orders that can no longer be filled.
+    pub fn assert_not_expired(&self) -> Result<()> {
+        if self.is_expired(Clock::get()?.unix_timestamp) {
+            return Err(AuctionHouseError::TradeStateExpired.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Check a new or amended order expires in the future, if at all.
+pub fn assert_valid_expiry(expiry: Option<i64>) -> Result<()> {
+    match expiry {
+        Some(expiry) if expiry <= Clock::get()?.unix_timestamp => {
+            Err(AuctionHouseError::InvalidExpiry.into())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Deserialize a v2 trade state, `None` for legacy trade states.
+pub fn load_trade_state_v2(trade_state: &AccountInfo) -> Result<Option<TradeStateV2>> {
+    if trade_state.data_len() != TRADE_STATE_V2_SIZE {
+        return Ok(None);
+    }
+
+    if *trade_state.owner != crate::id() {
+        return Err(AuctionHouseError::TradeStateMismatch.into());
+    }
+
+    let data = trade_state.try_borrow_data()?;
+    Ok(Some(TradeStateV2::try_deserialize(&mut &data[..])?))
+}
+
+/// Bump of a legacy or v2 trade state, zero once it is closed or if it never existed.
+pub fn trade_state_bump(trade_state: &AccountInfo) -> Result<u8> {
+    if trade_state.data_is_empty() || trade_state.lamports() == 0 {
+        return Ok(0);
+    }
+
+    match load_trade_state_v2(trade_state)? {
+        Some(trade_state_v2) => Ok(trade_state_v2.bump),
+        None => Ok(trade_state.try_borrow_data()?[0]),
+    }
+}
+
+/// Expiry of a legacy trade state, stored after its bump when the order was placed with one.
+pub fn legacy_trade_state_expiry(trade_state: &AccountInfo) -> Result<Option<i64>> {
+    if trade_state.data_len() != TRADE_STATE_WITH_EXPIRY_SIZE {
+        return Ok(None);
+    }
+
+    let data = trade_state.try_borrow_data()?;
+    let mut expiry = [0u8; 8];
+    expiry.copy_from_slice(&data[TRADE_STATE_SIZE..TRADE_STATE_WITH_EXPIRY_SIZE]);
+    Ok(Some(i64::from_le_bytes(expiry)))
+}
+
+/// Reject legacy orders past their expiry.
+pub fn assert_legacy_trade_state_not_expired(trade_state: &AccountInfo) -> Result<()> {
+    match legacy_trade_state_expiry(trade_state)? {
+        Some(expiry) if Clock::get()?.unix_timestamp >= expiry => {
+            Err(AuctionHouseError::TradeStateExpired.into())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Size of a new legacy trade state, with room for the expiry if the order has one.
+pub fn legacy_trade_state_size(expiry: Option<i64>) -> usize {
+    match expiry {
+        Some(_) => TRADE_STATE_WITH_EXPIRY_SIZE,
+        None => TRADE_STATE_SIZE,
+    }
+}
+
+/// Write the expiry of a legacy trade state just created with `legacy_trade_state_size`. An open
+/// trade state keeps the expiry it was placed with, so placing it again with another one fails.
+pub fn write_legacy_trade_state_expiry(
+    trade_state: &AccountInfo,
+    created: bool,
+    expiry: Option<i64>,
+) -> Result<()> {
+    if !created {
+        if legacy_trade_state_expiry(trade_state)? != expiry {
+            return Err(AuctionHouseError::TradeStateMismatch.into());
+        }
+
+        return Ok(());
+    }
+
+    if let Some(expiry) = expiry {
+        let mut data = trade_state.try_borrow_mut_data()?;
+        data[TRADE_STATE_SIZE..TRADE_STATE_WITH_EXPIRY_SIZE].copy_from_slice(&expiry.to_le_bytes());
+    }
+
+    Ok(())
+}
+
+/// Create the v2 trade state with `order`, or amend the price, size and expiry of the existing one.
+pub fn write_trade_state_v2<'info>(
+    trade_state: &AccountInfo<'info>,
+    fee_payer: &AccountInfo<'info>,
+    fee_seeds: &[&[u8]],
+    rent: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    trade_state_seeds: &[&[u8]],
+    order: TradeStateV2,
+) -> Result<()> {
+    let order = if trade_state.data_is_empty() {
+        create_or_allocate_account_raw(
+            crate::id(),
+            trade_state,
+            rent,
+            system_program,
+            fee_payer,
+            TRADE_STATE_V2_SIZE,
+            fee_seeds,
+            trade_state_seeds,
+        )?;
+
+        order
+    } else {
+        let mut existing =
+            load_trade_state_v2(trade_state)?.ok_or(AuctionHouseError::TradeStateMismatch)?;
+        existing.price = order.price;
+        existing.token_size = order.token_size;
+        existing.expiry = order.expiry;
+
+        existing
+    };
+
+    let mut data = trade_state.try_borrow_mut_data()?;
+    order.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
+
+/// Accounts for the [`close_expired` handler](auction_house/fn.close_expired.html).
+#[derive(Accounts)]
+pub struct CloseExpired<'info> {
+    /// Anyone closing the expired trade state, paid the bounty.
+    #[account(mut)]
+    pub closer: Signer<'info>,
+
+    /// CHECK: Validated as an expired v2 trade state in close_expired.
+    /// Expired listing or bid.
+    #[account(mut)]
+    pub trade_state: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the fee payer recorded in the trade state.
+    /// Account that paid the trade state rent, refunded all of it but the bounty.
+    #[account(mut)]
+    pub fee_payer: UncheckedAccount<'info>,
+}
+
+/// Closing an expired listing leaves the delegate it approved for `program_as_signer`, or the sale
+/// delegate of a programmable NFT, on the seller's token account. Without its trade state the
+/// listing can't be filled, and the seller may revoke the delegate directly.
+pub fn close_expired(ctx: Context<CloseExpired>) -> Result<()> {
+    let closer = &ctx.accounts.closer;
+    let trade_state = &ctx.accounts.trade_state.to_account_info();
+    let fee_payer = &ctx.accounts.fee_payer.to_account_info();
+
+    let order = load_trade_state_v2(trade_state)?.ok_or(AuctionHouseError::TradeStateMismatch)?;
+    if !order.is_expired(Clock::get()?.unix_timestamp) {
+        return Err(AuctionHouseError::TradeStateNotExpired.into());
+    }
+    assert_keys_equal(fee_payer.key(), order.fee_payer)?;
+
+    let bounty = trade_state
+        .lamports()
+        .checked_mul(CLOSE_EXPIRED_BOUNTY_BPS)
+        .ok_or(AuctionHouseError::NumericalOverflow)?
+        .checked_div(10000)
+        .ok_or(AuctionHouseError::NumericalOverflow)?;
+    **trade_state.lamports.borrow_mut() = trade_state
+        .lamports()
+        .checked_sub(bounty)
+        .ok_or(AuctionHouseError::NumericalOverflow)?;
+    **closer.lamports.borrow_mut() = closer
+        .lamports()
+        .checked_add(bounty)
+        .ok_or(AuctionHouseError::NumericalOverflow)?;
+    close_account(trade_state, fee_payer)?;
+
+    emit!(TradeStateExpired {
+        auction_house: order.auction_house,
+        wallet: order.wallet,
+        token_mint: order.token_mint,
+        trade_state: trade_state.key(),
+        closer: closer.key(),
+        bounty,
+    });
+
+    Ok(())
+}
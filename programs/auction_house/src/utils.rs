@@ -1,24 +1,47 @@
-use crate::{errors::AuctionHouseError, AuctionHouse, PREFIX};
+use crate::{
+    errors::AuctionHouseError, metadata::get_royalties, trade_state::load_trade_state_v2,
+    AuctionHouse, Auctioneer, AuthorityScope, FEE_PAYER, PREFIX, ROYALTY_VAULT, SIGNER, TREASURY,
+};
 
 use anchor_lang::{
     prelude::*,
     solana_program::{
-        program::invoke_signed,
+        program::{invoke, invoke_signed},
         program_memory::{sol_memcmp, sol_memset},
         program_pack::{IsInitialized, Pack},
         pubkey::PUBKEY_BYTES,
         system_instruction,
     },
 };
-use anchor_spl::token::{Mint, Token, TokenAccount};
-use arrayref::array_ref;
-use mpl_token_metadata::state::{Metadata, TokenMetadataAccount};
-use spl_token::{instruction::initialize_account2, state::Account as SplAccount};
+use anchor_spl::token_interface::{Mint, TokenInterface};
+use mpl_token_metadata::{
+    instruction::{
+        builders::{DelegateBuilder, RevokeBuilder, TransferBuilder},
+        DelegateArgs, InstructionBuilder, RevokeArgs, TransferArgs,
+    },
+    state::{Metadata, ProgrammableConfig, TokenMetadataAccount, TokenStandard},
+};
+use spl_token_2022::{
+    extension::{
+        transfer_fee::{
+            instruction::harvest_withheld_tokens_to_mint, TransferFeeAmount, TransferFeeConfig,
+        },
+        BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+    },
+    instruction::{initialize_account2, transfer_checked},
+    state::{Account as TokenAccountState, Mint as MintState},
+};
 use std::{convert::TryInto, slice::Iter};
 
-pub fn assert_is_ata(ata: &AccountInfo, wallet: &Pubkey, mint: &Pubkey) -> Result<SplAccount> {
-    assert_owned_by(ata, &spl_token::id())?;
-    let ata_account: SplAccount = assert_initialized(ata)?;
+pub fn assert_is_ata(
+    ata: &AccountInfo,
+    wallet: &Pubkey,
+    mint: &Pubkey,
+) -> Result<TokenAccountState> {
+    if *ata.owner != spl_token::id() {
+        assert_owned_by(ata, &spl_token_2022::id())?;
+    }
+    let ata_account = unpack_token_account(ata)?;
     assert_keys_equal(ata_account.owner, *wallet)?;
     assert_keys_equal(ata_account.mint, *mint)?;
 
@@ -49,7 +72,7 @@ pub fn make_ata<'a>(
             fee_payer.key,
             wallet.key,
             mint.key,
-            &spl_token::ID,
+            token_program.key,
         ),
         &[
             ata,
@@ -67,24 +90,139 @@ pub fn make_ata<'a>(
     Ok(())
 }
 
-pub fn assert_metadata_valid<'a>(
-    metadata: &UncheckedAccount,
-    token_account: &anchor_lang::prelude::Account<'a, TokenAccount>,
+/// Unpack an SPL Token or Token-2022 account, ignoring any extensions it carries.
+pub fn unpack_token_account(account_info: &AccountInfo) -> Result<TokenAccountState> {
+    let data = account_info.try_borrow_data()?;
+    let account = StateWithExtensions::<TokenAccountState>::unpack(&data)
+        .map_err(|_| AuctionHouseError::UninitializedAccount)?;
+
+    Ok(account.base)
+}
+
+/// Size of a token account for `mint`, including the extensions Token-2022 requires for it.
+pub fn get_token_account_len(mint: &AccountInfo) -> Result<usize> {
+    if *mint.owner != spl_token_2022::id() {
+        return Ok(TokenAccountState::LEN);
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<MintState>::unpack(&data)?;
+    let account_extensions =
+        ExtensionType::get_required_init_account_extensions(&mint_state.get_extension_types()?);
+
+    Ok(ExtensionType::get_account_len::<TokenAccountState>(
+        &account_extensions,
+    ))
+}
+
+/// Amount that has to be sent so `amount` arrives after the mint's transfer fee, if it has one.
+pub fn get_amount_with_transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    if *mint.owner != spl_token_2022::id() {
+        return Ok(amount);
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<MintState>::unpack(&data)?;
+    match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(transfer_fee_config) => Ok(transfer_fee_config
+            .get_epoch_fee(Clock::get()?.epoch)
+            .calculate_pre_fee_amount(amount)
+            .ok_or(AuctionHouseError::NumericalOverflow)?),
+        Err(_) => Ok(amount),
+    }
+}
+
+/// Transfer fee the mint withholds when `amount` is sent, or zero if it has none.
+pub fn get_transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    if *mint.owner != spl_token_2022::id() {
+        return Ok(0);
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<MintState>::unpack(&data)?;
+    match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(transfer_fee_config) => Ok(transfer_fee_config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(AuctionHouseError::NumericalOverflow)?),
+        Err(_) => Ok(0),
+    }
+}
+
+/// Move transfer fees withheld in a Token-2022 `account` to its mint, as accounts holding withheld
+/// fees cannot be closed. Harvesting needs no signature.
+pub fn harvest_withheld_fees<'a>(
+    token_program: &AccountInfo<'a>,
+    mint: &AccountInfo<'a>,
+    account: &AccountInfo<'a>,
 ) -> Result<()> {
-    assert_derivation(
-        &mpl_token_metadata::id(),
-        &metadata.to_account_info(),
+    if *account.owner != spl_token_2022::id() {
+        return Ok(());
+    }
+
+    let withheld_amount = {
+        let data = account.try_borrow_data()?;
+        let account_state = StateWithExtensions::<TokenAccountState>::unpack(&data)?;
+        account_state
+            .get_extension::<TransferFeeAmount>()
+            .map(|transfer_fee_amount| u64::from(transfer_fee_amount.withheld_amount))
+            .unwrap_or(0)
+    };
+    if withheld_amount == 0 {
+        return Ok(());
+    }
+
+    invoke(
+        &harvest_withheld_tokens_to_mint(token_program.key, mint.key, &[account.key])?,
+        &[mint.clone(), account.clone(), token_program.clone()],
+    )?;
+
+    Ok(())
+}
+
+/// Transfer `amount` of `mint` with `transfer_checked`, which both the SPL Token and Token-2022
+/// programs support. Any transfer fee is withheld from what `destination` receives, so the amount
+/// it actually receives is returned.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_tokens_checked<'a>(
+    token_program: &AccountInfo<'a>,
+    source: &AccountInfo<'a>,
+    mint: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<u64> {
+    let decimals = {
+        let data = mint.try_borrow_data()?;
+        StateWithExtensions::<MintState>::unpack(&data)?
+            .base
+            .decimals
+    };
+
+    invoke_signed(
+        &transfer_checked(
+            token_program.key,
+            source.key,
+            mint.key,
+            destination.key,
+            authority.key,
+            &[],
+            amount,
+            decimals,
+        )?,
         &[
-            mpl_token_metadata::state::PREFIX.as_bytes(),
-            mpl_token_metadata::id().as_ref(),
-            token_account.mint.as_ref(),
+            source.clone(),
+            mint.clone(),
+            destination.clone(),
+            authority.clone(),
+            token_program.clone(),
         ],
+        signer_seeds,
     )?;
 
-    if metadata.data_is_empty() {
-        return Err(AuctionHouseError::MetadataDoesntExist.into());
-    }
-    Ok(())
+    Ok(amount
+        .checked_sub(get_transfer_fee(mint, amount)?)
+        .ok_or(AuctionHouseError::NumericalOverflow)?)
 }
 
 pub fn get_fee_payer<'a, 'b>(
@@ -107,6 +245,45 @@ pub fn get_fee_payer<'a, 'b>(
     Ok((fee_payer, seeds))
 }
 
+/// Reject new sells, bids and sales while the Auction House is paused.
+pub fn assert_not_paused(auction_house: &AuctionHouse) -> Result<()> {
+    if auction_house.is_paused {
+        return Err(AuctionHouseError::AuctionHousePaused.into());
+    }
+
+    Ok(())
+}
+
+pub fn assert_valid_auctioneer(
+    auction_house: &Account<AuctionHouse>,
+    auctioneer_authority: &Pubkey,
+    auctioneer: &Account<Auctioneer>,
+) -> Result<()> {
+    if !auction_house.has_auctioneer {
+        return Err(AuctionHouseError::NoAuctioneerProgramSet.into());
+    }
+
+    assert_keys_equal(auctioneer.auction_house, auction_house.key())?;
+    assert_keys_equal(auctioneer.auctioneer_authority, *auctioneer_authority)?;
+
+    Ok(())
+}
+
+pub fn assert_valid_auctioneer_and_scope(
+    auction_house: &Account<AuctionHouse>,
+    auctioneer_authority: &Pubkey,
+    auctioneer: &Account<Auctioneer>,
+    scope: AuthorityScope,
+) -> Result<()> {
+    assert_valid_auctioneer(auction_house, auctioneer_authority, auctioneer)?;
+
+    if !auctioneer.has_scope(scope) {
+        return Err(AuctionHouseError::MissingAuctioneerScope.into());
+    }
+
+    Ok(())
+}
+
 pub fn assert_keys_equal(key1: Pubkey, key2: Pubkey) -> Result<()> {
     if sol_memcmp(key1.as_ref(), key2.as_ref(), PUBKEY_BYTES) != 0 {
         err!(AuctionHouseError::PublicKeyMismatch)
@@ -132,41 +309,36 @@ pub fn assert_owned_by(account: &AccountInfo, owner: &Pubkey) -> Result<()> {
     }
 }
 
+/// Pay the Auction House fee on `size` to its treasury. Returns the fee taken out of the escrow and
+/// the amount the treasury received after any transfer fee.
 #[allow(clippy::too_many_arguments)]
 pub fn pay_auction_house_fees<'a>(
     auction_house: &anchor_lang::prelude::Account<'a, AuctionHouse>,
     auction_house_treasury: &AccountInfo<'a>,
     escrow_payment_account: &AccountInfo<'a>,
+    treasury_mint: &AccountInfo<'a>,
     token_program: &AccountInfo<'a>,
     system_program: &AccountInfo<'a>,
     signer_seeds: &[&[u8]],
     size: u64,
     is_native: bool,
-) -> Result<u64> {
+) -> Result<(u64, u64)> {
     let fees = auction_house.seller_fee_basis_points;
     let total_fee = (fees as u128)
         .checked_mul(size as u128)
         .ok_or(AuctionHouseError::NumericalOverflow)?
         .checked_div(10000)
         .ok_or(AuctionHouseError::NumericalOverflow)? as u64;
-    if !is_native {
-        invoke_signed(
-            &spl_token::instruction::transfer(
-                token_program.key,
-                escrow_payment_account.key,
-                auction_house_treasury.key,
-                &auction_house.key(),
-                &[],
-                total_fee,
-            )?,
-            &[
-                escrow_payment_account.clone(),
-                auction_house_treasury.clone(),
-                token_program.clone(),
-                auction_house.to_account_info(),
-            ],
+    let fee_received = if !is_native {
+        transfer_tokens_checked(
+            token_program,
+            escrow_payment_account,
+            treasury_mint,
+            auction_house_treasury,
+            &auction_house.to_account_info(),
+            total_fee,
             &[signer_seeds],
-        )?;
+        )?
     } else {
         invoke_signed(
             &system_instruction::transfer(
@@ -181,16 +353,17 @@ pub fn pay_auction_house_fees<'a>(
             ],
             &[signer_seeds],
         )?;
-    }
-    Ok(total_fee)
+        total_fee
+    };
+    Ok((total_fee, fee_received))
 }
 
 pub fn create_program_token_account_if_not_present<'a>(
     payment_account: &UncheckedAccount<'a>,
     system_program: &Program<'a, System>,
     fee_payer: &AccountInfo<'a>,
-    token_program: &Program<'a, Token>,
-    treasury_mint: &anchor_lang::prelude::Account<'a, Mint>,
+    token_program: &Interface<'a, TokenInterface>,
+    treasury_mint: &InterfaceAccount<'a, Mint>,
     owner: &AccountInfo<'a>,
     rent: &Sysvar<'a, Rent>,
     signer_seeds: &[&[u8]],
@@ -204,7 +377,7 @@ pub fn create_program_token_account_if_not_present<'a>(
             &rent.to_account_info(),
             system_program,
             fee_payer,
-            spl_token::state::Account::LEN,
+            get_token_account_len(&treasury_mint.to_account_info())?,
             fee_seeds,
             signer_seeds,
         )?;
@@ -229,10 +402,22 @@ pub fn create_program_token_account_if_not_present<'a>(
     Ok(())
 }
 
+/// Pay each creator their share of the royalties out of the escrow.
+///
+/// Expects a creator and their treasury mint token account per creator for SPL treasuries, or a
+/// creator and their royalty vault per creator for native ones. A native creator whose balance
+/// would stay below the rent-exempt minimum is credited to their royalty vault instead, to be
+/// withdrawn later with `claim_royalties`. The Auction House fee account pays the rent of a vault
+/// the first time it is credited.
+///
+/// Returns what is left of `size` for the seller, and the royalties creators actually received
+/// after any transfer fee.
 #[allow(clippy::too_many_arguments)]
 pub fn pay_creator_fees<'a>(
     remaining_accounts: &mut Iter<AccountInfo<'a>>,
     metadata_info: &AccountInfo<'a>,
+    auction_house: &Account<'a, AuctionHouse>,
+    auction_house_fee_account: &AccountInfo<'a>,
     escrow_payment_account: &AccountInfo<'a>,
     payment_account_owner: &AccountInfo<'a>,
     fee_payer: &AccountInfo<'a>,
@@ -245,19 +430,20 @@ pub fn pay_creator_fees<'a>(
     fee_payer_seeds: &[&[u8]],
     size: u64,
     is_native: bool,
-) -> Result<u64> {
-    let metadata = Metadata::from_account_info(metadata_info)?;
-    let fees = metadata.data.seller_fee_basis_points;
+) -> Result<(u64, u64)> {
+    let royalties = get_royalties(metadata_info)?;
+    let fees = royalties.seller_fee_basis_points;
     let total_fee = (fees as u128)
         .checked_mul(size as u128)
         .ok_or(AuctionHouseError::NumericalOverflow)?
         .checked_div(10000)
         .ok_or(AuctionHouseError::NumericalOverflow)? as u64;
     let mut remaining_fee = total_fee;
+    let mut royalties_received: u64 = 0;
     let remaining_size = size
         .checked_sub(total_fee)
         .ok_or(AuctionHouseError::NumericalOverflow)?;
-    match metadata.data.creators {
+    match royalties.creators {
         Some(creators) => {
             for creator in creators {
                 let pct = creator.share as u128;
@@ -267,19 +453,6 @@ pub fn pay_creator_fees<'a>(
                         .checked_div(100)
                         .ok_or(AuctionHouseError::NumericalOverflow)? as u64;
                 let current_creator_info = next_account_info(remaining_accounts)?;
-                let creator_rent_minimum =
-                    Rent::get()?.minimum_balance(current_creator_info.data.borrow().len());
-                if is_native
-                    && ((creator_fee + **current_creator_info.lamports.borrow())
-                        < creator_rent_minimum)
-                {
-                    msg!(
-                        "cannot pay creator {} {} lamports since balance violates rent exempt minimum",
-                        current_creator_info.key,
-                        creator_fee
-                    );
-                    continue;
-                }
 
                 remaining_fee = remaining_fee
                     .checked_sub(creator_fee)
@@ -306,25 +479,56 @@ pub fn pay_creator_fees<'a>(
                         &treasury_mint.key(),
                     )?;
                     if creator_fee > 0 {
-                        invoke_signed(
-                            &spl_token::instruction::transfer(
-                                token_program.key,
-                                escrow_payment_account.key,
-                                current_creator_token_account_info.key,
-                                payment_account_owner.key,
-                                &[],
-                                creator_fee,
-                            )?,
-                            &[
-                                escrow_payment_account.clone(),
-                                current_creator_token_account_info.clone(),
-                                token_program.clone(),
-                                payment_account_owner.clone(),
-                            ],
+                        let creator_fee_received = transfer_tokens_checked(
+                            token_program,
+                            escrow_payment_account,
+                            treasury_mint,
+                            current_creator_token_account_info,
+                            payment_account_owner,
+                            creator_fee,
                             &[signer_seeds],
                         )?;
+                        royalties_received = royalties_received
+                            .checked_add(creator_fee_received)
+                            .ok_or(AuctionHouseError::NumericalOverflow)?;
+                    }
+                } else {
+                    let royalty_vault_info = next_account_info(remaining_accounts)?;
+                    assert_derivation(
+                        &crate::id(),
+                        royalty_vault_info,
+                        &[
+                            PREFIX.as_bytes(),
+                            auction_house.key().as_ref(),
+                            ROYALTY_VAULT.as_bytes(),
+                            current_creator_info.key.as_ref(),
+                        ],
+                    )?;
+                    if creator_fee == 0 {
+                        continue;
                     }
-                } else if creator_fee > 0 {
+                    royalties_received = royalties_received
+                        .checked_add(creator_fee)
+                        .ok_or(AuctionHouseError::NumericalOverflow)?;
+
+                    let creator_rent_minimum =
+                        Rent::get()?.minimum_balance(current_creator_info.data_len());
+                    let creator_balance = creator_fee
+                        .checked_add(current_creator_info.lamports())
+                        .ok_or(AuctionHouseError::NumericalOverflow)?;
+                    if creator_balance < creator_rent_minimum {
+                        credit_royalty_vault(
+                            auction_house,
+                            royalty_vault_info,
+                            escrow_payment_account,
+                            auction_house_fee_account,
+                            system_program,
+                            signer_seeds,
+                            creator_fee,
+                        )?;
+                        continue;
+                    }
+
                     invoke_signed(
                         &system_instruction::transfer(
                             escrow_payment_account.key,
@@ -346,30 +550,60 @@ pub fn pay_creator_fees<'a>(
         }
     }
     // Any dust is returned to the party posting the NFT
-    Ok(remaining_size
-        .checked_add(remaining_fee)
-        .ok_or(AuctionHouseError::NumericalOverflow)?)
+    Ok((
+        remaining_size
+            .checked_add(remaining_fee)
+            .ok_or(AuctionHouseError::NumericalOverflow)?,
+        royalties_received,
+    ))
+}
+
+/// Credit `amount` of unpaid native royalties to a creator's royalty vault. The Auction House fee
+/// account tops the vault up to the rent-exempt minimum the first time it is used, so neither the
+/// trading parties nor the creator's royalties pay for it.
+fn credit_royalty_vault<'a>(
+    auction_house: &Account<'a, AuctionHouse>,
+    royalty_vault: &AccountInfo<'a>,
+    escrow_payment_account: &AccountInfo<'a>,
+    auction_house_fee_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    signer_seeds: &[&[u8]],
+    amount: u64,
+) -> Result<()> {
+    let rent_shortfall = Rent::get()?
+        .minimum_balance(royalty_vault.data_len())
+        .saturating_sub(royalty_vault.lamports());
+    if rent_shortfall > 0 {
+        pay_from_fee_account(
+            auction_house,
+            auction_house_fee_account,
+            royalty_vault,
+            system_program,
+            rent_shortfall,
+        )?;
+    }
+
+    invoke_signed(
+        &system_instruction::transfer(escrow_payment_account.key, royalty_vault.key, amount),
+        &[
+            escrow_payment_account.clone(),
+            royalty_vault.clone(),
+            system_program.clone(),
+        ],
+        &[signer_seeds],
+    )?;
+
+    Ok(())
 }
 
-/// Cheap method to just grab mint Pubkey from token account, instead of deserializing entire thing
+/// Grab the mint Pubkey from an SPL Token or Token-2022 token account.
 pub fn get_mint_from_token_account(token_account_info: &AccountInfo) -> Result<Pubkey> {
-    // TokeAccount layout:   mint(32), owner(32), ...
-    let data = token_account_info.try_borrow_data()?;
-    let mint_data = array_ref![data, 0, 32];
-    Ok(Pubkey::new_from_array(*mint_data))
+    Ok(unpack_token_account(token_account_info)?.mint)
 }
 
-/// Cheap method to just grab delegate Pubkey from token account, instead of deserializing entire thing
+/// Grab the delegate Pubkey from an SPL Token or Token-2022 token account.
 pub fn get_delegate_from_token_account(token_account_info: &AccountInfo) -> Result<Option<Pubkey>> {
-    // TokeAccount layout:   mint(32), owner(32), ...
-    let data = token_account_info.try_borrow_data()?;
-    let key_data = array_ref![data, 76, 32];
-    let coption_data = u32::from_le_bytes(*array_ref![data, 72, 4]);
-    if coption_data == 0 {
-        Ok(None)
-    } else {
-        Ok(Some(Pubkey::new_from_array(*key_data)))
-    }
+    Ok(unpack_token_account(token_account_info)?.delegate.into())
 }
 
 /// Create account almost from scratch, lifted from
@@ -453,6 +687,19 @@ pub fn assert_valid_trade_state(
     token_holder: &Pubkey,
     ts_bump: u8,
 ) -> Result<u8> {
+    // V2 trade states hold their order, so no derivation is needed.
+    if let Some(trade_state_v2) = load_trade_state_v2(trade_state)? {
+        trade_state_v2.assert_order(
+            wallet,
+            &auction_house.key(),
+            token_holder,
+            mint,
+            buyer_price,
+            token_size,
+        )?;
+        return Ok(trade_state_v2.bump);
+    }
+
     let ah_pubkey = &auction_house.key();
     let mint_bytes = mint.as_ref();
     let treasury_mint_bytes = auction_house.treasury_mint.as_ref();
@@ -543,3 +790,359 @@ pub fn close_account<'a>(
 
     Ok(())
 }
+
+/// Returns true if the metadata account describes a programmable NFT.
+pub fn is_programmable(metadata_info: &AccountInfo) -> Result<bool> {
+    if metadata_info.data_is_empty() || *metadata_info.owner != mpl_token_metadata::id() {
+        return Ok(false);
+    }
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    Ok(metadata.token_standard == Some(TokenStandard::ProgrammableNonFungible))
+}
+
+/// Returns the authorization rule set of a programmable NFT, checking it against the passed account.
+fn get_rule_set(metadata_info: &AccountInfo, auth_rules: &AccountInfo) -> Result<Option<Pubkey>> {
+    let metadata = Metadata::from_account_info(metadata_info)?;
+
+    match metadata.programmable_config {
+        Some(ProgrammableConfig::V1 {
+            rule_set: Some(rule_set),
+        }) => {
+            assert_keys_equal(auth_rules.key(), rule_set)?;
+            Ok(Some(rule_set))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Approves `program_as_signer` as the sale delegate of a programmable NFT through Token Metadata.
+///
+/// Expects the remaining accounts: metadata program, token mint, master edition, token record,
+/// authorization rules program, authorization rules and the instructions sysvar.
+#[allow(clippy::too_many_arguments)]
+pub fn delegate_programmable<'a>(
+    remaining_accounts: &mut Iter<AccountInfo<'a>>,
+    metadata_info: &AccountInfo<'a>,
+    token_account: &AccountInfo<'a>,
+    wallet: &AccountInfo<'a>,
+    program_as_signer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    amount: u64,
+) -> Result<()> {
+    let metadata_program = next_account_info(remaining_accounts)?;
+    let token_mint = next_account_info(remaining_accounts)?;
+    let edition = next_account_info(remaining_accounts)?;
+    let token_record = next_account_info(remaining_accounts)?;
+    let auth_rules_program = next_account_info(remaining_accounts)?;
+    let auth_rules = next_account_info(remaining_accounts)?;
+    let sysvar_instructions = next_account_info(remaining_accounts)?;
+
+    assert_keys_equal(metadata_program.key(), mpl_token_metadata::id())?;
+
+    let mut builder = DelegateBuilder::new();
+    builder
+        .delegate(program_as_signer.key())
+        .metadata(metadata_info.key())
+        .master_edition(edition.key())
+        .token_record(token_record.key())
+        .mint(token_mint.key())
+        .token(token_account.key())
+        .authority(wallet.key())
+        .payer(wallet.key())
+        .system_program(system_program.key())
+        .sysvar_instructions(sysvar_instructions.key())
+        .spl_token_program(token_program.key());
+
+    if let Some(rule_set) = get_rule_set(metadata_info, auth_rules)? {
+        builder
+            .authorization_rules_program(auth_rules_program.key())
+            .authorization_rules(rule_set);
+    }
+
+    let delegate = builder
+        .build(DelegateArgs::SaleV1 {
+            amount,
+            authorization_data: None,
+        })
+        .map_err(|_| AuctionHouseError::InstructionBuilderFailed)?;
+
+    invoke(
+        &delegate.instruction(),
+        &[
+            program_as_signer.clone(),
+            metadata_info.clone(),
+            edition.clone(),
+            token_record.clone(),
+            token_mint.clone(),
+            token_account.clone(),
+            wallet.clone(),
+            system_program.clone(),
+            sysvar_instructions.clone(),
+            token_program.clone(),
+            auth_rules_program.clone(),
+            auth_rules.clone(),
+            metadata_program.clone(),
+        ],
+    )?;
+
+    Ok(())
+}
+
+/// Revokes the `program_as_signer` sale delegate of a programmable NFT through Token Metadata.
+///
+/// Expects the remaining accounts: metadata program, metadata, program as signer, master edition,
+/// token record, system program, authorization rules program, authorization rules and the
+/// instructions sysvar.
+pub fn revoke_programmable<'a>(
+    remaining_accounts: &mut Iter<AccountInfo<'a>>,
+    token_account: &AccountInfo<'a>,
+    token_mint: &AccountInfo<'a>,
+    wallet: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+) -> Result<()> {
+    let metadata_program = next_account_info(remaining_accounts)?;
+    let metadata_info = next_account_info(remaining_accounts)?;
+    let program_as_signer = next_account_info(remaining_accounts)?;
+    let edition = next_account_info(remaining_accounts)?;
+    let token_record = next_account_info(remaining_accounts)?;
+    let system_program = next_account_info(remaining_accounts)?;
+    let auth_rules_program = next_account_info(remaining_accounts)?;
+    let auth_rules = next_account_info(remaining_accounts)?;
+    let sysvar_instructions = next_account_info(remaining_accounts)?;
+
+    assert_keys_equal(metadata_program.key(), mpl_token_metadata::id())?;
+    assert_derivation(
+        &crate::id(),
+        program_as_signer,
+        &[PREFIX.as_bytes(), SIGNER.as_bytes()],
+    )?;
+
+    let mut builder = RevokeBuilder::new();
+    builder
+        .delegate(program_as_signer.key())
+        .metadata(metadata_info.key())
+        .master_edition(edition.key())
+        .token_record(token_record.key())
+        .mint(token_mint.key())
+        .token(token_account.key())
+        .authority(wallet.key())
+        .payer(wallet.key())
+        .system_program(system_program.key())
+        .sysvar_instructions(sysvar_instructions.key())
+        .spl_token_program(token_program.key());
+
+    if let Some(rule_set) = get_rule_set(metadata_info, auth_rules)? {
+        builder
+            .authorization_rules_program(auth_rules_program.key())
+            .authorization_rules(rule_set);
+    }
+
+    let revoke = builder
+        .build(RevokeArgs::SaleV1)
+        .map_err(|_| AuctionHouseError::InstructionBuilderFailed)?;
+
+    invoke(
+        &revoke.instruction(),
+        &[
+            program_as_signer.clone(),
+            metadata_info.clone(),
+            edition.clone(),
+            token_record.clone(),
+            token_mint.clone(),
+            token_account.clone(),
+            wallet.clone(),
+            system_program.clone(),
+            sysvar_instructions.clone(),
+            token_program.clone(),
+            auth_rules_program.clone(),
+            auth_rules.clone(),
+            metadata_program.clone(),
+        ],
+    )?;
+
+    Ok(())
+}
+
+/// Transfers a programmable NFT to the buyer through Token Metadata, signing as the sale delegate.
+///
+/// Expects the remaining accounts: metadata program, master edition, seller token record, buyer
+/// token record, authorization rules program, authorization rules and the instructions sysvar.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_programmable<'a>(
+    remaining_accounts: &mut Iter<AccountInfo<'a>>,
+    metadata_info: &AccountInfo<'a>,
+    token_account: &AccountInfo<'a>,
+    seller: &AccountInfo<'a>,
+    buyer_receipt_token_account: &AccountInfo<'a>,
+    buyer: &AccountInfo<'a>,
+    token_mint: &AccountInfo<'a>,
+    program_as_signer: &AccountInfo<'a>,
+    fee_payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    ata_program: &AccountInfo<'a>,
+    program_as_signer_seeds: &[&[u8]],
+    fee_payer_seeds: &[&[u8]],
+    amount: u64,
+) -> Result<()> {
+    let metadata_program = next_account_info(remaining_accounts)?;
+    let edition = next_account_info(remaining_accounts)?;
+    let owner_token_record = next_account_info(remaining_accounts)?;
+    let destination_token_record = next_account_info(remaining_accounts)?;
+    let auth_rules_program = next_account_info(remaining_accounts)?;
+    let auth_rules = next_account_info(remaining_accounts)?;
+    let sysvar_instructions = next_account_info(remaining_accounts)?;
+
+    assert_keys_equal(metadata_program.key(), mpl_token_metadata::id())?;
+
+    let mut builder = TransferBuilder::new();
+    builder
+        .token(token_account.key())
+        .token_owner(seller.key())
+        .destination(buyer_receipt_token_account.key())
+        .destination_owner(buyer.key())
+        .mint(token_mint.key())
+        .metadata(metadata_info.key())
+        .edition(edition.key())
+        .owner_token_record(owner_token_record.key())
+        .destination_token_record(destination_token_record.key())
+        .authority(program_as_signer.key())
+        .payer(fee_payer.key())
+        .system_program(system_program.key())
+        .sysvar_instructions(sysvar_instructions.key())
+        .spl_token_program(token_program.key())
+        .spl_ata_program(ata_program.key());
+
+    if let Some(rule_set) = get_rule_set(metadata_info, auth_rules)? {
+        builder
+            .authorization_rules_program(auth_rules_program.key())
+            .authorization_rules(rule_set);
+    }
+
+    let transfer = builder
+        .build(TransferArgs::V1 {
+            amount,
+            authorization_data: None,
+        })
+        .map_err(|_| AuctionHouseError::InstructionBuilderFailed)?;
+
+    let account_infos = [
+        token_account.clone(),
+        seller.clone(),
+        buyer_receipt_token_account.clone(),
+        buyer.clone(),
+        token_mint.clone(),
+        metadata_info.clone(),
+        edition.clone(),
+        owner_token_record.clone(),
+        destination_token_record.clone(),
+        program_as_signer.clone(),
+        fee_payer.clone(),
+        system_program.clone(),
+        sysvar_instructions.clone(),
+        token_program.clone(),
+        ata_program.clone(),
+        auth_rules_program.clone(),
+        auth_rules.clone(),
+        metadata_program.clone(),
+    ];
+
+    if fee_payer_seeds.is_empty() {
+        invoke_signed(
+            &transfer.instruction(),
+            &account_infos,
+            &[program_as_signer_seeds],
+        )?;
+    } else {
+        invoke_signed(
+            &transfer.instruction(),
+            &account_infos,
+            &[program_as_signer_seeds, fee_payer_seeds],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Transfer `amount` lamports from the Auction House fee account to `destination`.
+pub fn pay_from_fee_account<'a>(
+    auction_house: &Account<'a, AuctionHouse>,
+    auction_house_fee_account: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    amount: u64,
+) -> Result<()> {
+    let auction_house_key = auction_house.key();
+    let seeds = [
+        PREFIX.as_bytes(),
+        auction_house_key.as_ref(),
+        FEE_PAYER.as_bytes(),
+        &[auction_house.fee_payer_bump],
+    ];
+
+    invoke_signed(
+        &system_instruction::transfer(auction_house_fee_account.key, destination.key, amount),
+        &[
+            auction_house_fee_account.clone(),
+            destination.clone(),
+            system_program.clone(),
+        ],
+        &[&seeds],
+    )?;
+
+    Ok(())
+}
+
+/// Transfer `amount` of the treasury mint from the Auction House treasury to `destination`.
+pub fn pay_from_treasury<'a>(
+    auction_house: &Account<'a, AuctionHouse>,
+    treasury_mint: &AccountInfo<'a>,
+    auction_house_treasury: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    amount: u64,
+) -> Result<()> {
+    let is_native = treasury_mint.key() == spl_token::native_mint::id();
+
+    if !is_native {
+        let auction_house_seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref(),
+            &[auction_house.bump],
+        ];
+
+        transfer_tokens_checked(
+            token_program,
+            auction_house_treasury,
+            treasury_mint,
+            destination,
+            &auction_house.to_account_info(),
+            amount,
+            &[&auction_house_seeds],
+        )?;
+    } else {
+        let auction_house_key = auction_house.key();
+        let auction_house_treasury_seeds = [
+            PREFIX.as_bytes(),
+            auction_house_key.as_ref(),
+            TREASURY.as_bytes(),
+            &[auction_house.treasury_bump],
+        ];
+
+        invoke_signed(
+            &system_instruction::transfer(auction_house_treasury.key, destination.key, amount),
+            &[
+                auction_house_treasury.clone(),
+                destination.clone(),
+                system_program.clone(),
+            ],
+            &[&auction_house_treasury_seeds],
+        )?;
+    }
+
+    Ok(())
+}
@@ -1,6 +1,105 @@
 use anchor_lang::{prelude::*, AnchorDeserialize};
+use anchor_spl::token_interface::{Mint, TokenInterface};
+use spl_token_2022::instruction::close_account as close_token_account;
 
-use crate::{constants::*, errors::*, utils::*, AuctionHouse, *};
+use crate::{constants::*, errors::*, escrow_lock::*, utils::*, AuctionHouse, *};
+
+#[derive(Accounts, Clone)]
+#[instruction(escrow_payment_bump: u8)]
+pub struct Withdraw<'info> {
+    /// CHECK: Validated in withdraw_logic.
+    /// User wallet account.
+    pub wallet: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in withdraw_logic.
+    /// SPL token account or native SOL account to transfer funds to. If the account is a native SOL account, this is the same as the wallet address.
+    #[account(mut)]
+    pub receipt_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_payment_account: UncheckedAccount<'info>,
+
+    /// Auction House instance treasury mint account.
+    pub treasury_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: Validated in withdraw_logic.
+    /// Auction House instance authority account.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump=auction_house.bump,
+        has_one=authority,
+        has_one=treasury_mint,
+        has_one=auction_house_fee_account
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Auction House instance fee account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            FEE_PAYER.as_bytes()
+        ],
+        bump=auction_house.fee_payer_bump
+    )]
+    pub auction_house_fee_account: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: Account seeds checked in constraint, may not exist if no auctioneer locked the wallet's funds.
+    /// Escrow lock PDA account of the wallet, holding back the funds of its highest auction bids.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            ESCROW_LOCK.as_bytes(),
+            wallet.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_lock: UncheckedAccount<'info>,
+}
+
+impl<'info> From<AuctioneerWithdraw<'info>> for Withdraw<'info> {
+    fn from(a: AuctioneerWithdraw<'info>) -> Withdraw<'info> {
+        Withdraw {
+            wallet: a.wallet,
+            receipt_account: a.receipt_account,
+            escrow_payment_account: a.escrow_payment_account,
+            treasury_mint: a.treasury_mint,
+            authority: a.authority,
+            auction_house: a.auction_house,
+            auction_house_fee_account: a.auction_house_fee_account,
+            token_program: a.token_program,
+            system_program: a.system_program,
+            associated_token_program: a.associated_token_program,
+            rent: a.rent,
+            escrow_lock: a.escrow_lock,
+        }
+    }
+}
 
 #[derive(Accounts, Clone)]
 #[instruction(escrow_payment_bump: u8)]
@@ -28,7 +127,7 @@ pub struct AuctioneerWithdraw<'info> {
     pub escrow_payment_account: UncheckedAccount<'info>,
 
     /// Auction House instance treasury mint account.
-    pub treasury_mint: Box<Account<'info, Mint>>,
+    pub treasury_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// CHECK: Validated in withdraw_logic.
     /// Auction House instance authority account.
@@ -76,10 +175,200 @@ pub struct AuctioneerWithdraw<'info> {
         bump = auctioneer.bump
     )]
     pub auctioneer: Account<'info, Auctioneer>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: Account seeds checked in constraint, may not exist if no auctioneer locked the wallet's funds.
+    /// Escrow lock PDA account of the wallet, holding back the funds of its highest auction bids.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            ESCROW_LOCK.as_bytes(),
+            wallet.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_lock: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts, Clone)]
+#[instruction(escrow_payment_bump: u8)]
+pub struct CloseEscrowAccount<'info> {
+    /// User wallet account, receiving the rent of an SPL escrow.
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    /// CHECK: Validated in close_escrow_account.
+    /// SPL token account to transfer the remaining tokens to, the wallet's associated token account. If the treasury is native SOL, this is the same as the wallet address.
+    #[account(mut)]
+    pub receipt_account: UncheckedAccount<'info>,
+
+    /// CHECK: Account seeds checked in constraint.
+    /// Buyer escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref()
+        ],
+        bump = escrow_payment_bump
+    )]
+    pub escrow_payment_account: UncheckedAccount<'info>,
+
+    /// Auction House instance treasury mint account. Writable so Token-2022 transfer fees withheld in the escrow can be harvested to it.
+    #[account(mut)]
+    pub treasury_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump = auction_house.bump,
+        has_one = treasury_mint
+    )]
+    pub auction_house: Account<'info, AuctionHouse>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: Account seeds checked in constraint, may not exist if no auctioneer locked the wallet's funds.
+    /// Escrow lock PDA account of the wallet, holding back the funds of its highest auction bids.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            ESCROW_LOCK.as_bytes(),
+            wallet.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_lock: UncheckedAccount<'info>,
+}
+
+impl<'info> From<AuctioneerCloseEscrowAccount<'info>> for CloseEscrowAccount<'info> {
+    fn from(a: AuctioneerCloseEscrowAccount<'info>) -> CloseEscrowAccount<'info> {
+        CloseEscrowAccount {
+            wallet: a.wallet,
+            receipt_account: a.receipt_account,
+            escrow_payment_account: a.escrow_payment_account,
+            treasury_mint: a.treasury_mint,
+            auction_house: a.auction_house,
+            token_program: a.token_program,
+            system_program: a.system_program,
+            associated_token_program: a.associated_token_program,
+            rent: a.rent,
+            escrow_lock: a.escrow_lock,
+        }
+    }
+}
+
+#[derive(Accounts, Clone)]
+#[instruction(escrow_payment_bump: u8)]
+pub struct AuctioneerCloseEscrowAccount<'info> {
+    /// User wallet account, receiving the rent of an SPL escrow.
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    /// CHECK: Validated in close_escrow_account_logic.
+    /// SPL token account to transfer the remaining tokens to, the wallet's associated token account. If the treasury is native SOL, this is the same as the wallet address.
+    #[account(mut)]
+    pub receipt_account: UncheckedAccount<'info>,
+
+    /// CHECK: Account seeds checked in constraint.
+    /// Buyer escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref()
+        ],
+        bump = escrow_payment_bump
+    )]
+    pub escrow_payment_account: UncheckedAccount<'info>,
+
+    /// Auction House instance treasury mint account. Writable so Token-2022 transfer fees withheld in the escrow can be harvested to it.
+    #[account(mut)]
+    pub treasury_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: Validated in auctioneer_close_escrow_account.
+    /// The auctioneer authority - typically a PDA of the Auctioneer program running this action.
+    pub auctioneer_authority: Signer<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump = auction_house.bump,
+        has_one = treasury_mint
+    )]
+    pub auction_house: Account<'info, AuctionHouse>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// The auctioneer PDA owned by Auction House.
+    #[account(
+        seeds = [
+            AUCTIONEER.as_bytes(),
+            auction_house.key().as_ref(),
+            auctioneer_authority.key().as_ref()
+        ],
+        bump = auctioneer.bump
+    )]
+    pub auctioneer: Account<'info, Auctioneer>,
+
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: Account seeds checked in constraint, may not exist if no auctioneer locked the wallet's funds.
+    /// Escrow lock PDA account of the wallet, holding back the funds of its highest auction bids.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            ESCROW_LOCK.as_bytes(),
+            wallet.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_lock: UncheckedAccount<'info>,
+}
+
+/// Withdraw `amount` from the escrow payment account for your specific wallet.
+pub fn withdraw<'info>(
+    ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>,
+    escrow_payment_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    let auction_house = &ctx.accounts.auction_house;
+
+    if auction_house.has_auctioneer {
+        return Err(AuctionHouseError::MustUseAuctioneerHandler.into());
+    }
+
+    if escrow_payment_bump
+        != *ctx
+            .bumps
+            .get("escrow_payment_account")
+            .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?
+    {
+        return Err(AuctionHouseError::BumpSeedNotInHashMap.into());
+    }
+
+    withdraw_logic(ctx.accounts, escrow_payment_bump, amount)
 }
 
 pub fn auctioneer_withdraw<'info>(
@@ -89,9 +378,12 @@ pub fn auctioneer_withdraw<'info>(
 ) -> Result<()> {
     let auction_house = &ctx.accounts.auction_house;
 
-    if !auction_house.has_auctioneer {
-        return Err(AuctionHouseError::NoAuctioneerProgramSet.into());
-    }
+    assert_valid_auctioneer_and_scope(
+        auction_house,
+        &ctx.accounts.auctioneer_authority.key(),
+        &ctx.accounts.auctioneer,
+        AuthorityScope::Withdraw,
+    )?;
 
     if escrow_payment_bump
         != *ctx
@@ -102,14 +394,14 @@ pub fn auctioneer_withdraw<'info>(
         return Err(AuctionHouseError::BumpSeedNotInHashMap.into());
     }
 
-    let mut accounts: AuctioneerWithdraw<'info> = (*ctx.accounts).clone();
+    let mut accounts: Withdraw<'info> = (*ctx.accounts).clone().into();
 
     withdraw_logic(&mut accounts, escrow_payment_bump, amount)
 }
 
 #[allow(clippy::needless_lifetimes)]
 fn withdraw_logic<'info>(
-    accounts: &mut AuctioneerWithdraw<'info>,
+    accounts: &mut Withdraw<'info>,
     escrow_payment_bump: u8,
     amount: u64,
 ) -> Result<()> {
@@ -190,25 +482,27 @@ fn withdraw_logic<'info>(
         }
 
         assert_is_ata(receipt_account, &wallet.key(), &treasury_mint.key())?;
-        invoke_signed(
-            &spl_token::instruction::transfer(
-                token_program.key,
-                &escrow_payment_account.key(),
-                &receipt_account.key(),
-                &auction_house.key(),
-                &[],
-                amount,
-            )?,
-            &[
-                escrow_payment_account.to_account_info(),
-                receipt_account.to_account_info(),
-                token_program.to_account_info(),
-                auction_house.to_account_info(),
-            ],
+        assert_unlocked_funds(
+            &accounts.escrow_lock,
+            unpack_token_account(escrow_payment_account)?.amount,
+            amount,
+        )?;
+        transfer_tokens_checked(
+            &token_program.to_account_info(),
+            escrow_payment_account,
+            &treasury_mint.to_account_info(),
+            receipt_account,
+            &auction_house.to_account_info(),
+            amount,
             &[&ah_seeds],
         )?;
     } else {
         assert_keys_equal(receipt_account.key(), wallet.key())?;
+        assert_unlocked_funds(
+            &accounts.escrow_lock,
+            escrow_payment_account.lamports(),
+            amount,
+        )?;
         let rent_shortfall = verify_withdrawal(escrow_payment_account.to_account_info(), amount)?;
         let checked_amount = amount
             .checked_sub(rent_shortfall)
@@ -229,5 +523,163 @@ fn withdraw_logic<'info>(
         )?;
     }
 
+    emit!(EscrowWithdrawn {
+        auction_house: auction_house_key,
+        wallet: wallet_key,
+        escrow_payment_account: escrow_payment_account.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+/// Close the escrow payment account of the wallet. A native escrow sends all its lamports to the
+/// wallet. An SPL escrow sends its tokens to the wallet's associated token account, then is closed
+/// by the Auction House, which owns it, with its rent going to the wallet.
+pub fn close_escrow_account<'info>(
+    ctx: Context<'_, '_, '_, 'info, CloseEscrowAccount<'info>>,
+    escrow_payment_bump: u8,
+) -> Result<()> {
+    // Escrows of houses with an auctioneer are closed through it.
+    if ctx.accounts.auction_house.has_auctioneer {
+        return Err(AuctionHouseError::MustUseAuctioneerHandler.into());
+    }
+
+    close_escrow_account_logic(ctx.accounts, escrow_payment_bump)
+}
+
+/// Close the escrow payment account of the wallet, through the auctioneer.
+pub fn auctioneer_close_escrow_account<'info>(
+    ctx: Context<'_, '_, '_, 'info, AuctioneerCloseEscrowAccount<'info>>,
+    escrow_payment_bump: u8,
+) -> Result<()> {
+    assert_valid_auctioneer_and_scope(
+        &ctx.accounts.auction_house,
+        &ctx.accounts.auctioneer_authority.key(),
+        &ctx.accounts.auctioneer,
+        AuthorityScope::Withdraw,
+    )?;
+
+    let mut accounts: CloseEscrowAccount<'info> = (*ctx.accounts).clone().into();
+
+    close_escrow_account_logic(&mut accounts, escrow_payment_bump)
+}
+
+#[allow(clippy::needless_lifetimes)]
+fn close_escrow_account_logic<'info>(
+    accounts: &mut CloseEscrowAccount<'info>,
+    escrow_payment_bump: u8,
+) -> Result<()> {
+    let wallet = &accounts.wallet;
+    let receipt_account = &accounts.receipt_account;
+    let escrow_payment_account = &accounts.escrow_payment_account;
+    let treasury_mint = &accounts.treasury_mint;
+    let auction_house = &accounts.auction_house;
+    let token_program = &accounts.token_program;
+    let system_program = &accounts.system_program;
+    let associated_token_program = &accounts.associated_token_program;
+    let rent = &accounts.rent;
+
+    let auction_house_key = auction_house.key();
+    let wallet_key = wallet.key();
+
+    // Whatever the path, funds reserved by highest auction bids stay in the escrow.
+    if locked_amount(&accounts.escrow_lock)? > 0 {
+        return Err(AuctionHouseError::EscrowFundsLocked.into());
+    }
+
+    if treasury_mint.key() == spl_token::native_mint::id() {
+        assert_keys_equal(receipt_account.key(), wallet_key)?;
+
+        let escrow_signer_seeds = [
+            PREFIX.as_bytes(),
+            auction_house_key.as_ref(),
+            wallet_key.as_ref(),
+            &[escrow_payment_bump],
+        ];
+
+        invoke_signed(
+            &system_instruction::transfer(
+                &escrow_payment_account.key(),
+                &wallet_key,
+                escrow_payment_account.lamports(),
+            ),
+            &[
+                escrow_payment_account.to_account_info(),
+                wallet.to_account_info(),
+                system_program.to_account_info(),
+            ],
+            &[&escrow_signer_seeds],
+        )?;
+
+        return Ok(());
+    }
+
+    assert_owned_by(escrow_payment_account, token_program.key)?;
+    let escrow = unpack_token_account(escrow_payment_account)?;
+
+    let ah_seeds = [
+        PREFIX.as_bytes(),
+        auction_house.creator.as_ref(),
+        auction_house.treasury_mint.as_ref(),
+        &[auction_house.bump],
+    ];
+
+    if escrow.amount > 0 {
+        if receipt_account.data_is_empty() {
+            make_ata(
+                receipt_account.to_account_info(),
+                wallet.to_account_info(),
+                treasury_mint.to_account_info(),
+                wallet.to_account_info(),
+                associated_token_program.to_account_info(),
+                token_program.to_account_info(),
+                system_program.to_account_info(),
+                rent.to_account_info(),
+                &[],
+            )?;
+        }
+
+        let rec_acct = assert_is_ata(receipt_account, &wallet_key, &treasury_mint.key())?;
+
+        // Make sure you cant get rugged
+        if rec_acct.delegate.is_some() {
+            return Err(AuctionHouseError::BuyerATACannotHaveDelegate.into());
+        }
+
+        transfer_tokens_checked(
+            &token_program.to_account_info(),
+            escrow_payment_account,
+            &treasury_mint.to_account_info(),
+            receipt_account,
+            &auction_house.to_account_info(),
+            escrow.amount,
+            &[&ah_seeds],
+        )?;
+    }
+
+    harvest_withheld_fees(
+        &token_program.to_account_info(),
+        &treasury_mint.to_account_info(),
+        escrow_payment_account,
+    )?;
+
+    invoke_signed(
+        &close_token_account(
+            token_program.key,
+            escrow_payment_account.key,
+            wallet.key,
+            &auction_house_key,
+            &[],
+        )?,
+        &[
+            escrow_payment_account.to_account_info(),
+            wallet.to_account_info(),
+            auction_house.to_account_info(),
+            token_program.to_account_info(),
+        ],
+        &[&ah_seeds],
+    )?;
+
     Ok(())
 }
@@ -0,0 +1,338 @@
+use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize};
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+use crate::{
+    constants::*,
+    errors::AuctionHouseError,
+    events::{QueuedWithdrawalCancelled, QueuedWithdrawalExecuted, WithdrawalQueued},
+    utils::*,
+    AuctionHouse,
+};
+
+/// Limits on how fast the authority can withdraw from the treasury and fee accounts.
+#[account]
+pub struct WithdrawalPolicy {
+    pub auction_house: Pubkey,
+    /// Treasury mint amount that can be withdrawn immediately per epoch.
+    pub treasury_epoch_cap: u64,
+    /// Lamports that can be withdrawn immediately from the fee account per epoch.
+    pub fee_epoch_cap: u64,
+    /// Seconds a queued withdrawal waits before it can be executed.
+    pub timelock: i64,
+    /// Epoch the withdrawn amounts below were counted in.
+    pub epoch: u64,
+    pub treasury_withdrawn: u64,
+    pub fee_withdrawn: u64,
+    pub next_withdrawal_id: u64,
+    pub bump: u8,
+}
+
+/// A withdrawal above the epoch cap, waiting for the policy timelock to pass.
+#[account]
+pub struct QueuedWithdrawal {
+    pub auction_house: Pubkey,
+    pub id: u64,
+    pub source: WithdrawalSource,
+    pub amount: u64,
+    pub executable_at: i64,
+    pub bump: u8,
+}
+
+/// Account a withdrawal is taken from.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WithdrawalSource {
+    Treasury,
+    Fee,
+}
+
+/// Accounts for the [`create_withdrawal_policy` handler](auction_house/fn.create_withdrawal_policy.html).
+#[derive(Accounts)]
+pub struct CreateWithdrawalPolicy<'info> {
+    /// Authority key for the Auction House, paying for the policy account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref(), auction_house.treasury_mint.as_ref()], bump=auction_house.bump, has_one=authority)]
+    pub auction_house: Account<'info, AuctionHouse>,
+
+    /// Withdrawal policy PDA account.
+    #[account(init, seeds=[PREFIX.as_bytes(), auction_house.key().as_ref(), WITHDRAWAL_POLICY.as_bytes()], bump, space=WITHDRAWAL_POLICY_SIZE, payer=authority)]
+    pub withdrawal_policy: Account<'info, WithdrawalPolicy>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts for the [`update_withdrawal_policy` handler](auction_house/fn.update_withdrawal_policy.html).
+#[derive(Accounts)]
+pub struct UpdateWithdrawalPolicy<'info> {
+    /// Authority key for the Auction House.
+    pub authority: Signer<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref(), auction_house.treasury_mint.as_ref()], bump=auction_house.bump, has_one=authority)]
+    pub auction_house: Account<'info, AuctionHouse>,
+
+    /// Withdrawal policy PDA account.
+    #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.key().as_ref(), WITHDRAWAL_POLICY.as_bytes()], bump=withdrawal_policy.bump)]
+    pub withdrawal_policy: Account<'info, WithdrawalPolicy>,
+}
+
+/// Accounts for the [`queue_withdrawal` handler](auction_house/fn.queue_withdrawal.html).
+#[derive(Accounts)]
+pub struct QueueWithdrawal<'info> {
+    /// Authority key for the Auction House, paying for the queued withdrawal account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref(), auction_house.treasury_mint.as_ref()], bump=auction_house.bump, has_one=authority)]
+    pub auction_house: Account<'info, AuctionHouse>,
+
+    /// Withdrawal policy PDA account.
+    #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.key().as_ref(), WITHDRAWAL_POLICY.as_bytes()], bump=withdrawal_policy.bump)]
+    pub withdrawal_policy: Account<'info, WithdrawalPolicy>,
+
+    /// Queued withdrawal PDA account.
+    #[account(init, seeds=[PREFIX.as_bytes(), auction_house.key().as_ref(), QUEUED_WITHDRAWAL.as_bytes(), &withdrawal_policy.next_withdrawal_id.to_le_bytes()], bump, space=QUEUED_WITHDRAWAL_SIZE, payer=authority)]
+    pub queued_withdrawal: Account<'info, QueuedWithdrawal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts for the [`execute_queued_withdrawal` handler](auction_house/fn.execute_queued_withdrawal.html).
+#[derive(Accounts)]
+pub struct ExecuteQueuedWithdrawal<'info> {
+    /// Authority key for the Auction House, refunded the queued withdrawal rent.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Treasury mint account, either native SOL mint or a SPL Token or Token-2022 mint.
+    pub treasury_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: Verified with has_one constraint on auction house account.
+    /// SOL or SPL token account to receive Auction House treasury withdrawals.
+    #[account(mut)]
+    pub treasury_withdrawal_destination: UncheckedAccount<'info>,
+
+    /// CHECK: Verified with has_one constraint on auction house account.
+    /// Account to receive Auction House fee withdrawals.
+    #[account(mut)]
+    pub fee_withdrawal_destination: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Auction House treasury PDA account.
+    #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.key().as_ref(), TREASURY.as_bytes()], bump=auction_house.treasury_bump)]
+    pub auction_house_treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Auction House instance fee account.
+    #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.key().as_ref(), FEE_PAYER.as_bytes()], bump=auction_house.fee_payer_bump)]
+    pub auction_house_fee_account: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref(), treasury_mint.key().as_ref()], bump=auction_house.bump, has_one=authority, has_one=treasury_mint, has_one=treasury_withdrawal_destination, has_one=fee_withdrawal_destination, has_one=auction_house_treasury, has_one=auction_house_fee_account)]
+    pub auction_house: Account<'info, AuctionHouse>,
+
+    /// Queued withdrawal PDA account.
+    #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.key().as_ref(), QUEUED_WITHDRAWAL.as_bytes(), &queued_withdrawal.id.to_le_bytes()], bump=queued_withdrawal.bump, has_one=auction_house, close=authority)]
+    pub queued_withdrawal: Account<'info, QueuedWithdrawal>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts for the [`cancel_queued_withdrawal` handler](auction_house/fn.cancel_queued_withdrawal.html).
+#[derive(Accounts)]
+pub struct CancelQueuedWithdrawal<'info> {
+    /// Authority key for the Auction House, refunded the queued withdrawal rent.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(seeds=[PREFIX.as_bytes(), auction_house.creator.as_ref(), auction_house.treasury_mint.as_ref()], bump=auction_house.bump, has_one=authority)]
+    pub auction_house: Account<'info, AuctionHouse>,
+
+    /// Queued withdrawal PDA account.
+    #[account(mut, seeds=[PREFIX.as_bytes(), auction_house.key().as_ref(), QUEUED_WITHDRAWAL.as_bytes(), &queued_withdrawal.id.to_le_bytes()], bump=queued_withdrawal.bump, has_one=auction_house, close=authority)]
+    pub queued_withdrawal: Account<'info, QueuedWithdrawal>,
+}
+
+pub fn create_withdrawal_policy(
+    ctx: Context<CreateWithdrawalPolicy>,
+    treasury_epoch_cap: u64,
+    fee_epoch_cap: u64,
+    timelock: i64,
+) -> Result<()> {
+    if timelock < 0 {
+        return Err(AuctionHouseError::InvalidWithdrawalPolicy.into());
+    }
+
+    let withdrawal_policy = &mut ctx.accounts.withdrawal_policy;
+    withdrawal_policy.auction_house = ctx.accounts.auction_house.key();
+    withdrawal_policy.treasury_epoch_cap = treasury_epoch_cap;
+    withdrawal_policy.fee_epoch_cap = fee_epoch_cap;
+    withdrawal_policy.timelock = timelock;
+    withdrawal_policy.epoch = Clock::get()?.epoch;
+    withdrawal_policy.bump = *ctx
+        .bumps
+        .get("withdrawal_policy")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+
+    ctx.accounts.auction_house.has_withdrawal_policy = true;
+
+    Ok(())
+}
+
+/// Policies can only be tightened, otherwise the authority could lift the cap and withdraw at once.
+pub fn update_withdrawal_policy(
+    ctx: Context<UpdateWithdrawalPolicy>,
+    treasury_epoch_cap: u64,
+    fee_epoch_cap: u64,
+    timelock: i64,
+) -> Result<()> {
+    let withdrawal_policy = &mut ctx.accounts.withdrawal_policy;
+
+    if treasury_epoch_cap > withdrawal_policy.treasury_epoch_cap
+        || fee_epoch_cap > withdrawal_policy.fee_epoch_cap
+        || timelock < withdrawal_policy.timelock
+    {
+        return Err(AuctionHouseError::InvalidWithdrawalPolicy.into());
+    }
+
+    withdrawal_policy.treasury_epoch_cap = treasury_epoch_cap;
+    withdrawal_policy.fee_epoch_cap = fee_epoch_cap;
+    withdrawal_policy.timelock = timelock;
+
+    Ok(())
+}
+
+pub fn queue_withdrawal(
+    ctx: Context<QueueWithdrawal>,
+    source: WithdrawalSource,
+    amount: u64,
+) -> Result<()> {
+    let withdrawal_policy = &mut ctx.accounts.withdrawal_policy;
+    let queued_withdrawal = &mut ctx.accounts.queued_withdrawal;
+
+    queued_withdrawal.auction_house = ctx.accounts.auction_house.key();
+    queued_withdrawal.id = withdrawal_policy.next_withdrawal_id;
+    queued_withdrawal.source = source;
+    queued_withdrawal.amount = amount;
+    queued_withdrawal.executable_at = Clock::get()?
+        .unix_timestamp
+        .checked_add(withdrawal_policy.timelock)
+        .ok_or(AuctionHouseError::NumericalOverflow)?;
+    queued_withdrawal.bump = *ctx
+        .bumps
+        .get("queued_withdrawal")
+        .ok_or(AuctionHouseError::BumpSeedNotInHashMap)?;
+
+    withdrawal_policy.next_withdrawal_id = withdrawal_policy
+        .next_withdrawal_id
+        .checked_add(1)
+        .ok_or(AuctionHouseError::NumericalOverflow)?;
+
+    emit!(WithdrawalQueued {
+        auction_house: queued_withdrawal.auction_house,
+        queued_withdrawal: queued_withdrawal.key(),
+        source,
+        amount,
+        executable_at: queued_withdrawal.executable_at,
+    });
+
+    Ok(())
+}
+
+pub fn execute_queued_withdrawal<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteQueuedWithdrawal<'info>>,
+) -> Result<()> {
+    let auction_house = &ctx.accounts.auction_house;
+    let queued_withdrawal = &ctx.accounts.queued_withdrawal;
+
+    if Clock::get()?.unix_timestamp < queued_withdrawal.executable_at {
+        return Err(AuctionHouseError::WithdrawalTimelocked.into());
+    }
+
+    match queued_withdrawal.source {
+        WithdrawalSource::Treasury => pay_from_treasury(
+            auction_house,
+            &ctx.accounts.treasury_mint.to_account_info(),
+            &ctx.accounts.auction_house_treasury.to_account_info(),
+            &ctx.accounts
+                .treasury_withdrawal_destination
+                .to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            queued_withdrawal.amount,
+        )?,
+        WithdrawalSource::Fee => pay_from_fee_account(
+            auction_house,
+            &ctx.accounts.auction_house_fee_account.to_account_info(),
+            &ctx.accounts.fee_withdrawal_destination.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            queued_withdrawal.amount,
+        )?,
+    }
+
+    emit!(QueuedWithdrawalExecuted {
+        auction_house: auction_house.key(),
+        queued_withdrawal: queued_withdrawal.key(),
+        source: queued_withdrawal.source,
+        amount: queued_withdrawal.amount,
+    });
+
+    Ok(())
+}
+
+pub fn cancel_queued_withdrawal(ctx: Context<CancelQueuedWithdrawal>) -> Result<()> {
+    emit!(QueuedWithdrawalCancelled {
+        auction_house: ctx.accounts.auction_house.key(),
+        queued_withdrawal: ctx.accounts.queued_withdrawal.key(),
+    });
+
+    Ok(())
+}
+
+/// Count an immediate withdrawal against the epoch cap of the Auction House withdrawal policy, if
+/// it has one. The policy is expected as the first remaining account.
+pub fn record_policy_withdrawal<'info>(
+    auction_house: &Account<'info, AuctionHouse>,
+    remaining_accounts: &[AccountInfo<'info>],
+    source: WithdrawalSource,
+    amount: u64,
+) -> Result<()> {
+    if !auction_house.has_withdrawal_policy {
+        return Ok(());
+    }
+
+    let withdrawal_policy_info = remaining_accounts
+        .first()
+        .ok_or(AuctionHouseError::InvalidWithdrawalPolicy)?;
+    let mut withdrawal_policy = Account::<WithdrawalPolicy>::try_from(withdrawal_policy_info)?;
+    if withdrawal_policy.auction_house != auction_house.key() || !withdrawal_policy_info.is_writable
+    {
+        return Err(AuctionHouseError::InvalidWithdrawalPolicy.into());
+    }
+
+    let epoch = Clock::get()?.epoch;
+    if withdrawal_policy.epoch != epoch {
+        withdrawal_policy.epoch = epoch;
+        withdrawal_policy.treasury_withdrawn = 0;
+        withdrawal_policy.fee_withdrawn = 0;
+    }
+
+    let policy = &mut *withdrawal_policy;
+    let (withdrawn, cap) = match source {
+        WithdrawalSource::Treasury => (&mut policy.treasury_withdrawn, policy.treasury_epoch_cap),
+        WithdrawalSource::Fee => (&mut policy.fee_withdrawn, policy.fee_epoch_cap),
+    };
+    *withdrawn = withdrawn
+        .checked_add(amount)
+        .ok_or(AuctionHouseError::NumericalOverflow)?;
+    if *withdrawn > cap {
+        return Err(AuctionHouseError::WithdrawalExceedsEpochCap.into());
+    }
+
+    withdrawal_policy.exit(&crate::id())
+}
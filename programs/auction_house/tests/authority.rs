@@ -0,0 +1,172 @@
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
+use anchor_lang::prelude::Pubkey;
+use auction_house::errors::AuctionHouseError;
+use solana_program_test::tokio;
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn propose_and_accept_authority_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, auction_house_data) =
+        create_auction_house(&mut context, 100, false)
+            .await
+            .expect("Failed to create Auction House");
+
+    // ProposeAuthority
+
+    let new_authority = Keypair::new();
+    let new_fee_withdrawal_destination = Keypair::new();
+
+    let ix = propose_authority_instruction(
+        &auction_house,
+        &auction_house_data,
+        &new_authority.pubkey(),
+        &new_fee_withdrawal_destination.pubkey(),
+        &auction_house_data.treasury_withdrawal_destination,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let auction_house_data = get_auction_house_data(&mut context, &auction_house).await;
+
+    // Nothing changes until the proposal is accepted
+    assert_eq!(auction_house_data.authority, authority.pubkey());
+    assert_eq!(auction_house_data.pending_authority, new_authority.pubkey());
+    assert_eq!(
+        auction_house_data.pending_fee_withdrawal_destination,
+        new_fee_withdrawal_destination.pubkey()
+    );
+    assert_eq!(
+        auction_house_data.pending_treasury_withdrawal_destination,
+        Pubkey::default()
+    );
+
+    // AcceptAuthority
+
+    let ix = accept_authority_instruction(
+        &auction_house,
+        &auction_house_data,
+        &context.payer.pubkey(),
+        &context.payer.pubkey(),
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[
+            &context.payer,
+            &new_authority,
+            &new_fee_withdrawal_destination,
+        ],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let auction_house_data = get_auction_house_data(&mut context, &auction_house).await;
+
+    assert_eq!(auction_house_data.authority, new_authority.pubkey());
+    assert_eq!(
+        auction_house_data.fee_withdrawal_destination,
+        new_fee_withdrawal_destination.pubkey()
+    );
+    assert_eq!(
+        auction_house_data.treasury_withdrawal_destination,
+        context.payer.pubkey()
+    );
+    assert_eq!(auction_house_data.pending_authority, Pubkey::default());
+    assert_eq!(
+        auction_house_data.pending_fee_withdrawal_destination,
+        Pubkey::default()
+    );
+}
+
+#[tokio::test]
+async fn accept_authority_without_new_authority_signature_failure() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, auction_house_data) =
+        create_auction_house(&mut context, 100, false)
+            .await
+            .expect("Failed to create Auction House");
+
+    // ProposeAuthority
+
+    let new_authority = Keypair::new();
+
+    let ix = propose_authority_instruction(
+        &auction_house,
+        &auction_house_data,
+        &new_authority.pubkey(),
+        &auction_house_data.fee_withdrawal_destination,
+        &auction_house_data.treasury_withdrawal_destination,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let auction_house_data = get_auction_house_data(&mut context, &auction_house).await;
+
+    // AcceptAuthority without the new authority signing
+
+    let mut ix = accept_authority_instruction(
+        &auction_house,
+        &auction_house_data,
+        &context.payer.pubkey(),
+        &context.payer.pubkey(),
+    );
+    ix.accounts[2].is_signer = false;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::NoValidSignerPresent.into());
+}
+
+#[tokio::test]
+async fn accept_authority_without_pending_change_failure() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let ix = accept_authority_instruction(
+        &auction_house,
+        &auction_house_data,
+        &context.payer.pubkey(),
+        &context.payer.pubkey(),
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::NoPendingAuthorityChange.into());
+}
@@ -0,0 +1,216 @@
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
+use anchor_lang::prelude::AccountMeta;
+use auction_house::{errors::AuctionHouseError, pda::find_blocked_wallet_address};
+use solana_program_test::tokio;
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn sell_blocked_seller_failure() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, auction_house_data) =
+        create_auction_house(&mut context, 100, false)
+            .await
+            .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let ix = block_wallet_instruction(&auction_house, &auction_house_data, &token.owner.pubkey());
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let auction_house_data = get_auction_house_data(&mut context, &auction_house).await;
+    assert!(auction_house_data.has_blocklist);
+
+    let (_, mut ix) = sell_instruction(&auction_house, &auction_house_data, &token, ONE_SOL, 1);
+    ix.accounts.push(AccountMeta::new_readonly(
+        find_blocked_wallet_address(&auction_house, &token.owner.pubkey()).0,
+        false,
+    ));
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::WalletBlocked.into());
+}
+
+#[tokio::test]
+async fn buy_blocked_buyer_failure() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, auction_house_data) =
+        create_auction_house(&mut context, 100, false)
+            .await
+            .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let ix = block_wallet_instruction(&auction_house, &auction_house_data, &buyer.pubkey());
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let (_, mut ix) = buy_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer.pubkey(),
+        ONE_SOL,
+        1,
+    );
+    ix.accounts.push(AccountMeta::new_readonly(
+        find_blocked_wallet_address(&auction_house, &buyer.pubkey()).0,
+        false,
+    ));
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::WalletBlocked.into());
+}
+
+#[tokio::test]
+async fn execute_sale_blocked_seller_failure() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, auction_house_data) =
+        create_auction_house(&mut context, 100, false)
+            .await
+            .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    // Sell and Buy before the seller is blocked
+
+    let (sell_accounts, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let (buy_accounts, buy_tx) = buy(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer,
+        ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(buy_tx)
+        .await
+        .unwrap();
+
+    let ix = block_wallet_instruction(&auction_house, &auction_house_data, &token.owner.pubkey());
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Execute sale
+
+    let (_, mut ix) = execute_sale_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        None,
+        &sell_accounts,
+        &buyer.pubkey(),
+        &buy_accounts.buyer_trade_state,
+        ONE_SOL,
+        1,
+        None,
+        None,
+    );
+    ix.accounts.push(AccountMeta::new_readonly(
+        find_blocked_wallet_address(&auction_house, &buyer.pubkey()).0,
+        false,
+    ));
+    ix.accounts.push(AccountMeta::new_readonly(
+        find_blocked_wallet_address(&auction_house, &token.owner.pubkey()).0,
+        false,
+    ));
+    let tx = Transaction::new_signed_with_payer(
+        &[ix.clone()],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::WalletBlocked.into());
+
+    // UnblockWallet lets the sale go through
+
+    let unblock_ix =
+        unblock_wallet_instruction(&auction_house, &auction_house_data, &token.owner.pubkey());
+    let tx = Transaction::new_signed_with_payer(
+        &[unblock_ix, ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to execute sale");
+}
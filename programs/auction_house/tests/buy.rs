@@ -0,0 +1,61 @@
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
+use solana_program_test::tokio;
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn buy_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let bid_amount = ONE_SOL;
+
+    let (buy_accounts, buy_tx) = buy(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer,
+        bid_amount,
+    );
+    context
+        .banks_client
+        .process_transaction(buy_tx)
+        .await
+        .unwrap();
+
+    let buyer_trade_state_account = context
+        .banks_client
+        .get_account(buy_accounts.buyer_trade_state)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    assert_eq!(buyer_trade_state_account.data.len(), 1);
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let rent_exempt_min: u64 = rent.minimum_balance(0);
+
+    let escrow_payment_account = context
+        .banks_client
+        .get_account(buy_accounts.escrow_payment_account)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    // Bid amount is topped up into escrow
+    assert_eq!(escrow_payment_account.lamports, bid_amount + rent_exempt_min);
+}
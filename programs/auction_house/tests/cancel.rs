@@ -0,0 +1,152 @@
+use anchor_client::solana_sdk::{signer::Signer, transaction::Transaction};
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::token::spl_token;
+use mpl_token_metadata::{
+    pda::find_token_record_account,
+    state::{TokenMetadataAccount, TokenRecord, TokenState},
+};
+use solana_program_test::tokio;
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn cancel_sell_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    // Sell
+
+    let (sell_accounts, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    // Cancel
+
+    let (_, cancel_tx) = cancel(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &token.owner,
+        &sell_accounts.seller_trade_state,
+        ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(cancel_tx)
+        .await
+        .unwrap();
+
+    let seller_trade_state_account = context
+        .banks_client
+        .get_account(sell_accounts.seller_trade_state)
+        .await
+        .unwrap();
+
+    // Assert seller trade state is closed
+    assert!(seller_trade_state_account.is_none());
+
+    let token_account = context
+        .banks_client
+        .get_account(token.ata)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    let token_account_data = spl_token::state::Account::unpack(&token_account.data).unwrap();
+
+    // Assert token account delegate is revoked
+    assert!(token_account_data.delegate.is_none());
+}
+
+#[tokio::test]
+async fn cancel_programmable_sell_success() {
+    let mut context = auction_house_pnft_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_pnft(&mut context)
+        .await
+        .expect("Failed to create pNFT");
+
+    // Sell
+
+    let (sell_accounts, mut sell_ix) =
+        sell_instruction(&auction_house, &auction_house_data, &token, ONE_SOL, 1);
+    add_programmable_sell_accounts(&mut sell_ix, &token);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[sell_ix],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to sell pNFT");
+
+    // Cancel
+
+    let (_, mut cancel_ix) = cancel_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &token.owner.pubkey(),
+        &sell_accounts.seller_trade_state,
+        ONE_SOL,
+    );
+    add_programmable_cancel_accounts(&mut cancel_ix, &token);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[cancel_ix],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let (token_record, _) = find_token_record_account(&token.mint.pubkey(), &token.ata);
+    let token_record_account = context
+        .banks_client
+        .get_account(token_record)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let token_record_data = TokenRecord::safe_deserialize(&token_record_account.data).unwrap();
+
+    // Assert the sale delegate is revoked and the token is unlocked
+    assert!(token_record_data.delegate.is_none());
+    assert_eq!(token_record_data.state, TokenState::Unlocked);
+
+    let token_account = context
+        .banks_client
+        .get_account(token.ata)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    let token_account_data = spl_token::state::Account::unpack(&token_account.data).unwrap();
+
+    assert!(token_account_data.delegate.is_none());
+}
@@ -0,0 +1,176 @@
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
+use auction_house::{errors::AuctionHouseError, pda::find_royalty_vault_address};
+use nft_minter::utils::Creator;
+use solana_program_test::tokio;
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn claim_royalties_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    // A fresh creator wallet that cannot receive less than the rent-exempt minimum
+    let creator = Keypair::new();
+    let metadata_creators = vec![Creator {
+        address: creator.pubkey(),
+        verified: false,
+        share: 100,
+    }];
+
+    let token = create_nft(&mut context, Some(metadata_creators.clone()))
+        .await
+        .expect("Failed to create NFT");
+
+    let price = ONE_SOL / 2;
+    // 10 basis points of the price
+    let royalties = price / 1000;
+
+    // Sell
+
+    let (sell_accounts, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        price,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    // Buy
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let (buy_accounts, buy_tx) = buy(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer,
+        price,
+    );
+    context
+        .banks_client
+        .process_transaction(buy_tx)
+        .await
+        .unwrap();
+
+    // The Auction House fee account pays the royalty vault rent
+    airdrop(
+        &mut context,
+        &auction_house_data.auction_house_fee_account,
+        ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    // Execute sale
+
+    let (_, execute_sale_tx) = execute_sale(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        Some(metadata_creators),
+        &sell_accounts,
+        &buyer.pubkey(),
+        &buy_accounts.buyer_trade_state,
+        price,
+        &buyer,
+    );
+    context
+        .banks_client
+        .process_transaction(execute_sale_tx)
+        .await
+        .unwrap();
+
+    // Royalties were credited to the vault instead of being skipped
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let vault_rent_minimum = rent.minimum_balance(0);
+    let (royalty_vault, _) = find_royalty_vault_address(&auction_house, &creator.pubkey());
+
+    let royalty_vault_balance = context
+        .banks_client
+        .get_balance(royalty_vault)
+        .await
+        .unwrap();
+    assert_eq!(royalty_vault_balance, vault_rent_minimum + royalties);
+
+    let auction_house_fee_account_balance = context
+        .banks_client
+        .get_balance(auction_house_data.auction_house_fee_account)
+        .await
+        .unwrap();
+    assert_eq!(
+        auction_house_fee_account_balance,
+        ONE_SOL - vault_rent_minimum
+    );
+
+    let creator_balance = context
+        .banks_client
+        .get_balance(creator.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(creator_balance, 0);
+
+    // Claim royalties once the creator wallet is funded
+
+    airdrop(&mut context, &creator.pubkey(), ONE_SOL)
+        .await
+        .unwrap();
+
+    let (_, claim_royalties_tx) = claim_royalties(&mut context, &auction_house, &creator);
+    context
+        .banks_client
+        .process_transaction(claim_royalties_tx)
+        .await
+        .unwrap();
+
+    let creator_balance = context
+        .banks_client
+        .get_balance(creator.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(creator_balance, ONE_SOL + royalties);
+
+    let royalty_vault_balance = context
+        .banks_client
+        .get_balance(royalty_vault)
+        .await
+        .unwrap();
+    assert_eq!(royalty_vault_balance, vault_rent_minimum);
+}
+
+#[tokio::test]
+async fn claim_royalties_empty_vault_failure() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, _) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let creator = Keypair::new();
+    airdrop(&mut context, &creator.pubkey(), ONE_SOL)
+        .await
+        .unwrap();
+
+    let (_, claim_royalties_tx) = claim_royalties(&mut context, &auction_house, &creator);
+    let tx_error = context
+        .banks_client
+        .process_transaction(claim_royalties_tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::NoRoyaltiesToClaim.into());
+}
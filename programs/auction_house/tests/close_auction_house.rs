@@ -0,0 +1,395 @@
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
+use anchor_lang::prelude::AccountMeta;
+use anchor_spl::token::spl_token;
+use auction_house::{
+    errors::AuctionHouseError,
+    pda::{find_auctioneer_address, find_blocked_wallet_address},
+    AuthorityScope,
+};
+use solana_program_test::tokio;
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn close_auction_house_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, auction_house_data) =
+        create_auction_house(&mut context, 100, false)
+            .await
+            .expect("Failed to create Auction House");
+
+    airdrop(
+        &mut context,
+        &auction_house_data.auction_house_fee_account,
+        ONE_SOL,
+    )
+    .await
+    .unwrap();
+    airdrop(
+        &mut context,
+        &auction_house_data.auction_house_treasury,
+        ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let wallet = Keypair::new();
+    let ix = block_wallet_instruction(&auction_house, &auction_house_data, &wallet.pubkey());
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let (blocked_wallet, _) = find_blocked_wallet_address(&auction_house, &wallet.pubkey());
+
+    let fee_account_balance = context
+        .banks_client
+        .get_balance(auction_house_data.auction_house_fee_account)
+        .await
+        .unwrap();
+    let treasury_balance = context
+        .banks_client
+        .get_balance(auction_house_data.auction_house_treasury)
+        .await
+        .unwrap();
+
+    // Both withdrawal destinations are the context payer for native treasuries.
+    let destination_balance_before = context
+        .banks_client
+        .get_balance(auction_house_data.fee_withdrawal_destination)
+        .await
+        .unwrap();
+    let authority_balance_before = context
+        .banks_client
+        .get_balance(authority.pubkey())
+        .await
+        .unwrap();
+
+    // CloseAuctionHouse
+
+    let mut ix = close_auction_house_instruction(&auction_house, &auction_house_data);
+    ix.accounts.push(AccountMeta::new(blocked_wallet, false));
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    for account in [
+        auction_house,
+        auction_house_data.auction_house_fee_account,
+        auction_house_data.auction_house_treasury,
+        blocked_wallet,
+    ] {
+        assert!(context
+            .banks_client
+            .get_account(account)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    let destination_balance_after = context
+        .banks_client
+        .get_balance(auction_house_data.fee_withdrawal_destination)
+        .await
+        .unwrap();
+    let authority_balance_after = context
+        .banks_client
+        .get_balance(authority.pubkey())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        destination_balance_after,
+        destination_balance_before + fee_account_balance + treasury_balance
+    );
+    // The reclaimed rent is more than the transaction fee.
+    assert!(authority_balance_after > authority_balance_before);
+}
+
+#[tokio::test]
+async fn close_auction_house_with_auctioneer_failure() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, auction_house_data) =
+        create_auction_house(&mut context, 100, false)
+            .await
+            .expect("Failed to create Auction House");
+
+    let auctioneer_authority = Keypair::new();
+    let (auctioneer, _) = find_auctioneer_address(&auction_house, &auctioneer_authority.pubkey());
+    let delegate_tx = delegate(
+        &mut context,
+        &auction_house,
+        &authority,
+        &auctioneer_authority.pubkey(),
+        &auctioneer,
+        vec![AuthorityScope::Sell],
+    );
+    context
+        .banks_client
+        .process_transaction(delegate_tx)
+        .await
+        .unwrap();
+
+    let ix = close_auction_house_instruction(&auction_house, &auction_house_data);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(
+        tx_error,
+        AuctionHouseError::AuctioneersStillDelegated.into(),
+    );
+}
+
+#[tokio::test]
+async fn close_auction_house_with_transferred_authority_failure() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, auction_house_data) =
+        create_auction_house(&mut context, 100, false)
+            .await
+            .expect("Failed to create Auction House");
+
+    let new_authority = Keypair::new();
+    let ix = propose_authority_instruction(
+        &auction_house,
+        &auction_house_data,
+        &new_authority.pubkey(),
+        &auction_house_data.fee_withdrawal_destination,
+        &auction_house_data.treasury_withdrawal_destination,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let auction_house_data = get_auction_house_data(&mut context, &auction_house).await;
+    let ix = accept_authority_instruction(
+        &auction_house,
+        &auction_house_data,
+        &context.payer.pubkey(),
+        &context.payer.pubkey(),
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &new_authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // The new authority can't close a house only the creator could recreate
+
+    airdrop(&mut context, &new_authority.pubkey(), ONE_SOL)
+        .await
+        .unwrap();
+    let auction_house_data = get_auction_house_data(&mut context, &auction_house).await;
+    let ix = close_auction_house_instruction(&auction_house, &auction_house_data);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&new_authority.pubkey()),
+        &[&new_authority],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::AuthorityNotCreator.into());
+}
+
+#[tokio::test]
+async fn close_auction_house_with_withdrawal_policy_not_drained_failure() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, auction_house_data) =
+        create_auction_house(&mut context, 100, false)
+            .await
+            .expect("Failed to create Auction House");
+
+    let ix = create_withdrawal_policy_instruction(
+        &auction_house,
+        &auction_house_data,
+        ONE_SOL,
+        ONE_SOL,
+        60,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    airdrop(
+        &mut context,
+        &auction_house_data.auction_house_treasury,
+        ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    let ix = close_auction_house_instruction(&auction_house, &auction_house_data);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::AuctionHouseNotDrained.into());
+}
+
+#[tokio::test]
+async fn recreate_closed_auction_house_recovers_escrows_and_listings() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, auction_house_data) =
+        create_auction_house(&mut context, 100, false)
+            .await
+            .expect("Failed to create Auction House");
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 2 * ONE_SOL)
+        .await
+        .unwrap();
+    let (deposit_accounts, deposit_tx) = deposit(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &buyer,
+        ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(deposit_tx)
+        .await
+        .unwrap();
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+    let (sell_accounts, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .unwrap();
+
+    // CloseAuctionHouse with an open escrow and listing
+
+    let ix = close_auction_house_instruction(&auction_house, &auction_house_data);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Recreating it with the same authority and treasury mint gives back the same addresses.
+
+    let ix = create_auction_house_instruction(
+        &authority.pubkey(),
+        &auction_house_data.treasury_mint,
+        &spl_token::id(),
+        &auction_house_data.treasury_withdrawal_destination,
+        &context.payer.pubkey(),
+        &auction_house_data.fee_withdrawal_destination,
+        100,
+        false,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let recreated_data = get_auction_house_data(&mut context, &auction_house).await;
+    assert_eq!(
+        recreated_data.auction_house_fee_account,
+        auction_house_data.auction_house_fee_account
+    );
+
+    // The buyer withdraws the escrow and the seller cancels the listing.
+
+    let (_, withdraw_tx) = withdraw(
+        &mut context,
+        &auction_house,
+        &recreated_data,
+        &buyer,
+        ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(withdraw_tx)
+        .await
+        .unwrap();
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let escrow_balance = context
+        .banks_client
+        .get_balance(deposit_accounts.escrow_payment_account)
+        .await
+        .unwrap();
+    assert_eq!(escrow_balance, rent.minimum_balance(0));
+
+    let (_, cancel_tx) = cancel(
+        &mut context,
+        &auction_house,
+        &recreated_data,
+        &token,
+        &token.owner,
+        &sell_accounts.seller_trade_state,
+        ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(cancel_tx)
+        .await
+        .unwrap();
+
+    assert!(context
+        .banks_client
+        .get_account(sell_accounts.seller_trade_state)
+        .await
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,166 @@
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
+use anchor_spl::token::spl_token;
+use solana_program_test::tokio;
+use spl_token_2022::{
+    extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions},
+    state::{Account as TokenAccount, Mint},
+};
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn close_escrow_account_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 2 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let (deposit_accounts, deposit_tx) = deposit(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &buyer,
+        ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(deposit_tx)
+        .await
+        .unwrap();
+
+    let buyer_balance = context
+        .banks_client
+        .get_balance(buyer.pubkey())
+        .await
+        .unwrap();
+    let escrow_balance = context
+        .banks_client
+        .get_balance(deposit_accounts.escrow_payment_account)
+        .await
+        .unwrap();
+
+    let (_, ix) = close_escrow_account_instruction(
+        &auction_house,
+        &auction_house_data,
+        &buyer.pubkey(),
+        &spl_token::id(),
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let closed_escrow_payment_account = context
+        .banks_client
+        .get_account(deposit_accounts.escrow_payment_account)
+        .await
+        .expect("Account not found");
+    assert!(closed_escrow_payment_account.is_none());
+
+    // Less the transaction fee
+    let buyer_balance_after = context
+        .banks_client
+        .get_balance(buyer.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(buyer_balance_after, buyer_balance + escrow_balance - 5_000);
+}
+
+#[tokio::test]
+async fn close_escrow_account_token_2022_transfer_fee_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    // 1% transfer fee on the treasury mint
+    let treasury_mint = create_transfer_fee_mint(&mut context, 100, u64::MAX)
+        .await
+        .expect("Failed to create treasury mint");
+
+    let (_, auction_house, auction_house_data) = create_auction_house_with_treasury_mint(
+        &mut context,
+        &treasury_mint.pubkey(),
+        &spl_token_2022::id(),
+        100,
+        false,
+    )
+    .await
+    .expect("Failed to create Auction House");
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 2 * ONE_SOL)
+        .await
+        .unwrap();
+    let payment_account = mint_token_2022(
+        &mut context,
+        &treasury_mint.pubkey(),
+        &buyer.pubkey(),
+        10_000,
+    )
+    .await
+    .expect("Failed to mint treasury tokens");
+
+    let (deposit_accounts, deposit_ix) = deposit_instruction(
+        &auction_house,
+        &auction_house_data,
+        &buyer.pubkey(),
+        &payment_account,
+        &spl_token_2022::id(),
+        1_000,
+    );
+    let (close_accounts, close_ix) = close_escrow_account_instruction(
+        &auction_house,
+        &auction_house_data,
+        &buyer.pubkey(),
+        &spl_token_2022::id(),
+    );
+    assert_eq!(close_accounts.receipt_account, payment_account);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[deposit_ix, close_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to close escrow account");
+
+    let closed_escrow_payment_account = context
+        .banks_client
+        .get_account(deposit_accounts.escrow_payment_account)
+        .await
+        .expect("Account not found");
+    assert!(closed_escrow_payment_account.is_none());
+
+    // The deposit cost 1_011 to credit 1_000, of which 990 came back after the transfer fee
+    let payment_account = context
+        .banks_client
+        .get_account(payment_account)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let payment = StateWithExtensions::<TokenAccount>::unpack(&payment_account.data).unwrap();
+    assert_eq!(payment.base.amount, 10_000 - 1_011 + 990);
+
+    // The fee withheld in the escrow on deposit was harvested to the mint before closing
+    let mint_account = context
+        .banks_client
+        .get_account(treasury_mint.pubkey())
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_account.data).unwrap();
+    let transfer_fee_config = mint.get_extension::<TransferFeeConfig>().unwrap();
+    assert_eq!(u64::from(transfer_fee_config.withheld_amount), 11);
+}
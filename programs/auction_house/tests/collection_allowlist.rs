@@ -0,0 +1,175 @@
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
+use anchor_lang::{prelude::AccountMeta, solana_program::pubkey::Pubkey};
+use auction_house::{errors::AuctionHouseError, pda::find_allowed_collection_address};
+use solana_program_test::tokio;
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn sell_and_buy_allowed_collection_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, auction_house_data) =
+        create_auction_house(&mut context, 100, false)
+            .await
+            .expect("Failed to create Auction House");
+
+    let (token, collection) = create_nft_in_collection(&mut context)
+        .await
+        .expect("Failed to create NFT");
+
+    // AddAllowedCollection
+
+    let ix = add_allowed_collection_instruction(&auction_house, &auction_house_data, &collection);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let auction_house_data = get_auction_house_data(&mut context, &auction_house).await;
+    assert!(auction_house_data.has_collection_allowlist);
+
+    let allowed_collection = find_allowed_collection_address(&auction_house, &collection).0;
+
+    // Sell
+
+    let (_, mut ix) = sell_instruction(&auction_house, &auction_house_data, &token, ONE_SOL, 1);
+    ix.accounts
+        .push(AccountMeta::new_readonly(allowed_collection, false));
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    // Buy
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let (_, mut ix) = buy_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer.pubkey(),
+        ONE_SOL,
+        1,
+    );
+    ix.accounts
+        .push(AccountMeta::new_readonly(allowed_collection, false));
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to buy NFT");
+}
+
+#[tokio::test]
+async fn sell_outside_allowed_collections_failure() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, auction_house_data) =
+        create_auction_house(&mut context, 100, false)
+            .await
+            .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let collection = Pubkey::new_unique();
+    let ix = add_allowed_collection_instruction(&auction_house, &auction_house_data, &collection);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // The NFT has no verified collection, so no allowed collection account can match it.
+    let (_, mut ix) = sell_instruction(&auction_house, &auction_house_data, &token, ONE_SOL, 1);
+    ix.accounts.push(AccountMeta::new_readonly(
+        find_allowed_collection_address(&auction_house, &collection).0,
+        false,
+    ));
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::CollectionNotAllowed.into());
+}
+
+#[tokio::test]
+async fn sell_removed_collection_failure() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, auction_house_data) =
+        create_auction_house(&mut context, 100, false)
+            .await
+            .expect("Failed to create Auction House");
+
+    let (token, collection) = create_nft_in_collection(&mut context)
+        .await
+        .expect("Failed to create NFT");
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            add_allowed_collection_instruction(&auction_house, &auction_house_data, &collection),
+            remove_allowed_collection_instruction(&auction_house, &auction_house_data, &collection),
+        ],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Removing the last collection keeps the allowlist in place.
+    let auction_house_data = get_auction_house_data(&mut context, &auction_house).await;
+    assert!(auction_house_data.has_collection_allowlist);
+
+    let (_, mut ix) = sell_instruction(&auction_house, &auction_house_data, &token, ONE_SOL, 1);
+    ix.accounts.push(AccountMeta::new_readonly(
+        find_allowed_collection_address(&auction_house, &collection).0,
+        false,
+    ));
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::CollectionNotAllowed.into());
+}
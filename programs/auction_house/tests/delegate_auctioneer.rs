@@ -1,9 +1,14 @@
 use anchor_client::solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
 use anchor_lang::{
-    solana_program::{instruction::Instruction, system_program},
+    solana_program::{instruction::Instruction, system_program, sysvar},
     AccountDeserialize, InstructionData, ToAccountMetas,
 };
-use auction_house::{errors::AuctionHouseError, pda::find_auctioneer_address, AuctionHouse};
+use anchor_spl::token::spl_token;
+use auction_house::{
+    errors::AuctionHouseError,
+    pda::{find_auctioneer_address, find_escrow_payment_account_address},
+    AuctionHouse, Auctioneer, AuthorityScope,
+};
 use solana_program_test::tokio;
 
 mod utils;
@@ -22,7 +27,9 @@ async fn delegate_auctioneer_success() {
     let auctioneer_authority = Keypair::new();
     let (auctioneer, _) = find_auctioneer_address(&auction_house, &auctioneer_authority.pubkey());
 
-    let data = auction_house::instruction::DelegateAuctioneer {};
+    let data = auction_house::instruction::DelegateAuctioneer {
+        scopes: vec![AuthorityScope::Sell, AuthorityScope::Cancel],
+    };
 
     let accounts = auction_house::accounts::DelegateAuctioneer {
         auction_house,
@@ -58,10 +65,24 @@ async fn delegate_auctioneer_success() {
 
     assert_eq!(auction_house_data.authority, authority.pubkey());
     assert!(auction_house_data.has_auctioneer);
+
+    let auctioneer_account = context
+        .banks_client
+        .get_account(auctioneer)
+        .await
+        .unwrap()
+        .expect("Auctioneer account not found");
+
+    let auctioneer_data =
+        Auctioneer::try_deserialize(&mut auctioneer_account.data.as_ref()).unwrap();
+
+    assert!(auctioneer_data.has_scope(AuthorityScope::Sell));
+    assert!(auctioneer_data.has_scope(AuthorityScope::Cancel));
+    assert!(!auctioneer_data.has_scope(AuthorityScope::ExecuteSale));
 }
 
 #[tokio::test]
-async fn failure_delegate_auctioneer_redelegate() {
+async fn delegate_multiple_auctioneers_success() {
     let mut context = auction_house_program_test().start_with_context().await;
 
     let (authority, auction_house, _) = create_auction_house(&mut context, 100, false)
@@ -79,10 +100,11 @@ async fn failure_delegate_auctioneer_redelegate() {
         &authority,
         &auctioneer_authority.pubkey(),
         &auctioneer,
+        all_scopes(),
     );
     context.banks_client.process_transaction(tx).await.unwrap();
 
-    // Auctioneer redelegation
+    // Second auctioneer on the same Auction House
 
     let new_auctioneer_authority = Keypair::new();
     let (new_auctioneer, _) =
@@ -94,6 +116,80 @@ async fn failure_delegate_auctioneer_redelegate() {
         &authority,
         &new_auctioneer_authority.pubkey(),
         &new_auctioneer,
+        vec![AuthorityScope::Deposit, AuthorityScope::Withdraw],
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let auction_house_account = context
+        .banks_client
+        .get_account(auction_house)
+        .await
+        .unwrap()
+        .expect("Auction House account not found");
+
+    let auction_house_data =
+        AuctionHouse::try_deserialize(&mut auction_house_account.data.as_ref()).unwrap();
+
+    assert!(auction_house_data.has_auctioneer);
+    assert_eq!(auction_house_data.auctioneer_count, 2);
+
+    // Undelegating one auctioneer keeps the other one active
+
+    let tx = undelegate(
+        &mut context,
+        &auction_house,
+        &authority,
+        &auctioneer_authority.pubkey(),
+        &auctioneer,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let auction_house_account = context
+        .banks_client
+        .get_account(auction_house)
+        .await
+        .unwrap()
+        .expect("Auction House account not found");
+
+    let auction_house_data =
+        AuctionHouse::try_deserialize(&mut auction_house_account.data.as_ref()).unwrap();
+
+    assert!(auction_house_data.has_auctioneer);
+    assert_eq!(auction_house_data.auctioneer_count, 1);
+}
+
+#[tokio::test]
+async fn failure_delegate_auctioneer_redelegate() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, _) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    // DelegateAuctioneer
+
+    let auctioneer_authority = Keypair::new();
+    let (auctioneer, _) = find_auctioneer_address(&auction_house, &auctioneer_authority.pubkey());
+
+    let tx = delegate(
+        &mut context,
+        &auction_house,
+        &authority,
+        &auctioneer_authority.pubkey(),
+        &auctioneer,
+        all_scopes(),
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Same auctioneer redelegation
+
+    let tx = delegate(
+        &mut context,
+        &auction_house,
+        &authority,
+        &auctioneer_authority.pubkey(),
+        &auctioneer,
+        vec![AuthorityScope::Sell],
     );
 
     let tx_error = context
@@ -102,8 +198,184 @@ async fn failure_delegate_auctioneer_redelegate() {
         .await
         .unwrap_err();
 
-    assert_error(
-        tx_error,
-        AuctionHouseError::AuctionHouseAlreadyDelegated.into(),
+    assert_error(tx_error, ERR_ACCOUNT_ALREADY_IN_USE);
+}
+
+#[tokio::test]
+async fn delegate_auctioneer_on_legacy_delegated_house_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, _) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let auctioneer_authority = Keypair::new();
+    let (auctioneer, _) = find_auctioneer_address(&auction_house, &auctioneer_authority.pubkey());
+
+    let tx = delegate(
+        &mut context,
+        &auction_house,
+        &authority,
+        &auctioneer_authority.pubkey(),
+        &auctioneer,
+        all_scopes(),
     );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Houses delegated before auctioneer_count was tracked have a zero count.
+    let mut legacy_data = get_auction_house_data(&mut context, &auction_house).await;
+    legacy_data.auctioneer_count = 0;
+    overwrite_account_data(&mut context, &auction_house, &legacy_data).await;
+
+    let new_auctioneer_authority = Keypair::new();
+    let (new_auctioneer, _) =
+        find_auctioneer_address(&auction_house, &new_auctioneer_authority.pubkey());
+
+    let tx = delegate(
+        &mut context,
+        &auction_house,
+        &authority,
+        &new_auctioneer_authority.pubkey(),
+        &new_auctioneer,
+        all_scopes(),
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let auction_house_data = get_auction_house_data(&mut context, &auction_house).await;
+    assert_eq!(auction_house_data.auctioneer_count, 2);
+
+    // Undelegating the legacy auctioneer keeps the new one active.
+    let tx = undelegate(
+        &mut context,
+        &auction_house,
+        &authority,
+        &auctioneer_authority.pubkey(),
+        &auctioneer,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let auction_house_data = get_auction_house_data(&mut context, &auction_house).await;
+    assert!(auction_house_data.has_auctioneer);
+    assert_eq!(auction_house_data.auctioneer_count, 1);
+}
+
+#[tokio::test]
+async fn legacy_auctioneer_without_scopes_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, auction_house_data) =
+        create_auction_house(&mut context, 100, false)
+            .await
+            .expect("Failed to create Auction House");
+
+    let auctioneer_authority = Keypair::new();
+    let (auctioneer, _) = find_auctioneer_address(&auction_house, &auctioneer_authority.pubkey());
+
+    let tx = delegate(
+        &mut context,
+        &auction_house,
+        &authority,
+        &auctioneer_authority.pubkey(),
+        &auctioneer,
+        vec![AuthorityScope::Sell],
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Auctioneers delegated before scopes were stored have an empty mask.
+    let auctioneer_account = context
+        .banks_client
+        .get_account(auctioneer)
+        .await
+        .unwrap()
+        .expect("Auctioneer account not found");
+    let mut legacy_auctioneer =
+        Auctioneer::try_deserialize(&mut auctioneer_account.data.as_ref()).unwrap();
+    legacy_auctioneer.scopes = 0;
+    overwrite_account_data(&mut context, &auctioneer, &legacy_auctioneer).await;
+
+    // AuctioneerDeposit
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 2 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let (escrow_payment_account, escrow_payment_bump) =
+        find_escrow_payment_account_address(&auction_house, &buyer.pubkey());
+
+    let data = auction_house::instruction::AuctioneerDeposit {
+        escrow_payment_bump,
+        amount: ONE_SOL,
+    };
+
+    let accounts = auction_house::accounts::AuctioneerDeposit {
+        wallet: buyer.pubkey(),
+        payment_account: buyer.pubkey(),
+        transfer_authority: buyer.pubkey(),
+        escrow_payment_account,
+        treasury_mint: auction_house_data.treasury_mint,
+        authority: auction_house_data.authority,
+        auctioneer_authority: auctioneer_authority.pubkey(),
+        auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        auctioneer,
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&buyer.pubkey()),
+        &[&buyer, &auctioneer_authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let escrow_payment_account = context
+        .banks_client
+        .get_account(escrow_payment_account)
+        .await
+        .unwrap()
+        .expect("Escrow payment account not found");
+    let rent = context.banks_client.get_rent().await.unwrap();
+    assert_eq!(
+        escrow_payment_account.lamports,
+        ONE_SOL + rent.minimum_balance(0)
+    );
+}
+
+#[tokio::test]
+async fn failure_delegate_auctioneer_without_scopes() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, _) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let auctioneer_authority = Keypair::new();
+    let (auctioneer, _) = find_auctioneer_address(&auction_house, &auctioneer_authority.pubkey());
+
+    let tx = delegate(
+        &mut context,
+        &auction_house,
+        &authority,
+        &auctioneer_authority.pubkey(),
+        &auctioneer,
+        vec![],
+    );
+
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::MissingAuctioneerScopes.into());
 }
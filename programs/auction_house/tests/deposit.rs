@@ -0,0 +1,120 @@
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
+use solana_program_test::tokio;
+use spl_token_2022::{extension::StateWithExtensions, state::Account as TokenAccount};
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn deposit_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 2 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let (deposit_accounts, deposit_tx) = deposit(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &buyer,
+        ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(deposit_tx)
+        .await
+        .unwrap();
+
+    let escrow_payment_account_data_len = 0;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let rent_exempt_min: u64 = rent.minimum_balance(escrow_payment_account_data_len);
+
+    let escrow_payment_account = context
+        .banks_client
+        .get_account(deposit_accounts.escrow_payment_account)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    assert_eq!(escrow_payment_account.lamports, ONE_SOL + rent_exempt_min);
+}
+
+#[tokio::test]
+async fn deposit_token_2022_transfer_fee_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    // 1% transfer fee on the treasury mint
+    let treasury_mint = create_transfer_fee_mint(&mut context, 100, u64::MAX)
+        .await
+        .expect("Failed to create treasury mint");
+
+    let (_, auction_house, auction_house_data) = create_auction_house_with_treasury_mint(
+        &mut context,
+        &treasury_mint.pubkey(),
+        &spl_token_2022::id(),
+        100,
+        false,
+    )
+    .await
+    .expect("Failed to create Auction House");
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 2 * ONE_SOL)
+        .await
+        .unwrap();
+    let payment_account = mint_token_2022(
+        &mut context,
+        &treasury_mint.pubkey(),
+        &buyer.pubkey(),
+        10_000,
+    )
+    .await
+    .expect("Failed to mint treasury tokens");
+
+    let (deposit_accounts, deposit_ix) = deposit_instruction(
+        &auction_house,
+        &auction_house_data,
+        &buyer.pubkey(),
+        &payment_account,
+        &spl_token_2022::id(),
+        1_000,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[deposit_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to deposit");
+
+    let escrow_payment_account = context
+        .banks_client
+        .get_account(deposit_accounts.escrow_payment_account)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let escrow = StateWithExtensions::<TokenAccount>::unpack(&escrow_payment_account.data).unwrap();
+
+    // The escrow is credited the full amount; the buyer covers the transfer fee
+    assert_eq!(escrow.base.amount, 1_000);
+
+    let payment_account = context
+        .banks_client
+        .get_account(payment_account)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let payment = StateWithExtensions::<TokenAccount>::unpack(&payment_account.data).unwrap();
+
+    assert_eq!(payment.base.amount, 10_000 - 1_011);
+}
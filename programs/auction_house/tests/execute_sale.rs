@@ -0,0 +1,876 @@
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
+use anchor_lang::{prelude::AccountMeta, solana_program::program_pack::Pack, ToAccountMetas};
+use anchor_spl::{associated_token::get_associated_token_address, token::spl_token};
+use auction_house::{errors::AuctionHouseError, events::SaleExecuted};
+use nft_minter::utils::Creator;
+use solana_program_test::tokio;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_token_2022::{extension::StateWithExtensions, state::Account as TokenAccount};
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn execute_sale_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let price = ONE_SOL;
+
+    // Sell
+
+    let (sell_accounts, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        price,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    // Buy
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let (buy_accounts, buy_tx) = buy(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer,
+        price,
+    );
+    context
+        .banks_client
+        .process_transaction(buy_tx)
+        .await
+        .unwrap();
+
+    // Execute sale
+
+    let (execute_sale_accounts, execute_sale_tx) = execute_sale(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        None,
+        &sell_accounts,
+        &buyer.pubkey(),
+        &buy_accounts.buyer_trade_state,
+        price,
+        &buyer,
+    );
+    let logs = process_transaction_with_logs(&mut context, execute_sale_tx)
+        .await
+        .unwrap();
+
+    let buyer_token_account = context
+        .banks_client
+        .get_account(get_associated_token_address(
+            &buyer.pubkey(),
+            &token.mint.pubkey(),
+        ))
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    let buyer_token_account_data =
+        spl_token::state::Account::unpack(&buyer_token_account.data).unwrap();
+
+    assert_eq!(buyer_token_account_data.amount, 1);
+
+    // Assert the sale event carries the payout breakdown
+    let event = find_event::<SaleExecuted>(&logs).expect("SaleExecuted event not emitted");
+    let auction_house_fee = price * 100 / 10000;
+    assert_eq!(event.auction_house, auction_house);
+    assert_eq!(event.buyer, buyer.pubkey());
+    assert_eq!(event.seller, token.owner.pubkey());
+    assert_eq!(event.token_mint, token.mint.pubkey());
+    assert_eq!(event.price, price);
+    assert_eq!(event.token_size, 1);
+    assert_eq!(event.royalties, 0);
+    assert_eq!(event.auction_house_fee, auction_house_fee);
+    assert_eq!(event.seller_proceeds, price - auction_house_fee);
+    assert!(!event.partial);
+
+    // Assert both trade states are closed
+    let seller_trade_state_account = context
+        .banks_client
+        .get_account(execute_sale_accounts.seller_trade_state)
+        .await
+        .unwrap();
+    assert!(seller_trade_state_account.is_none());
+
+    let buyer_trade_state_account = context
+        .banks_client
+        .get_account(execute_sale_accounts.buyer_trade_state)
+        .await
+        .unwrap();
+    assert!(buyer_trade_state_account.is_none());
+}
+
+#[tokio::test]
+async fn execute_sale_public_bid_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let price = ONE_SOL;
+
+    // Public buy placed before the token is listed
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let (public_buy_accounts, public_buy_tx) = public_buy(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer,
+        price,
+    );
+    context
+        .banks_client
+        .process_transaction(public_buy_tx)
+        .await
+        .unwrap();
+
+    // Sell
+
+    let (sell_accounts, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        price,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    // Execute sale
+
+    let (_, execute_sale_tx) = execute_sale(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        None,
+        &sell_accounts,
+        &buyer.pubkey(),
+        &public_buy_accounts.buyer_trade_state,
+        price,
+        &token.owner,
+    );
+    context
+        .banks_client
+        .process_transaction(execute_sale_tx)
+        .await
+        .unwrap();
+
+    let buyer_token_account = context
+        .banks_client
+        .get_account(get_associated_token_address(
+            &buyer.pubkey(),
+            &token.mint.pubkey(),
+        ))
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    let buyer_token_account_data =
+        spl_token::state::Account::unpack(&buyer_token_account.data).unwrap();
+
+    assert_eq!(buyer_token_account_data.amount, 1);
+
+    let buyer_trade_state_account = context
+        .banks_client
+        .get_account(public_buy_accounts.buyer_trade_state)
+        .await
+        .unwrap();
+    assert!(buyer_trade_state_account.is_none());
+}
+
+#[tokio::test]
+async fn failure_execute_sale_price_mismatch() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    // Sell
+
+    let (sell_accounts, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        2 * ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    // Buy below the listing price
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let bid_amount = ONE_SOL;
+
+    let (buy_accounts, buy_tx) = buy(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer,
+        bid_amount,
+    );
+    context
+        .banks_client
+        .process_transaction(buy_tx)
+        .await
+        .unwrap();
+
+    // Execute sale
+
+    let (_, execute_sale_tx) = execute_sale(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        None,
+        &sell_accounts,
+        &buyer.pubkey(),
+        &buy_accounts.buyer_trade_state,
+        bid_amount,
+        &buyer,
+    );
+
+    let tx_error = context
+        .banks_client
+        .process_transaction(execute_sale_tx)
+        .await
+        .unwrap_err();
+
+    // Seller trade state was derived from a different price
+    assert_error(tx_error, ERR_CONSTRAINT_SEEDS);
+}
+
+#[tokio::test]
+async fn execute_partial_sale_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_sft(&mut context, 10)
+        .await
+        .expect("Failed to create SFT");
+
+    // Sell 10 tokens for 0.1 SOL each
+
+    let listing_size = 10;
+    let listing_price = ONE_SOL;
+
+    let (sell_accounts, sell_ix) = sell_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        listing_price,
+        listing_size,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[sell_ix],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to sell SFT");
+
+    // Buy 3 of them
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let partial_size = 3;
+    let partial_price = listing_price / listing_size * partial_size;
+
+    let (buy_accounts, buy_ix) = buy_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer.pubkey(),
+        partial_price,
+        partial_size,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[buy_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Execute partial sale
+
+    let (execute_sale_accounts, execute_sale_ix) = execute_sale_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        None,
+        &sell_accounts,
+        &buyer.pubkey(),
+        &buy_accounts.buyer_trade_state,
+        listing_price,
+        listing_size,
+        Some(partial_size),
+        Some(partial_price),
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[execute_sale_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let buyer_token_account = context
+        .banks_client
+        .get_account(get_associated_token_address(
+            &buyer.pubkey(),
+            &token.mint.pubkey(),
+        ))
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    let buyer_token_account_data =
+        spl_token::state::Account::unpack(&buyer_token_account.data).unwrap();
+
+    assert_eq!(buyer_token_account_data.amount, partial_size);
+
+    // Assert the listing stays open for the remainder
+    let seller_trade_state_account = context
+        .banks_client
+        .get_account(execute_sale_accounts.seller_trade_state)
+        .await
+        .unwrap();
+    assert!(seller_trade_state_account.is_some());
+
+    let seller_token_account = context
+        .banks_client
+        .get_account(token.ata)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    let seller_token_account_data =
+        spl_token::state::Account::unpack(&seller_token_account.data).unwrap();
+
+    assert_eq!(
+        seller_token_account_data.amount,
+        listing_size - partial_size
+    );
+    assert_eq!(
+        seller_token_account_data.delegated_amount,
+        listing_size - partial_size
+    );
+}
+
+#[tokio::test]
+async fn execute_partial_sale_indivisible_price_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_sft(&mut context, 10)
+        .await
+        .expect("Failed to create SFT");
+
+    // A listing price that does not divide by the listing size
+
+    let listing_size = 10;
+    let listing_price = ONE_SOL + 9;
+
+    let (sell_accounts, sell_ix) = sell_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        listing_price,
+        listing_size,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[sell_ix],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to sell SFT");
+
+    // Half of the listing costs half of its price rounded down, not half of the rounded down
+    // per-token price
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let partial_size = 5;
+    let partial_price = ONE_SOL / 2 + 4;
+
+    let (buy_accounts, buy_ix) = buy_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer.pubkey(),
+        partial_price,
+        partial_size,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[buy_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let (_, execute_sale_ix) = execute_sale_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        None,
+        &sell_accounts,
+        &buyer.pubkey(),
+        &buy_accounts.buyer_trade_state,
+        listing_price,
+        listing_size,
+        Some(partial_size),
+        Some(partial_price),
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[execute_sale_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let buyer_token_account = context
+        .banks_client
+        .get_account(get_associated_token_address(
+            &buyer.pubkey(),
+            &token.mint.pubkey(),
+        ))
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    let buyer_token_account_data =
+        spl_token::state::Account::unpack(&buyer_token_account.data).unwrap();
+
+    assert_eq!(buyer_token_account_data.amount, partial_size);
+}
+
+#[tokio::test]
+async fn failure_execute_partial_sale_price_mismatch() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_sft(&mut context, 10)
+        .await
+        .expect("Failed to create SFT");
+
+    let listing_size = 10;
+    let listing_price = ONE_SOL;
+
+    let (sell_accounts, sell_ix) = sell_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        listing_price,
+        listing_size,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[sell_ix],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to sell SFT");
+
+    // Bid below the listing's per-token price
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let partial_size = 3;
+    let partial_price = listing_price / listing_size * partial_size - 1;
+
+    let (buy_accounts, buy_ix) = buy_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer.pubkey(),
+        partial_price,
+        partial_size,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[buy_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let (_, execute_sale_ix) = execute_sale_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        None,
+        &sell_accounts,
+        &buyer.pubkey(),
+        &buy_accounts.buyer_trade_state,
+        listing_price,
+        listing_size,
+        Some(partial_size),
+        Some(partial_price),
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[execute_sale_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::PartialPriceMismatch.into());
+}
+
+#[tokio::test]
+async fn execute_sale_programmable_success() {
+    let mut context = auction_house_pnft_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_pnft(&mut context)
+        .await
+        .expect("Failed to create pNFT");
+
+    let price = ONE_SOL;
+
+    // Sell
+
+    let (sell_accounts, mut sell_ix) =
+        sell_instruction(&auction_house, &auction_house_data, &token, price, 1);
+    add_programmable_sell_accounts(&mut sell_ix, &token);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[sell_ix],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to sell pNFT");
+
+    // Buy
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let (buy_accounts, buy_tx) = buy(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer,
+        price,
+    );
+    context
+        .banks_client
+        .process_transaction(buy_tx)
+        .await
+        .unwrap();
+
+    // Execute sale
+
+    let (execute_sale_accounts, mut execute_sale_ix) = execute_sale_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        None,
+        &sell_accounts,
+        &buyer.pubkey(),
+        &buy_accounts.buyer_trade_state,
+        price,
+        1,
+        None,
+        None,
+    );
+    add_programmable_execute_sale_accounts(&mut execute_sale_ix, &token, &buyer.pubkey());
+
+    let tx = Transaction::new_signed_with_payer(
+        &[execute_sale_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let buyer_token_account = context
+        .banks_client
+        .get_account(get_associated_token_address(
+            &buyer.pubkey(),
+            &token.mint.pubkey(),
+        ))
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    let buyer_token_account_data =
+        spl_token::state::Account::unpack(&buyer_token_account.data).unwrap();
+
+    assert_eq!(buyer_token_account_data.amount, 1);
+
+    // Assert the listing is closed
+    let seller_trade_state_account = context
+        .banks_client
+        .get_account(execute_sale_accounts.seller_trade_state)
+        .await
+        .unwrap();
+    assert!(seller_trade_state_account.is_none());
+}
+
+#[tokio::test]
+async fn execute_sale_token_2022_transfer_fee_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    // 1% transfer fee on the treasury mint
+    let treasury_mint = create_transfer_fee_mint(&mut context, 100, u64::MAX)
+        .await
+        .expect("Failed to create treasury mint");
+
+    let (_, auction_house, auction_house_data) = create_auction_house_with_treasury_mint(
+        &mut context,
+        &treasury_mint.pubkey(),
+        &spl_token_2022::id(),
+        100,
+        false,
+    )
+    .await
+    .expect("Failed to create Auction House");
+
+    let creator = Keypair::new();
+    let metadata_creators = vec![Creator {
+        address: creator.pubkey(),
+        verified: false,
+        share: 100,
+    }];
+    let token = create_nft(&mut context, Some(metadata_creators))
+        .await
+        .expect("Failed to create NFT");
+
+    let price = 100_000;
+
+    // Sell
+
+    let (sell_accounts, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        price,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    // Buy, paying from a Token-2022 account of the treasury mint
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+    let payment_account = mint_token_2022(
+        &mut context,
+        &treasury_mint.pubkey(),
+        &buyer.pubkey(),
+        2 * price,
+    )
+    .await
+    .expect("Failed to mint treasury tokens");
+
+    let (mut buy_accounts, mut buy_ix) = buy_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer.pubkey(),
+        price,
+        1,
+    );
+    buy_accounts.payment_account = payment_account;
+    buy_accounts.token_program = spl_token_2022::id();
+    buy_ix.accounts = buy_accounts.to_account_metas(None);
+    let tx = Transaction::new_signed_with_payer(
+        &[buy_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to buy NFT");
+
+    // Execute sale
+
+    let (mut execute_sale_accounts, mut execute_sale_ix) = execute_sale_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        None,
+        &sell_accounts,
+        &buyer.pubkey(),
+        &buy_accounts.buyer_trade_state,
+        price,
+        1,
+        None,
+        None,
+    );
+    let seller_payment_receipt_account = get_associated_token_address_with_program_id(
+        &token.owner.pubkey(),
+        &treasury_mint.pubkey(),
+        &spl_token_2022::id(),
+    );
+    let creator_token_account = get_associated_token_address_with_program_id(
+        &creator.pubkey(),
+        &treasury_mint.pubkey(),
+        &spl_token_2022::id(),
+    );
+    execute_sale_accounts.seller_payment_receipt_account = seller_payment_receipt_account;
+    execute_sale_accounts.treasury_token_program = spl_token_2022::id();
+    execute_sale_ix.accounts = execute_sale_accounts.to_account_metas(None);
+    execute_sale_ix.accounts.extend([
+        AccountMeta::new(creator.pubkey(), false),
+        AccountMeta::new(creator_token_account, false),
+    ]);
+    let tx = Transaction::new_signed_with_payer(
+        &[execute_sale_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+    let logs = process_transaction_with_logs(&mut context, tx)
+        .await
+        .unwrap();
+
+    // 10 basis points of royalties and 100 of house fee; each leg loses 1% to the transfer fee
+    let royalties = price / 1000;
+    let auction_house_fee = price * 100 / 10000;
+    let seller_share = price - royalties - auction_house_fee;
+    let event = find_event::<SaleExecuted>(&logs).expect("SaleExecuted event not emitted");
+    assert_eq!(event.price, price);
+    assert_eq!(event.royalties, royalties - royalties / 100);
+    assert_eq!(
+        event.auction_house_fee,
+        auction_house_fee - auction_house_fee / 100
+    );
+    assert_eq!(event.seller_proceeds, seller_share - seller_share / 100);
+
+    // The event matches what the seller and creator actually received
+    for (token_account, received) in [
+        (seller_payment_receipt_account, event.seller_proceeds),
+        (creator_token_account, event.royalties),
+    ] {
+        let account = context
+            .banks_client
+            .get_account(token_account)
+            .await
+            .expect("Account not found")
+            .expect("Account is empty");
+        let account = StateWithExtensions::<TokenAccount>::unpack(&account.data).unwrap();
+        assert_eq!(account.base.amount, received);
+    }
+
+    // The escrow paid out exactly the price
+    let escrow_payment_account = context
+        .banks_client
+        .get_account(execute_sale_accounts.escrow_payment_account)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let escrow = StateWithExtensions::<TokenAccount>::unpack(&escrow_payment_account.data).unwrap();
+    assert_eq!(escrow.base.amount, 0);
+}
@@ -0,0 +1,114 @@
+use anchor_client::solana_sdk::{
+    account::{Account, AccountSharedData},
+    signer::Signer,
+    transaction::Transaction,
+};
+use anchor_lang::AccountSerialize;
+use auction_house::{
+    constants::{AUCTION_HOUSE_SIZE, AUCTION_HOUSE_VERSION},
+    errors::AuctionHouseError,
+};
+use solana_program_test::tokio;
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn migrate_auction_house_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, auction_house_data) =
+        create_auction_house(&mut context, 100, false)
+            .await
+            .expect("Failed to create Auction House");
+
+    assert_eq!(auction_house_data.version, AUCTION_HOUSE_VERSION);
+
+    // Rewrite the account as a legacy house: no version byte and no padding.
+    let mut legacy_data = auction_house_data.clone();
+    legacy_data.version = 0;
+    // Delegated before auctioneer_count was tracked.
+    legacy_data.has_auctioneer = true;
+    legacy_data.auctioneer_count = 0;
+    let mut data = Vec::new();
+    legacy_data.try_serialize(&mut data).unwrap();
+    // The version is the last field, a single byte.
+    data.truncate(data.len() - 1);
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    context.set_account(
+        &auction_house,
+        &AccountSharedData::from(Account {
+            lamports: rent.minimum_balance(data.len()),
+            data,
+            owner: auction_house::id(),
+            executable: false,
+            rent_epoch: 0,
+        }),
+    );
+
+    // MigrateAuctionHouse
+
+    let ix = migrate_auction_house_instruction(&auction_house, &auction_house_data);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let auction_house_account = context
+        .banks_client
+        .get_account(auction_house)
+        .await
+        .unwrap()
+        .expect("Auction House account not found");
+    assert_eq!(auction_house_account.data.len(), AUCTION_HOUSE_SIZE);
+    assert_eq!(
+        auction_house_account.lamports,
+        rent.minimum_balance(AUCTION_HOUSE_SIZE)
+    );
+
+    let migrated_data = get_auction_house_data(&mut context, &auction_house).await;
+    assert_eq!(migrated_data.version, AUCTION_HOUSE_VERSION);
+    assert!(migrated_data.has_auctioneer);
+    assert_eq!(migrated_data.auctioneer_count, 1);
+    assert_eq!(migrated_data.authority, auction_house_data.authority);
+    assert_eq!(
+        migrated_data.treasury_withdrawal_destination,
+        auction_house_data.treasury_withdrawal_destination
+    );
+    assert_eq!(
+        migrated_data.seller_fee_basis_points,
+        auction_house_data.seller_fee_basis_points
+    );
+}
+
+#[tokio::test]
+async fn migrate_current_auction_house_failure() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, auction_house_data) =
+        create_auction_house(&mut context, 100, false)
+            .await
+            .expect("Failed to create Auction House");
+
+    let ix = migrate_auction_house_instruction(&auction_house, &auction_house_data);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(
+        tx_error,
+        AuctionHouseError::AuctionHouseAlreadyMigrated.into(),
+    );
+}
@@ -0,0 +1,128 @@
+use auction_house::errors::AuctionHouseError;
+use solana_program_test::tokio;
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn sell_while_paused_failure() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, auction_house_data) =
+        create_auction_house(&mut context, 100, false)
+            .await
+            .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let pause_tx = set_pause_state(&mut context, &auction_house, &authority, true);
+    context
+        .banks_client
+        .process_transaction(pause_tx)
+        .await
+        .unwrap();
+
+    let auction_house_data = get_auction_house_data(&mut context, &auction_house).await;
+    assert!(auction_house_data.is_paused);
+
+    let (_, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        ONE_SOL,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::AuctionHousePaused.into());
+
+    // Resume
+
+    let resume_tx = set_pause_state(&mut context, &auction_house, &authority, false);
+    context
+        .banks_client
+        .process_transaction(resume_tx)
+        .await
+        .unwrap();
+
+    let auction_house_data = get_auction_house_data(&mut context, &auction_house).await;
+    assert!(!auction_house_data.is_paused);
+    context.get_new_latest_blockhash().await.unwrap();
+
+    let (_, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .expect("Failed to sell NFT");
+}
+
+#[tokio::test]
+async fn cancel_while_paused_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, auction_house_data) =
+        create_auction_house(&mut context, 100, false)
+            .await
+            .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let (sell_accounts, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    let pause_tx = set_pause_state(&mut context, &auction_house, &authority, true);
+    context
+        .banks_client
+        .process_transaction(pause_tx)
+        .await
+        .unwrap();
+
+    let (_, cancel_tx) = cancel(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &token.owner,
+        &sell_accounts.seller_trade_state,
+        ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(cancel_tx)
+        .await
+        .unwrap();
+
+    let seller_trade_state_account = context
+        .banks_client
+        .get_account(sell_accounts.seller_trade_state)
+        .await
+        .unwrap();
+
+    // Listings can still be taken down while trading is paused
+    assert!(seller_trade_state_account.is_none());
+}
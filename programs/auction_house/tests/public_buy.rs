@@ -0,0 +1,64 @@
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
+use auction_house::pda::find_trade_state_address;
+use solana_program_test::tokio;
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn public_buy_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let bid_amount = ONE_SOL;
+
+    let (public_buy_accounts, public_buy_tx) = public_buy(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer,
+        bid_amount,
+    );
+    context
+        .banks_client
+        .process_transaction(public_buy_tx)
+        .await
+        .unwrap();
+
+    let buyer_trade_state_account = context
+        .banks_client
+        .get_account(public_buy_accounts.buyer_trade_state)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    assert_eq!(buyer_trade_state_account.data.len(), 1);
+
+    // Public trade state is not derived from the token account
+    let (private_buyer_trade_state, _) = find_trade_state_address(
+        &buyer.pubkey(),
+        &auction_house,
+        &token.ata,
+        &auction_house_data.treasury_mint,
+        &token.mint.pubkey(),
+        bid_amount,
+        1,
+    );
+    assert_ne!(
+        public_buy_accounts.buyer_trade_state,
+        private_buyer_trade_state
+    );
+}
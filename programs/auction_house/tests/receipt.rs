@@ -0,0 +1,807 @@
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
+use anchor_lang::{prelude::Pubkey, AccountDeserialize, ToAccountMetas};
+use auction_house::{
+    errors::AuctionHouseError,
+    pda::find_trade_state_v2_address,
+    receipt::{
+        BidReceipt, ListingReceipt, PurchaseReceipt, BUY_AUCTION_HOUSE_INDEX, BUY_METADATA_INDEX,
+        BUY_TOKEN_ACCOUNT_INDEX, BUY_TRADE_STATE_INDEX, BUY_WALLET_INDEX, CANCEL_TRADE_STATE_INDEX,
+        EXECUTE_SALE_AUCTION_HOUSE_INDEX, EXECUTE_SALE_BUYER_INDEX,
+        EXECUTE_SALE_BUYER_TRADE_STATE_INDEX, EXECUTE_SALE_METADATA_INDEX,
+        EXECUTE_SALE_SELLER_INDEX, EXECUTE_SALE_SELLER_TRADE_STATE_INDEX,
+        EXECUTE_SALE_TOKEN_MINT_INDEX, SELL_AUCTION_HOUSE_INDEX, SELL_METADATA_INDEX,
+        SELL_TOKEN_ACCOUNT_INDEX, SELL_TRADE_STATE_INDEX, SELL_WALLET_INDEX,
+    },
+    trade_state::TradeSide,
+};
+use solana_program_test::tokio;
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn print_and_cancel_listing_receipt_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    // Sell and print listing receipt
+
+    let (sell_accounts, sell_ix) =
+        sell_instruction(&auction_house, &auction_house_data, &token, ONE_SOL, 1);
+    let (receipt_accounts, receipt_ix) = print_listing_receipt_instruction(
+        &token.owner.pubkey(),
+        &sell_accounts.seller_trade_state,
+        &token.ata,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[sell_ix, receipt_ix],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    let receipt_account = context
+        .banks_client
+        .get_account(receipt_accounts.receipt)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let receipt = ListingReceipt::try_deserialize(&mut receipt_account.data.as_ref()).unwrap();
+
+    assert_eq!(receipt.trade_state, sell_accounts.seller_trade_state);
+    assert_eq!(receipt.auction_house, auction_house);
+    assert_eq!(receipt.seller, token.owner.pubkey());
+    assert_eq!(receipt.metadata, token.metadata);
+    assert_eq!(receipt.token_mint, token.mint.pubkey());
+    assert_eq!(receipt.price, ONE_SOL);
+    assert_eq!(receipt.token_size, 1);
+    assert_eq!(receipt.purchase_receipt, None);
+    assert_eq!(receipt.canceled_at, None);
+
+    // Cancel and mark listing receipt as canceled
+
+    let (_, cancel_ix) = cancel_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &token.owner.pubkey(),
+        &sell_accounts.seller_trade_state,
+        ONE_SOL,
+    );
+    let (_, cancel_receipt_ix) =
+        cancel_listing_receipt_instruction(&sell_accounts.seller_trade_state);
+    let tx = Transaction::new_signed_with_payer(
+        &[cancel_ix, cancel_receipt_ix],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to cancel listing");
+
+    let receipt_account = context
+        .banks_client
+        .get_account(receipt_accounts.receipt)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let receipt = ListingReceipt::try_deserialize(&mut receipt_account.data.as_ref()).unwrap();
+
+    assert!(receipt.canceled_at.is_some());
+}
+
+#[tokio::test]
+async fn print_purchase_receipt_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let price = ONE_SOL;
+
+    // Sell and print listing receipt
+
+    let (sell_accounts, sell_ix) =
+        sell_instruction(&auction_house, &auction_house_data, &token, price, 1);
+    let (_, receipt_ix) = print_listing_receipt_instruction(
+        &token.owner.pubkey(),
+        &sell_accounts.seller_trade_state,
+        &token.ata,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[sell_ix, receipt_ix],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    // Buy and print bid receipt
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let (buy_accounts, buy_ix) = buy_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer.pubkey(),
+        price,
+        1,
+    );
+    let (bid_receipt_accounts, receipt_ix) =
+        print_bid_receipt_instruction(&buyer.pubkey(), &buy_accounts.buyer_trade_state, &token.ata);
+    let tx = Transaction::new_signed_with_payer(
+        &[buy_ix, receipt_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to buy NFT");
+
+    let bid_receipt_account = context
+        .banks_client
+        .get_account(bid_receipt_accounts.receipt)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let bid_receipt = BidReceipt::try_deserialize(&mut bid_receipt_account.data.as_ref()).unwrap();
+
+    assert_eq!(bid_receipt.buyer, buyer.pubkey());
+    assert_eq!(bid_receipt.token_account, Some(token.ata));
+    assert_eq!(bid_receipt.price, price);
+
+    // Execute sale and print purchase receipt
+
+    let (execute_sale_accounts, execute_sale_ix) = execute_sale_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        None,
+        &sell_accounts,
+        &buyer.pubkey(),
+        &buy_accounts.buyer_trade_state,
+        price,
+        1,
+        None,
+        None,
+    );
+    let (purchase_receipt_accounts, receipt_ix) = print_purchase_receipt_instruction(
+        &buyer.pubkey(),
+        &execute_sale_accounts.seller_trade_state,
+        &execute_sale_accounts.buyer_trade_state,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[execute_sale_ix, receipt_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to execute sale");
+
+    let purchase_receipt_account = context
+        .banks_client
+        .get_account(purchase_receipt_accounts.purchase_receipt)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let purchase_receipt =
+        PurchaseReceipt::try_deserialize(&mut purchase_receipt_account.data.as_ref()).unwrap();
+
+    assert_eq!(purchase_receipt.buyer, buyer.pubkey());
+    assert_eq!(purchase_receipt.seller, token.owner.pubkey());
+    assert_eq!(purchase_receipt.auction_house, auction_house);
+    assert_eq!(purchase_receipt.token_mint, token.mint.pubkey());
+    assert_eq!(purchase_receipt.price, price);
+    assert_eq!(purchase_receipt.token_size, 1);
+
+    // Assert listing and bid receipts point at the purchase receipt
+    let listing_receipt_account = context
+        .banks_client
+        .get_account(purchase_receipt_accounts.listing_receipt)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let listing_receipt =
+        ListingReceipt::try_deserialize(&mut listing_receipt_account.data.as_ref()).unwrap();
+    assert_eq!(
+        listing_receipt.purchase_receipt,
+        Some(purchase_receipt_accounts.purchase_receipt)
+    );
+
+    let bid_receipt_account = context
+        .banks_client
+        .get_account(purchase_receipt_accounts.bid_receipt)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let bid_receipt = BidReceipt::try_deserialize(&mut bid_receipt_account.data.as_ref()).unwrap();
+    assert_eq!(
+        bid_receipt.purchase_receipt,
+        Some(purchase_receipt_accounts.purchase_receipt)
+    );
+}
+
+#[tokio::test]
+async fn print_v2_receipts_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    // List with sell_v2 and print listing receipt
+
+    let (sell_accounts, sell_ix) = sell_v2_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        ONE_SOL,
+        1,
+        None,
+    );
+    let (listing_receipt_accounts, receipt_ix) = print_listing_receipt_instruction(
+        &token.owner.pubkey(),
+        &sell_accounts.seller_trade_state,
+        &token.ata,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[sell_ix, receipt_ix],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    // Amend the listing price, which rewrites the live receipt
+
+    let price = 2 * ONE_SOL;
+    let (_, sell_ix) =
+        sell_v2_instruction(&auction_house, &auction_house_data, &token, price, 1, None);
+    let (_, receipt_ix) = print_listing_receipt_instruction(
+        &token.owner.pubkey(),
+        &sell_accounts.seller_trade_state,
+        &token.ata,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[sell_ix, receipt_ix],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to amend listing");
+
+    let listing_receipt_account = context
+        .banks_client
+        .get_account(listing_receipt_accounts.receipt)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let listing_receipt =
+        ListingReceipt::try_deserialize(&mut listing_receipt_account.data.as_ref()).unwrap();
+
+    let (_, seller_trade_state_bump) = find_trade_state_v2_address(
+        &token.owner.pubkey(),
+        &auction_house,
+        &token.ata,
+        TradeSide::Sell,
+    );
+    assert_eq!(
+        listing_receipt.trade_state,
+        sell_accounts.seller_trade_state
+    );
+    assert_eq!(listing_receipt.seller, token.owner.pubkey());
+    assert_eq!(listing_receipt.price, price);
+    assert_eq!(listing_receipt.trade_state_bump, seller_trade_state_bump);
+
+    // Bid with buy_v2 and print bid receipt
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let (buy_accounts, buy_ix) = buy_v2_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer.pubkey(),
+        price,
+        1,
+        None,
+    );
+    let (bid_receipt_accounts, receipt_ix) =
+        print_bid_receipt_instruction(&buyer.pubkey(), &buy_accounts.buyer_trade_state, &token.ata);
+    let tx = Transaction::new_signed_with_payer(
+        &[buy_ix, receipt_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to buy NFT");
+
+    let bid_receipt_account = context
+        .banks_client
+        .get_account(bid_receipt_accounts.receipt)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let bid_receipt = BidReceipt::try_deserialize(&mut bid_receipt_account.data.as_ref()).unwrap();
+
+    assert_eq!(bid_receipt.buyer, buyer.pubkey());
+    assert_eq!(bid_receipt.token_account, Some(token.ata));
+    assert_eq!(bid_receipt.price, price);
+
+    // Execute sale with execute_sale_v2 and print purchase receipt
+
+    let (_, execute_sale_ix) = execute_sale_v2_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &sell_accounts,
+        &buyer.pubkey(),
+        &buy_accounts.buyer_trade_state,
+        price,
+        1,
+        None,
+        None,
+    );
+    let (purchase_receipt_accounts, receipt_ix) = print_purchase_receipt_instruction(
+        &buyer.pubkey(),
+        &sell_accounts.seller_trade_state,
+        &buy_accounts.buyer_trade_state,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[execute_sale_ix, receipt_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to execute sale");
+
+    let purchase_receipt_account = context
+        .banks_client
+        .get_account(purchase_receipt_accounts.purchase_receipt)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let purchase_receipt =
+        PurchaseReceipt::try_deserialize(&mut purchase_receipt_account.data.as_ref()).unwrap();
+
+    assert_eq!(purchase_receipt.buyer, buyer.pubkey());
+    assert_eq!(purchase_receipt.seller, token.owner.pubkey());
+    assert_eq!(purchase_receipt.price, price);
+    assert_eq!(purchase_receipt.token_size, 1);
+}
+
+#[tokio::test]
+async fn failure_print_listing_receipt_instruction_mismatch() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    // Print a listing receipt after a buy instead of a sell
+
+    let (buy_accounts, buy_ix) = buy_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer.pubkey(),
+        ONE_SOL,
+        1,
+    );
+    let (_, receipt_ix) = print_listing_receipt_instruction(
+        &buyer.pubkey(),
+        &buy_accounts.buyer_trade_state,
+        &token.ata,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[buy_ix, receipt_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::InstructionMismatch.into());
+}
+
+#[tokio::test]
+async fn failure_print_listing_receipt_already_printed() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let (sell_accounts, sell_ix) =
+        sell_instruction(&auction_house, &auction_house_data, &token, ONE_SOL, 1);
+    let (_, receipt_ix) = print_listing_receipt_instruction(
+        &token.owner.pubkey(),
+        &sell_accounts.seller_trade_state,
+        &token.ata,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[sell_ix.clone(), receipt_ix.clone()],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    // Listing again at the same price keeps the trade state, and the live receipt can't be overwritten
+
+    context.get_new_latest_blockhash().await.unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[sell_ix, receipt_ix],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::ReceiptAlreadyPrinted.into());
+}
+
+#[tokio::test]
+async fn reprint_canceled_listing_receipt_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let (sell_accounts, sell_ix) =
+        sell_instruction(&auction_house, &auction_house_data, &token, ONE_SOL, 1);
+    let (receipt_accounts, receipt_ix) = print_listing_receipt_instruction(
+        &token.owner.pubkey(),
+        &sell_accounts.seller_trade_state,
+        &token.ata,
+    );
+    let (_, cancel_ix) = cancel_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &token.owner.pubkey(),
+        &sell_accounts.seller_trade_state,
+        ONE_SOL,
+    );
+    let (_, cancel_receipt_ix) =
+        cancel_listing_receipt_instruction(&sell_accounts.seller_trade_state);
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            sell_ix.clone(),
+            receipt_ix.clone(),
+            cancel_ix,
+            cancel_receipt_ix,
+        ],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to list and cancel NFT");
+
+    // Listing again at the same price reuses the trade state and its canceled receipt
+
+    context.get_new_latest_blockhash().await.unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[sell_ix, receipt_ix],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to relist NFT");
+
+    let receipt_account = context
+        .banks_client
+        .get_account(receipt_accounts.receipt)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let receipt = ListingReceipt::try_deserialize(&mut receipt_account.data.as_ref()).unwrap();
+
+    assert_eq!(receipt.trade_state, sell_accounts.seller_trade_state);
+    assert_eq!(receipt.price, ONE_SOL);
+    assert_eq!(receipt.purchase_receipt, None);
+    assert_eq!(receipt.canceled_at, None);
+}
+
+#[test]
+fn receipt_account_indices_match_accounts_structs() {
+    let sell = auction_house::accounts::Sell {
+        wallet: Pubkey::new_unique(),
+        token_account: Pubkey::new_unique(),
+        metadata: Pubkey::new_unique(),
+        authority: Pubkey::new_unique(),
+        auction_house: Pubkey::new_unique(),
+        auction_house_fee_account: Pubkey::new_unique(),
+        seller_trade_state: Pubkey::new_unique(),
+        free_seller_trade_state: Pubkey::new_unique(),
+        program_as_signer: Pubkey::new_unique(),
+        token_program: Pubkey::new_unique(),
+        system_program: Pubkey::new_unique(),
+        rent: Pubkey::new_unique(),
+    };
+    let sell_v2 = auction_house::accounts::SellV2 {
+        wallet: sell.wallet,
+        token_account: sell.token_account,
+        metadata: sell.metadata,
+        authority: sell.authority,
+        auction_house: sell.auction_house,
+        auction_house_fee_account: sell.auction_house_fee_account,
+        seller_trade_state: sell.seller_trade_state,
+        program_as_signer: sell.program_as_signer,
+        token_program: sell.token_program,
+        system_program: sell.system_program,
+        rent: sell.rent,
+    };
+    for metas in [sell.to_account_metas(None), sell_v2.to_account_metas(None)] {
+        assert_eq!(metas[SELL_WALLET_INDEX].pubkey, sell.wallet);
+        assert_eq!(metas[SELL_TOKEN_ACCOUNT_INDEX].pubkey, sell.token_account);
+        assert_eq!(metas[SELL_METADATA_INDEX].pubkey, sell.metadata);
+        assert_eq!(metas[SELL_AUCTION_HOUSE_INDEX].pubkey, sell.auction_house);
+        assert_eq!(
+            metas[SELL_TRADE_STATE_INDEX].pubkey,
+            sell.seller_trade_state
+        );
+    }
+
+    let buy = auction_house::accounts::Buy {
+        wallet: Pubkey::new_unique(),
+        payment_account: Pubkey::new_unique(),
+        transfer_authority: Pubkey::new_unique(),
+        treasury_mint: Pubkey::new_unique(),
+        token_account: Pubkey::new_unique(),
+        metadata: Pubkey::new_unique(),
+        escrow_payment_account: Pubkey::new_unique(),
+        authority: Pubkey::new_unique(),
+        auction_house: Pubkey::new_unique(),
+        auction_house_fee_account: Pubkey::new_unique(),
+        buyer_trade_state: Pubkey::new_unique(),
+        token_program: Pubkey::new_unique(),
+        system_program: Pubkey::new_unique(),
+        rent: Pubkey::new_unique(),
+    };
+    let public_buy = auction_house::accounts::PublicBuy {
+        wallet: buy.wallet,
+        payment_account: buy.payment_account,
+        transfer_authority: buy.transfer_authority,
+        treasury_mint: buy.treasury_mint,
+        token_account: buy.token_account,
+        metadata: buy.metadata,
+        escrow_payment_account: buy.escrow_payment_account,
+        authority: buy.authority,
+        auction_house: buy.auction_house,
+        auction_house_fee_account: buy.auction_house_fee_account,
+        buyer_trade_state: buy.buyer_trade_state,
+        token_program: buy.token_program,
+        system_program: buy.system_program,
+        rent: buy.rent,
+    };
+    let buy_v2 = auction_house::accounts::BuyV2 {
+        wallet: buy.wallet,
+        payment_account: buy.payment_account,
+        transfer_authority: buy.transfer_authority,
+        treasury_mint: buy.treasury_mint,
+        token_account: buy.token_account,
+        metadata: buy.metadata,
+        escrow_payment_account: buy.escrow_payment_account,
+        authority: buy.authority,
+        auction_house: buy.auction_house,
+        auction_house_fee_account: buy.auction_house_fee_account,
+        buyer_trade_state: buy.buyer_trade_state,
+        token_program: buy.token_program,
+        system_program: buy.system_program,
+        rent: buy.rent,
+    };
+    let public_buy_v2 = auction_house::accounts::PublicBuyV2 {
+        wallet: buy.wallet,
+        payment_account: buy.payment_account,
+        transfer_authority: buy.transfer_authority,
+        treasury_mint: buy.treasury_mint,
+        token_account: buy.token_account,
+        metadata: buy.metadata,
+        escrow_payment_account: buy.escrow_payment_account,
+        authority: buy.authority,
+        auction_house: buy.auction_house,
+        auction_house_fee_account: buy.auction_house_fee_account,
+        buyer_trade_state: buy.buyer_trade_state,
+        token_program: buy.token_program,
+        system_program: buy.system_program,
+        rent: buy.rent,
+    };
+    for metas in [
+        buy.to_account_metas(None),
+        public_buy.to_account_metas(None),
+        buy_v2.to_account_metas(None),
+        public_buy_v2.to_account_metas(None),
+    ] {
+        assert_eq!(metas[BUY_WALLET_INDEX].pubkey, buy.wallet);
+        assert_eq!(metas[BUY_TOKEN_ACCOUNT_INDEX].pubkey, buy.token_account);
+        assert_eq!(metas[BUY_METADATA_INDEX].pubkey, buy.metadata);
+        assert_eq!(metas[BUY_AUCTION_HOUSE_INDEX].pubkey, buy.auction_house);
+        assert_eq!(metas[BUY_TRADE_STATE_INDEX].pubkey, buy.buyer_trade_state);
+    }
+
+    let execute_sale = auction_house::accounts::ExecuteSale {
+        buyer: Pubkey::new_unique(),
+        seller: Pubkey::new_unique(),
+        token_account: Pubkey::new_unique(),
+        token_mint: Pubkey::new_unique(),
+        metadata: Pubkey::new_unique(),
+        treasury_mint: Pubkey::new_unique(),
+        escrow_payment_account: Pubkey::new_unique(),
+        seller_payment_receipt_account: Pubkey::new_unique(),
+        buyer_receipt_token_account: Pubkey::new_unique(),
+        authority: Pubkey::new_unique(),
+        auction_house: Pubkey::new_unique(),
+        auction_house_fee_account: Pubkey::new_unique(),
+        auction_house_treasury: Pubkey::new_unique(),
+        buyer_trade_state: Pubkey::new_unique(),
+        seller_trade_state: Pubkey::new_unique(),
+        free_trade_state: Pubkey::new_unique(),
+        token_program: Pubkey::new_unique(),
+        system_program: Pubkey::new_unique(),
+        associated_token_program: Pubkey::new_unique(),
+        program_as_signer: Pubkey::new_unique(),
+        rent: Pubkey::new_unique(),
+        treasury_token_program: Pubkey::new_unique(),
+    };
+    let execute_sale_v2 = auction_house::accounts::ExecuteSaleV2 {
+        buyer: execute_sale.buyer,
+        seller: execute_sale.seller,
+        token_account: execute_sale.token_account,
+        token_mint: execute_sale.token_mint,
+        metadata: execute_sale.metadata,
+        treasury_mint: execute_sale.treasury_mint,
+        escrow_payment_account: execute_sale.escrow_payment_account,
+        seller_payment_receipt_account: execute_sale.seller_payment_receipt_account,
+        buyer_receipt_token_account: execute_sale.buyer_receipt_token_account,
+        authority: execute_sale.authority,
+        auction_house: execute_sale.auction_house,
+        auction_house_fee_account: execute_sale.auction_house_fee_account,
+        auction_house_treasury: execute_sale.auction_house_treasury,
+        buyer_trade_state: execute_sale.buyer_trade_state,
+        seller_trade_state: execute_sale.seller_trade_state,
+        token_program: execute_sale.token_program,
+        system_program: execute_sale.system_program,
+        associated_token_program: execute_sale.associated_token_program,
+        program_as_signer: execute_sale.program_as_signer,
+        rent: execute_sale.rent,
+        treasury_token_program: execute_sale.treasury_token_program,
+    };
+    for metas in [
+        execute_sale.to_account_metas(None),
+        execute_sale_v2.to_account_metas(None),
+    ] {
+        assert_eq!(metas[EXECUTE_SALE_BUYER_INDEX].pubkey, execute_sale.buyer);
+        assert_eq!(metas[EXECUTE_SALE_SELLER_INDEX].pubkey, execute_sale.seller);
+        assert_eq!(
+            metas[EXECUTE_SALE_TOKEN_MINT_INDEX].pubkey,
+            execute_sale.token_mint
+        );
+        assert_eq!(
+            metas[EXECUTE_SALE_METADATA_INDEX].pubkey,
+            execute_sale.metadata
+        );
+        assert_eq!(
+            metas[EXECUTE_SALE_AUCTION_HOUSE_INDEX].pubkey,
+            execute_sale.auction_house
+        );
+        assert_eq!(
+            metas[EXECUTE_SALE_BUYER_TRADE_STATE_INDEX].pubkey,
+            execute_sale.buyer_trade_state
+        );
+        assert_eq!(
+            metas[EXECUTE_SALE_SELLER_TRADE_STATE_INDEX].pubkey,
+            execute_sale.seller_trade_state
+        );
+    }
+
+    let cancel = auction_house::accounts::Cancel {
+        wallet: Pubkey::new_unique(),
+        token_account: Pubkey::new_unique(),
+        token_mint: Pubkey::new_unique(),
+        authority: Pubkey::new_unique(),
+        auction_house: Pubkey::new_unique(),
+        auction_house_fee_account: Pubkey::new_unique(),
+        trade_state: Pubkey::new_unique(),
+        token_program: Pubkey::new_unique(),
+    };
+    let metas = cancel.to_account_metas(None);
+    assert_eq!(metas[CANCEL_TRADE_STATE_INDEX].pubkey, cancel.trade_state);
+}
@@ -0,0 +1,197 @@
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::token::spl_token;
+use auction_house::{
+    errors::AuctionHouseError,
+    pda::{find_auctioneer_address, find_program_as_signer_address},
+};
+use mpl_token_metadata::{
+    pda::find_token_record_account,
+    state::{TokenMetadataAccount, TokenRecord, TokenState},
+};
+use solana_program_test::tokio;
+use spl_token_2022::{extension::StateWithExtensions, state::Account as Token2022Account};
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn sell_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let (sell_accounts, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    let seller_trade_state_account = context
+        .banks_client
+        .get_account(sell_accounts.seller_trade_state)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    assert_eq!(seller_trade_state_account.data.len(), 1);
+
+    let token_account = context
+        .banks_client
+        .get_account(token.ata)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    let token_account_data = spl_token::state::Account::unpack(&token_account.data).unwrap();
+
+    // Assert program as signer is the token account delegate
+    assert_eq!(
+        token_account_data.delegate,
+        Some(sell_accounts.program_as_signer).into()
+    );
+}
+
+#[tokio::test]
+async fn failure_sell_auctioneer_delegated() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, auction_house_data) =
+        create_auction_house(&mut context, 100, false)
+            .await
+            .expect("Failed to create Auction House");
+
+    let auctioneer_authority = Keypair::new();
+    let (auctioneer, _) = find_auctioneer_address(&auction_house, &auctioneer_authority.pubkey());
+
+    let tx = delegate(
+        &mut context,
+        &auction_house,
+        &authority,
+        &auctioneer_authority.pubkey(),
+        &auctioneer,
+        all_scopes(),
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let (_, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        ONE_SOL,
+    );
+
+    let tx_error = context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::MustUseAuctioneerHandler.into());
+}
+
+#[tokio::test]
+async fn sell_programmable_success() {
+    let mut context = auction_house_pnft_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_pnft(&mut context)
+        .await
+        .expect("Failed to create pNFT");
+
+    let (_, mut sell_ix) =
+        sell_instruction(&auction_house, &auction_house_data, &token, ONE_SOL, 1);
+    add_programmable_sell_accounts(&mut sell_ix, &token);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[sell_ix],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to sell pNFT");
+
+    let (token_record, _) = find_token_record_account(&token.mint.pubkey(), &token.ata);
+    let token_record_account = context
+        .banks_client
+        .get_account(token_record)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let token_record_data = TokenRecord::safe_deserialize(&token_record_account.data).unwrap();
+
+    let (program_as_signer, _) = find_program_as_signer_address();
+
+    // Assert the program is the sale delegate and the token is locked
+    assert_eq!(token_record_data.delegate, Some(program_as_signer));
+    assert_eq!(token_record_data.state, TokenState::Listed);
+}
+
+#[tokio::test]
+async fn sell_token_2022_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_token_2022_nft(&mut context, 500, &[])
+        .await
+        .expect("Failed to create Token-2022 NFT");
+
+    let (sell_accounts, mut sell_ix) =
+        sell_instruction(&auction_house, &auction_house_data, &token, ONE_SOL, 1);
+    set_token_program(&mut sell_ix, &spl_token_2022::id());
+
+    let tx = Transaction::new_signed_with_payer(
+        &[sell_ix],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to sell Token-2022 NFT");
+
+    let token_account = context
+        .banks_client
+        .get_account(token.ata)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let token_account_data =
+        StateWithExtensions::<Token2022Account>::unpack(&token_account.data).unwrap();
+
+    // Assert program as signer is the token account delegate
+    assert_eq!(
+        token_account_data.base.delegate,
+        Some(sell_accounts.program_as_signer).into()
+    );
+}
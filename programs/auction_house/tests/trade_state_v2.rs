@@ -0,0 +1,852 @@
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::{associated_token::get_associated_token_address, token::spl_token};
+use auction_house::{
+    constants::{CLOSE_EXPIRED_BOUNTY_BPS, TRADE_STATE_WITH_EXPIRY_SIZE},
+    errors::AuctionHouseError,
+    trade_state::{TradeSide, TradeType},
+};
+use solana_program_test::tokio;
+use std::time::SystemTime;
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn sell_v2_amends_listing_in_place() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let (sell_accounts, sell_ix) = sell_v2_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        ONE_SOL,
+        1,
+        None,
+    );
+    let sell_tx = Transaction::new_signed_with_payer(
+        &[sell_ix],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    let listing = get_trade_state_v2(&mut context, &sell_accounts.seller_trade_state).await;
+    assert_eq!(listing.auction_house, auction_house);
+    assert_eq!(listing.wallet, token.owner.pubkey());
+    assert_eq!(listing.token_account, token.ata);
+    assert_eq!(listing.token_mint, token.mint.pubkey());
+    assert_eq!(listing.side, TradeSide::Sell);
+    assert_eq!(listing.trade_type, TradeType::Private);
+    assert_eq!(listing.price, ONE_SOL);
+    assert_eq!(listing.token_size, 1);
+
+    // Amend the price later on, the listing keeps its address and creation slot
+
+    context.warp_to_slot(100).unwrap();
+
+    let (amend_accounts, amend_ix) = sell_v2_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        2 * ONE_SOL,
+        1,
+        None,
+    );
+    let amend_tx = Transaction::new_signed_with_payer(
+        &[amend_ix],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(amend_tx)
+        .await
+        .expect("Failed to amend listing");
+
+    assert_eq!(
+        amend_accounts.seller_trade_state,
+        sell_accounts.seller_trade_state
+    );
+    let amended = get_trade_state_v2(&mut context, &sell_accounts.seller_trade_state).await;
+    assert_eq!(amended.price, 2 * ONE_SOL);
+    assert_eq!(amended.created_slot, listing.created_slot);
+    assert_eq!(amended.bump, listing.bump);
+}
+
+#[tokio::test]
+async fn execute_sale_v2_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let price = ONE_SOL;
+
+    // Sell
+
+    let (sell_accounts, sell_ix) =
+        sell_v2_instruction(&auction_house, &auction_house_data, &token, price, 1, None);
+    let sell_tx = Transaction::new_signed_with_payer(
+        &[sell_ix],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    // Buy
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let (buy_accounts, buy_ix) = buy_v2_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer.pubkey(),
+        price,
+        1,
+        None,
+    );
+    let buy_tx = Transaction::new_signed_with_payer(
+        &[buy_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(buy_tx)
+        .await
+        .expect("Failed to buy NFT");
+
+    let bid = get_trade_state_v2(&mut context, &buy_accounts.buyer_trade_state).await;
+    assert_eq!(bid.side, TradeSide::Buy);
+    assert_eq!(bid.trade_type, TradeType::Private);
+    assert_eq!(bid.price, price);
+
+    // Execute sale
+
+    let (execute_sale_accounts, execute_sale_ix) = execute_sale_v2_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &sell_accounts,
+        &buyer.pubkey(),
+        &buy_accounts.buyer_trade_state,
+        price,
+        1,
+        None,
+        None,
+    );
+    let execute_sale_tx = Transaction::new_signed_with_payer(
+        &[execute_sale_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(execute_sale_tx)
+        .await
+        .expect("Failed to execute sale");
+
+    let buyer_token_account = context
+        .banks_client
+        .get_account(get_associated_token_address(
+            &buyer.pubkey(),
+            &token.mint.pubkey(),
+        ))
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let buyer_token_account_data =
+        spl_token::state::Account::unpack(&buyer_token_account.data).unwrap();
+    assert_eq!(buyer_token_account_data.amount, 1);
+
+    // Assert both trade states are closed
+    let seller_trade_state_account = context
+        .banks_client
+        .get_account(execute_sale_accounts.seller_trade_state)
+        .await
+        .unwrap();
+    assert!(seller_trade_state_account.is_none());
+
+    let buyer_trade_state_account = context
+        .banks_client
+        .get_account(execute_sale_accounts.buyer_trade_state)
+        .await
+        .unwrap();
+    assert!(buyer_trade_state_account.is_none());
+}
+
+#[tokio::test]
+async fn execute_sale_v2_public_bid_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let price = ONE_SOL;
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let (buy_accounts, buy_ix) = public_buy_v2_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer.pubkey(),
+        price,
+        1,
+        None,
+    );
+    let (sell_accounts, sell_ix) =
+        sell_v2_instruction(&auction_house, &auction_house_data, &token, price, 1, None);
+    let tx = Transaction::new_signed_with_payer(
+        &[buy_ix, sell_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer, &token.owner],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to place bid and listing");
+
+    let bid = get_trade_state_v2(&mut context, &buy_accounts.buyer_trade_state).await;
+    assert_eq!(bid.trade_type, TradeType::Public);
+    assert_eq!(bid.token_account, Default::default());
+
+    let (_, execute_sale_ix) = execute_sale_v2_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &sell_accounts,
+        &buyer.pubkey(),
+        &buy_accounts.buyer_trade_state,
+        price,
+        1,
+        None,
+        None,
+    );
+    let execute_sale_tx = Transaction::new_signed_with_payer(
+        &[execute_sale_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(execute_sale_tx)
+        .await
+        .expect("Failed to execute sale");
+
+    let buyer_token_account = context
+        .banks_client
+        .get_account(get_associated_token_address(
+            &buyer.pubkey(),
+            &token.mint.pubkey(),
+        ))
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let buyer_token_account_data =
+        spl_token::state::Account::unpack(&buyer_token_account.data).unwrap();
+    assert_eq!(buyer_token_account_data.amount, 1);
+}
+
+#[tokio::test]
+async fn execute_sale_v2_stale_price_failure() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    // The seller raises the price after the buyer bid the original one
+
+    let (sell_accounts, sell_ix) = sell_v2_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        ONE_SOL,
+        1,
+        None,
+    );
+    let (_, amend_ix) = sell_v2_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        2 * ONE_SOL,
+        1,
+        None,
+    );
+    let sell_tx = Transaction::new_signed_with_payer(
+        &[sell_ix, amend_ix],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    let (buy_accounts, buy_ix) = buy_v2_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer.pubkey(),
+        ONE_SOL,
+        1,
+        None,
+    );
+    let buy_tx = Transaction::new_signed_with_payer(
+        &[buy_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(buy_tx)
+        .await
+        .expect("Failed to buy NFT");
+
+    let (_, execute_sale_ix) = execute_sale_v2_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &sell_accounts,
+        &buyer.pubkey(),
+        &buy_accounts.buyer_trade_state,
+        ONE_SOL,
+        1,
+        None,
+        None,
+    );
+    let execute_sale_tx = Transaction::new_signed_with_payer(
+        &[execute_sale_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(execute_sale_tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::TradeStateMismatch.into());
+}
+
+#[tokio::test]
+async fn cancel_v2_listing_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let (sell_accounts, sell_ix) = sell_v2_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        ONE_SOL,
+        1,
+        None,
+    );
+    let sell_tx = Transaction::new_signed_with_payer(
+        &[sell_ix],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    // The price and size of a v2 listing are read from the trade state
+    let (_, cancel_tx) = cancel(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &token.owner,
+        &sell_accounts.seller_trade_state,
+        0,
+    );
+    context
+        .banks_client
+        .process_transaction(cancel_tx)
+        .await
+        .expect("Failed to cancel listing");
+
+    let seller_trade_state_account = context
+        .banks_client
+        .get_account(sell_accounts.seller_trade_state)
+        .await
+        .unwrap();
+    assert!(seller_trade_state_account.is_none());
+
+    let token_account = context
+        .banks_client
+        .get_account(token.ata)
+        .await
+        .unwrap()
+        .unwrap();
+    let token_account_data = spl_token::state::Account::unpack(&token_account.data).unwrap();
+    assert!(token_account_data.delegate.is_none());
+}
+
+fn expiry_in(seconds: i64) -> i64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs() as i64
+        + seconds
+}
+
+#[tokio::test]
+async fn sell_v2_past_expiry_failure() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let (_, sell_ix) = sell_v2_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        ONE_SOL,
+        1,
+        Some(expiry_in(-60)),
+    );
+    let sell_tx = Transaction::new_signed_with_payer(
+        &[sell_ix],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::InvalidExpiry.into());
+}
+
+#[tokio::test]
+async fn execute_sale_v2_expired_bid_failure() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let (sell_accounts, sell_ix) = sell_v2_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        ONE_SOL,
+        1,
+        None,
+    );
+    let (buy_accounts, buy_ix) = buy_v2_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer.pubkey(),
+        ONE_SOL,
+        1,
+        Some(expiry_in(60)),
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[sell_ix, buy_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer, &token.owner],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to place listing and bid");
+
+    // Move the clock past the bid expiry
+    context.warp_to_slot(120 * 400).unwrap();
+
+    let (_, execute_sale_ix) = execute_sale_v2_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &sell_accounts,
+        &buyer.pubkey(),
+        &buy_accounts.buyer_trade_state,
+        ONE_SOL,
+        1,
+        None,
+        None,
+    );
+    let execute_sale_tx = Transaction::new_signed_with_payer(
+        &[execute_sale_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(execute_sale_tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::TradeStateExpired.into());
+}
+
+#[tokio::test]
+async fn close_expired_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let (buy_accounts, buy_ix) = public_buy_v2_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer.pubkey(),
+        ONE_SOL,
+        1,
+        Some(expiry_in(60)),
+    );
+    let buy_tx = Transaction::new_signed_with_payer(
+        &[buy_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(buy_tx)
+        .await
+        .expect("Failed to buy NFT");
+
+    let bid = get_trade_state_v2(&mut context, &buy_accounts.buyer_trade_state).await;
+    assert_eq!(bid.fee_payer, buyer.pubkey());
+
+    let closer = Keypair::new();
+    airdrop(&mut context, &closer.pubkey(), ONE_SOL)
+        .await
+        .unwrap();
+
+    // The bid can't be closed before it expires
+
+    let (_, close_ix) = close_expired_instruction(
+        &closer.pubkey(),
+        &buy_accounts.buyer_trade_state,
+        &buyer.pubkey(),
+    );
+    let close_tx = Transaction::new_signed_with_payer(
+        &[close_ix.clone()],
+        Some(&closer.pubkey()),
+        &[&closer],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(close_tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::TradeStateNotExpired.into());
+
+    context.warp_to_slot(120 * 400).unwrap();
+
+    let trade_state_lamports = context
+        .banks_client
+        .get_balance(buy_accounts.buyer_trade_state)
+        .await
+        .unwrap();
+    let buyer_lamports = context
+        .banks_client
+        .get_balance(buyer.pubkey())
+        .await
+        .unwrap();
+    let closer_lamports = context
+        .banks_client
+        .get_balance(closer.pubkey())
+        .await
+        .unwrap();
+
+    let close_tx = Transaction::new_signed_with_payer(
+        &[close_ix],
+        Some(&closer.pubkey()),
+        &[&closer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(close_tx)
+        .await
+        .expect("Failed to close expired bid");
+
+    let buyer_trade_state_account = context
+        .banks_client
+        .get_account(buy_accounts.buyer_trade_state)
+        .await
+        .unwrap();
+    assert!(buyer_trade_state_account.is_none());
+
+    // The closer is paid the bounty net of the transaction fee, the buyer gets the rest of the rent
+    let bounty = trade_state_lamports * CLOSE_EXPIRED_BOUNTY_BPS / 10000;
+    assert_eq!(
+        context
+            .banks_client
+            .get_balance(buyer.pubkey())
+            .await
+            .unwrap(),
+        buyer_lamports + trade_state_lamports - bounty
+    );
+    assert_eq!(
+        context
+            .banks_client
+            .get_balance(closer.pubkey())
+            .await
+            .unwrap(),
+        closer_lamports + bounty - 5_000
+    );
+}
+
+#[tokio::test]
+async fn close_expired_listing_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let (sell_accounts, sell_ix) = sell_v2_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        ONE_SOL,
+        1,
+        Some(expiry_in(60)),
+    );
+    let sell_tx = Transaction::new_signed_with_payer(
+        &[sell_ix],
+        Some(&token.owner.pubkey()),
+        &[&token.owner],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    context.warp_to_slot(120 * 400).unwrap();
+
+    let closer = Keypair::new();
+    airdrop(&mut context, &closer.pubkey(), ONE_SOL)
+        .await
+        .unwrap();
+
+    let (_, close_ix) = close_expired_instruction(
+        &closer.pubkey(),
+        &sell_accounts.seller_trade_state,
+        &token.owner.pubkey(),
+    );
+    let close_tx = Transaction::new_signed_with_payer(
+        &[close_ix],
+        Some(&closer.pubkey()),
+        &[&closer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(close_tx)
+        .await
+        .expect("Failed to close expired listing");
+
+    let seller_trade_state_account = context
+        .banks_client
+        .get_account(sell_accounts.seller_trade_state)
+        .await
+        .unwrap();
+    assert!(seller_trade_state_account.is_none());
+
+    // The delegate stays until the seller revokes it, but nothing can sell through it
+    let token_account = context
+        .banks_client
+        .get_account(token.ata)
+        .await
+        .unwrap()
+        .unwrap();
+    let token_account_data = spl_token::state::Account::unpack(&token_account.data).unwrap();
+    assert!(token_account_data.delegate.is_some());
+}
+
+#[tokio::test]
+async fn execute_sale_expired_legacy_listing_failure() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let (sell_accounts, sell_ix) = sell_with_expiry_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        ONE_SOL,
+        1,
+        Some(expiry_in(60)),
+    );
+    let (buy_accounts, buy_ix) = buy_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer.pubkey(),
+        ONE_SOL,
+        1,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[sell_ix, buy_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer, &token.owner],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .expect("Failed to place listing and bid");
+
+    let seller_trade_state = context
+        .banks_client
+        .get_account(sell_accounts.seller_trade_state)
+        .await
+        .unwrap()
+        .expect("Seller trade state missing");
+    assert_eq!(seller_trade_state.data.len(), TRADE_STATE_WITH_EXPIRY_SIZE);
+
+    // Move the clock past the listing expiry
+    context.warp_to_slot(120 * 400).unwrap();
+
+    let (_, execute_sale_ix) = execute_sale_instruction(
+        &auction_house,
+        &auction_house_data,
+        &token,
+        None,
+        &sell_accounts,
+        &buyer.pubkey(),
+        &buy_accounts.buyer_trade_state,
+        ONE_SOL,
+        1,
+        None,
+        None,
+    );
+    let execute_sale_tx = Transaction::new_signed_with_payer(
+        &[execute_sale_ix],
+        Some(&buyer.pubkey()),
+        &[&buyer],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(execute_sale_tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::TradeStateExpired.into());
+}
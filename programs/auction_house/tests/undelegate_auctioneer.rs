@@ -0,0 +1,103 @@
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
+use anchor_lang::{prelude::Pubkey, AccountDeserialize};
+use auction_house::{pda::find_auctioneer_address, AuctionHouse};
+use solana_program_test::tokio;
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn undelegate_auctioneer_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, _) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let auctioneer_authority = Keypair::new();
+    let (auctioneer, _) = find_auctioneer_address(&auction_house, &auctioneer_authority.pubkey());
+
+    let tx = delegate(
+        &mut context,
+        &auction_house,
+        &authority,
+        &auctioneer_authority.pubkey(),
+        &auctioneer,
+        all_scopes(),
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // UndelegateAuctioneer
+
+    let tx = undelegate(
+        &mut context,
+        &auction_house,
+        &authority,
+        &auctioneer_authority.pubkey(),
+        &auctioneer,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let auction_house_account = context
+        .banks_client
+        .get_account(auction_house)
+        .await
+        .unwrap()
+        .expect("Auction House account not found");
+
+    let auction_house_data =
+        AuctionHouse::try_deserialize(&mut auction_house_account.data.as_ref()).unwrap();
+
+    assert!(!auction_house_data.has_auctioneer);
+    assert_eq!(auction_house_data.auctioneer_address, Pubkey::default());
+
+    let auctioneer_account = context.banks_client.get_account(auctioneer).await.unwrap();
+
+    // Assert auctioneer account is closed
+    assert!(auctioneer_account.is_none());
+}
+
+#[tokio::test]
+async fn failure_undelegate_auctioneer_wrong_authority() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, _) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let auctioneer_authority = Keypair::new();
+    let (auctioneer, _) = find_auctioneer_address(&auction_house, &auctioneer_authority.pubkey());
+
+    let tx = delegate(
+        &mut context,
+        &auction_house,
+        &authority,
+        &auctioneer_authority.pubkey(),
+        &auctioneer,
+        all_scopes(),
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // UndelegateAuctioneer signed by someone other than the Auction House authority
+
+    let wrong_authority = Keypair::new();
+    airdrop(&mut context, &wrong_authority.pubkey(), ONE_SOL)
+        .await
+        .unwrap();
+
+    let tx = undelegate(
+        &mut context,
+        &auction_house,
+        &wrong_authority,
+        &auctioneer_authority.pubkey(),
+        &auctioneer,
+    );
+
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, ERR_CONSTRAINT_HAS_ONE);
+}
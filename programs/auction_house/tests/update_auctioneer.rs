@@ -0,0 +1,116 @@
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
+use anchor_lang::AccountDeserialize;
+use auction_house::{pda::find_auctioneer_address, AuctionHouse, Auctioneer};
+use solana_program_test::tokio;
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn update_auctioneer_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, _) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let auctioneer_authority = Keypair::new();
+    let (auctioneer, _) = find_auctioneer_address(&auction_house, &auctioneer_authority.pubkey());
+
+    let tx = delegate(
+        &mut context,
+        &auction_house,
+        &authority,
+        &auctioneer_authority.pubkey(),
+        &auctioneer,
+        all_scopes(),
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // UpdateAuctioneer
+
+    let new_auctioneer_authority = Keypair::new();
+    let (new_auctioneer, _) =
+        find_auctioneer_address(&auction_house, &new_auctioneer_authority.pubkey());
+
+    let tx = update_auctioneer(
+        &mut context,
+        &auction_house,
+        &authority,
+        &auctioneer_authority.pubkey(),
+        &auctioneer,
+        &new_auctioneer_authority.pubkey(),
+        &new_auctioneer,
+        all_scopes(),
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let auction_house_account = context
+        .banks_client
+        .get_account(auction_house)
+        .await
+        .unwrap()
+        .expect("Auction House account not found");
+
+    let auction_house_data =
+        AuctionHouse::try_deserialize(&mut auction_house_account.data.as_ref()).unwrap();
+
+    assert!(auction_house_data.has_auctioneer);
+    assert_eq!(auction_house_data.auctioneer_address, new_auctioneer);
+
+    let new_auctioneer_account = context
+        .banks_client
+        .get_account(new_auctioneer)
+        .await
+        .unwrap()
+        .expect("Auctioneer account not found");
+
+    let new_auctioneer_data =
+        Auctioneer::try_deserialize(&mut new_auctioneer_account.data.as_ref()).unwrap();
+
+    assert_eq!(
+        new_auctioneer_data.auctioneer_authority,
+        new_auctioneer_authority.pubkey()
+    );
+
+    let auctioneer_account = context.banks_client.get_account(auctioneer).await.unwrap();
+
+    // Assert previous auctioneer account is closed
+    assert!(auctioneer_account.is_none());
+}
+
+#[tokio::test]
+async fn failure_update_auctioneer_not_delegated() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, _) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let auctioneer_authority = Keypair::new();
+    let (auctioneer, _) = find_auctioneer_address(&auction_house, &auctioneer_authority.pubkey());
+
+    let new_auctioneer_authority = Keypair::new();
+    let (new_auctioneer, _) =
+        find_auctioneer_address(&auction_house, &new_auctioneer_authority.pubkey());
+
+    let tx = update_auctioneer(
+        &mut context,
+        &auction_house,
+        &authority,
+        &auctioneer_authority.pubkey(),
+        &auctioneer,
+        &new_auctioneer_authority.pubkey(),
+        &new_auctioneer,
+        all_scopes(),
+    );
+
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    // There is no auctioneer PDA to replace
+    assert_error(tx_error, ERR_ACCOUNT_NOT_INITIALIZED);
+}
@@ -1,26 +1,78 @@
 #![allow(dead_code)]
 
 use anchor_client::solana_sdk::transaction::{Transaction, TransactionError};
-use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
+use anchor_client::solana_sdk::{
+    account::{Account, AccountSharedData},
+    signature::Keypair,
+    signer::Signer,
+};
+use anchor_lang::prelude::AccountMeta;
 use anchor_lang::solana_program::instruction::{Instruction, InstructionError};
+use anchor_lang::solana_program::{program_option::COption, program_pack::Pack};
 use anchor_lang::solana_program::{system_instruction, system_program, sysvar};
 use anchor_lang::{prelude::Pubkey, AccountDeserialize};
+use anchor_lang::{AccountSerialize, AnchorSerialize, Event};
 use anchor_lang::{InstructionData, ToAccountMetas};
-use anchor_spl::associated_token::{self};
+use anchor_spl::associated_token::{self, get_associated_token_address};
 use anchor_spl::token::spl_token;
 use auction_house::pda::*;
-use auction_house::AuctionHouse;
+use auction_house::{
+    trade_state::{TradeSide, TradeStateV2},
+    withdrawal_policy::WithdrawalSource,
+    AuctionHouse, AuthorityScope,
+};
+use mpl_token_metadata::{
+    instruction::{
+        builders::{CreateBuilder, MintBuilder},
+        set_and_verify_collection, CreateArgs, InstructionBuilder, MintArgs,
+    },
+    pda::find_token_record_account,
+    state::{AssetData, TokenStandard},
+};
+use nft_minter::pda::*;
+use nft_minter::utils::{token_metadata_program_id, Creator};
 use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+use spl_associated_token_account::{
+    get_associated_token_address_with_program_id, instruction::create_associated_token_account,
+};
+use spl_token_2022::extension::{
+    transfer_fee::instruction::initialize_transfer_fee_config, ExtensionType,
+};
 use std::io;
 
 // Error = Error code
 pub const ERR_AUCTION_HOUSE_ALREADY_INITIALIZED: u32 = 0;
 pub const ERR_INSUFFICIENT_FUNDS: u32 = 1;
+pub const ERR_ACCOUNT_ALREADY_IN_USE: u32 = 0;
+pub const ERR_CONSTRAINT_HAS_ONE: u32 = 2001;
+pub const ERR_CONSTRAINT_SEEDS: u32 = 2006;
+pub const ERR_ACCOUNT_NOT_INITIALIZED: u32 = 3012;
 
 pub const ONE_SOL: u64 = 1_000_000_000;
 
+pub fn all_scopes() -> Vec<AuthorityScope> {
+    vec![
+        AuthorityScope::Deposit,
+        AuthorityScope::Buy,
+        AuthorityScope::PublicBuy,
+        AuthorityScope::ExecuteSale,
+        AuthorityScope::Sell,
+        AuthorityScope::Cancel,
+        AuthorityScope::Withdraw,
+    ]
+}
+
 pub fn auction_house_program_test() -> ProgramTest {
-    let program = ProgramTest::new("auction_house", auction_house::id(), None);
+    let mut program = ProgramTest::new("auction_house", auction_house::id(), None);
+    program.add_program("nft_minter", nft_minter::id(), None);
+    program.add_program("mpl_token_metadata", token_metadata_program_id(), None);
+    program
+}
+
+/// Programmable NFT transfers go through Token Auth Rules, which only these tests load.
+pub fn auction_house_pnft_program_test() -> ProgramTest {
+    let mut program = auction_house_program_test();
+    program.add_program("mpl_token_auth_rules", mpl_token_auth_rules::id(), None);
     program
 }
 
@@ -38,21 +90,90 @@ pub async fn create_auction_house(
     context: &mut ProgramTestContext,
     seller_fee_basis_points: u16,
     can_change_sale_price: bool,
+) -> Result<(Keypair, Pubkey, AuctionHouse), BanksClientError> {
+    create_auction_house_with_treasury_mint(
+        context,
+        &spl_token::native_mint::id(),
+        &spl_token::id(),
+        seller_fee_basis_points,
+        can_change_sale_price,
+    )
+    .await
+}
+
+pub async fn create_auction_house_with_treasury_mint(
+    context: &mut ProgramTestContext,
+    treasury_mint: &Pubkey,
+    token_program: &Pubkey,
+    seller_fee_basis_points: u16,
+    can_change_sale_price: bool,
 ) -> Result<(Keypair, Pubkey, AuctionHouse), BanksClientError> {
     // CreateAuctionHouse
     let authority = Keypair::new();
     airdrop(context, &authority.pubkey(), 10 * ONE_SOL).await?;
 
-    let treasury_mint = spl_token::native_mint::id();
+    let treasury_mint = *treasury_mint;
+    let treasury_withdrawal_destination = if treasury_mint == spl_token::native_mint::id() {
+        context.payer.pubkey()
+    } else {
+        get_associated_token_address_with_program_id(
+            &context.payer.pubkey(),
+            &treasury_mint,
+            token_program,
+        )
+    };
+
+    let create_auction_house_ix = create_auction_house_instruction(
+        &authority.pubkey(),
+        &treasury_mint,
+        token_program,
+        &treasury_withdrawal_destination,
+        &context.payer.pubkey(),
+        &context.payer.pubkey(),
+        seller_fee_basis_points,
+        can_change_sale_price,
+    );
+    let (auction_house, _) = find_auction_house_address(&authority.pubkey(), &treasury_mint);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_auction_house_ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await?;
+
+    let auction_house_account = context
+        .banks_client
+        .get_account(auction_house)
+        .await?
+        .expect("Auction House account not found");
+    let auction_house_data =
+        AuctionHouse::try_deserialize(&mut auction_house_account.data.as_ref())
+            .map_err(|e| BanksClientError::Io(io::Error::new(io::ErrorKind::InvalidData, e)))?;
+
+    Ok((authority, auction_house, auction_house_data))
+}
 
-    let (auction_house, auction_house_bump) =
-        find_auction_house_address(&authority.pubkey(), &treasury_mint);
+/// Create an Auction House of `authority`, which also pays for it. Also used to recreate a closed
+/// Auction House at the same address.
+pub fn create_auction_house_instruction(
+    authority: &Pubkey,
+    treasury_mint: &Pubkey,
+    token_program: &Pubkey,
+    treasury_withdrawal_destination: &Pubkey,
+    treasury_withdrawal_destination_owner: &Pubkey,
+    fee_withdrawal_destination: &Pubkey,
+    seller_fee_basis_points: u16,
+    can_change_sale_price: bool,
+) -> Instruction {
+    let (auction_house, auction_house_bump) = find_auction_house_address(authority, treasury_mint);
     let (auction_house_fee_account, auction_house_fee_account_bump) =
         find_auction_house_fee_account_address(&auction_house);
     let (auction_house_treasury, auction_house_treasury_bump) =
         find_auction_house_treasury_address(&auction_house);
 
-    let create_auction_house_ix = Instruction {
+    Instruction {
         program_id: auction_house::id(),
         data: auction_house::instruction::CreateAuctionHouse {
             _bump: auction_house_bump,
@@ -63,41 +184,386 @@ pub async fn create_auction_house(
         }
         .data(),
         accounts: auction_house::accounts::CreateAuctionHouse {
-            treasury_mint,
-            payer: authority.pubkey(),
-            authority: authority.pubkey(),
-            fee_withdrawal_destination: context.payer.pubkey(),
-            treasury_withdrawal_destination: context.payer.pubkey(),
-            treasury_withdrawal_destination_owner: context.payer.pubkey(),
+            treasury_mint: *treasury_mint,
+            payer: *authority,
+            authority: *authority,
+            fee_withdrawal_destination: *fee_withdrawal_destination,
+            treasury_withdrawal_destination: *treasury_withdrawal_destination,
+            treasury_withdrawal_destination_owner: *treasury_withdrawal_destination_owner,
             auction_house,
             auction_house_fee_account,
             auction_house_treasury,
-            token_program: spl_token::id(),
+            token_program: *token_program,
             system_program: system_program::id(),
             associated_token_program: associated_token::ID,
             rent: sysvar::rent::id(),
         }
         .to_account_metas(None),
+    }
+}
+
+pub fn propose_authority_instruction(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    new_authority: &Pubkey,
+    fee_withdrawal_destination: &Pubkey,
+    treasury_withdrawal_destination: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: auction_house::id(),
+        data: auction_house::instruction::ProposeAuthority {}.data(),
+        accounts: auction_house::accounts::ProposeAuthority {
+            treasury_mint: auction_house_data.treasury_mint,
+            authority: auction_house_data.authority,
+            new_authority: *new_authority,
+            fee_withdrawal_destination: *fee_withdrawal_destination,
+            treasury_withdrawal_destination: *treasury_withdrawal_destination,
+            auction_house: *auction_house,
+        }
+        .to_account_metas(None),
+    }
+}
+
+/// Accepts the pending changes of `auction_house_data`, requiring a signature from each new key.
+pub fn accept_authority_instruction(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    payer: &Pubkey,
+    treasury_withdrawal_destination_owner: &Pubkey,
+) -> Instruction {
+    let pending_or_current = |pending: Pubkey, current: Pubkey| {
+        if pending == Pubkey::default() {
+            current
+        } else {
+            pending
+        }
     };
 
-    let tx = Transaction::new_signed_with_payer(
-        &[create_auction_house_ix],
-        Some(&authority.pubkey()),
-        &[&authority],
-        context.last_blockhash,
-    );
-    context.banks_client.process_transaction(tx).await?;
+    let accounts = auction_house::accounts::AcceptAuthority {
+        treasury_mint: auction_house_data.treasury_mint,
+        payer: *payer,
+        new_authority: pending_or_current(
+            auction_house_data.pending_authority,
+            auction_house_data.authority,
+        ),
+        fee_withdrawal_destination: pending_or_current(
+            auction_house_data.pending_fee_withdrawal_destination,
+            auction_house_data.fee_withdrawal_destination,
+        ),
+        treasury_withdrawal_destination: pending_or_current(
+            auction_house_data.pending_treasury_withdrawal_destination,
+            auction_house_data.treasury_withdrawal_destination,
+        ),
+        treasury_withdrawal_destination_owner: *treasury_withdrawal_destination_owner,
+        auction_house: *auction_house,
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        associated_token_program: associated_token::ID,
+        rent: sysvar::rent::id(),
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    for account_meta in account_metas.iter_mut() {
+        if (account_meta.pubkey == auction_house_data.pending_authority
+            || account_meta.pubkey == auction_house_data.pending_fee_withdrawal_destination)
+            && account_meta.pubkey != Pubkey::default()
+        {
+            account_meta.is_signer = true;
+        }
+    }
+    if auction_house_data.pending_treasury_withdrawal_destination != Pubkey::default() {
+        account_metas[5].is_signer = true;
+    }
+
+    Instruction {
+        program_id: auction_house::id(),
+        data: auction_house::instruction::AcceptAuthority {}.data(),
+        accounts: account_metas,
+    }
+}
 
+pub async fn get_auction_house_data(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+) -> AuctionHouse {
     let auction_house_account = context
         .banks_client
-        .get_account(auction_house)
-        .await?
+        .get_account(*auction_house)
+        .await
+        .unwrap()
         .expect("Auction House account not found");
-    let auction_house_data =
-        AuctionHouse::try_deserialize(&mut auction_house_account.data.as_ref())
-            .map_err(|e| BanksClientError::Io(io::Error::new(io::ErrorKind::InvalidData, e)))?;
 
-    Ok((authority, auction_house, auction_house_data))
+    AuctionHouse::try_deserialize(&mut auction_house_account.data.as_ref()).unwrap()
+}
+
+/// Overwrite the data of an existing program account in place, keeping its size and lamports.
+/// Used to fabricate state written by older program versions.
+pub async fn overwrite_account_data<T: AccountSerialize>(
+    context: &mut ProgramTestContext,
+    address: &Pubkey,
+    account_data: &T,
+) {
+    let mut account = context
+        .banks_client
+        .get_account(*address)
+        .await
+        .unwrap()
+        .expect("Account not found");
+
+    let mut data = Vec::new();
+    account_data.try_serialize(&mut data).unwrap();
+    account.data[..data.len()].copy_from_slice(&data);
+
+    context.set_account(address, &AccountSharedData::from(account));
+}
+
+pub fn create_withdrawal_policy_instruction(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    treasury_epoch_cap: u64,
+    fee_epoch_cap: u64,
+    timelock: i64,
+) -> Instruction {
+    Instruction {
+        program_id: auction_house::id(),
+        data: auction_house::instruction::CreateWithdrawalPolicy {
+            treasury_epoch_cap,
+            fee_epoch_cap,
+            timelock,
+        }
+        .data(),
+        accounts: auction_house::accounts::CreateWithdrawalPolicy {
+            authority: auction_house_data.authority,
+            auction_house: *auction_house,
+            withdrawal_policy: find_withdrawal_policy_address(auction_house).0,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None),
+    }
+}
+
+/// Withdraws from a native treasury, passing the withdrawal policy as a remaining account.
+pub fn withdraw_from_treasury_instruction(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    amount: u64,
+) -> Instruction {
+    let mut accounts = auction_house::accounts::WithdrawFromTreasury {
+        treasury_mint: auction_house_data.treasury_mint,
+        treasury_withdrawal_destination: auction_house_data.treasury_withdrawal_destination,
+        auction_house_treasury: auction_house_data.auction_house_treasury,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+    accounts.push(AccountMeta::new(
+        find_withdrawal_policy_address(auction_house).0,
+        false,
+    ));
+
+    Instruction {
+        program_id: auction_house::id(),
+        data: auction_house::instruction::WithdrawFromTreasury { amount }.data(),
+        accounts,
+    }
+}
+
+pub fn queue_withdrawal_instruction(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    withdrawal_id: u64,
+    source: WithdrawalSource,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: auction_house::id(),
+        data: auction_house::instruction::QueueWithdrawal { source, amount }.data(),
+        accounts: auction_house::accounts::QueueWithdrawal {
+            authority: auction_house_data.authority,
+            auction_house: *auction_house,
+            withdrawal_policy: find_withdrawal_policy_address(auction_house).0,
+            queued_withdrawal: find_queued_withdrawal_address(auction_house, withdrawal_id).0,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub fn execute_queued_withdrawal_instruction(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    withdrawal_id: u64,
+) -> Instruction {
+    Instruction {
+        program_id: auction_house::id(),
+        data: auction_house::instruction::ExecuteQueuedWithdrawal {}.data(),
+        accounts: auction_house::accounts::ExecuteQueuedWithdrawal {
+            authority: auction_house_data.authority,
+            treasury_mint: auction_house_data.treasury_mint,
+            treasury_withdrawal_destination: auction_house_data.treasury_withdrawal_destination,
+            fee_withdrawal_destination: auction_house_data.fee_withdrawal_destination,
+            auction_house_treasury: auction_house_data.auction_house_treasury,
+            auction_house_fee_account: auction_house_data.auction_house_fee_account,
+            auction_house: *auction_house,
+            queued_withdrawal: find_queued_withdrawal_address(auction_house, withdrawal_id).0,
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub fn cancel_queued_withdrawal_instruction(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    withdrawal_id: u64,
+) -> Instruction {
+    Instruction {
+        program_id: auction_house::id(),
+        data: auction_house::instruction::CancelQueuedWithdrawal {}.data(),
+        accounts: auction_house::accounts::CancelQueuedWithdrawal {
+            authority: auction_house_data.authority,
+            auction_house: *auction_house,
+            queued_withdrawal: find_queued_withdrawal_address(auction_house, withdrawal_id).0,
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub fn add_allowed_collection_instruction(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    collection: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: auction_house::id(),
+        data: auction_house::instruction::AddAllowedCollection {}.data(),
+        accounts: auction_house::accounts::AddAllowedCollection {
+            authority: auction_house_data.authority,
+            collection: *collection,
+            auction_house: *auction_house,
+            allowed_collection: find_allowed_collection_address(auction_house, collection).0,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub fn remove_allowed_collection_instruction(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    collection: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: auction_house::id(),
+        data: auction_house::instruction::RemoveAllowedCollection {}.data(),
+        accounts: auction_house::accounts::RemoveAllowedCollection {
+            authority: auction_house_data.authority,
+            auction_house: *auction_house,
+            allowed_collection: find_allowed_collection_address(auction_house, collection).0,
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub fn block_wallet_instruction(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    wallet: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: auction_house::id(),
+        data: auction_house::instruction::BlockWallet {}.data(),
+        accounts: auction_house::accounts::BlockWallet {
+            authority: auction_house_data.authority,
+            wallet: *wallet,
+            auction_house: *auction_house,
+            blocked_wallet: find_blocked_wallet_address(auction_house, wallet).0,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub fn unblock_wallet_instruction(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    wallet: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: auction_house::id(),
+        data: auction_house::instruction::UnblockWallet {}.data(),
+        accounts: auction_house::accounts::UnblockWallet {
+            authority: auction_house_data.authority,
+            auction_house: *auction_house,
+            blocked_wallet: find_blocked_wallet_address(auction_house, wallet).0,
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub fn close_auction_house_instruction(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+) -> Instruction {
+    Instruction {
+        program_id: auction_house::id(),
+        data: auction_house::instruction::CloseAuctionHouse {}.data(),
+        accounts: auction_house::accounts::CloseAuctionHouse {
+            treasury_mint: auction_house_data.treasury_mint,
+            authority: auction_house_data.authority,
+            treasury_withdrawal_destination: auction_house_data.treasury_withdrawal_destination,
+            fee_withdrawal_destination: auction_house_data.fee_withdrawal_destination,
+            auction_house_treasury: auction_house_data.auction_house_treasury,
+            auction_house_fee_account: auction_house_data.auction_house_fee_account,
+            auction_house: *auction_house,
+            token_program: spl_token::id(),
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub fn migrate_auction_house_instruction(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+) -> Instruction {
+    Instruction {
+        program_id: auction_house::id(),
+        data: auction_house::instruction::MigrateAuctionHouse {}.data(),
+        accounts: auction_house::accounts::MigrateAuctionHouse {
+            authority: auction_house_data.authority,
+            auction_house: *auction_house,
+            system_program: system_program::id(),
+        }
+        .to_account_metas(None),
+    }
+}
+
+pub fn set_pause_state(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    authority: &Keypair,
+    paused: bool,
+) -> Transaction {
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: auction_house::instruction::SetPauseState { paused }.data(),
+        accounts: auction_house::accounts::SetPauseState {
+            authority: authority.pubkey(),
+            auction_house: *auction_house,
+        }
+        .to_account_metas(None),
+    };
+
+    Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[authority],
+        context.last_blockhash,
+    )
 }
 
 pub fn delegate(
@@ -106,8 +572,9 @@ pub fn delegate(
     authority: &Keypair,
     auctioneer_authority: &Pubkey,
     auctioneer: &Pubkey,
+    scopes: Vec<AuthorityScope>,
 ) -> Transaction {
-    let data = auction_house::instruction::DelegateAuctioneer {};
+    let data = auction_house::instruction::DelegateAuctioneer { scopes };
 
     let accounts = auction_house::accounts::DelegateAuctioneer {
         auction_house: *auction_house,
@@ -131,6 +598,73 @@ pub fn delegate(
     )
 }
 
+pub fn undelegate(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    authority: &Keypair,
+    auctioneer_authority: &Pubkey,
+    auctioneer: &Pubkey,
+) -> Transaction {
+    let data = auction_house::instruction::UndelegateAuctioneer {};
+
+    let accounts = auction_house::accounts::UndelegateAuctioneer {
+        auction_house: *auction_house,
+        authority: authority.pubkey(),
+        auctioneer_authority: *auctioneer_authority,
+        auctioneer: *auctioneer,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[authority],
+        context.last_blockhash,
+    )
+}
+
+pub fn update_auctioneer(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    authority: &Keypair,
+    auctioneer_authority: &Pubkey,
+    auctioneer: &Pubkey,
+    new_auctioneer_authority: &Pubkey,
+    new_auctioneer: &Pubkey,
+    scopes: Vec<AuthorityScope>,
+) -> Transaction {
+    let data = auction_house::instruction::UpdateAuctioneer { scopes };
+
+    let accounts = auction_house::accounts::UpdateAuctioneer {
+        auction_house: *auction_house,
+        authority: authority.pubkey(),
+        auctioneer_authority: *auctioneer_authority,
+        auctioneer: *auctioneer,
+        new_auctioneer_authority: *new_auctioneer_authority,
+        new_auctioneer: *new_auctioneer,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[authority],
+        context.last_blockhash,
+    )
+}
+
 pub async fn airdrop(
     context: &mut ProgramTestContext,
     receiver: &Pubkey,
@@ -151,3 +685,1647 @@ pub async fn airdrop(
 
     Ok(())
 }
+
+/// Process `tx` and return its log messages, so emitted events can be inspected.
+pub async fn process_transaction_with_logs(
+    context: &mut ProgramTestContext,
+    tx: Transaction,
+) -> Result<Vec<String>, BanksClientError> {
+    let result = context
+        .banks_client
+        .process_transaction_with_metadata(tx)
+        .await?;
+    result.result.map_err(BanksClientError::TransactionError)?;
+
+    Ok(result
+        .metadata
+        .map(|metadata| metadata.log_messages)
+        .unwrap_or_default())
+}
+
+/// Find the first event of type `T` in the log messages of a transaction.
+pub fn find_event<T: Event>(logs: &[String]) -> Option<T> {
+    logs.iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .filter_map(|data| anchor_lang::__private::base64::decode(data).ok())
+        .find(|data| data.starts_with(&T::discriminator()))
+        .and_then(|data| T::try_from_slice(&data[8..]).ok())
+}
+
+pub async fn create_transfer_fee_mint(
+    context: &mut ProgramTestContext,
+    transfer_fee_basis_points: u16,
+    maximum_fee: u64,
+) -> Result<Keypair, BanksClientError> {
+    let mint = Keypair::new();
+    let space = ExtensionType::get_account_len::<spl_token_2022::state::Mint>(&[
+        ExtensionType::TransferFeeConfig,
+    ]);
+    let rent = context.banks_client.get_rent().await?;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &context.payer.pubkey(),
+                &mint.pubkey(),
+                rent.minimum_balance(space),
+                space as u64,
+                &spl_token_2022::id(),
+            ),
+            initialize_transfer_fee_config(
+                &spl_token_2022::id(),
+                &mint.pubkey(),
+                None,
+                None,
+                transfer_fee_basis_points,
+                maximum_fee,
+            )
+            .unwrap(),
+            spl_token_2022::instruction::initialize_mint2(
+                &spl_token_2022::id(),
+                &mint.pubkey(),
+                &context.payer.pubkey(),
+                None,
+                0,
+            )
+            .unwrap(),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &mint],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await?;
+
+    Ok(mint)
+}
+
+/// Creates `owner`'s Token-2022 associated token account for `mint` and mints `amount` into it.
+/// The mint authority must be the context payer.
+pub async fn mint_token_2022(
+    context: &mut ProgramTestContext,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+) -> Result<Pubkey, BanksClientError> {
+    let ata = get_associated_token_address_with_program_id(owner, mint, &spl_token_2022::id());
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            create_associated_token_account(
+                &context.payer.pubkey(),
+                owner,
+                mint,
+                &spl_token_2022::id(),
+            ),
+            spl_token_2022::instruction::mint_to(
+                &spl_token_2022::id(),
+                mint,
+                &ata,
+                &context.payer.pubkey(),
+                &[],
+                amount,
+            )
+            .unwrap(),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await?;
+
+    Ok(ata)
+}
+
+#[derive(Debug)]
+pub struct NFT {
+    pub mint: Keypair,          // Mint
+    pub owner: Keypair,         // Seller
+    pub ata: Pubkey,            // Token account
+    pub metadata: Pubkey,       // Metaplex Metadata
+    pub master_edition: Pubkey, // Metaplex Master Edition
+}
+
+pub async fn create_nft(
+    context: &mut ProgramTestContext,
+    metadata_creators: Option<Vec<Creator>>,
+) -> Result<NFT, BanksClientError> {
+    let owner = Keypair::new();
+    airdrop(context, &owner.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    create_nft_with_owner(context, owner, metadata_creators).await
+}
+
+/// Creates an NFT whose owner is also its update authority.
+async fn create_nft_with_owner(
+    context: &mut ProgramTestContext,
+    owner: Keypair,
+    metadata_creators: Option<Vec<Creator>>,
+) -> Result<NFT, BanksClientError> {
+    let mint = Keypair::new();
+
+    let ata = get_associated_token_address(&owner.pubkey(), &mint.pubkey());
+    let (metadata, _) = find_metadata_account(&mint.pubkey());
+    let (master_edition, _) = find_master_edition_account(&mint.pubkey());
+
+    // CreateToken
+    let create_token_ix = create_token_instruction(
+        &owner.pubkey(),
+        &mint.pubkey(),
+        &metadata,
+        metadata_creators,
+    );
+
+    // MintToken
+    let mint_token_ix = Instruction {
+        program_id: nft_minter::id(),
+        data: nft_minter::instruction::MintToken {
+            max_supply: Some(0),
+        }
+        .data(),
+        accounts: nft_minter::accounts::MintToken {
+            payer: owner.pubkey(),
+            mint_account: mint.pubkey(),
+            mint_authority: owner.pubkey(),
+            update_authority: owner.pubkey(),
+            associated_token_account: ata,
+            metadata_account: metadata,
+            edition_account: master_edition,
+            token_metadata_program: token_metadata_program_id(),
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            rent: sysvar::rent::id(),
+        }
+        .to_account_metas(None),
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_token_ix, mint_token_ix],
+        Some(&owner.pubkey()),
+        &[&mint, &owner],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await?;
+
+    Ok(NFT {
+        mint,
+        owner,
+        ata,
+        metadata,
+        master_edition,
+    })
+}
+
+/// Creates an NFT that is a verified member of a new collection NFT, returning it with the
+/// collection mint.
+pub async fn create_nft_in_collection(
+    context: &mut ProgramTestContext,
+) -> Result<(NFT, Pubkey), BanksClientError> {
+    let collection = create_nft(context, None).await?;
+
+    // Verifying needs the NFT and the collection to share an update authority.
+    let owner = Keypair::from_bytes(&collection.owner.to_bytes()).unwrap();
+    let nft = create_nft_with_owner(context, owner, None).await?;
+
+    let ix = set_and_verify_collection(
+        mpl_token_metadata::id(),
+        nft.metadata,
+        nft.owner.pubkey(),
+        nft.owner.pubkey(),
+        nft.owner.pubkey(),
+        collection.mint.pubkey(),
+        collection.metadata,
+        collection.master_edition,
+        None,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&nft.owner.pubkey()),
+        &[&nft.owner],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await?;
+
+    Ok((nft, collection.mint.pubkey()))
+}
+
+fn create_token_instruction(
+    owner: &Pubkey,
+    mint: &Pubkey,
+    metadata: &Pubkey,
+    metadata_creators: Option<Vec<Creator>>,
+) -> Instruction {
+    Instruction {
+        program_id: nft_minter::id(),
+        data: nft_minter::instruction::CreateToken {
+            name: "Solana Course NFT".to_string(),
+            symbol: "SOLC".to_string(),
+            uri: "https://raw.githubusercontent.com/arsenijkovalov/nft-assets/main/assets/nft.json"
+                .to_string(),
+            creators: metadata_creators,
+            seller_fee_basis_points: 10,
+            is_mutable: false,
+        }
+        .data(),
+        accounts: nft_minter::accounts::CreateToken {
+            payer: *owner,
+            mint_account: *mint,
+            mint_authority: *owner,
+            update_authority: *owner,
+            metadata_account: *metadata,
+            token_metadata_program: token_metadata_program_id(),
+            system_program: system_program::id(),
+            token_program: anchor_spl::token::ID,
+            rent: sysvar::rent::id(),
+        }
+        .to_account_metas(None),
+    }
+}
+
+/// Creates a semi-fungible token with metadata and no master edition, minting `amount` to the owner.
+pub async fn create_sft(
+    context: &mut ProgramTestContext,
+    amount: u64,
+) -> Result<NFT, BanksClientError> {
+    let mint = Keypair::new();
+    let owner = Keypair::new();
+    airdrop(context, &owner.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let ata = get_associated_token_address(&owner.pubkey(), &mint.pubkey());
+    let (metadata, _) = find_metadata_account(&mint.pubkey());
+    let (master_edition, _) = find_master_edition_account(&mint.pubkey());
+
+    let create_token_ix =
+        create_token_instruction(&owner.pubkey(), &mint.pubkey(), &metadata, None);
+
+    let create_ata_ix =
+        associated_token::spl_associated_token_account::instruction::create_associated_token_account(
+            &owner.pubkey(),
+            &owner.pubkey(),
+            &mint.pubkey(),
+            &spl_token::id(),
+        );
+
+    let mint_to_ix = spl_token::instruction::mint_to(
+        &spl_token::id(),
+        &mint.pubkey(),
+        &ata,
+        &owner.pubkey(),
+        &[],
+        amount,
+    )
+    .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_token_ix, create_ata_ix, mint_to_ix],
+        Some(&owner.pubkey()),
+        &[&mint, &owner],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await?;
+
+    Ok(NFT {
+        mint,
+        owner,
+        ata,
+        metadata,
+        master_edition,
+    })
+}
+
+/// Creates a programmable NFT without an authorization rule set.
+pub async fn create_pnft(context: &mut ProgramTestContext) -> Result<NFT, BanksClientError> {
+    let mint = Keypair::new();
+    let owner = Keypair::new();
+    airdrop(context, &owner.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let ata = get_associated_token_address(&owner.pubkey(), &mint.pubkey());
+    let (metadata, _) = find_metadata_account(&mint.pubkey());
+    let (master_edition, _) = find_master_edition_account(&mint.pubkey());
+    let (token_record, _) = find_token_record_account(&mint.pubkey(), &ata);
+
+    let mut asset_data = AssetData::new(
+        TokenStandard::ProgrammableNonFungible,
+        "Solana Course NFT".to_string(),
+        "SOLC".to_string(),
+        "https://raw.githubusercontent.com/arsenijkovalov/nft-assets/main/assets/nft.json"
+            .to_string(),
+    );
+    asset_data.seller_fee_basis_points = 10;
+
+    let create_ix = CreateBuilder::new()
+        .metadata(metadata)
+        .master_edition(master_edition)
+        .mint(mint.pubkey())
+        .authority(owner.pubkey())
+        .payer(owner.pubkey())
+        .update_authority(owner.pubkey())
+        .initialize_mint(true)
+        .update_authority_as_signer(true)
+        .build(CreateArgs::V1 {
+            asset_data,
+            decimals: Some(0),
+            print_supply: None,
+        })
+        .unwrap()
+        .instruction();
+
+    let mint_ix = MintBuilder::new()
+        .token(ata)
+        .token_owner(owner.pubkey())
+        .metadata(metadata)
+        .master_edition(master_edition)
+        .token_record(token_record)
+        .mint(mint.pubkey())
+        .authority(owner.pubkey())
+        .payer(owner.pubkey())
+        .build(MintArgs::V1 {
+            amount: 1,
+            authorization_data: None,
+        })
+        .unwrap()
+        .instruction();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_ix, mint_ix],
+        Some(&owner.pubkey()),
+        &[&mint, &owner],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await?;
+
+    Ok(NFT {
+        mint,
+        owner,
+        ata,
+        metadata,
+        master_edition,
+    })
+}
+
+/// Marks the metadata writable and appends the Token Metadata accounts `sell` needs for a programmable NFT.
+pub fn add_programmable_sell_accounts(ix: &mut Instruction, token: &NFT) {
+    let (token_record, _) = find_token_record_account(&token.mint.pubkey(), &token.ata);
+
+    set_writable(ix, &token.metadata);
+    ix.accounts.extend([
+        AccountMeta::new_readonly(token_metadata_program_id(), false),
+        AccountMeta::new_readonly(token.mint.pubkey(), false),
+        AccountMeta::new_readonly(token.master_edition, false),
+        AccountMeta::new(token_record, false),
+        AccountMeta::new_readonly(mpl_token_auth_rules::id(), false),
+        AccountMeta::new_readonly(token_metadata_program_id(), false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+    ]);
+}
+
+/// Appends the Token Metadata accounts `cancel` needs to revoke the delegate of a programmable NFT.
+pub fn add_programmable_cancel_accounts(ix: &mut Instruction, token: &NFT) {
+    let (token_record, _) = find_token_record_account(&token.mint.pubkey(), &token.ata);
+    let (program_as_signer, _) = find_program_as_signer_address();
+
+    ix.accounts.extend([
+        AccountMeta::new_readonly(token_metadata_program_id(), false),
+        AccountMeta::new(token.metadata, false),
+        AccountMeta::new_readonly(program_as_signer, false),
+        AccountMeta::new_readonly(token.master_edition, false),
+        AccountMeta::new(token_record, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(mpl_token_auth_rules::id(), false),
+        AccountMeta::new_readonly(token_metadata_program_id(), false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+    ]);
+}
+
+/// Marks the metadata writable and appends the Token Metadata accounts `execute_sale` needs to
+/// transfer a programmable NFT. Creator accounts have to be added before these.
+pub fn add_programmable_execute_sale_accounts(ix: &mut Instruction, token: &NFT, buyer: &Pubkey) {
+    let (owner_token_record, _) = find_token_record_account(&token.mint.pubkey(), &token.ata);
+    let buyer_ata = get_associated_token_address(buyer, &token.mint.pubkey());
+    let (destination_token_record, _) = find_token_record_account(&token.mint.pubkey(), &buyer_ata);
+
+    set_writable(ix, &token.metadata);
+    ix.accounts.extend([
+        AccountMeta::new_readonly(token_metadata_program_id(), false),
+        AccountMeta::new_readonly(token.master_edition, false),
+        AccountMeta::new(owner_token_record, false),
+        AccountMeta::new(destination_token_record, false),
+        AccountMeta::new_readonly(mpl_token_auth_rules::id(), false),
+        AccountMeta::new_readonly(token_metadata_program_id(), false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+    ]);
+}
+
+/// Creates an NFT under Token-2022 whose metadata pointer targets the mint itself and whose
+/// token-metadata extension carries the royalties. The accounts are written directly since the
+/// test validator's Token-2022 build predates these extensions.
+pub async fn create_token_2022_nft(
+    context: &mut ProgramTestContext,
+    seller_fee_basis_points: u16,
+    creators: &[(Pubkey, u8)],
+) -> Result<NFT, BanksClientError> {
+    let mint = Keypair::new();
+    let owner = Keypair::new();
+    airdrop(context, &owner.pubkey(), 10 * ONE_SOL).await?;
+
+    let ata = get_associated_token_address_with_program_id(
+        &owner.pubkey(),
+        &mint.pubkey(),
+        &spl_token_2022::id(),
+    );
+
+    // Base mint, padded to the token account length and followed by the account type.
+    let mut mint_data = vec![0; spl_token_2022::state::Mint::LEN];
+    spl_token_2022::state::Mint {
+        mint_authority: COption::Some(owner.pubkey()),
+        supply: 1,
+        decimals: 0,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    }
+    .pack_into_slice(&mut mint_data);
+    mint_data.resize(spl_token_2022::state::Account::LEN, 0);
+    mint_data.push(1);
+
+    // MetadataPointer extension: authority, metadata address.
+    mint_data.extend_from_slice(&18u16.to_le_bytes());
+    mint_data.extend_from_slice(&64u16.to_le_bytes());
+    mint_data.extend_from_slice(owner.pubkey().as_ref());
+    mint_data.extend_from_slice(mint.pubkey().as_ref());
+
+    // TokenMetadata extension with the royalties as additional metadata.
+    let creators = creators
+        .iter()
+        .map(|(address, share)| format!("{}:{}", address, share))
+        .collect::<Vec<_>>()
+        .join(",");
+    let mut additional_metadata = vec![(
+        "seller_fee_basis_points".to_string(),
+        seller_fee_basis_points.to_string(),
+    )];
+    if !creators.is_empty() {
+        additional_metadata.push(("creators".to_string(), creators));
+    }
+    let token_metadata = (
+        owner.pubkey(),
+        mint.pubkey(),
+        "Token 2022 NFT".to_string(),
+        "T22".to_string(),
+        "https://example.com/t22.json".to_string(),
+        additional_metadata,
+    )
+        .try_to_vec()
+        .unwrap();
+    mint_data.extend_from_slice(&19u16.to_le_bytes());
+    mint_data.extend_from_slice(&(token_metadata.len() as u16).to_le_bytes());
+    mint_data.extend_from_slice(&token_metadata);
+
+    let mut ata_data = vec![0; spl_token_2022::state::Account::LEN];
+    spl_token_2022::state::Account {
+        mint: mint.pubkey(),
+        owner: owner.pubkey(),
+        amount: 1,
+        state: spl_token_2022::state::AccountState::Initialized,
+        ..Default::default()
+    }
+    .pack_into_slice(&mut ata_data);
+
+    let rent = context.banks_client.get_rent().await?;
+    for (address, data) in [(mint.pubkey(), mint_data), (ata, ata_data)] {
+        context.set_account(
+            &address,
+            &AccountSharedData::from(Account {
+                lamports: rent.minimum_balance(data.len()),
+                data,
+                owner: spl_token_2022::id(),
+                executable: false,
+                rent_epoch: 0,
+            }),
+        );
+    }
+
+    Ok(NFT {
+        metadata: mint.pubkey(),
+        master_edition: Pubkey::default(),
+        mint,
+        owner,
+        ata,
+    })
+}
+
+/// Points the SPL Token program account of `ix` at `token_program` instead.
+pub fn set_token_program(ix: &mut Instruction, token_program: &Pubkey) {
+    for meta in ix.accounts.iter_mut() {
+        if meta.pubkey == spl_token::id() {
+            meta.pubkey = *token_program;
+        }
+    }
+}
+
+fn set_writable(ix: &mut Instruction, pubkey: &Pubkey) {
+    for meta in ix.accounts.iter_mut() {
+        if meta.pubkey == *pubkey {
+            meta.is_writable = true;
+        }
+    }
+}
+
+pub fn sell(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    token: &NFT,
+    buyer_price: u64, // Listing price
+) -> (auction_house::accounts::Sell, Transaction) {
+    let (accounts, ix) = sell_instruction(auction_house, auction_house_data, token, buyer_price, 1);
+
+    (
+        accounts,
+        Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&token.owner.pubkey()),
+            &[&token.owner],
+            context.last_blockhash,
+        ),
+    )
+}
+
+pub fn sell_instruction(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    token: &NFT,
+    buyer_price: u64, // Listing price
+    token_size: u64,
+) -> (auction_house::accounts::Sell, Instruction) {
+    sell_with_expiry_instruction(
+        auction_house,
+        auction_house_data,
+        token,
+        buyer_price,
+        token_size,
+        None,
+    )
+}
+
+pub fn sell_with_expiry_instruction(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    token: &NFT,
+    buyer_price: u64, // Listing price
+    token_size: u64,
+    expiry: Option<i64>,
+) -> (auction_house::accounts::Sell, Instruction) {
+    let (seller_trade_state, seller_trade_state_bump) = find_trade_state_address(
+        &token.owner.pubkey(),
+        auction_house,
+        &token.ata,
+        &auction_house_data.treasury_mint,
+        &token.mint.pubkey(),
+        buyer_price,
+        token_size,
+    );
+
+    let (free_seller_trade_state, free_seller_trade_state_bump) = find_trade_state_address(
+        &token.owner.pubkey(),
+        auction_house,
+        &token.ata,
+        &auction_house_data.treasury_mint,
+        &token.mint.pubkey(),
+        0,
+        token_size,
+    );
+
+    let (program_as_signer, program_as_signer_bump) = find_program_as_signer_address();
+
+    let data = auction_house::instruction::Sell {
+        trade_state_bump: seller_trade_state_bump,
+        free_trade_state_bump: free_seller_trade_state_bump,
+        program_as_signer_bump,
+        buyer_price,
+        token_size,
+        expiry,
+    };
+
+    let accounts = auction_house::accounts::Sell {
+        wallet: token.owner.pubkey(),
+        token_account: token.ata,
+        metadata: token.metadata,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        seller_trade_state,
+        free_seller_trade_state,
+        program_as_signer,
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (accounts, ix)
+}
+
+pub fn buy(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    token: &NFT,
+    buyer: &Keypair,
+    buyer_price: u64, // Bid amount
+) -> (auction_house::accounts::Buy, Transaction) {
+    let (accounts, ix) = buy_instruction(
+        auction_house,
+        auction_house_data,
+        token,
+        &buyer.pubkey(),
+        buyer_price,
+        1,
+    );
+
+    (
+        accounts,
+        Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&buyer.pubkey()),
+            &[buyer],
+            context.last_blockhash,
+        ),
+    )
+}
+
+pub fn buy_instruction(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    token: &NFT,
+    buyer: &Pubkey,
+    buyer_price: u64, // Bid amount
+    token_size: u64,
+) -> (auction_house::accounts::Buy, Instruction) {
+    let (escrow_payment_account, escrow_payment_account_bump) =
+        find_escrow_payment_account_address(auction_house, buyer);
+    let (buyer_trade_state, buyer_trade_state_bump) = find_trade_state_address(
+        buyer,
+        auction_house,
+        &token.ata,
+        &auction_house_data.treasury_mint,
+        &token.mint.pubkey(),
+        buyer_price,
+        token_size,
+    );
+
+    let data = auction_house::instruction::Buy {
+        trade_state_bump: buyer_trade_state_bump,
+        escrow_payment_bump: escrow_payment_account_bump,
+        buyer_price,
+        token_size,
+        expiry: None,
+    };
+
+    let accounts = auction_house::accounts::Buy {
+        wallet: *buyer,
+        payment_account: *buyer,
+        transfer_authority: *buyer,
+        treasury_mint: auction_house_data.treasury_mint,
+        token_account: token.ata,
+        metadata: token.metadata,
+        escrow_payment_account,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        buyer_trade_state,
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (accounts, ix)
+}
+
+pub fn public_buy(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    token: &NFT,
+    buyer: &Keypair,
+    buyer_price: u64, // Bid amount
+) -> (auction_house::accounts::PublicBuy, Transaction) {
+    let (escrow_payment_account, escrow_payment_account_bump) =
+        find_escrow_payment_account_address(auction_house, &buyer.pubkey());
+    let (buyer_trade_state, buyer_trade_state_bump) = find_public_bid_trade_state_address(
+        &buyer.pubkey(),
+        auction_house,
+        &auction_house_data.treasury_mint,
+        &token.mint.pubkey(),
+        buyer_price,
+        1,
+    );
+
+    let data = auction_house::instruction::PublicBuy {
+        trade_state_bump: buyer_trade_state_bump,
+        escrow_payment_bump: escrow_payment_account_bump,
+        buyer_price,
+        token_size: 1,
+        expiry: None,
+    };
+
+    let accounts = auction_house::accounts::PublicBuy {
+        wallet: buyer.pubkey(),
+        payment_account: buyer.pubkey(),
+        transfer_authority: buyer.pubkey(),
+        treasury_mint: auction_house_data.treasury_mint,
+        token_account: token.ata,
+        metadata: token.metadata,
+        escrow_payment_account,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        buyer_trade_state,
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (
+        accounts,
+        Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&buyer.pubkey()),
+            &[buyer],
+            context.last_blockhash,
+        ),
+    )
+}
+
+pub fn execute_sale(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    token: &NFT,
+    metadata_creators: Option<Vec<Creator>>,
+    sell_accounts: &auction_house::accounts::Sell,
+    buyer: &Pubkey,
+    buyer_trade_state: &Pubkey,
+    buyer_price: u64,
+    signer_payer: &Keypair,
+) -> (auction_house::accounts::ExecuteSale, Transaction) {
+    let (accounts, ix) = execute_sale_instruction(
+        auction_house,
+        auction_house_data,
+        token,
+        metadata_creators,
+        sell_accounts,
+        buyer,
+        buyer_trade_state,
+        buyer_price,
+        1,
+        None,
+        None,
+    );
+
+    (
+        accounts,
+        Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&signer_payer.pubkey()),
+            &[signer_payer],
+            context.last_blockhash,
+        ),
+    )
+}
+
+pub fn execute_sale_instruction(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    token: &NFT,
+    metadata_creators: Option<Vec<Creator>>,
+    sell_accounts: &auction_house::accounts::Sell,
+    buyer: &Pubkey,
+    buyer_trade_state: &Pubkey,
+    buyer_price: u64,
+    token_size: u64,
+    partial_order_size: Option<u64>,
+    partial_order_price: Option<u64>,
+) -> (auction_house::accounts::ExecuteSale, Instruction) {
+    let buyer_receipt_token_account = get_associated_token_address(buyer, &token.mint.pubkey());
+    let (escrow_payment_account, escrow_payment_account_bump) =
+        find_escrow_payment_account_address(auction_house, buyer);
+    let (_, program_as_signer_bump) = find_program_as_signer_address();
+    let (_, free_seller_trade_state_bump) = find_trade_state_address(
+        &token.owner.pubkey(),
+        auction_house,
+        &sell_accounts.token_account,
+        &auction_house_data.treasury_mint,
+        &token.mint.pubkey(),
+        0,
+        token_size,
+    );
+
+    let data = auction_house::instruction::ExecuteSale {
+        escrow_payment_bump: escrow_payment_account_bump,
+        _free_trade_state_bump: free_seller_trade_state_bump,
+        program_as_signer_bump,
+        buyer_price,
+        token_size,
+        partial_order_size,
+        partial_order_price,
+    };
+
+    let accounts = auction_house::accounts::ExecuteSale {
+        buyer: *buyer,
+        seller: sell_accounts.wallet,
+        token_account: sell_accounts.token_account,
+        token_mint: token.mint.pubkey(),
+        metadata: token.metadata,
+        treasury_mint: auction_house_data.treasury_mint,
+        escrow_payment_account,
+        seller_payment_receipt_account: token.owner.pubkey(),
+        buyer_receipt_token_account,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        auction_house_treasury: auction_house_data.auction_house_treasury,
+        buyer_trade_state: *buyer_trade_state,
+        seller_trade_state: sell_accounts.seller_trade_state,
+        free_trade_state: sell_accounts.free_seller_trade_state,
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        associated_token_program: associated_token::ID,
+        program_as_signer: sell_accounts.program_as_signer,
+        rent: sysvar::rent::id(),
+        treasury_token_program: spl_token::id(),
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    if let Some(creators) = metadata_creators {
+        for creator in &creators {
+            account_metas.push(AccountMeta {
+                pubkey: creator.address,
+                is_signer: false,
+                is_writable: true,
+            });
+            account_metas.push(AccountMeta {
+                pubkey: find_royalty_vault_address(auction_house, &creator.address).0,
+                is_signer: false,
+                is_writable: true,
+            });
+        }
+    }
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: account_metas,
+    };
+
+    (accounts, ix)
+}
+
+pub fn sell_v2_instruction(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    token: &NFT,
+    price: u64,
+    token_size: u64,
+    expiry: Option<i64>,
+) -> (auction_house::accounts::SellV2, Instruction) {
+    let (seller_trade_state, _) = find_trade_state_v2_address(
+        &token.owner.pubkey(),
+        auction_house,
+        &token.ata,
+        TradeSide::Sell,
+    );
+    let (program_as_signer, _) = find_program_as_signer_address();
+
+    let data = auction_house::instruction::SellV2 {
+        price,
+        token_size,
+        expiry,
+    };
+
+    let accounts = auction_house::accounts::SellV2 {
+        wallet: token.owner.pubkey(),
+        token_account: token.ata,
+        metadata: token.metadata,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        seller_trade_state,
+        program_as_signer,
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (accounts, ix)
+}
+
+pub fn buy_v2_instruction(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    token: &NFT,
+    buyer: &Pubkey,
+    price: u64,
+    token_size: u64,
+    expiry: Option<i64>,
+) -> (auction_house::accounts::BuyV2, Instruction) {
+    let (escrow_payment_account, _) = find_escrow_payment_account_address(auction_house, buyer);
+    let (buyer_trade_state, _) =
+        find_trade_state_v2_address(buyer, auction_house, &token.ata, TradeSide::Buy);
+
+    let data = auction_house::instruction::BuyV2 {
+        price,
+        token_size,
+        expiry,
+    };
+
+    let accounts = auction_house::accounts::BuyV2 {
+        wallet: *buyer,
+        payment_account: *buyer,
+        transfer_authority: *buyer,
+        treasury_mint: auction_house_data.treasury_mint,
+        token_account: token.ata,
+        metadata: token.metadata,
+        escrow_payment_account,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        buyer_trade_state,
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (accounts, ix)
+}
+
+pub fn public_buy_v2_instruction(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    token: &NFT,
+    buyer: &Pubkey,
+    price: u64,
+    token_size: u64,
+    expiry: Option<i64>,
+) -> (auction_house::accounts::PublicBuyV2, Instruction) {
+    let (escrow_payment_account, _) = find_escrow_payment_account_address(auction_house, buyer);
+    let (buyer_trade_state, _) =
+        find_trade_state_v2_address(buyer, auction_house, &token.mint.pubkey(), TradeSide::Buy);
+
+    let data = auction_house::instruction::PublicBuyV2 {
+        price,
+        token_size,
+        expiry,
+    };
+
+    let accounts = auction_house::accounts::PublicBuyV2 {
+        wallet: *buyer,
+        payment_account: *buyer,
+        transfer_authority: *buyer,
+        treasury_mint: auction_house_data.treasury_mint,
+        token_account: token.ata,
+        metadata: token.metadata,
+        escrow_payment_account,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        buyer_trade_state,
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (accounts, ix)
+}
+
+pub fn execute_sale_v2_instruction(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    token: &NFT,
+    sell_accounts: &auction_house::accounts::SellV2,
+    buyer: &Pubkey,
+    buyer_trade_state: &Pubkey,
+    buyer_price: u64,
+    token_size: u64,
+    partial_order_size: Option<u64>,
+    partial_order_price: Option<u64>,
+) -> (auction_house::accounts::ExecuteSaleV2, Instruction) {
+    let buyer_receipt_token_account = get_associated_token_address(buyer, &token.mint.pubkey());
+    let (escrow_payment_account, _) = find_escrow_payment_account_address(auction_house, buyer);
+
+    let data = auction_house::instruction::ExecuteSaleV2 {
+        buyer_price,
+        token_size,
+        partial_order_size,
+        partial_order_price,
+    };
+
+    let accounts = auction_house::accounts::ExecuteSaleV2 {
+        buyer: *buyer,
+        seller: sell_accounts.wallet,
+        token_account: sell_accounts.token_account,
+        token_mint: token.mint.pubkey(),
+        metadata: token.metadata,
+        treasury_mint: auction_house_data.treasury_mint,
+        escrow_payment_account,
+        seller_payment_receipt_account: sell_accounts.wallet,
+        buyer_receipt_token_account,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        auction_house_treasury: auction_house_data.auction_house_treasury,
+        buyer_trade_state: *buyer_trade_state,
+        seller_trade_state: sell_accounts.seller_trade_state,
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        associated_token_program: associated_token::ID,
+        program_as_signer: sell_accounts.program_as_signer,
+        rent: sysvar::rent::id(),
+        treasury_token_program: spl_token::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (accounts, ix)
+}
+
+pub fn close_expired_instruction(
+    closer: &Pubkey,
+    trade_state: &Pubkey,
+    fee_payer: &Pubkey,
+) -> (auction_house::accounts::CloseExpired, Instruction) {
+    let data = auction_house::instruction::CloseExpired {};
+
+    let accounts = auction_house::accounts::CloseExpired {
+        closer: *closer,
+        trade_state: *trade_state,
+        fee_payer: *fee_payer,
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (accounts, ix)
+}
+
+pub async fn get_trade_state_v2(
+    context: &mut ProgramTestContext,
+    trade_state: &Pubkey,
+) -> TradeStateV2 {
+    let account = context
+        .banks_client
+        .get_account(*trade_state)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    TradeStateV2::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+pub fn deposit(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    buyer: &Keypair,
+    amount: u64,
+) -> (auction_house::accounts::Deposit, Transaction) {
+    let (accounts, ix) = deposit_instruction(
+        auction_house,
+        auction_house_data,
+        &buyer.pubkey(),
+        &buyer.pubkey(),
+        &spl_token::id(),
+        amount,
+    );
+
+    (
+        accounts,
+        Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&buyer.pubkey()),
+            &[buyer],
+            context.last_blockhash,
+        ),
+    )
+}
+
+pub fn deposit_instruction(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    buyer: &Pubkey,
+    payment_account: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+) -> (auction_house::accounts::Deposit, Instruction) {
+    let (escrow_payment_account, escrow_payment_account_bump) =
+        find_escrow_payment_account_address(auction_house, buyer);
+
+    let data = auction_house::instruction::Deposit {
+        escrow_payment_bump: escrow_payment_account_bump,
+        amount,
+    };
+
+    let accounts = auction_house::accounts::Deposit {
+        wallet: *buyer,
+        payment_account: *payment_account,
+        transfer_authority: *buyer,
+        escrow_payment_account,
+        treasury_mint: auction_house_data.treasury_mint,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        token_program: *token_program,
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (accounts, ix)
+}
+
+pub fn withdraw(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    buyer: &Keypair,
+    amount: u64,
+) -> (auction_house::accounts::Withdraw, Transaction) {
+    let (escrow_payment_account, escrow_payment_account_bump) =
+        find_escrow_payment_account_address(auction_house, &buyer.pubkey());
+    let (escrow_lock, _) = find_escrow_lock_address(auction_house, &buyer.pubkey());
+
+    let data = auction_house::instruction::Withdraw {
+        escrow_payment_bump: escrow_payment_account_bump,
+        amount,
+    };
+
+    let accounts = auction_house::accounts::Withdraw {
+        wallet: buyer.pubkey(),
+        receipt_account: buyer.pubkey(),
+        escrow_payment_account,
+        treasury_mint: auction_house_data.treasury_mint,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        associated_token_program: associated_token::ID,
+        rent: sysvar::rent::id(),
+        escrow_lock,
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (
+        accounts,
+        Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&buyer.pubkey()),
+            &[buyer],
+            context.last_blockhash,
+        ),
+    )
+}
+
+pub fn auctioneer_withdraw(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    buyer: &Pubkey,
+    auctioneer_authority: &Keypair,
+    amount: u64,
+) -> (auction_house::accounts::AuctioneerWithdraw, Transaction) {
+    let (escrow_payment_account, escrow_payment_account_bump) =
+        find_escrow_payment_account_address(auction_house, buyer);
+    let (auctioneer, _) = find_auctioneer_address(auction_house, &auctioneer_authority.pubkey());
+    let (escrow_lock, _) = find_escrow_lock_address(auction_house, buyer);
+
+    let data = auction_house::instruction::AuctioneerWithdraw {
+        escrow_payment_bump: escrow_payment_account_bump,
+        amount,
+    };
+
+    let accounts = auction_house::accounts::AuctioneerWithdraw {
+        wallet: *buyer,
+        receipt_account: *buyer,
+        escrow_payment_account,
+        treasury_mint: auction_house_data.treasury_mint,
+        authority: auction_house_data.authority,
+        auctioneer_authority: auctioneer_authority.pubkey(),
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        auctioneer,
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        associated_token_program: associated_token::ID,
+        rent: sysvar::rent::id(),
+        escrow_lock,
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (
+        accounts,
+        Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&auctioneer_authority.pubkey()),
+            &[auctioneer_authority],
+            context.last_blockhash,
+        ),
+    )
+}
+
+pub fn lock_escrow(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    buyer: &Keypair,
+    auctioneer_authority: &Keypair,
+    amount: u64,
+) -> (auction_house::accounts::AuctioneerLockEscrow, Transaction) {
+    let (auctioneer, _) = find_auctioneer_address(auction_house, &auctioneer_authority.pubkey());
+    let (escrow_lock, _) = find_escrow_lock_address(auction_house, &buyer.pubkey());
+
+    let data = auction_house::instruction::AuctioneerLockEscrow { amount };
+
+    let accounts = auction_house::accounts::AuctioneerLockEscrow {
+        wallet: buyer.pubkey(),
+        auction_house: *auction_house,
+        auctioneer_authority: auctioneer_authority.pubkey(),
+        auctioneer,
+        escrow_lock,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (
+        accounts,
+        Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&buyer.pubkey()),
+            &[buyer, auctioneer_authority],
+            context.last_blockhash,
+        ),
+    )
+}
+
+pub fn release_escrow(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    buyer: &Pubkey,
+    auctioneer_authority: &Keypair,
+    amount: u64,
+) -> (
+    auction_house::accounts::AuctioneerReleaseEscrow,
+    Transaction,
+) {
+    let (auctioneer, _) = find_auctioneer_address(auction_house, &auctioneer_authority.pubkey());
+    let (escrow_lock, _) = find_escrow_lock_address(auction_house, buyer);
+
+    let data = auction_house::instruction::AuctioneerReleaseEscrow { amount };
+
+    let accounts = auction_house::accounts::AuctioneerReleaseEscrow {
+        wallet: *buyer,
+        auction_house: *auction_house,
+        auctioneer_authority: auctioneer_authority.pubkey(),
+        auctioneer,
+        escrow_lock,
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (
+        accounts,
+        Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&auctioneer_authority.pubkey()),
+            &[auctioneer_authority],
+            context.last_blockhash,
+        ),
+    )
+}
+
+pub fn close_escrow_account_instruction(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    buyer: &Pubkey,
+    token_program: &Pubkey,
+) -> (auction_house::accounts::CloseEscrowAccount, Instruction) {
+    let (escrow_payment_account, escrow_payment_account_bump) =
+        find_escrow_payment_account_address(auction_house, buyer);
+    let (escrow_lock, _) = find_escrow_lock_address(auction_house, buyer);
+    let receipt_account = if auction_house_data.treasury_mint == spl_token::native_mint::id() {
+        *buyer
+    } else {
+        get_associated_token_address_with_program_id(
+            buyer,
+            &auction_house_data.treasury_mint,
+            token_program,
+        )
+    };
+
+    let data = auction_house::instruction::CloseEscrowAccount {
+        escrow_payment_bump: escrow_payment_account_bump,
+    };
+
+    let accounts = auction_house::accounts::CloseEscrowAccount {
+        wallet: *buyer,
+        receipt_account,
+        escrow_payment_account,
+        treasury_mint: auction_house_data.treasury_mint,
+        auction_house: *auction_house,
+        token_program: *token_program,
+        system_program: system_program::id(),
+        associated_token_program: associated_token::ID,
+        rent: sysvar::rent::id(),
+        escrow_lock,
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (accounts, ix)
+}
+
+pub fn claim_royalties(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    creator: &Keypair,
+) -> (auction_house::accounts::ClaimRoyalties, Transaction) {
+    let (royalty_vault, _) = find_royalty_vault_address(auction_house, &creator.pubkey());
+
+    let accounts = auction_house::accounts::ClaimRoyalties {
+        creator: creator.pubkey(),
+        auction_house: *auction_house,
+        royalty_vault,
+        system_program: system_program::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: auction_house::instruction::ClaimRoyalties {}.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (
+        accounts,
+        Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, creator],
+            context.last_blockhash,
+        ),
+    )
+}
+
+pub fn cancel(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    token: &NFT,
+    wallet: &Keypair,
+    trade_state: &Pubkey,
+    buyer_price: u64,
+) -> (auction_house::accounts::Cancel, Transaction) {
+    let (accounts, ix) = cancel_instruction(
+        auction_house,
+        auction_house_data,
+        token,
+        &wallet.pubkey(),
+        trade_state,
+        buyer_price,
+    );
+
+    (
+        accounts,
+        Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&wallet.pubkey()),
+            &[wallet],
+            context.last_blockhash,
+        ),
+    )
+}
+
+pub fn cancel_instruction(
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    token: &NFT,
+    wallet: &Pubkey,
+    trade_state: &Pubkey,
+    buyer_price: u64,
+) -> (auction_house::accounts::Cancel, Instruction) {
+    let data = auction_house::instruction::Cancel {
+        buyer_price,
+        token_size: 1,
+    };
+
+    let accounts = auction_house::accounts::Cancel {
+        wallet: *wallet,
+        token_account: token.ata,
+        token_mint: token.mint.pubkey(),
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        trade_state: *trade_state,
+        token_program: spl_token::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (accounts, ix)
+}
+
+pub fn print_listing_receipt_instruction(
+    bookkeeper: &Pubkey,
+    seller_trade_state: &Pubkey,
+    token_account: &Pubkey,
+) -> (auction_house::accounts::PrintListingReceipt, Instruction) {
+    let (receipt, _) = find_listing_receipt_address(seller_trade_state);
+
+    let accounts = auction_house::accounts::PrintListingReceipt {
+        receipt,
+        bookkeeper: *bookkeeper,
+        token_account: *token_account,
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+        instruction: sysvar::instructions::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: auction_house::instruction::PrintListingReceipt {}.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (accounts, ix)
+}
+
+pub fn cancel_listing_receipt_instruction(
+    seller_trade_state: &Pubkey,
+) -> (auction_house::accounts::CancelListingReceipt, Instruction) {
+    let (receipt, _) = find_listing_receipt_address(seller_trade_state);
+
+    let accounts = auction_house::accounts::CancelListingReceipt {
+        receipt,
+        system_program: system_program::id(),
+        instruction: sysvar::instructions::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: auction_house::instruction::CancelListingReceipt {}.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (accounts, ix)
+}
+
+pub fn print_bid_receipt_instruction(
+    bookkeeper: &Pubkey,
+    buyer_trade_state: &Pubkey,
+    token_account: &Pubkey,
+) -> (auction_house::accounts::PrintBidReceipt, Instruction) {
+    let (receipt, _) = find_bid_receipt_address(buyer_trade_state);
+
+    let accounts = auction_house::accounts::PrintBidReceipt {
+        receipt,
+        bookkeeper: *bookkeeper,
+        token_account: *token_account,
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+        instruction: sysvar::instructions::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: auction_house::instruction::PrintBidReceipt {}.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (accounts, ix)
+}
+
+pub fn cancel_bid_receipt_instruction(
+    buyer_trade_state: &Pubkey,
+) -> (auction_house::accounts::CancelBidReceipt, Instruction) {
+    let (receipt, _) = find_bid_receipt_address(buyer_trade_state);
+
+    let accounts = auction_house::accounts::CancelBidReceipt {
+        receipt,
+        system_program: system_program::id(),
+        instruction: sysvar::instructions::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: auction_house::instruction::CancelBidReceipt {}.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (accounts, ix)
+}
+
+pub fn print_purchase_receipt_instruction(
+    bookkeeper: &Pubkey,
+    seller_trade_state: &Pubkey,
+    buyer_trade_state: &Pubkey,
+) -> (auction_house::accounts::PrintPurchaseReceipt, Instruction) {
+    let (purchase_receipt, _) =
+        find_purchase_receipt_address(seller_trade_state, buyer_trade_state);
+    let (listing_receipt, _) = find_listing_receipt_address(seller_trade_state);
+    let (bid_receipt, _) = find_bid_receipt_address(buyer_trade_state);
+
+    let accounts = auction_house::accounts::PrintPurchaseReceipt {
+        purchase_receipt,
+        listing_receipt,
+        bid_receipt,
+        bookkeeper: *bookkeeper,
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+        instruction: sysvar::instructions::id(),
+    };
+
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: auction_house::instruction::PrintPurchaseReceipt {}.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (accounts, ix)
+}
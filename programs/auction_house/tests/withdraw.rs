@@ -0,0 +1,215 @@
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
+use auction_house::{errors::AuctionHouseError, pda::find_auctioneer_address};
+use solana_program_test::tokio;
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn withdraw_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 3 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let deposit_amount = 2 * ONE_SOL;
+    let (deposit_accounts, deposit_tx) = deposit(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &buyer,
+        deposit_amount,
+    );
+    context
+        .banks_client
+        .process_transaction(deposit_tx)
+        .await
+        .unwrap();
+
+    let escrow_payment_account_data_len = 0;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let rent_exempt_min: u64 = rent.minimum_balance(escrow_payment_account_data_len);
+
+    let escrow_payment_account = context
+        .banks_client
+        .get_account(deposit_accounts.escrow_payment_account)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    assert_eq!(
+        escrow_payment_account.lamports,
+        deposit_amount + rent_exempt_min
+    );
+
+    let withdraw_amount = deposit_amount - ONE_SOL;
+    let (_, withdraw_tx) = withdraw(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &buyer,
+        withdraw_amount,
+    );
+    context
+        .banks_client
+        .process_transaction(withdraw_tx)
+        .await
+        .unwrap();
+
+    let escrow_payment_account_after_withdraw = context
+        .banks_client
+        .get_account(deposit_accounts.escrow_payment_account)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+
+    assert_eq!(
+        escrow_payment_account_after_withdraw.lamports,
+        deposit_amount + rent_exempt_min - withdraw_amount
+    );
+}
+
+#[tokio::test]
+async fn failure_withdraw_locked_escrow_funds() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, auction_house_data) =
+        create_auction_house(&mut context, 100, false)
+            .await
+            .expect("Failed to create Auction House");
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 3 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let deposit_amount = 2 * ONE_SOL;
+    let (_, deposit_tx) = deposit(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &buyer,
+        deposit_amount,
+    );
+    context
+        .banks_client
+        .process_transaction(deposit_tx)
+        .await
+        .unwrap();
+
+    // One auctioneer runs the auction and locks the highest bid, another may only withdraw.
+    let bidding_authority = Keypair::new();
+    let withdrawing_authority = Keypair::new();
+    for auctioneer_authority in [&bidding_authority, &withdrawing_authority] {
+        airdrop(&mut context, &auctioneer_authority.pubkey(), ONE_SOL)
+            .await
+            .unwrap();
+        let (auctioneer, _) =
+            find_auctioneer_address(&auction_house, &auctioneer_authority.pubkey());
+        let tx = delegate(
+            &mut context,
+            &auction_house,
+            &authority,
+            &auctioneer_authority.pubkey(),
+            &auctioneer,
+            all_scopes(),
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+    }
+
+    let (_, lock_tx) = lock_escrow(
+        &mut context,
+        &auction_house,
+        &buyer,
+        &bidding_authority,
+        ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(lock_tx)
+        .await
+        .unwrap();
+
+    let (_, withdraw_tx) = auctioneer_withdraw(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &buyer.pubkey(),
+        &withdrawing_authority,
+        deposit_amount,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(withdraw_tx)
+        .await
+        .unwrap_err();
+    assert_error(tx_error, AuctionHouseError::EscrowFundsLocked.into());
+
+    // The auctioneer holding the lock can't be undelegated to reopen the direct withdraw path.
+    let (bidding_auctioneer, _) =
+        find_auctioneer_address(&auction_house, &bidding_authority.pubkey());
+    let undelegate_tx = undelegate(
+        &mut context,
+        &auction_house,
+        &authority,
+        &bidding_authority.pubkey(),
+        &bidding_auctioneer,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(undelegate_tx)
+        .await
+        .unwrap_err();
+    assert_error(
+        tx_error,
+        AuctionHouseError::AuctioneerHoldsEscrowLocks.into(),
+    );
+
+    // Unlocked funds stay withdrawable.
+    let (_, withdraw_tx) = auctioneer_withdraw(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &buyer.pubkey(),
+        &withdrawing_authority,
+        deposit_amount - ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(withdraw_tx)
+        .await
+        .unwrap();
+
+    let (_, release_tx) = release_escrow(
+        &mut context,
+        &auction_house,
+        &buyer.pubkey(),
+        &bidding_authority,
+        ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(release_tx)
+        .await
+        .unwrap();
+
+    context.get_new_latest_blockhash().await.unwrap();
+    let undelegate_tx = undelegate(
+        &mut context,
+        &auction_house,
+        &authority,
+        &bidding_authority.pubkey(),
+        &bidding_auctioneer,
+    );
+    context
+        .banks_client
+        .process_transaction(undelegate_tx)
+        .await
+        .unwrap();
+}
@@ -0,0 +1,224 @@
+use anchor_client::solana_sdk::{signer::Signer, transaction::Transaction};
+use auction_house::{
+    errors::AuctionHouseError, pda::find_queued_withdrawal_address,
+    withdrawal_policy::WithdrawalSource,
+};
+use solana_program_test::tokio;
+
+mod utils;
+use utils::setup_functions::*;
+
+#[tokio::test]
+async fn withdraw_from_treasury_over_epoch_cap_failure() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, auction_house_data) =
+        create_auction_house(&mut context, 100, false)
+            .await
+            .expect("Failed to create Auction House");
+
+    airdrop(
+        &mut context,
+        &auction_house_data.auction_house_treasury,
+        10 * ONE_SOL,
+    )
+    .await
+    .unwrap();
+
+    // CreateWithdrawalPolicy
+
+    let ix = create_withdrawal_policy_instruction(
+        &auction_house,
+        &auction_house_data,
+        2 * ONE_SOL,
+        ONE_SOL,
+        60,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Withdrawals within the cap go through immediately
+
+    let ix = withdraw_from_treasury_instruction(&auction_house, &auction_house_data, ONE_SOL);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // The cap is shared by all withdrawals in the epoch
+
+    let ix = withdraw_from_treasury_instruction(&auction_house, &auction_house_data, 2 * ONE_SOL);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(
+        tx_error,
+        AuctionHouseError::WithdrawalExceedsEpochCap.into(),
+    );
+}
+
+#[tokio::test]
+async fn execute_queued_withdrawal_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, auction_house_data) =
+        create_auction_house(&mut context, 100, false)
+            .await
+            .expect("Failed to create Auction House");
+
+    let amount = 5 * ONE_SOL;
+    airdrop(
+        &mut context,
+        &auction_house_data.auction_house_treasury,
+        amount,
+    )
+    .await
+    .unwrap();
+
+    // CreateWithdrawalPolicy and QueueWithdrawal
+
+    let create_withdrawal_policy_ix = create_withdrawal_policy_instruction(
+        &auction_house,
+        &auction_house_data,
+        ONE_SOL,
+        ONE_SOL,
+        60,
+    );
+    let queue_withdrawal_ix = queue_withdrawal_instruction(
+        &auction_house,
+        &auction_house_data,
+        0,
+        WithdrawalSource::Treasury,
+        amount,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[create_withdrawal_policy_ix, queue_withdrawal_ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // ExecuteQueuedWithdrawal before the timelock passed
+
+    let ix = execute_queued_withdrawal_instruction(&auction_house, &auction_house_data, 0);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix.clone()],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::WithdrawalTimelocked.into());
+
+    // ExecuteQueuedWithdrawal after the timelock passed
+
+    context.warp_to_slot(120 * 400).unwrap();
+    context.get_new_latest_blockhash().await.unwrap();
+
+    let destination_balance_before = context
+        .banks_client
+        .get_balance(auction_house_data.treasury_withdrawal_destination)
+        .await
+        .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let destination_balance_after = context
+        .banks_client
+        .get_balance(auction_house_data.treasury_withdrawal_destination)
+        .await
+        .unwrap();
+    let queued_withdrawal_account = context
+        .banks_client
+        .get_account(find_queued_withdrawal_address(&auction_house, 0).0)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        destination_balance_after - destination_balance_before,
+        amount
+    );
+    assert!(queued_withdrawal_account.is_none());
+}
+
+#[tokio::test]
+async fn cancel_queued_withdrawal_success() {
+    let mut context = auction_house_program_test().start_with_context().await;
+
+    let (authority, auction_house, auction_house_data) =
+        create_auction_house(&mut context, 100, false)
+            .await
+            .expect("Failed to create Auction House");
+
+    // CreateWithdrawalPolicy and QueueWithdrawal
+
+    let create_withdrawal_policy_ix = create_withdrawal_policy_instruction(
+        &auction_house,
+        &auction_house_data,
+        ONE_SOL,
+        ONE_SOL,
+        60,
+    );
+    let queue_withdrawal_ix = queue_withdrawal_instruction(
+        &auction_house,
+        &auction_house_data,
+        0,
+        WithdrawalSource::Fee,
+        5 * ONE_SOL,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[create_withdrawal_policy_ix, queue_withdrawal_ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // CancelQueuedWithdrawal
+
+    let ix = cancel_queued_withdrawal_instruction(&auction_house, &auction_house_data, 0);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let queued_withdrawal_account = context
+        .banks_client
+        .get_account(find_queued_withdrawal_address(&auction_house, 0).0)
+        .await
+        .unwrap();
+
+    assert!(queued_withdrawal_account.is_none());
+}
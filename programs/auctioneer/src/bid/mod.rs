@@ -1,15 +1,15 @@
 use anchor_lang::{prelude::*, AnchorDeserialize};
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use auction_house::{
     self,
-    constants::{AUCTIONEER, FEE_PAYER, PREFIX},
-    cpi::accounts::AuctioneerBuy as AHBuy,
+    constants::{AUCTIONEER, ESCROW_LOCK, FEE_PAYER, PREFIX},
+    cpi::accounts::{AuctioneerBuy as AHBuy, AuctioneerPublicBuy as AHPublicBuy},
     program::AuctionHouse as AuctionHouseProgram,
     AuctionHouse,
 };
 
-use crate::{constants::*, sell::config::*, utils::*};
+use crate::{constants::*, escrow_lock::*, sell::config::*, utils::*};
 
 #[derive(Accounts)]
 #[instruction(
@@ -45,7 +45,8 @@ pub struct AuctioneerBuy<'info> {
     pub seller: UncheckedAccount<'info>,
 
     // Accounts passed into Auction House CPI call
-    /// User wallet account.
+    /// User wallet account, paying for its escrow lock on its first bid.
+    #[account(mut)]
     wallet: Signer<'info>,
 
     /// CHECK: Verified through CPI
@@ -58,10 +59,10 @@ pub struct AuctioneerBuy<'info> {
     transfer_authority: UncheckedAccount<'info>,
 
     /// Auction House instance treasury mint account.
-    treasury_mint: Box<Account<'info, Mint>>,
+    treasury_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// SPL token account.
-    token_account: Box<Account<'info, TokenAccount>>,
+    token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// CHECK: Verified through CPI
     /// SPL token account metadata.
@@ -138,8 +139,9 @@ pub struct AuctioneerBuy<'info> {
     pub auctioneer_authority: UncheckedAccount<'info>,
 
     /// CHECK: Not dangerous. Account seeds checked in constraint.
-    /// The auctioneer PDA owned by Auction House.
+    /// The auctioneer PDA owned by Auction House, counting the escrow funds it locks.
     #[account(
+        mut,
         seeds = [
             AUCTIONEER.as_bytes(),
             auction_house.key().as_ref(),
@@ -150,27 +152,49 @@ pub struct AuctioneerBuy<'info> {
     )]
     pub auctioneer: Account<'info, auction_house::Auctioneer>,
 
-    token_program: Program<'info, Token>,
+    token_program: Interface<'info, TokenInterface>,
     system_program: Program<'info, System>,
     rent: Sysvar<'info, Rent>,
+
+    /// CHECK: Account seeds checked in constraint, created by Auction House on the first bid.
+    /// Escrow lock of the wallet, reserving its escrow funds while it holds highest bids.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            ESCROW_LOCK.as_bytes(),
+            wallet.key().as_ref()
+        ],
+        seeds::program = auction_house_program,
+        bump
+    )]
+    pub escrow_lock: UncheckedAccount<'info>,
+
+    /// CHECK: Validated against the listing's highest bid.
+    /// The wallet holding the listing's highest bid, required if it has one.
+    pub highest_bidder: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Validated against the highest bidder.
+    /// Escrow lock of the highest bidder, released as it is outbid.
+    #[account(mut)]
+    pub highest_bidder_escrow_lock: Option<UncheckedAccount<'info>>,
 }
 
 /// Create a private bid on a specific SPL token that is *held by a specific wallet*.
-pub fn auctioneer_buy(
-    ctx: Context<AuctioneerBuy>,
+pub fn auctioneer_buy<'info>(
+    ctx: Context<'_, '_, '_, 'info, AuctioneerBuy<'info>>,
     trade_state_bump: u8,
     escrow_payment_bump: u8,
     auctioneer_authority_bump: u8,
     buyer_price: u64,
     token_size: u64,
 ) -> Result<()> {
+    assert_auction_house_not_paused(&ctx.accounts.auction_house)?;
     assert_auction_active(&ctx.accounts.listing_config)?;
     assert_higher_bid(&ctx.accounts.listing_config, buyer_price)?;
     assert_exceeds_reserve_price(&ctx.accounts.listing_config, buyer_price)?;
     process_time_extension(&mut ctx.accounts.listing_config)?;
-    ctx.accounts.listing_config.highest_bid.amount = buyer_price;
-    ctx.accounts.listing_config.highest_bid.buyer_trade_state =
-        ctx.accounts.buyer_trade_state.key();
 
     let cpi_program = ctx.accounts.auction_house_program.to_account_info();
     let cpi_accounts = AHBuy {
@@ -203,12 +227,353 @@ pub fn auctioneer_buy(
         &[auctioneer_authority_bump],
     ];
 
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    // An allowed collection account is passed through as a remaining account.
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts)
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
     auction_house::cpi::auctioneer_buy(
         cpi_ctx.with_signer(&[&auctioneer_seeds]),
         trade_state_bump,
         escrow_payment_bump,
         buyer_price,
         token_size,
+    )?;
+
+    let escrow_locker = EscrowLocker {
+        auction_house_program: &ctx.accounts.auction_house_program.to_account_info(),
+        auction_house: &ctx.accounts.auction_house.to_account_info(),
+        auctioneer_authority: &ctx.accounts.auctioneer_authority.to_account_info(),
+        auctioneer: &ctx.accounts.auctioneer.to_account_info(),
+        auctioneer_authority_bump,
+    };
+    reserve_bid(
+        &escrow_locker,
+        &ctx.accounts.listing_config,
+        &ctx.accounts.wallet.to_account_info(),
+        &ctx.accounts.escrow_lock,
+        &ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.highest_bidder.as_ref(),
+        ctx.accounts.highest_bidder_escrow_lock.as_ref(),
+        &ctx.accounts.token_account.key(),
+        &ctx.accounts.treasury_mint.key(),
+        &ctx.accounts.token_account.mint,
+        token_size,
+        buyer_price,
+    )?;
+    set_highest_bid(
+        &mut ctx.accounts.listing_config,
+        ctx.accounts.buyer_trade_state.key(),
+        buyer_price,
+    );
+
+    // The bid only tops the escrow up to its own price, which may not cover the wallet's other
+    // highest bids.
+    assert_escrow_covers_locks(
+        &ctx.accounts.escrow_payment_account,
+        &ctx.accounts.treasury_mint.key(),
+        &ctx.accounts.escrow_lock,
     )
 }
+
+#[derive(Accounts)]
+#[instruction(
+    trade_state_bump: u8,
+    escrow_payment_bump: u8,
+    auctioneer_authority_bump: u8,
+    buyer_price: u64,
+    token_size: u64
+)]
+pub struct AuctioneerPublicBuy<'info> {
+    /// Auction House Program
+    pub auction_house_program: Program<'info, AuctionHouseProgram>,
+
+    // Accounts used for Auctioneer
+    /// The Listing Config used for listing settings
+    #[account(
+        mut,
+        seeds = [
+            LISTING_CONFIG.as_bytes(),
+            seller.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_account.key().as_ref(),
+            auction_house.treasury_mint.as_ref(),
+            token_account.mint.as_ref(),
+            &token_size.to_le_bytes()
+        ],
+        bump,
+    )]
+    pub listing_config: Account<'info, ListingConfig>,
+
+    /// The seller of the NFT
+    /// CHECK: Checked via trade state constraints
+    pub seller: UncheckedAccount<'info>,
+
+    // Accounts passed into Auction House CPI call
+    /// User wallet account, paying for its escrow lock on its first bid.
+    #[account(mut)]
+    wallet: Signer<'info>,
+
+    /// CHECK: Verified through CPI
+    /// User SOL or SPL account to transfer funds from.
+    #[account(mut)]
+    payment_account: UncheckedAccount<'info>,
+
+    /// CHECK:
+    /// SPL token account transfer authority.
+    transfer_authority: UncheckedAccount<'info>,
+
+    /// Auction House instance treasury mint account.
+    treasury_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// SPL token account.
+    token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Verified through CPI
+    /// SPL token account metadata.
+    metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref()
+        ],
+        seeds::program = auction_house_program,
+        bump = escrow_payment_bump
+    )]
+    escrow_payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Verified with has_one constraint on auction house account.
+    /// Auction House instance authority account.
+    authority: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        seeds::program = auction_house_program,
+        bump = auction_house.bump,
+        has_one = authority,
+        has_one = treasury_mint,
+        has_one = auction_house_fee_account
+    )]
+    auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Auction House instance fee account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            FEE_PAYER.as_bytes()
+        ],
+        seeds::program = auction_house_program,
+        bump = auction_house.fee_payer_bump
+    )]
+    auction_house_fee_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer public trade state PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            wallet.key().as_ref(),
+            auction_house.key().as_ref(),
+            treasury_mint.key().as_ref(),
+            token_account.mint.as_ref(),
+            buyer_price.to_le_bytes().as_ref(),
+            token_size.to_le_bytes().as_ref()
+        ],
+        seeds::program = auction_house_program,
+        bump = trade_state_bump
+    )]
+    buyer_trade_state: UncheckedAccount<'info>,
+
+    /// CHECK: Is used as a seed for auctioneer.
+    /// The auctioneer program PDA running this auction.
+    pub auctioneer_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// The auctioneer PDA owned by Auction House, counting the escrow funds it locks.
+    #[account(
+        mut,
+        seeds = [
+            AUCTIONEER.as_bytes(),
+            auction_house.key().as_ref(),
+            auctioneer_authority.key().as_ref()
+        ],
+        seeds::program = auction_house_program,
+        bump = auctioneer.bump,
+    )]
+    pub auctioneer: Account<'info, auction_house::Auctioneer>,
+
+    token_program: Interface<'info, TokenInterface>,
+    system_program: Program<'info, System>,
+    rent: Sysvar<'info, Rent>,
+
+    /// CHECK: Account seeds checked in constraint, created by Auction House on the first bid.
+    /// Escrow lock of the wallet, reserving its escrow funds while it holds highest bids.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            ESCROW_LOCK.as_bytes(),
+            wallet.key().as_ref()
+        ],
+        seeds::program = auction_house_program,
+        bump
+    )]
+    pub escrow_lock: UncheckedAccount<'info>,
+
+    /// CHECK: Validated against the listing's highest bid.
+    /// The wallet holding the listing's highest bid, required if it has one.
+    pub highest_bidder: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Validated against the highest bidder.
+    /// Escrow lock of the highest bidder, released as it is outbid.
+    #[account(mut)]
+    pub highest_bidder_escrow_lock: Option<UncheckedAccount<'info>>,
+}
+
+/// Create a public bid on a specific SPL token that is *held by any wallet*.
+pub fn auctioneer_public_buy<'info>(
+    ctx: Context<'_, '_, '_, 'info, AuctioneerPublicBuy<'info>>,
+    trade_state_bump: u8,
+    escrow_payment_bump: u8,
+    auctioneer_authority_bump: u8,
+    buyer_price: u64,
+    token_size: u64,
+) -> Result<()> {
+    assert_auction_house_not_paused(&ctx.accounts.auction_house)?;
+    assert_auction_active(&ctx.accounts.listing_config)?;
+    assert_higher_bid(&ctx.accounts.listing_config, buyer_price)?;
+    assert_exceeds_reserve_price(&ctx.accounts.listing_config, buyer_price)?;
+    process_time_extension(&mut ctx.accounts.listing_config)?;
+
+    let cpi_program = ctx.accounts.auction_house_program.to_account_info();
+    let cpi_accounts = AHPublicBuy {
+        wallet: ctx.accounts.wallet.to_account_info(),
+        payment_account: ctx.accounts.payment_account.to_account_info(),
+        transfer_authority: ctx.accounts.transfer_authority.to_account_info(),
+        treasury_mint: ctx.accounts.treasury_mint.to_account_info(),
+        token_account: ctx.accounts.token_account.to_account_info(),
+        metadata: ctx.accounts.metadata.to_account_info(),
+        escrow_payment_account: ctx.accounts.escrow_payment_account.to_account_info(),
+        auction_house: ctx.accounts.auction_house.to_account_info(),
+        auction_house_fee_account: ctx.accounts.auction_house_fee_account.to_account_info(),
+        buyer_trade_state: ctx.accounts.buyer_trade_state.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+        auctioneer_authority: ctx.accounts.auctioneer_authority.to_account_info(),
+        auctioneer: ctx.accounts.auctioneer.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        rent: ctx.accounts.rent.to_account_info(),
+    };
+
+    let auction_house = &ctx.accounts.auction_house;
+    let ah_key = auction_house.key();
+    let auctioneer_authority = &ctx.accounts.auctioneer_authority;
+    let _aa_key = auctioneer_authority.key();
+
+    let auctioneer_seeds = [
+        AUCTIONEER.as_bytes(),
+        ah_key.as_ref(),
+        &[auctioneer_authority_bump],
+    ];
+
+    // An allowed collection account is passed through as a remaining account.
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts)
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+    auction_house::cpi::auctioneer_public_buy(
+        cpi_ctx.with_signer(&[&auctioneer_seeds]),
+        trade_state_bump,
+        escrow_payment_bump,
+        buyer_price,
+        token_size,
+    )?;
+
+    let escrow_locker = EscrowLocker {
+        auction_house_program: &ctx.accounts.auction_house_program.to_account_info(),
+        auction_house: &ctx.accounts.auction_house.to_account_info(),
+        auctioneer_authority: &ctx.accounts.auctioneer_authority.to_account_info(),
+        auctioneer: &ctx.accounts.auctioneer.to_account_info(),
+        auctioneer_authority_bump,
+    };
+    reserve_bid(
+        &escrow_locker,
+        &ctx.accounts.listing_config,
+        &ctx.accounts.wallet.to_account_info(),
+        &ctx.accounts.escrow_lock,
+        &ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.highest_bidder.as_ref(),
+        ctx.accounts.highest_bidder_escrow_lock.as_ref(),
+        &ctx.accounts.token_account.key(),
+        &ctx.accounts.treasury_mint.key(),
+        &ctx.accounts.token_account.mint,
+        token_size,
+        buyer_price,
+    )?;
+    set_highest_bid(
+        &mut ctx.accounts.listing_config,
+        ctx.accounts.buyer_trade_state.key(),
+        buyer_price,
+    );
+
+    // The bid only tops the escrow up to its own price, which may not cover the wallet's other
+    // highest bids.
+    assert_escrow_covers_locks(
+        &ctx.accounts.escrow_payment_account,
+        &ctx.accounts.treasury_mint.key(),
+        &ctx.accounts.escrow_lock,
+    )
+}
+
+/// Move the escrow reservation of the listing's highest bid, if any, to the new bid of `wallet`.
+fn reserve_bid<'info>(
+    escrow_locker: &EscrowLocker<'_, 'info>,
+    listing_config: &ListingConfig,
+    wallet: &AccountInfo<'info>,
+    escrow_lock: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    highest_bidder: Option<&UncheckedAccount<'info>>,
+    highest_bidder_escrow_lock: Option<&UncheckedAccount<'info>>,
+    token_account: &Pubkey,
+    treasury_mint: &Pubkey,
+    token_mint: &Pubkey,
+    token_size: u64,
+    buyer_price: u64,
+) -> Result<()> {
+    let highest_bidder = HighestBidder::from_accounts(
+        listing_config,
+        highest_bidder.map(|account| account.as_ref()),
+        highest_bidder_escrow_lock.map(|account| account.as_ref()),
+    )?;
+
+    if let Some(highest_bidder) = highest_bidder {
+        highest_bidder.assert_valid(
+            listing_config,
+            escrow_locker.auction_house.key,
+            token_account,
+            treasury_mint,
+            token_mint,
+            token_size,
+        )?;
+
+        // Raising your own bid releases your previous one from the same lock.
+        escrow_locker.release(
+            highest_bidder.wallet,
+            highest_bidder.escrow_lock,
+            listing_config.highest_bid.amount,
+        )?;
+    }
+
+    escrow_locker.lock(wallet, escrow_lock, system_program, buyer_price)
+}
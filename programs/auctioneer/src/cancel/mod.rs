@@ -1,7 +1,7 @@
 use anchor_lang::{
     prelude::*, solana_program::program::invoke_signed, AnchorDeserialize, InstructionData,
 };
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use auction_house::{
     self,
@@ -11,7 +11,7 @@ use auction_house::{
     AuctionHouse,
 };
 
-use crate::{constants::*, errors::*, sell::config::*};
+use crate::{constants::*, errors::*, escrow_lock::*, sell::config::*};
 
 #[derive(Accounts, Clone)]
 #[instruction(
@@ -50,10 +50,10 @@ pub struct AuctioneerCancel<'info> {
 
     /// SPL token account containing the token of the sale to be canceled.
     #[account(mut)]
-    pub token_account: Box<Account<'info, TokenAccount>>,
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// Token mint account of SPL token.
-    pub token_mint: Box<Account<'info, Mint>>,
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// CHECK: If the AH authority is signer then we sign the auctioneer_authority CPI.
     /// Auction House instance authority account.
@@ -97,8 +97,9 @@ pub struct AuctioneerCancel<'info> {
     pub auctioneer_authority: UncheckedAccount<'info>,
 
     /// CHECK: Checked in seed constraints
-    /// The auctioneer PDA owned by Auction House.
+    /// The auctioneer PDA owned by Auction House, counting the escrow funds it locks.
     #[account(
+        mut,
         seeds = [
             AUCTIONEER.as_bytes(),
             auction_house.key().as_ref(),
@@ -109,12 +110,21 @@ pub struct AuctioneerCancel<'info> {
     )]
     pub auctioneer: Account<'info, auction_house::Auctioneer>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// CHECK: Validated against the listing's highest bid.
+    /// Wallet holding the listing's highest bid, required when the seller cancels a listing with bids.
+    pub highest_bidder: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Validated against the highest bidder.
+    /// Escrow lock of the highest bidder, released when the listing is cancelled.
+    #[account(mut)]
+    pub highest_bidder_escrow_lock: Option<UncheckedAccount<'info>>,
 }
 
 // Cancel a bid or listing, transferring all lamports from the trade state account to the fee payer, and setting the trade state account data to zero so it can be garbage collected.
-pub fn auctioneer_cancel(
-    ctx: Context<AuctioneerCancel>,
+pub fn auctioneer_cancel<'info>(
+    ctx: Context<'_, '_, '_, 'info, AuctioneerCancel<'info>>,
     auctioneer_authority_bump: u8,
     buyer_price: u64,
     token_size: u64,
@@ -142,20 +152,28 @@ pub fn auctioneer_cancel(
         token_size,
     };
 
+    let mut cpi_account_metas: Vec<AccountMeta> = cpi_accounts
+        .to_account_metas(None)
+        .into_iter()
+        .zip(cpi_accounts.to_account_infos())
+        .map(|mut pair| {
+            pair.0.is_signer = pair.1.is_signer;
+            if pair.0.pubkey == ctx.accounts.auctioneer_authority.key() {
+                pair.0.is_signer = true;
+            }
+            pair.0
+        })
+        .collect();
+
+    // Programmable NFTs pass their Token Metadata accounts as remaining accounts.
+    cpi_account_metas.append(&mut ctx.remaining_accounts.to_vec().to_account_metas(None));
+
+    let mut cpi_account_infos: Vec<AccountInfo> = cpi_accounts.to_account_infos();
+    cpi_account_infos.append(&mut ctx.remaining_accounts.to_vec());
+
     let ix = anchor_lang::solana_program::instruction::Instruction {
         program_id: cpi_program.key(),
-        accounts: cpi_accounts
-            .to_account_metas(None)
-            .into_iter()
-            .zip(cpi_accounts.to_account_infos())
-            .map(|mut pair| {
-                pair.0.is_signer = pair.1.is_signer;
-                if pair.0.pubkey == ctx.accounts.auctioneer_authority.key() {
-                    pair.0.is_signer = true;
-                }
-                pair.0
-            })
-            .collect(),
+        accounts: cpi_account_metas,
         data: cancel_data.data(),
     };
 
@@ -170,12 +188,46 @@ pub fn auctioneer_cancel(
         &[auctioneer_authority_bump],
     ];
 
-    invoke_signed(&ix, &cpi_accounts.to_account_infos(), &[&auctioneer_seeds])?;
+    invoke_signed(&ix, &cpi_account_infos, &[&auctioneer_seeds])?;
 
     // Close the Listing Config account if the seller is canceling their listing.
     if ctx.accounts.token_account.owner == ctx.accounts.wallet.key()
         && ctx.accounts.wallet.is_signer
     {
+        let highest_bidder = HighestBidder::from_accounts(
+            &ctx.accounts.listing_config,
+            ctx.accounts
+                .highest_bidder
+                .as_ref()
+                .map(|account| account.as_ref()),
+            ctx.accounts
+                .highest_bidder_escrow_lock
+                .as_ref()
+                .map(|account| account.as_ref()),
+        )?;
+        if let Some(highest_bidder) = highest_bidder {
+            highest_bidder.assert_valid(
+                &ctx.accounts.listing_config,
+                &ah_key,
+                &ctx.accounts.token_account.key(),
+                &ctx.accounts.auction_house.treasury_mint,
+                &ctx.accounts.token_mint.key(),
+                token_size,
+            )?;
+            let escrow_locker = EscrowLocker {
+                auction_house_program: &cpi_program,
+                auction_house: &ctx.accounts.auction_house.to_account_info(),
+                auctioneer_authority: &ctx.accounts.auctioneer_authority.to_account_info(),
+                auctioneer: &ctx.accounts.auctioneer.to_account_info(),
+                auctioneer_authority_bump,
+            };
+            escrow_locker.release(
+                highest_bidder.wallet,
+                highest_bidder.escrow_lock,
+                ctx.accounts.listing_config.highest_bid.amount,
+            )?;
+        }
+
         let listing_config = &ctx.accounts.listing_config.to_account_info();
         let seller = &ctx.accounts.seller.to_account_info();
 
@@ -1,2 +1,5 @@
 pub const LISTING_CONFIG: &str = "listing_config";
 pub const AUCTIONEER_BUYER_PRICE: u64 = u64::MAX;
+/// Seconds after an auction ends during which its highest bid stays locked for the seller to
+/// settle. Past it, anyone may release the lock.
+pub const ESCROW_LOCK_GRACE_PERIOD: i64 = 7 * 24 * 60 * 60;
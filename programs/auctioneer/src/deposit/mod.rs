@@ -1,5 +1,5 @@
 use anchor_lang::{prelude::*, AnchorDeserialize};
-use anchor_spl::token::{Mint, Token};
+use anchor_spl::token_interface::{Mint, TokenInterface};
 
 use auction_house::{
     self,
@@ -45,7 +45,7 @@ pub struct AuctioneerDeposit<'info> {
     pub escrow_payment_account: UncheckedAccount<'info>,
 
     /// Auction House instance treasury mint account.
-    pub treasury_mint: Box<Account<'info, Mint>>,
+    pub treasury_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// CHECK: Verified through CPI
     /// Auction House instance authority account.
@@ -97,7 +97,7 @@ pub struct AuctioneerDeposit<'info> {
     )]
     pub auctioneer: Account<'info, auction_house::Auctioneer>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
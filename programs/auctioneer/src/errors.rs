@@ -41,4 +41,28 @@ pub enum AuctioneerError {
     // 6009
     #[msg("The highest bidder is not allowed to cancel")]
     CannotCancelHighestBid,
+
+    // 6010
+    #[msg("The Auction House is paused")]
+    AuctionHousePaused,
+
+    // 6011
+    #[msg("The escrow does not hold enough funds beyond those locked by highest bids")]
+    InsufficientUnlockedFunds,
+
+    // 6012
+    #[msg("The highest bidder and their escrow lock must be passed")]
+    HighestBidderRequired,
+
+    // 6013
+    #[msg("The accounts passed do not belong to the highest bidder")]
+    HighestBidderMismatch,
+
+    // 6014
+    #[msg("The highest bid stays locked until the grace period after the auction end has passed")]
+    EscrowLockGracePeriod,
+
+    // 6015
+    #[msg("The listing has no highest bid")]
+    NoHighestBid,
 }
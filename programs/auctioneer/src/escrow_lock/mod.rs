@@ -0,0 +1,331 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::spl_token,
+    token_interface::{Mint, TokenAccount},
+};
+
+use auction_house::{
+    constants::{AUCTIONEER, PREFIX},
+    cpi::accounts::{
+        AuctioneerLockEscrow as AHLockEscrow, AuctioneerReleaseEscrow as AHReleaseEscrow,
+    },
+    escrow_lock::locked_amount,
+    pda::{
+        find_escrow_lock_address, find_public_bid_trade_state_address, find_trade_state_address,
+    },
+    program::AuctionHouse as AuctionHouseProgram,
+    utils::unpack_token_account,
+    AuctionHouse,
+};
+
+use crate::{constants::*, errors::*, events::EscrowLockReleased, sell::config::ListingConfig};
+
+/// Accounts of the wallet holding a listing's highest bid, whose reservation is released when it
+/// is outbid or the listing is cancelled.
+pub struct HighestBidder<'a, 'info> {
+    pub wallet: &'a AccountInfo<'info>,
+    pub escrow_lock: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> HighestBidder<'a, 'info> {
+    /// The highest bidder accounts, required once the listing has a bid.
+    pub fn from_accounts(
+        listing_config: &ListingConfig,
+        wallet: Option<&'a AccountInfo<'info>>,
+        escrow_lock: Option<&'a AccountInfo<'info>>,
+    ) -> Result<Option<Self>> {
+        if listing_config.highest_bid.amount == 0 {
+            return Ok(None);
+        }
+
+        match (wallet, escrow_lock) {
+            (Some(wallet), Some(escrow_lock)) => Ok(Some(Self {
+                wallet,
+                escrow_lock,
+            })),
+            _ => err!(AuctioneerError::HighestBidderRequired),
+        }
+    }
+
+    /// Check the wallet placed the listing's highest bid, privately or publicly, and that the
+    /// escrow lock is its own.
+    pub fn assert_valid(
+        &self,
+        listing_config: &ListingConfig,
+        auction_house: &Pubkey,
+        token_account: &Pubkey,
+        treasury_mint: &Pubkey,
+        token_mint: &Pubkey,
+        token_size: u64,
+    ) -> Result<()> {
+        let highest_bid = &listing_config.highest_bid;
+        let (trade_state, _) = find_trade_state_address(
+            self.wallet.key,
+            auction_house,
+            token_account,
+            treasury_mint,
+            token_mint,
+            highest_bid.amount,
+            token_size,
+        );
+        let (public_trade_state, _) = find_public_bid_trade_state_address(
+            self.wallet.key,
+            auction_house,
+            treasury_mint,
+            token_mint,
+            highest_bid.amount,
+            token_size,
+        );
+        if highest_bid.buyer_trade_state != trade_state
+            && highest_bid.buyer_trade_state != public_trade_state
+        {
+            return err!(AuctioneerError::HighestBidderMismatch);
+        }
+
+        let (escrow_lock, _) = find_escrow_lock_address(auction_house, self.wallet.key);
+        if *self.escrow_lock.key != escrow_lock {
+            return err!(AuctioneerError::HighestBidderMismatch);
+        }
+
+        Ok(())
+    }
+}
+
+/// Accounts the Auctioneer signs with to update the escrow locks Auction House enforces.
+pub struct EscrowLocker<'a, 'info> {
+    pub auction_house_program: &'a AccountInfo<'info>,
+    pub auction_house: &'a AccountInfo<'info>,
+    pub auctioneer_authority: &'a AccountInfo<'info>,
+    pub auctioneer: &'a AccountInfo<'info>,
+    pub auctioneer_authority_bump: u8,
+}
+
+impl<'a, 'info> EscrowLocker<'a, 'info> {
+    /// Reserve `amount` of the escrow of `wallet`, which pays for its escrow lock on its first bid.
+    pub fn lock(
+        &self,
+        wallet: &AccountInfo<'info>,
+        escrow_lock: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        let ah_key = self.auction_house.key();
+        let auctioneer_seeds = [
+            AUCTIONEER.as_bytes(),
+            ah_key.as_ref(),
+            &[self.auctioneer_authority_bump],
+        ];
+
+        let cpi_accounts = AHLockEscrow {
+            wallet: wallet.clone(),
+            auction_house: self.auction_house.clone(),
+            auctioneer_authority: self.auctioneer_authority.clone(),
+            auctioneer: self.auctioneer.clone(),
+            escrow_lock: escrow_lock.clone(),
+            system_program: system_program.clone(),
+        };
+        auction_house::cpi::auctioneer_lock_escrow(
+            CpiContext::new_with_signer(
+                self.auction_house_program.clone(),
+                cpi_accounts,
+                &[&auctioneer_seeds],
+            ),
+            amount,
+        )
+    }
+
+    /// Release `amount` from the escrow lock of `wallet`, whose address was already checked.
+    pub fn release(
+        &self,
+        wallet: &AccountInfo<'info>,
+        escrow_lock: &AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        let ah_key = self.auction_house.key();
+        let auctioneer_seeds = [
+            AUCTIONEER.as_bytes(),
+            ah_key.as_ref(),
+            &[self.auctioneer_authority_bump],
+        ];
+
+        let cpi_accounts = AHReleaseEscrow {
+            wallet: wallet.clone(),
+            auction_house: self.auction_house.clone(),
+            auctioneer_authority: self.auctioneer_authority.clone(),
+            auctioneer: self.auctioneer.clone(),
+            escrow_lock: escrow_lock.clone(),
+        };
+        auction_house::cpi::auctioneer_release_escrow(
+            CpiContext::new_with_signer(
+                self.auction_house_program.clone(),
+                cpi_accounts,
+                &[&auctioneer_seeds],
+            ),
+            amount,
+        )
+    }
+}
+
+/// Funds held by an escrow payment account, lamports for native SOL and tokens otherwise.
+pub fn escrow_balance(escrow_payment_account: &AccountInfo, treasury_mint: &Pubkey) -> Result<u64> {
+    if *treasury_mint == spl_token::native_mint::id() {
+        return Ok(escrow_payment_account.lamports());
+    }
+
+    if escrow_payment_account.data_is_empty() {
+        return Ok(0);
+    }
+
+    Ok(unpack_token_account(escrow_payment_account)?.amount)
+}
+
+/// Check the escrow covers every bid the wallet's lock reserves funds for.
+pub fn assert_escrow_covers_locks(
+    escrow_payment_account: &AccountInfo,
+    treasury_mint: &Pubkey,
+    escrow_lock: &AccountInfo,
+) -> Result<()> {
+    if escrow_balance(escrow_payment_account, treasury_mint)? < locked_amount(escrow_lock)? {
+        return err!(AuctioneerError::InsufficientUnlockedFunds);
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(token_size: u64)]
+pub struct ReleaseEscrowLock<'info> {
+    /// Auction House Program
+    pub auction_house_program: Program<'info, AuctionHouseProgram>,
+
+    /// The Listing Config of the unsettled auction.
+    #[account(
+        mut,
+        seeds = [
+            LISTING_CONFIG.as_bytes(),
+            seller.key().as_ref(),
+            auction_house.key().as_ref(),
+            token_account.key().as_ref(),
+            auction_house.treasury_mint.as_ref(),
+            token_mint.key().as_ref(),
+            &token_size.to_le_bytes()
+        ],
+        bump,
+    )]
+    pub listing_config: Account<'info, ListingConfig>,
+
+    /// CHECK: Checked via listing config seeds.
+    /// The seller of the NFT.
+    pub seller: UncheckedAccount<'info>,
+
+    /// SPL token account containing the token of the sale.
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token mint account of SPL token.
+    #[account(address = token_account.mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        seeds::program = auction_house_program,
+        bump = auction_house.bump
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Validated against the listing's highest bid.
+    /// Wallet holding the listing's highest bid.
+    pub highest_bidder: UncheckedAccount<'info>,
+
+    /// CHECK: Validated against the highest bidder.
+    /// Escrow lock of the highest bidder.
+    #[account(mut)]
+    pub highest_bidder_escrow_lock: UncheckedAccount<'info>,
+
+    /// CHECK: Account seeds checked in constraint.
+    /// The auctioneer program PDA running this auction.
+    #[account(
+        seeds = [
+            AUCTIONEER.as_bytes(),
+            auction_house.key().as_ref()
+        ],
+        bump
+    )]
+    pub auctioneer_authority: UncheckedAccount<'info>,
+
+    /// The auctioneer PDA owned by Auction House, counting the funds it locks.
+    #[account(
+        mut,
+        seeds = [
+            AUCTIONEER.as_bytes(),
+            auction_house.key().as_ref(),
+            auctioneer_authority.key().as_ref()
+        ],
+        seeds::program = auction_house_program,
+        bump = auctioneer.bump,
+    )]
+    pub auctioneer: Account<'info, auction_house::Auctioneer>,
+}
+
+/// Release the escrow funds locked by the highest bid of an auction the seller did not settle
+/// within `ESCROW_LOCK_GRACE_PERIOD` of its end. The bid stops being the highest bid, so the
+/// auction can no longer be settled, the bidder may cancel it and the seller may cancel the
+/// listing.
+pub fn release_escrow_lock(ctx: Context<ReleaseEscrowLock>, token_size: u64) -> Result<()> {
+    let listing_config = &mut ctx.accounts.listing_config;
+
+    let release_time = listing_config
+        .end_time
+        .checked_add(ESCROW_LOCK_GRACE_PERIOD)
+        .ok_or(AuctioneerError::EscrowLockGracePeriod)?;
+    if Clock::get()?.unix_timestamp < release_time {
+        return err!(AuctioneerError::EscrowLockGracePeriod);
+    }
+
+    let highest_bidder = HighestBidder::from_accounts(
+        listing_config,
+        Some(ctx.accounts.highest_bidder.as_ref()),
+        Some(ctx.accounts.highest_bidder_escrow_lock.as_ref()),
+    )?
+    .ok_or(AuctioneerError::NoHighestBid)?;
+    highest_bidder.assert_valid(
+        listing_config,
+        &ctx.accounts.auction_house.key(),
+        &ctx.accounts.token_account.key(),
+        &ctx.accounts.auction_house.treasury_mint,
+        &ctx.accounts.token_mint.key(),
+        token_size,
+    )?;
+
+    let released_bid = listing_config.highest_bid.clone();
+    let escrow_locker = EscrowLocker {
+        auction_house_program: &ctx.accounts.auction_house_program.to_account_info(),
+        auction_house: &ctx.accounts.auction_house.to_account_info(),
+        auctioneer_authority: &ctx.accounts.auctioneer_authority.to_account_info(),
+        auctioneer: &ctx.accounts.auctioneer.to_account_info(),
+        auctioneer_authority_bump: *ctx
+            .bumps
+            .get("auctioneer_authority")
+            .ok_or(AuctioneerError::BumpSeedNotInHashMap)?,
+    };
+    escrow_locker.release(
+        highest_bidder.wallet,
+        highest_bidder.escrow_lock,
+        released_bid.amount,
+    )?;
+
+    listing_config.highest_bid.amount = 0;
+    listing_config.highest_bid.buyer_trade_state = Pubkey::default();
+
+    emit!(EscrowLockReleased {
+        listing_config: listing_config.key(),
+        buyer_trade_state: released_bid.buyer_trade_state,
+        amount: released_bid.amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+/// Emitted when a timed auction listing is created.
+#[event]
+pub struct AuctionCreated {
+    pub listing_config: Pubkey,
+    pub auction_house: Pubkey,
+    pub seller: Pubkey,
+    pub token_mint: Pubkey,
+    pub token_size: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub reserve_price: u64,
+    pub min_bid_increment: u64,
+    pub time_ext_period: u32,
+    pub time_ext_delta: u32,
+}
+
+/// Emitted when a bid replaces the current highest bid of an auction.
+#[event]
+pub struct Outbid {
+    pub listing_config: Pubkey,
+    pub previous_buyer_trade_state: Pubkey,
+    pub previous_amount: u64,
+    pub buyer_trade_state: Pubkey,
+    pub amount: u64,
+    /// The auction end time, including any extension triggered by this bid.
+    pub end_time: i64,
+}
+
+/// Emitted when the escrow lock of an unsettled auction's highest bid is released after the grace
+/// period.
+#[event]
+pub struct EscrowLockReleased {
+    pub listing_config: Pubkey,
+    pub buyer_trade_state: Pubkey,
+    pub amount: u64,
+}
@@ -1,15 +1,15 @@
 use anchor_lang::{prelude::*, AnchorDeserialize, InstructionData};
-use anchor_spl::{associated_token::AssociatedToken, token::Token};
+use anchor_spl::{associated_token::AssociatedToken, token_interface::TokenInterface};
 
 use auction_house::{
     self,
-    constants::{AUCTIONEER, FEE_PAYER, PREFIX, SIGNER, TREASURY},
+    constants::{AUCTIONEER, ESCROW_LOCK, FEE_PAYER, PREFIX, SIGNER, TREASURY},
     cpi::accounts::AuctioneerExecuteSale as AHExecuteSale,
     program::AuctionHouse as AuctionHouseProgram,
     AuctionHouse,
 };
 
-use crate::{constants::*, sell::config::*, utils::*};
+use crate::{constants::*, escrow_lock::EscrowLocker, sell::config::*, utils::*};
 
 use anchor_lang::solana_program::program::invoke_signed;
 
@@ -199,8 +199,9 @@ pub struct AuctioneerExecuteSale<'info> {
     pub auctioneer_authority: UncheckedAccount<'info>,
 
     /// CHECK: Not dangerous. Account seeds checked in constraint.
-    /// The auctioneer PDA owned by Auction House.
+    /// The auctioneer PDA owned by Auction House, counting the escrow funds it locks.
     #[account(
+        mut,
         seeds = [
             AUCTIONEER.as_bytes(),
             auction_house.key().as_ref(),
@@ -222,10 +223,28 @@ pub struct AuctioneerExecuteSale<'info> {
     )]
     pub program_as_signer: UncheckedAccount<'info>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
+
+    /// Token program owning the treasury mint, either SPL Token or Token-2022.
+    pub treasury_token_program: Interface<'info, TokenInterface>,
+
+    /// CHECK: Account seeds checked in constraint, may not exist for bids placed before escrow locking.
+    /// Escrow lock of the buyer, released by the settled bid.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            ESCROW_LOCK.as_bytes(),
+            buyer.key().as_ref()
+        ],
+        seeds::program = auction_house_program,
+        bump
+    )]
+    pub escrow_lock: UncheckedAccount<'info>,
     // Remaining accounts (Provide only if token metadata has creators)
     //    #[account(mut)]
     //    pub creator1: Option<UncheckedAccount<'info>>,
@@ -248,6 +267,7 @@ pub fn auctioneer_execute_sale<'info>(
     buyer_price: u64,
     token_size: u64,
 ) -> Result<()> {
+    assert_auction_house_not_paused(&ctx.accounts.auction_house)?;
     assert_auction_over(&ctx.accounts.listing_config)?;
     assert_highest_bidder(
         &ctx.accounts.listing_config,
@@ -282,6 +302,7 @@ pub fn auctioneer_execute_sale<'info>(
         associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
         program_as_signer: ctx.accounts.program_as_signer.to_account_info(),
         rent: ctx.accounts.rent.to_account_info(),
+        treasury_token_program: ctx.accounts.treasury_token_program.to_account_info(),
     };
 
     let execute_sale_data = auction_house::instruction::AuctioneerExecuteSale {
@@ -290,6 +311,8 @@ pub fn auctioneer_execute_sale<'info>(
         program_as_signer_bump,
         buyer_price,
         token_size,
+        partial_order_size: None,
+        partial_order_price: None,
     };
 
     let mut cpi_account_metas: Vec<AccountMeta> = cpi_accounts
@@ -329,6 +352,19 @@ pub fn auctioneer_execute_sale<'info>(
 
     invoke_signed(&ix, &cpi_account_infos, &[&auctioneer_seeds])?;
 
+    let escrow_locker = EscrowLocker {
+        auction_house_program: &cpi_program,
+        auction_house: &ctx.accounts.auction_house.to_account_info(),
+        auctioneer_authority: &ctx.accounts.auctioneer_authority.to_account_info(),
+        auctioneer: &ctx.accounts.auctioneer.to_account_info(),
+        auctioneer_authority_bump,
+    };
+    escrow_locker.release(
+        &ctx.accounts.buyer.to_account_info(),
+        &ctx.accounts.escrow_lock,
+        ctx.accounts.listing_config.highest_bid.amount,
+    )?;
+
     // Close the Listing Config account.
     let listing_config = &ctx.accounts.listing_config.to_account_info();
     let seller = &ctx.accounts.seller.to_account_info();
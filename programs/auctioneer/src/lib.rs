@@ -6,13 +6,18 @@ pub mod cancel;
 pub mod constants;
 pub mod deposit;
 pub mod errors;
+pub mod escrow_lock;
+pub mod events;
 pub mod execute_sale;
 pub mod pda;
 pub mod sell;
 pub mod utils;
 pub mod withdraw;
 
-use crate::{authorize::*, bid::*, cancel::*, deposit::*, execute_sale::*, sell::*, withdraw::*};
+use crate::{
+    authorize::*, bid::*, cancel::*, deposit::*, escrow_lock::*, execute_sale::*, sell::*,
+    withdraw::*,
+};
 
 use anchor_lang::prelude::*;
 
@@ -29,8 +34,8 @@ pub mod auctioneer {
     }
 
     // Create a sell bid by creating a `seller_trade_state` account and approving the program as the token delegate.
-    pub fn sell(
-        ctx: Context<AuctioneerSell>,
+    pub fn sell<'info>(
+        ctx: Context<'_, '_, '_, 'info, AuctioneerSell<'info>>,
         trade_state_bump: u8,
         free_trade_state_bump: u8,
         program_as_signer_bump: u8,
@@ -70,8 +75,8 @@ pub mod auctioneer {
     }
 
     // Create a private buy bid by creating a `buyer_trade_state` account and an `escrow_payment` account and funding the escrow with the necessary SOL or SPL token amount.
-    pub fn buy(
-        ctx: Context<AuctioneerBuy>,
+    pub fn buy<'info>(
+        ctx: Context<'_, '_, '_, 'info, AuctioneerBuy<'info>>,
         trade_state_bump: u8,
         escrow_payment_bump: u8,
         auctioneer_authority_bump: u8,
@@ -88,6 +93,25 @@ pub mod auctioneer {
         )
     }
 
+    // Create a public buy bid by creating a `public_buyer_trade_state` account and an `escrow_payment` account and funding the escrow with the necessary SOL or SPL token amount.
+    pub fn public_buy<'info>(
+        ctx: Context<'_, '_, '_, 'info, AuctioneerPublicBuy<'info>>,
+        trade_state_bump: u8,
+        escrow_payment_bump: u8,
+        auctioneer_authority_bump: u8,
+        buyer_price: u64,
+        token_size: u64,
+    ) -> Result<()> {
+        auctioneer_public_buy(
+            ctx,
+            trade_state_bump,
+            escrow_payment_bump,
+            auctioneer_authority_bump,
+            buyer_price,
+            token_size,
+        )
+    }
+
     // Execute sale between provided buyer and seller trade state accounts transferring funds to seller wallet and token to buyer wallet.
     #[inline(never)]
     pub fn execute_sale<'info>(
@@ -120,13 +144,27 @@ pub mod auctioneer {
         auctioneer_withdraw(ctx, escrow_payment_bump, auctioneer_authority_bump, amount)
     }
 
+    // Close the escrow payment account of your wallet once none of its funds are locked by highest bids.
+    pub fn close_escrow_account(
+        ctx: Context<AuctioneerCloseEscrowAccount>,
+        escrow_payment_bump: u8,
+        auctioneer_authority_bump: u8,
+    ) -> Result<()> {
+        auctioneer_close_escrow_account(ctx, escrow_payment_bump, auctioneer_authority_bump)
+    }
+
     // Cancel a bid or listing, transferring all lamports from the trade state account to the fee payer, and setting the trade state account data to zero so it can be garbage collected.
-    pub fn cancel(
-        ctx: Context<AuctioneerCancel>,
+    pub fn cancel<'info>(
+        ctx: Context<'_, '_, '_, 'info, AuctioneerCancel<'info>>,
         auctioneer_authority_bump: u8,
         buyer_price: u64,
         token_size: u64,
     ) -> Result<()> {
         auctioneer_cancel(ctx, auctioneer_authority_bump, buyer_price, token_size)
     }
+
+    // Release the escrow funds locked by the highest bid of an auction left unsettled past the grace period after its end.
+    pub fn release_escrow_lock(ctx: Context<ReleaseEscrowLock>, token_size: u64) -> Result<()> {
+        escrow_lock::release_escrow_lock(ctx, token_size)
+    }
 }
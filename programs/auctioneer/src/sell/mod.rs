@@ -2,12 +2,15 @@
 
 pub mod config;
 
-use crate::{constants::*, errors::*, sell::config::*};
+use crate::{
+    constants::*, errors::*, events::AuctionCreated, sell::config::*,
+    utils::assert_auction_house_not_paused,
+};
 
 use anchor_lang::{
     prelude::*, solana_program::program::invoke_signed, AnchorDeserialize, InstructionData,
 };
-use anchor_spl::token::{Token, TokenAccount};
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
 
 use auction_house::{
     self,
@@ -55,7 +58,7 @@ pub struct AuctioneerSell<'info> {
 
     /// SPL token account containing token for sale.
     #[account(mut)]
-    pub token_account: Box<Account<'info, TokenAccount>>,
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// CHECK: Verified through CPI
     /// Metaplex metadata account decorating SPL mint account.
@@ -158,14 +161,14 @@ pub struct AuctioneerSell<'info> {
     )]
     pub program_as_signer: UncheckedAccount<'info>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
 /// Create a sell bid by creating a `seller_trade_state` account and approving the program as the token delegate.
-pub fn auctioneer_sell(
-    ctx: Context<AuctioneerSell>,
+pub fn auctioneer_sell<'info>(
+    ctx: Context<'_, '_, '_, 'info, AuctioneerSell<'info>>,
     trade_state_bump: u8,
     free_trade_state_bump: u8,
     program_as_signer_bump: u8,
@@ -178,6 +181,8 @@ pub fn auctioneer_sell(
     time_ext_period: Option<u32>,
     time_ext_delta: Option<u32>,
 ) -> Result<()> {
+    assert_auction_house_not_paused(&ctx.accounts.auction_house)?;
+
     ctx.accounts.listing_config.version = ListingConfigVersion::V0;
     ctx.accounts.listing_config.highest_bid.version = ListingConfigVersion::V0;
     ctx.accounts.listing_config.start_time = start_time;
@@ -216,20 +221,28 @@ pub fn auctioneer_sell(
         token_size,
     };
 
+    let mut cpi_account_metas: Vec<AccountMeta> = cpi_accounts
+        .to_account_metas(None)
+        .into_iter()
+        .zip(cpi_accounts.to_account_infos())
+        .map(|mut pair| {
+            pair.0.is_signer = pair.1.is_signer;
+            if pair.0.pubkey == ctx.accounts.auctioneer_authority.key() {
+                pair.0.is_signer = true;
+            }
+            pair.0
+        })
+        .collect();
+
+    // Programmable NFTs pass their Token Metadata accounts as remaining accounts.
+    cpi_account_metas.append(&mut ctx.remaining_accounts.to_vec().to_account_metas(None));
+
+    let mut cpi_account_infos: Vec<AccountInfo> = cpi_accounts.to_account_infos();
+    cpi_account_infos.append(&mut ctx.remaining_accounts.to_vec());
+
     let ix = anchor_lang::solana_program::instruction::Instruction {
         program_id: cpi_program.key(),
-        accounts: cpi_accounts
-            .to_account_metas(None)
-            .into_iter()
-            .zip(cpi_accounts.to_account_infos())
-            .map(|mut pair| {
-                pair.0.is_signer = pair.1.is_signer;
-                if pair.0.pubkey == ctx.accounts.auctioneer_authority.key() {
-                    pair.0.is_signer = true;
-                }
-                pair.0
-            })
-            .collect(),
+        accounts: cpi_account_metas,
         data: sell_data.data(),
     };
 
@@ -244,7 +257,22 @@ pub fn auctioneer_sell(
         &[auctioneer_authority_bump],
     ];
 
-    invoke_signed(&ix, &cpi_accounts.to_account_infos(), &[&auctioneer_seeds])?;
+    invoke_signed(&ix, &cpi_account_infos, &[&auctioneer_seeds])?;
+
+    let listing_config = &ctx.accounts.listing_config;
+    emit!(AuctionCreated {
+        listing_config: listing_config.key(),
+        auction_house: ah_key,
+        seller: ctx.accounts.wallet.key(),
+        token_mint: ctx.accounts.token_account.mint,
+        token_size,
+        start_time: listing_config.start_time,
+        end_time: listing_config.end_time,
+        reserve_price: listing_config.reserve_price,
+        min_bid_increment: listing_config.min_bid_increment,
+        time_ext_period: listing_config.time_ext_period,
+        time_ext_delta: listing_config.time_ext_delta,
+    });
 
     Ok(())
 }
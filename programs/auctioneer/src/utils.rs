@@ -1,6 +1,16 @@
 use anchor_lang::prelude::*;
 
-use crate::{errors::*, sell::config::*};
+use auction_house::AuctionHouse;
+
+use crate::{errors::*, events::Outbid, sell::config::*};
+
+pub fn assert_auction_house_not_paused(auction_house: &AuctionHouse) -> Result<()> {
+    if auction_house.is_paused {
+        return err!(AuctioneerError::AuctionHousePaused);
+    }
+
+    Ok(())
+}
 
 pub fn assert_auction_active(listing_config: &Account<ListingConfig>) -> Result<()> {
     let clock = Clock::get()?;
@@ -73,3 +83,25 @@ pub fn process_time_extension(listing_config: &mut Account<ListingConfig>) -> Re
 
     Ok(())
 }
+
+/// Record a new highest bid on the listing, emitting `Outbid` if it replaces an earlier one.
+pub fn set_highest_bid(
+    listing_config: &mut Account<ListingConfig>,
+    buyer_trade_state: Pubkey,
+    amount: u64,
+) {
+    let previous_bid = listing_config.highest_bid.clone();
+    listing_config.highest_bid.amount = amount;
+    listing_config.highest_bid.buyer_trade_state = buyer_trade_state;
+
+    if previous_bid.amount > 0 {
+        emit!(Outbid {
+            listing_config: listing_config.key(),
+            previous_buyer_trade_state: previous_bid.buyer_trade_state,
+            previous_amount: previous_bid.amount,
+            buyer_trade_state,
+            amount,
+            end_time: listing_config.end_time,
+        });
+    }
+}
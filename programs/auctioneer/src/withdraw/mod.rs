@@ -3,13 +3,15 @@ use anchor_lang::{
 };
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{Mint, Token},
+    token_interface::{Mint, TokenInterface},
 };
 
 use auction_house::{
     self,
-    constants::{AUCTIONEER, FEE_PAYER, PREFIX},
-    cpi::accounts::AuctioneerWithdraw as AHWithdraw,
+    constants::{AUCTIONEER, ESCROW_LOCK, FEE_PAYER, PREFIX},
+    cpi::accounts::{
+        AuctioneerCloseEscrowAccount as AHCloseEscrowAccount, AuctioneerWithdraw as AHWithdraw,
+    },
     program::AuctionHouse as AuctionHouseProgram,
     AuctionHouse,
 };
@@ -47,7 +49,7 @@ pub struct AuctioneerWithdraw<'info> {
     pub escrow_payment_account: UncheckedAccount<'info>,
 
     /// Auction House instance treasury mint account.
-    pub treasury_mint: Box<Account<'info, Mint>>,
+    pub treasury_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// CHECK: Verified through CPI
     /// Auction House instance authority account.
@@ -105,10 +107,24 @@ pub struct AuctioneerWithdraw<'info> {
     )]
     pub auctioneer: Account<'info, auction_house::Auctioneer>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: Account seeds checked in constraint, may not exist if the wallet never bid.
+    /// Escrow lock of the wallet, holding back the funds of its highest bids.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            ESCROW_LOCK.as_bytes(),
+            wallet.key().as_ref()
+        ],
+        seeds::program = auction_house_program,
+        bump
+    )]
+    pub escrow_lock: UncheckedAccount<'info>,
 }
 
 /// Withdraw but with an auctioneer.
@@ -133,6 +149,7 @@ pub fn auctioneer_withdraw(
         system_program: ctx.accounts.system_program.to_account_info(),
         associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
         rent: ctx.accounts.rent.to_account_info(),
+        escrow_lock: ctx.accounts.escrow_lock.to_account_info(),
     };
 
     let withdraw_data = auction_house::instruction::AuctioneerWithdraw {
@@ -172,3 +189,151 @@ pub fn auctioneer_withdraw(
 
     Ok(())
 }
+
+#[derive(Accounts, Clone)]
+#[instruction(
+    escrow_payment_bump: u8,
+    auctioneer_authority_bump: u8
+)]
+pub struct AuctioneerCloseEscrowAccount<'info> {
+    /// Auction House Program
+    pub auction_house_program: Program<'info, AuctionHouseProgram>,
+
+    /// User wallet account, receiving the rent of an SPL escrow.
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    /// CHECK: Verified through CPI
+    /// SPL token account to transfer the remaining tokens to, the wallet's associated token account. If the treasury is native SOL, this is the same as the wallet address.
+    #[account(mut)]
+    pub receipt_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            wallet.key().as_ref()
+        ],
+        seeds::program = auction_house_program,
+        bump = escrow_payment_bump
+    )]
+    pub escrow_payment_account: UncheckedAccount<'info>,
+
+    /// Auction House instance treasury mint account.
+    #[account(mut)]
+    pub treasury_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        seeds::program = auction_house_program,
+        bump = auction_house.bump,
+        has_one = treasury_mint
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Verified through CPI
+    /// The auctioneer program PDA running this auction.
+    #[account(
+        seeds = [
+            AUCTIONEER.as_bytes(),
+            auction_house.key().as_ref()
+        ],
+        bump = auctioneer_authority_bump
+    )]
+    pub auctioneer_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// The auctioneer PDA owned by Auction House.
+    #[account(
+        seeds = [
+            AUCTIONEER.as_bytes(),
+            auction_house.key().as_ref(),
+            auctioneer_authority.key().as_ref()
+        ],
+        seeds::program = auction_house_program,
+        bump = auctioneer.bump,
+    )]
+    pub auctioneer: Account<'info, auction_house::Auctioneer>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: Account seeds checked in constraint, may not exist if the wallet never bid.
+    /// Escrow lock of the wallet, holding back the funds of its highest bids.
+    #[account(
+        seeds = [
+            PREFIX.as_bytes(),
+            auction_house.key().as_ref(),
+            ESCROW_LOCK.as_bytes(),
+            wallet.key().as_ref()
+        ],
+        seeds::program = auction_house_program,
+        bump
+    )]
+    pub escrow_lock: UncheckedAccount<'info>,
+}
+
+/// Close the escrow account of the wallet, once none of its funds are locked by highest bids.
+pub fn auctioneer_close_escrow_account(
+    ctx: Context<AuctioneerCloseEscrowAccount>,
+    escrow_payment_bump: u8,
+    auctioneer_authority_bump: u8,
+) -> Result<()> {
+    let cpi_program = ctx.accounts.auction_house_program.to_account_info();
+    let cpi_accounts = AHCloseEscrowAccount {
+        wallet: ctx.accounts.wallet.to_account_info(),
+        receipt_account: ctx.accounts.receipt_account.to_account_info(),
+        escrow_payment_account: ctx.accounts.escrow_payment_account.to_account_info(),
+        treasury_mint: ctx.accounts.treasury_mint.to_account_info(),
+        auctioneer_authority: ctx.accounts.auctioneer_authority.to_account_info(),
+        auction_house: ctx.accounts.auction_house.to_account_info(),
+        auctioneer: ctx.accounts.auctioneer.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+        rent: ctx.accounts.rent.to_account_info(),
+        escrow_lock: ctx.accounts.escrow_lock.to_account_info(),
+    };
+
+    let close_escrow_account_data = auction_house::instruction::AuctioneerCloseEscrowAccount {
+        escrow_payment_bump,
+    };
+
+    let ix = anchor_lang::solana_program::instruction::Instruction {
+        program_id: cpi_program.key(),
+        accounts: cpi_accounts
+            .to_account_metas(None)
+            .into_iter()
+            .zip(cpi_accounts.to_account_infos())
+            .map(|mut pair| {
+                pair.0.is_signer = pair.1.is_signer;
+                if pair.0.pubkey == ctx.accounts.auctioneer_authority.key() {
+                    pair.0.is_signer = true;
+                }
+                pair.0
+            })
+            .collect(),
+        data: close_escrow_account_data.data(),
+    };
+
+    let ah_key = ctx.accounts.auction_house.key();
+    let auctioneer_seeds = [
+        AUCTIONEER.as_bytes(),
+        ah_key.as_ref(),
+        &[auctioneer_authority_bump],
+    ];
+
+    invoke_signed(&ix, &cpi_accounts.to_account_infos(), &[&auctioneer_seeds])?;
+
+    Ok(())
+}
@@ -76,6 +76,7 @@ async fn buy_success() {
         &auction_house_data,
         &token,
         &buyer,
+        None,
         bid_amount,
     );
     context
@@ -98,6 +99,101 @@ async fn buy_success() {
     assert_eq!(listing_config_data.highest_bid.amount, bid_amount);
 }
 
+#[tokio::test]
+async fn public_buy_success() {
+    let mut context = auctioneer_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    // Sell
+
+    let (sell_accounts, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        (SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs()
+            - 60) as i64,
+        (SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs()
+            + 60) as i64,
+        None,
+        None,
+        None,
+        None,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    // Public buy
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+
+    let (_, deposit_tx) = deposit(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &buyer,
+        5 * ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(deposit_tx)
+        .await
+        .unwrap();
+
+    let bid_amount = ONE_SOL;
+
+    let (public_buy_accounts, public_buy_tx) = public_buy(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer,
+        None,
+        bid_amount,
+    );
+    context
+        .banks_client
+        .process_transaction(public_buy_tx)
+        .await
+        .unwrap();
+
+    let listing_config_account = context
+        .banks_client
+        .get_account(sell_accounts.listing_config)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+
+    let listing_config_data =
+        ListingConfig::try_deserialize(&mut listing_config_account.as_ref()).unwrap();
+
+    assert_eq!(listing_config_data.highest_bid.amount, bid_amount);
+    assert_eq!(
+        listing_config_data.highest_bid.buyer_trade_state,
+        public_buy_accounts.buyer_trade_state
+    );
+}
+
 #[tokio::test]
 async fn multiple_buy_success() {
     let mut context = auctioneer_program_test().start_with_context().await;
@@ -166,6 +262,7 @@ async fn multiple_buy_success() {
         &auction_house_data,
         &token,
         &buyer1,
+        None,
         bid_amount1,
     );
     context
@@ -202,6 +299,7 @@ async fn multiple_buy_success() {
         &auction_house_data,
         &token,
         &buyer2,
+        Some(&buyer1.pubkey()),
         bid_amount2,
     );
     context
@@ -292,6 +390,7 @@ async fn failure_buy_below_reserve_price() {
         &auction_house_data,
         &token,
         &buyer,
+        None,
         bid_amount,
     );
 
@@ -372,6 +471,7 @@ async fn failure_multiple_buy_increment() {
         &auction_house_data,
         &token,
         &buyer1,
+        None,
         bid_amount1,
     );
     context
@@ -408,6 +508,7 @@ async fn failure_multiple_buy_increment() {
         &auction_house_data,
         &token,
         &buyer2,
+        Some(&buyer1.pubkey()),
         bid_amount2,
     );
 
@@ -504,6 +605,7 @@ async fn multiple_buy_time_ext_success() {
         &auction_house_data,
         &token,
         &buyer1,
+        None,
         bid_amount1,
     );
     context
@@ -556,6 +658,7 @@ async fn multiple_buy_time_ext_success() {
         &auction_house_data,
         &token,
         &buyer2,
+        Some(&buyer1.pubkey()),
         bid_amount2,
     );
     context
@@ -52,6 +52,7 @@ async fn cancel_sell_success() {
         &auction_house_data,
         &token,
         &sell_accounts.seller_trade_state,
+        None,
     );
     context
         .banks_client
@@ -137,6 +138,7 @@ async fn failure_cancel_buy_highest_bid() {
         &auction_house_data,
         &token,
         &buyer,
+        None,
         bid_amount,
     );
     context
@@ -173,6 +175,7 @@ async fn failure_cancel_buy_highest_bid() {
         &auction_house_data,
         &token,
         &buyer2,
+        Some(&buyer.pubkey()),
         bid_amount2,
     );
     context
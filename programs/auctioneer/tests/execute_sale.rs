@@ -79,6 +79,7 @@ async fn execute_sale_success() {
         &auction_house_data,
         &token,
         &buyer,
+        None,
         bid_amount,
     );
     context
@@ -181,6 +182,7 @@ async fn execute_sale_multiple_buy_success() {
         &auction_house_data,
         &token,
         &buyer,
+        None,
         bid_amount,
     );
     context
@@ -217,6 +219,7 @@ async fn execute_sale_multiple_buy_success() {
         &auction_house_data,
         &token,
         &buyer2,
+        Some(&buyer.pubkey()),
         bid_amount2,
     );
     context
@@ -319,6 +322,7 @@ async fn failure_execute_sale_multiple_buy_not_highest_bid() {
         &auction_house_data,
         &token,
         &buyer,
+        None,
         bid_amount,
     );
     context
@@ -355,6 +359,7 @@ async fn failure_execute_sale_multiple_buy_not_highest_bid() {
         &auction_house_data,
         &token,
         &buyer2,
+        Some(&buyer.pubkey()),
         bid_amount2,
     );
     context
@@ -481,6 +486,7 @@ async fn execute_sale_with_metadata_creators_success() {
         &auction_house_data,
         &token,
         &buyer,
+        None,
         bid_amount,
     );
     context
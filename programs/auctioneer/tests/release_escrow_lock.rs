@@ -0,0 +1,200 @@
+use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
+use anchor_lang::{solana_program::clock::Clock, AccountDeserialize};
+use auction_house::escrow_lock::EscrowLock;
+use auctioneer::{
+    constants::ESCROW_LOCK_GRACE_PERIOD, errors::AuctioneerError, sell::config::ListingConfig,
+};
+use solana_program_test::{tokio, ProgramTestContext};
+use std::time::SystemTime;
+
+mod utils;
+use utils::setup_functions::*;
+
+async fn set_unix_timestamp(context: &mut ProgramTestContext, unix_timestamp: i64) {
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp = unix_timestamp;
+    context.set_sysvar(&clock);
+}
+
+#[tokio::test]
+async fn release_escrow_lock_success() {
+    let mut context = auctioneer_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs() as i64;
+    let end_time = now + 60;
+    let (sell_accounts, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        now - 60,
+        end_time,
+        None,
+        None,
+        None,
+        None,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+    let (buy_accounts, buy_tx) = buy(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer,
+        None,
+        2 * ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(buy_tx)
+        .await
+        .unwrap();
+
+    // The seller never settles the auction.
+    set_unix_timestamp(&mut context, end_time + ESCROW_LOCK_GRACE_PERIOD).await;
+
+    let closer = Keypair::new();
+    airdrop(&mut context, &closer.pubkey(), ONE_SOL)
+        .await
+        .unwrap();
+    let (_, release_tx) = release_escrow_lock(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer.pubkey(),
+        &closer,
+    );
+    context
+        .banks_client
+        .process_transaction(release_tx)
+        .await
+        .unwrap();
+
+    let escrow_lock_account = context
+        .banks_client
+        .get_account(buy_accounts.escrow_lock)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let escrow_lock = EscrowLock::try_deserialize(&mut escrow_lock_account.data.as_ref()).unwrap();
+    assert_eq!(escrow_lock.locked_amount, 0);
+
+    let listing_config_account = context
+        .banks_client
+        .get_account(sell_accounts.listing_config)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let listing_config =
+        ListingConfig::try_deserialize(&mut listing_config_account.data.as_ref()).unwrap();
+    assert_eq!(listing_config.highest_bid.amount, 0);
+
+    // The bidder gets its funds back.
+    let (_, withdraw_tx) = withdraw(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &buyer,
+        2 * ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(withdraw_tx)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn failure_release_escrow_lock_grace_period() {
+    let mut context = auctioneer_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs() as i64;
+    let end_time = now + 60;
+    let (_, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        now - 60,
+        end_time,
+        None,
+        None,
+        None,
+        None,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    let buyer = Keypair::new();
+    airdrop(&mut context, &buyer.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+    let (_, buy_tx) = buy(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer,
+        None,
+        2 * ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(buy_tx)
+        .await
+        .unwrap();
+
+    // The auction is over, but the seller may still settle it.
+    set_unix_timestamp(&mut context, end_time + ESCROW_LOCK_GRACE_PERIOD - 1).await;
+
+    let (_, release_tx) = release_escrow_lock(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer.pubkey(),
+        &buyer,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(release_tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctioneerError::EscrowLockGracePeriod.into());
+}
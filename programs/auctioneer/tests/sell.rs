@@ -5,8 +5,8 @@ use anchor_lang::{
     InstructionData, ToAccountMetas,
 };
 use anchor_spl::token::spl_token;
-use auction_house::pda::*;
-use auctioneer::pda::*;
+use auction_house::{errors::AuctionHouseError, pda::*, AuthorityScope};
+use auctioneer::{errors::AuctioneerError, pda::*};
 use solana_program_test::tokio;
 use std::time::SystemTime;
 
@@ -61,6 +61,53 @@ async fn sell_success() {
     assert_eq!(seller_trade_state_account.data.len(), 1);
 }
 
+#[tokio::test]
+async fn failure_sell_missing_scope() {
+    let mut context = auctioneer_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house_with_scopes(
+        &mut context,
+        100,
+        false,
+        vec![AuthorityScope::Deposit, AuthorityScope::Buy],
+    )
+    .await
+    .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let (_, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        (SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs()
+            - 60) as i64,
+        (SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs()
+            + 60) as i64,
+        None,
+        None,
+        None,
+        None,
+    );
+
+    let tx_error = context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::MissingAuctioneerScope.into());
+}
+
 #[tokio::test]
 async fn failure_sell_wrong_auctioneer_authority() {
     let mut context = auctioneer_program_test().start_with_context().await;
@@ -174,3 +221,53 @@ async fn failure_sell_wrong_auctioneer_authority() {
 
     assert_error(tx_error, ERR_ACCOUNT_NOT_INITIALIZED);
 }
+
+#[tokio::test]
+async fn failure_sell_auction_house_paused() {
+    let mut context = auctioneer_program_test().start_with_context().await;
+
+    let (authority, auction_house, auction_house_data) =
+        create_auction_house(&mut context, 100, false)
+            .await
+            .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let pause_tx = set_pause_state(&mut context, &auction_house, &authority, true);
+    context
+        .banks_client
+        .process_transaction(pause_tx)
+        .await
+        .unwrap();
+
+    let (_, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        (SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs()
+            - 60) as i64,
+        (SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs()
+            + 60) as i64,
+        None,
+        None,
+        None,
+        None,
+    );
+
+    let tx_error = context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctioneerError::AuctionHousePaused.into());
+}
@@ -10,7 +10,7 @@ use anchor_lang::{InstructionData, ToAccountMetas};
 use anchor_spl::associated_token::{self, get_associated_token_address};
 use anchor_spl::token::spl_token;
 use auction_house::pda::*;
-use auction_house::AuctionHouse;
+use auction_house::{AuctionHouse, AuthorityScope};
 use auctioneer::pda::*;
 use nft_minter::pda::*;
 use nft_minter::utils::{token_metadata_program_id, Creator};
@@ -156,6 +156,29 @@ pub async fn create_auction_house(
     context: &mut ProgramTestContext,
     seller_fee_basis_points: u16,
     can_change_sale_price: bool,
+) -> Result<(Keypair, Pubkey, AuctionHouse), BanksClientError> {
+    create_auction_house_with_scopes(
+        context,
+        seller_fee_basis_points,
+        can_change_sale_price,
+        vec![
+            AuthorityScope::Deposit,
+            AuthorityScope::Buy,
+            AuthorityScope::PublicBuy,
+            AuthorityScope::ExecuteSale,
+            AuthorityScope::Sell,
+            AuthorityScope::Cancel,
+            AuthorityScope::Withdraw,
+        ],
+    )
+    .await
+}
+
+pub async fn create_auction_house_with_scopes(
+    context: &mut ProgramTestContext,
+    seller_fee_basis_points: u16,
+    can_change_sale_price: bool,
+    scopes: Vec<AuthorityScope>,
 ) -> Result<(Keypair, Pubkey, AuctionHouse), BanksClientError> {
     // CreateAuctionHouse
     let authority = Keypair::new();
@@ -204,7 +227,7 @@ pub async fn create_auction_house(
 
     let delegate_auctioneer_ix = Instruction {
         program_id: auction_house::id(),
-        data: auction_house::instruction::DelegateAuctioneer {}.data(),
+        data: auction_house::instruction::DelegateAuctioneer { scopes }.data(),
         accounts: auction_house::accounts::DelegateAuctioneer {
             auction_house,
             authority: authority.pubkey(),
@@ -252,6 +275,30 @@ pub async fn create_auction_house(
     Ok((authority, auction_house, auction_house_data))
 }
 
+pub fn set_pause_state(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    authority: &Keypair,
+    paused: bool,
+) -> Transaction {
+    let ix = Instruction {
+        program_id: auction_house::id(),
+        data: auction_house::instruction::SetPauseState { paused }.data(),
+        accounts: auction_house::accounts::SetPauseState {
+            authority: authority.pubkey(),
+            auction_house: *auction_house,
+        }
+        .to_account_metas(None),
+    };
+
+    Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[authority],
+        context.last_blockhash,
+    )
+}
+
 pub fn sell(
     context: &mut ProgramTestContext,
     auction_house: &Pubkey,
@@ -355,6 +402,7 @@ pub fn buy(
     auction_house_data: &AuctionHouse,
     token: &NFT,
     buyer: &Keypair,
+    highest_bidder: Option<&Pubkey>,
     buyer_price: u64, // Bid amount
 ) -> (auctioneer::accounts::AuctioneerBuy, Transaction) {
     let (listing_config, _) = find_listing_config_address(
@@ -370,6 +418,9 @@ pub fn buy(
     let (auctioneer_authority, auctioneer_authority_bump) =
         find_auctioneer_authority_address(auction_house);
     let (auctioneer, _) = find_auctioneer_address(auction_house, &auctioneer_authority);
+    let (escrow_lock, _) = find_escrow_lock_address(auction_house, &buyer.pubkey());
+    let highest_bidder_escrow_lock =
+        highest_bidder.map(|bidder| find_escrow_lock_address(auction_house, bidder).0);
     let (buyer_trade_state, buyer_trade_state_bump) = find_trade_state_address(
         &buyer.pubkey(),
         auction_house,
@@ -408,6 +459,93 @@ pub fn buy(
         escrow_payment_account,
         auctioneer_authority,
         auctioneer,
+        escrow_lock,
+        highest_bidder: highest_bidder.copied(),
+        highest_bidder_escrow_lock,
+    };
+
+    let ix = Instruction {
+        program_id: auctioneer::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (
+        accounts,
+        Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&buyer.pubkey()),
+            &[buyer],
+            context.last_blockhash,
+        ),
+    )
+}
+
+pub fn public_buy(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    token: &NFT,
+    buyer: &Keypair,
+    highest_bidder: Option<&Pubkey>,
+    buyer_price: u64, // Bid amount
+) -> (auctioneer::accounts::AuctioneerPublicBuy, Transaction) {
+    let (listing_config, _) = find_listing_config_address(
+        &token.owner.pubkey(),
+        auction_house,
+        &token.ata,
+        &auction_house_data.treasury_mint,
+        &token.mint.pubkey(),
+        1,
+    );
+    let (escrow_payment_account, escrow_payment_account_bump) =
+        find_escrow_payment_account_address(auction_house, &buyer.pubkey());
+    let (auctioneer_authority, auctioneer_authority_bump) =
+        find_auctioneer_authority_address(auction_house);
+    let (auctioneer, _) = find_auctioneer_address(auction_house, &auctioneer_authority);
+    let (escrow_lock, _) = find_escrow_lock_address(auction_house, &buyer.pubkey());
+    let highest_bidder_escrow_lock =
+        highest_bidder.map(|bidder| find_escrow_lock_address(auction_house, bidder).0);
+    let (buyer_trade_state, buyer_trade_state_bump) = find_public_bid_trade_state_address(
+        &buyer.pubkey(),
+        auction_house,
+        &auction_house_data.treasury_mint,
+        &token.mint.pubkey(),
+        buyer_price,
+        1,
+    );
+
+    let data = auctioneer::instruction::PublicBuy {
+        trade_state_bump: buyer_trade_state_bump,
+        escrow_payment_bump: escrow_payment_account_bump,
+        auctioneer_authority_bump: auctioneer_authority_bump,
+        token_size: 1,
+        buyer_price,
+    };
+
+    let accounts = auctioneer::accounts::AuctioneerPublicBuy {
+        auction_house_program: auction_house::id(),
+        listing_config,
+        seller: token.owner.pubkey(),
+        wallet: buyer.pubkey(),
+        token_account: token.ata,
+        metadata: token.metadata,
+        authority: auction_house_data.authority,
+        auction_house: *auction_house,
+        auction_house_fee_account: auction_house_data.auction_house_fee_account,
+        buyer_trade_state,
+        token_program: spl_token::id(),
+        treasury_mint: auction_house_data.treasury_mint,
+        payment_account: buyer.pubkey(),
+        transfer_authority: buyer.pubkey(),
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+        escrow_payment_account,
+        auctioneer_authority,
+        auctioneer,
+        escrow_lock,
+        highest_bidder: highest_bidder.copied(),
+        highest_bidder_escrow_lock,
     };
 
     let ix = Instruction {
@@ -500,8 +638,10 @@ pub async fn execute_sale(
         system_program: system_program::id(),
         associated_token_program: anchor_spl::associated_token::ID,
         rent: sysvar::rent::id(),
+        treasury_token_program: spl_token::id(),
         auctioneer_authority,
         auctioneer,
+        escrow_lock: find_escrow_lock_address(auction_house, &buy_accounts.wallet).0,
     };
 
     let mut account_metas = accounts.to_account_metas(None);
@@ -512,6 +652,11 @@ pub async fn execute_sale(
                 is_signer: false,
                 is_writable: true,
             });
+            account_metas.push(AccountMeta {
+                pubkey: find_royalty_vault_address(auction_house, &creator.address).0,
+                is_signer: false,
+                is_writable: true,
+            });
         }
     }
 
@@ -597,6 +742,7 @@ pub fn withdraw(
     let (auctioneer_authority, auctioneer_authority_bump) =
         find_auctioneer_authority_address(auction_house);
     let (auctioneer, _) = find_auctioneer_address(auction_house, &auctioneer_authority);
+    let (escrow_lock, _) = find_escrow_lock_address(auction_house, &buyer.pubkey());
 
     let data = auctioneer::instruction::Withdraw {
         escrow_payment_bump: escrow_payment_account_bump,
@@ -619,6 +765,7 @@ pub fn withdraw(
         rent: sysvar::rent::id(),
         auctioneer_authority,
         auctioneer,
+        escrow_lock,
     };
 
     let ix = Instruction {
@@ -645,6 +792,7 @@ fn cancel(
     token: &NFT,
     wallet: &Keypair,
     trade_state: &Pubkey,
+    highest_bidder: Option<&Pubkey>,
     buyer_price: u64,
 ) -> (auctioneer::accounts::AuctioneerCancel, Transaction) {
     let (listing_config, _) = find_listing_config_address(
@@ -658,6 +806,8 @@ fn cancel(
     let (auctioneer_authority, auctioneer_authority_bump) =
         find_auctioneer_authority_address(auction_house);
     let (auctioneer, _) = find_auctioneer_address(auction_house, &auctioneer_authority);
+    let highest_bidder_escrow_lock =
+        highest_bidder.map(|bidder| find_escrow_lock_address(auction_house, bidder).0);
 
     let data = auctioneer::instruction::Cancel {
         auctioneer_authority_bump,
@@ -679,6 +829,8 @@ fn cancel(
         auctioneer_authority,
         auctioneer,
         token_program: spl_token::id(),
+        highest_bidder: highest_bidder.copied(),
+        highest_bidder_escrow_lock,
     };
 
     let ix = Instruction {
@@ -704,6 +856,7 @@ pub fn cancel_sell(
     auction_house_data: &AuctionHouse,
     token: &NFT,
     seller_trade_state: &Pubkey,
+    highest_bidder: Option<&Pubkey>,
 ) -> (auctioneer::accounts::AuctioneerCancel, Transaction) {
     cancel(
         context,
@@ -712,6 +865,7 @@ pub fn cancel_sell(
         token,
         &token.owner,
         seller_trade_state,
+        highest_bidder,
         u64::MAX,
     )
 }
@@ -732,6 +886,7 @@ pub fn cancel_buy(
         token,
         buyer,
         buyer_trade_state,
+        None,
         buyer_price,
     )
 }
@@ -739,10 +894,118 @@ pub fn cancel_buy(
 pub fn close_escrow_account(
     context: &mut ProgramTestContext,
     auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    buyer: &Keypair,
+) -> (
+    auctioneer::accounts::AuctioneerCloseEscrowAccount,
+    Transaction,
+) {
+    let (escrow_payment_account, escrow_payment_account_bump) =
+        find_escrow_payment_account_address(auction_house, &buyer.pubkey());
+    let (auctioneer_authority, auctioneer_authority_bump) =
+        find_auctioneer_authority_address(auction_house);
+    let (auctioneer, _) = find_auctioneer_address(auction_house, &auctioneer_authority);
+    let (escrow_lock, _) = find_escrow_lock_address(auction_house, &buyer.pubkey());
+
+    let data = auctioneer::instruction::CloseEscrowAccount {
+        escrow_payment_bump: escrow_payment_account_bump,
+        auctioneer_authority_bump,
+    };
+
+    let accounts = auctioneer::accounts::AuctioneerCloseEscrowAccount {
+        auction_house_program: auction_house::id(),
+        wallet: buyer.pubkey(),
+        receipt_account: buyer.pubkey(),
+        escrow_payment_account,
+        treasury_mint: auction_house_data.treasury_mint,
+        auction_house: *auction_house,
+        auctioneer_authority,
+        auctioneer,
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        associated_token_program: associated_token::ID,
+        rent: sysvar::rent::id(),
+        escrow_lock,
+    };
+
+    let ix = Instruction {
+        program_id: auctioneer::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (
+        accounts,
+        Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&buyer.pubkey()),
+            &[buyer],
+            context.last_blockhash,
+        ),
+    )
+}
+
+pub fn release_escrow_lock(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
+    token: &NFT,
+    highest_bidder: &Pubkey,
+    payer: &Keypair,
+) -> (auctioneer::accounts::ReleaseEscrowLock, Transaction) {
+    let (listing_config, _) = find_listing_config_address(
+        &token.owner.pubkey(),
+        auction_house,
+        &token.ata,
+        &auction_house_data.treasury_mint,
+        &token.mint.pubkey(),
+        1,
+    );
+    let (highest_bidder_escrow_lock, _) = find_escrow_lock_address(auction_house, highest_bidder);
+    let (auctioneer_authority, _) = find_auctioneer_authority_address(auction_house);
+    let (auctioneer, _) = find_auctioneer_address(auction_house, &auctioneer_authority);
+
+    let data = auctioneer::instruction::ReleaseEscrowLock { token_size: 1 };
+
+    let accounts = auctioneer::accounts::ReleaseEscrowLock {
+        auction_house_program: auction_house::id(),
+        listing_config,
+        seller: token.owner.pubkey(),
+        token_account: token.ata,
+        token_mint: token.mint.pubkey(),
+        auction_house: *auction_house,
+        highest_bidder: *highest_bidder,
+        highest_bidder_escrow_lock,
+        auctioneer_authority,
+        auctioneer,
+    };
+
+    let ix = Instruction {
+        program_id: auctioneer::id(),
+        data: data.data(),
+        accounts: accounts.to_account_metas(None),
+    };
+
+    (
+        accounts,
+        Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer.pubkey()),
+            &[payer],
+            context.last_blockhash,
+        ),
+    )
+}
+
+pub fn auction_house_close_escrow_account(
+    context: &mut ProgramTestContext,
+    auction_house: &Pubkey,
+    auction_house_data: &AuctionHouse,
     buyer: &Keypair,
 ) -> (auction_house::accounts::CloseEscrowAccount, Transaction) {
     let (escrow_payment_account, escrow_payment_account_bump) =
         find_escrow_payment_account_address(auction_house, &buyer.pubkey());
+    let (escrow_lock, _) = find_escrow_lock_address(auction_house, &buyer.pubkey());
 
     let data = auction_house::instruction::CloseEscrowAccount {
         escrow_payment_bump: escrow_payment_account_bump,
@@ -750,9 +1013,15 @@ pub fn close_escrow_account(
 
     let accounts = auction_house::accounts::CloseEscrowAccount {
         wallet: buyer.pubkey(),
+        receipt_account: buyer.pubkey(),
         escrow_payment_account,
+        treasury_mint: auction_house_data.treasury_mint,
         auction_house: *auction_house,
+        token_program: spl_token::id(),
         system_program: system_program::id(),
+        associated_token_program: associated_token::ID,
+        rent: sysvar::rent::id(),
+        escrow_lock,
     };
 
     let ix = Instruction {
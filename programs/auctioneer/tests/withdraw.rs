@@ -1,6 +1,8 @@
 use anchor_client::solana_sdk::{signature::Keypair, signer::Signer};
-use auction_house::errors::AuctionHouseError;
+use anchor_lang::AccountDeserialize;
+use auction_house::{errors::AuctionHouseError, escrow_lock::EscrowLock};
 use solana_program_test::tokio;
+use std::time::SystemTime;
 
 mod utils;
 use utils::setup_functions::*;
@@ -135,3 +137,137 @@ async fn failure_withdraw() {
 
     assert_error(tx_error, AuctionHouseError::InsufficientFunds.into());
 }
+
+#[tokio::test]
+async fn withdraw_locked_funds_after_outbid() {
+    let mut context = auctioneer_program_test().start_with_context().await;
+
+    let (_, auction_house, auction_house_data) = create_auction_house(&mut context, 100, false)
+        .await
+        .expect("Failed to create Auction House");
+
+    let token = create_nft(&mut context, None)
+        .await
+        .expect("Failed to create NFT");
+
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs() as i64;
+    let (_, sell_tx) = sell(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        now - 60,
+        now + 60,
+        None,
+        None,
+        None,
+        None,
+    );
+    context
+        .banks_client
+        .process_transaction(sell_tx)
+        .await
+        .expect("Failed to sell NFT");
+
+    // The whole deposit of buyer 1 backs its bid
+    let buyer1 = Keypair::new();
+    airdrop(&mut context, &buyer1.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+    let (deposit_accounts, deposit_tx) = deposit(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &buyer1,
+        2 * ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(deposit_tx)
+        .await
+        .unwrap();
+
+    let (buy_accounts1, buy_tx1) = buy(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer1,
+        None,
+        2 * ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(buy_tx1)
+        .await
+        .unwrap();
+
+    let (_, withdraw_tx) = withdraw(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &buyer1,
+        ONE_SOL,
+    );
+    let tx_error = context
+        .banks_client
+        .process_transaction(withdraw_tx)
+        .await
+        .unwrap_err();
+
+    assert_error(tx_error, AuctionHouseError::EscrowFundsLocked.into());
+
+    // Outbidding buyer 1 releases its funds
+    let buyer2 = Keypair::new();
+    airdrop(&mut context, &buyer2.pubkey(), 10 * ONE_SOL)
+        .await
+        .unwrap();
+    let (_, buy_tx2) = buy(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &token,
+        &buyer2,
+        Some(&buyer1.pubkey()),
+        3 * ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(buy_tx2)
+        .await
+        .unwrap();
+
+    let escrow_lock_account = context
+        .banks_client
+        .get_account(buy_accounts1.escrow_lock)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    let escrow_lock = EscrowLock::try_deserialize(&mut escrow_lock_account.data.as_ref()).unwrap();
+    assert_eq!(escrow_lock.locked_amount, 0);
+
+    let (_, withdraw_tx) = withdraw(
+        &mut context,
+        &auction_house,
+        &auction_house_data,
+        &buyer1,
+        2 * ONE_SOL,
+    );
+    context
+        .banks_client
+        .process_transaction(withdraw_tx)
+        .await
+        .unwrap();
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let escrow_payment_account = context
+        .banks_client
+        .get_account(deposit_accounts.escrow_payment_account)
+        .await
+        .expect("Account not found")
+        .expect("Account is empty");
+    assert_eq!(escrow_payment_account.lamports, rent.minimum_balance(0));
+}
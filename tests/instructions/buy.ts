@@ -13,6 +13,7 @@ export async function buy({
   sellerAddress,
   buyerPrice,
   tokenSize,
+  highestBidder,
 }: {
   auctioneerProgram: Program<Auctioneer>;
   auctionHouse: AuctionHouseData;
@@ -21,6 +22,7 @@ export async function buy({
   sellerAddress: anchor.web3.PublicKey;
   buyerPrice: number;
   tokenSize: number;
+  highestBidder?: anchor.web3.PublicKey; // Wallet of the bid being outbid, if any
 }) {
   const [listingConfigAddress] = pda.findListingConfigAddress({
     wallet: token.owner.publicKey,
@@ -55,6 +57,16 @@ export async function buy({
     auctionHouseAddress: auctionHouse.address,
     auctioneerAuthorityAddress,
   });
+  const [escrowLockAddress] = pda.findEscrowLockAddress({
+    wallet: buyerKeypair.publicKey,
+    auctionHouseAddress: auctionHouse.address,
+  });
+  const highestBidderEscrowLockAddress = highestBidder
+    ? pda.findEscrowLockAddress({
+        wallet: highestBidder,
+        auctionHouseAddress: auctionHouse.address,
+      })[0]
+    : null;
 
   const buyAccounts: BuyAccounts = {
     auctionHouseProgram: AUCTION_HOUSE_PROGRAM_ID,
@@ -73,6 +85,9 @@ export async function buy({
     buyerTradeState: buyerTradeStateAddress,
     auctioneerAuthority: auctioneerAuthorityAddress,
     auctioneer: auctioneerAddress,
+    escrowLock: escrowLockAddress,
+    highestBidder: highestBidder ?? null,
+    highestBidderEscrowLock: highestBidderEscrowLockAddress,
   };
 
   const buyTx = await auctioneerProgram.methods
@@ -13,6 +13,7 @@ export async function cancel({
   tradeStateAddress,
   buyerPrice,
   tokenSize,
+  highestBidder,
 }: {
   auctioneerProgram: Program<Auctioneer>;
   auctionHouse: AuctionHouseData;
@@ -21,6 +22,7 @@ export async function cancel({
   tradeStateAddress: anchor.web3.PublicKey;
   buyerPrice: BN;
   tokenSize: number;
+  highestBidder?: anchor.web3.PublicKey; // Required when the seller cancels a listing with bids
 }) {
   const [listingConfigAddress] = pda.findListingConfigAddress({
     wallet: token.owner.publicKey,
@@ -54,6 +56,13 @@ export async function cancel({
       tradeState: tradeStateAddress,
       auctioneerAuthority: auctioneerAuthorityAddress,
       auctioneer: auctioneerAddress,
+      highestBidder: highestBidder ?? null,
+      highestBidderEscrowLock: highestBidder
+        ? pda.findEscrowLockAddress({
+            wallet: highestBidder,
+            auctionHouseAddress: auctionHouse.address,
+          })[0]
+        : null,
     })
     .signers([walletKeypair])
     .rpc();
@@ -1,15 +1,16 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
-import { AuctionHouse } from "../../target/types/auction_house";
+import { Auctioneer } from "../../target/types/auctioneer";
 import * as pda from "../pda";
 import { AuctionHouseData } from "../interfaces";
+import { AUCTION_HOUSE_PROGRAM_ID } from "../generated";
 
 export async function closeEscrowAccount({
-  auctionHouseProgram,
+  auctioneerProgram,
   auctionHouse,
   walletKeypair,
 }: {
-  auctionHouseProgram: Program<AuctionHouse>;
+  auctioneerProgram: Program<Auctioneer>;
   auctionHouse: AuctionHouseData;
   walletKeypair: anchor.web3.Keypair;
 }) {
@@ -18,13 +19,31 @@ export async function closeEscrowAccount({
       wallet: walletKeypair.publicKey,
       auctionHouseAddress: auctionHouse.address,
     });
+  const [auctioneerAuthorityAddress, auctioneerAuthorityBump] =
+    pda.findAuctioneerAuthorityAddress({
+      auctionHouseAddress: auctionHouse.address,
+    });
+  const [auctioneerAddress] = pda.findAuctioneerAddress({
+    auctionHouseAddress: auctionHouse.address,
+    auctioneerAuthorityAddress,
+  });
+  const [escrowLockAddress] = pda.findEscrowLockAddress({
+    wallet: walletKeypair.publicKey,
+    auctionHouseAddress: auctionHouse.address,
+  });
 
-  const closeEscrowAccountTx = await auctionHouseProgram.methods
-    .closeEscrowAccount(escrowBump)
+  const closeEscrowAccountTx = await auctioneerProgram.methods
+    .closeEscrowAccount(escrowBump, auctioneerAuthorityBump)
     .accounts({
-      wallet: walletKeypair.publicKey,
+      auctionHouseProgram: AUCTION_HOUSE_PROGRAM_ID,
+      wallet: walletKeypair.publicKey, // Signer
+      receiptAccount: walletKeypair.publicKey,
       escrowPaymentAccount: escrowPaymentAccountAddress,
+      treasuryMint: auctionHouse.treasuryMint,
       auctionHouse: auctionHouse.address,
+      auctioneerAuthority: auctioneerAuthorityAddress,
+      auctioneer: auctioneerAddress,
+      escrowLock: escrowLockAddress,
     })
     .signers([walletKeypair])
     .rpc();
@@ -17,6 +17,7 @@ import {
   Transaction,
   sendAndConfirmTransaction,
 } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import { ONE_SOL } from "../utils";
 
 export async function executeSale({
@@ -94,7 +95,7 @@ export async function executeSale({
     auctionHouseAddress: auctionHouse.address,
   });
 
-  const remainingAccounts: anchor.web3.AccountMeta[] = []; // NFT creators
+  const remainingAccounts: anchor.web3.AccountMeta[] = []; // NFT creators and their royalty vaults
 
   if (creators) {
     for (const creator of creators) {
@@ -103,6 +104,14 @@ export async function executeSale({
         isWritable: true,
         isSigner: false,
       });
+      remainingAccounts.push({
+        pubkey: pda.findRoyaltyVaultAddress({
+          creator: creator.address,
+          auctionHouseAddress: auctionHouse.address,
+        })[0],
+        isWritable: true,
+        isSigner: false,
+      });
     }
   }
 
@@ -137,6 +146,12 @@ export async function executeSale({
       auctioneerAuthority: auctioneerAuthorityAddress,
       auctioneer: auctioneerAddress,
       programAsSigner: sellAccounts.programAsSigner,
+      escrowLock: pda.findEscrowLockAddress({
+        wallet: buyer,
+        auctionHouseAddress: auctionHouse.address,
+      })[0],
+      tokenProgram: TOKEN_PROGRAM_ID,
+      treasuryTokenProgram: TOKEN_PROGRAM_ID,
     })
     .remainingAccounts(remainingAccounts)
     .instruction();
@@ -29,6 +29,10 @@ export async function withdraw({
     auctionHouseAddress: auctionHouse.address,
     auctioneerAuthorityAddress,
   });
+  const [escrowLockAddress] = pda.findEscrowLockAddress({
+    wallet: buyerKeypair.publicKey,
+    auctionHouseAddress: auctionHouse.address,
+  });
 
   const withdrawTx = await auctioneerProgram.methods
     .withdraw(escrowBump, auctioneerAuthorityBump, new BN(amount))
@@ -43,6 +47,7 @@ export async function withdraw({
       auctionHouseFeeAccount: auctionHouse.auctionHouseFeeAccount,
       auctioneerAuthority: auctioneerAuthorityAddress,
       auctioneer: auctioneerAddress,
+      escrowLock: escrowLockAddress,
     })
     .signers([buyerKeypair])
     .rpc();
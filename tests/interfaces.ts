@@ -64,6 +64,9 @@ export interface BuyAccounts {
   buyerTradeState: anchor.web3.PublicKey;
   auctioneerAuthority: anchor.web3.PublicKey;
   auctioneer: anchor.web3.PublicKey;
+  escrowLock: anchor.web3.PublicKey;
+  highestBidder: anchor.web3.PublicKey | null;
+  highestBidderEscrowLock: anchor.web3.PublicKey | null;
 }
 
 export interface Creator {
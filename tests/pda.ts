@@ -15,6 +15,8 @@ const TREASURY = "treasury";
 const AUCTIONEER = "auctioneer";
 const LISTING_CONFIG = "listing_config";
 const SIGNER = "signer";
+const ROYALTY_VAULT = "royalty_vault";
+const ESCROW_LOCK = "escrow_lock";
 
 export const findMetadataAddress = ({
   mint,
@@ -216,3 +218,37 @@ export const findEscrowPaymentAccountAddress = ({
     [Buffer.from(PREFIX), auctionHouseAddress.toBuffer(), wallet.toBuffer()],
     AUCTION_HOUSE_PROGRAM_ID
   );
+
+export const findRoyaltyVaultAddress = ({
+  creator,
+  auctionHouseAddress,
+}: {
+  creator: anchor.web3.PublicKey;
+  auctionHouseAddress: anchor.web3.PublicKey;
+}): [anchor.web3.PublicKey, number] =>
+  anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(PREFIX),
+      auctionHouseAddress.toBuffer(),
+      Buffer.from(ROYALTY_VAULT),
+      creator.toBuffer(),
+    ],
+    AUCTION_HOUSE_PROGRAM_ID
+  );
+
+export const findEscrowLockAddress = ({
+  wallet,
+  auctionHouseAddress,
+}: {
+  wallet: anchor.web3.PublicKey;
+  auctionHouseAddress: anchor.web3.PublicKey;
+}): [anchor.web3.PublicKey, number] =>
+  anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(PREFIX),
+      auctionHouseAddress.toBuffer(),
+      Buffer.from(ESCROW_LOCK),
+      wallet.toBuffer(),
+    ],
+    AUCTION_HOUSE_PROGRAM_ID
+  );
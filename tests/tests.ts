@@ -128,6 +128,7 @@ describe("auction_house", () => {
       sellerAddress: sellAccounts.wallet,
       buyerPrice: bid1Amount,
       tokenSize: TOKEN_SIZE,
+      highestBidder: bidder0.publicKey,
     });
 
     console.log("Waiting for the end of the auction...");
@@ -267,6 +268,7 @@ describe("auction_house", () => {
       sellerAddress: sellAccounts.wallet,
       buyerPrice: bidAmount2,
       tokenSize: TOKEN_SIZE,
+      highestBidder: buyerKeypair.publicKey,
     });
 
     await sleep(1000);
@@ -314,7 +316,7 @@ describe("auction_house", () => {
     });
 
     await closeEscrowAccount({
-      auctionHouseProgram,
+      auctioneerProgram,
       auctionHouse,
       walletKeypair: buyerKeypair,
     });